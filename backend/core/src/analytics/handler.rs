@@ -9,7 +9,14 @@
  * 
  * Endpoints:
  * - GET /analytics/events/{event_id}: Event-specific analytics
+ * - POST /analytics/events/batch: Event analytics for several events at once
+ * - GET /analytics/events/{event_id}/scan-timeline: Scans per minute/hour
+ * - GET /analytics/events/{event_id}/funnel: Checkouts started vs paid vs abandoned
+ * - GET /analytics/events/{event_id}/attendance.csv: Per-ticket check-in export
+ * - GET /analytics/organizer/{organizer_id}/gate-activity: Busiest scanners across all events
+ * - GET /analytics/organizer/{organizer_id}/payout: Estimated net payout after fees and refunds
  * - GET /analytics/dashboard: Platform-wide summary
+ * - GET /analytics/revenue-by-provider: Successful payments by provider and currency (admin)
  * 
  * Metrics Provided:
  * - Ticket sales (sold, scanned, available)
@@ -21,17 +28,26 @@
  * for performance and simplicity of read-only aggregations
  */
 
+use std::sync::Arc;
+
 use axum::{
-    extract::{Path, State},
-    http::HeaderMap,
-    Json,
+    extract::State,
+    http::{header, HeaderMap},
+    response::{IntoResponse, Response},
 };
 use chrono;
 use serde_json::{json, Value};
-use sqlx::{PgPool, Row};
+use sqlx::{postgres::PgRow, PgPool, Row};
 use uuid::Uuid;
 
 use crate::error::{AppError, Result};
+use crate::event_cache::EventCache;
+use crate::extract::{Json, Path, Query};
+use crate::fees::normalize_amount;
+use crate::tickets::resale::RESALE_REF_LIKE;
+
+// Most events one batch call may ask for — a dashboard page, not a data export.
+const MAX_BATCH_EVENTS: usize = 50;
 
 /**
  * Extract user_id from X-User-ID header forwarded by Go gateway
@@ -44,14 +60,33 @@ fn extract_user_id(headers: &HeaderMap) -> Result<Uuid> {
         .ok_or(AppError::Unauthorized)
 }
 
+/**
+ * Require an admin caller
+ *
+ * The gateway forwards admin-token requests with X-User-Type: admin, so a
+ * regular user JWT can never reach these handlers with that value.
+ */
+fn require_admin(headers: &HeaderMap) -> Result<Uuid> {
+    let admin_id = extract_user_id(headers)?;
+    let user_type = headers
+        .get("x-user-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    if user_type != "admin" {
+        return Err(AppError::Forbidden);
+    }
+    Ok(admin_id)
+}
+
 /**
  * Get Event Analytics
  * 
  * Comprehensive analytics for a single event
  * 
  * Metrics:
- * - Ticket inventory (total, sold, available)
- * - Revenue (total sales in event currency)
+ * - Ticket inventory (total, sold, available) and effective capacity with overbooking
+ * - Revenue (total sales in event currency: paid tickets that still stand —
+ *   pending checkouts and cancelled or refunded tickets don't count)
  * - Attendance (scanned tickets)
  * - User satisfaction (average excitement rating)
  * 
@@ -68,64 +103,545 @@ pub async fn get_event_analytics(
 ) -> Result<Json<Value>> {
     let user_id = extract_user_id(&headers)?;
 
-    // Fetch event details and verify ownership
-    let event = sqlx::query(
-        r#"SELECT e.title, e.total_tickets, e.available_tickets, e.currency
-        FROM events e WHERE e.id = $1 AND e.organizer_id = $2"#,
+    let row = sqlx::query(EVENT_ANALYTICS_SQL)
+        .bind(vec![event_id])
+        .bind(user_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(AppError::Database)?
+        .ok_or_else(|| AppError::NotFound("Event not found or not owned by you".into()))?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": event_analytics(&row),
+    })))
+}
+
+// One row per owned event in $1 (organizer $2), read by event_analytics.
+// Shared by the single-event and batch endpoints so they can't drift apart.
+const EVENT_ANALYTICS_SQL: &str = r#"
+    SELECT e.id, e.title, e.total_tickets, e.available_tickets, e.overbook_allowance, e.currency,
+           COALESCE(SUM(t.total_price) FILTER (WHERE t.status IN ('valid', 'used', 'expired')), 0) AS total_revenue,
+           COALESCE(AVG(t.excitement_rating::float8), 0) AS avg_rating,
+           COUNT(t.id) FILTER (WHERE t.status = 'used') AS scanned
+    FROM events e
+    LEFT JOIN tickets t ON t.event_id = e.id
+    WHERE e.id = ANY($1) AND e.organizer_id = $2
+    GROUP BY e.id"#;
+
+/// Response body for one event's analytics, from an EVENT_ANALYTICS_SQL row.
+fn event_analytics(r: &PgRow) -> Value {
+    let total_tickets: i32 = r.get("total_tickets");
+    let available_tickets: i32 = r.get("available_tickets");
+    let overbook_allowance: i32 = r.get("overbook_allowance");
+    json!({
+        "event_id": r.get::<Uuid, _>("id"),
+        "title": r.get::<String, _>("title"),
+        "total_tickets": total_tickets,
+        "overbook_allowance": overbook_allowance,
+        "effective_capacity": total_tickets + overbook_allowance,
+        "sold_tickets": total_tickets - available_tickets,
+        "scanned_tickets": r.get::<i64, _>("scanned"),
+        "available_tickets": available_tickets,
+        "total_revenue": normalize_amount(r.get("total_revenue")),
+        "currency": r.get::<String, _>("currency"),
+        "average_rating": r.get::<f64, _>("avg_rating"),
+    })
+}
+
+/**
+ * Request body for batch event analytics
+ */
+#[derive(Debug, serde::Deserialize)]
+pub struct BatchAnalyticsRequest {
+    pub event_ids: Vec<Uuid>,
+}
+
+/**
+ * Get Event Analytics (batch)
+ * 
+ * The single-event analytics for up to MAX_BATCH_EVENTS events, in one
+ * grouped query. Every id must be owned by the caller — one foreign or
+ * unknown id fails the whole call, same as asking for it alone.
+ * 
+ * @param pool - Database connection pool
+ * @param req - { event_ids: [...] }, duplicates ignored
+ * @returns { events: [...] } in request order, same fields as the single-event endpoint
+ */
+pub async fn get_batch_event_analytics(
+    State(pool): State<PgPool>,
+    headers: HeaderMap,
+    Json(req): Json<BatchAnalyticsRequest>,
+) -> Result<Json<Value>> {
+    let user_id = extract_user_id(&headers)?;
+    let event_ids = batch_event_ids(req.event_ids)?;
+
+    let rows = sqlx::query(EVENT_ANALYTICS_SQL)
+        .bind(&event_ids)
+        .bind(user_id)
+        .fetch_all(&pool)
+        .await
+        .map_err(AppError::Database)?;
+
+    if rows.len() != event_ids.len() {
+        return Err(AppError::NotFound("Event not found or not owned by you".into()));
+    }
+
+    let mut by_id: std::collections::HashMap<Uuid, Value> = rows.iter()
+        .map(|r| (r.get("id"), event_analytics(r)))
+        .collect();
+    let events: Vec<Value> = event_ids.iter().filter_map(|id| by_id.remove(id)).collect();
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": { "events": events }
+    })))
+}
+
+// Dedupe (keeping first-seen order) and bound the batch.
+fn batch_event_ids(ids: Vec<Uuid>) -> Result<Vec<Uuid>> {
+    let mut seen = std::collections::HashSet::new();
+    let ids: Vec<Uuid> = ids.into_iter().filter(|id| seen.insert(*id)).collect();
+    if ids.is_empty() {
+        return Err(AppError::Validation("event_ids must not be empty".into()));
+    }
+    if ids.len() > MAX_BATCH_EVENTS {
+        return Err(AppError::Validation(format!("At most {} event_ids per batch", MAX_BATCH_EVENTS)));
+    }
+    Ok(ids)
+}
+
+/**
+ * Query params for the scan timeline
+ */
+#[derive(Debug, serde::Deserialize)]
+pub struct ScanTimelineQuery {
+    pub bucket: Option<String>,    // "minute" (default) | "hour"
+}
+
+/**
+ * Get Scan Timeline
+ * 
+ * Scans per time bucket for one event — shows peak gate pressure.
+ * Every scan attempt counts (valid, invalid, already_used): a queue of
+ * rejected scans is still load on the gate.
+ * 
+ * Only buckets with at least one scan are returned; the frontend fills gaps.
+ * 
+ * @param pool - Database connection pool
+ * @param events - Event cache (ownership check)
+ * @param event_id - Event ID (must be owned by caller)
+ * @param query - bucket size
+ * @returns { bucket, timeline: [{ period, scans }] } ordered by period
+ */
+pub async fn get_scan_timeline(
+    State(pool): State<PgPool>,
+    State(events): State<Arc<EventCache>>,
+    headers: HeaderMap,
+    Path(event_id): Path<Uuid>,
+    Query(query): Query<ScanTimelineQuery>,
+) -> Result<Json<Value>> {
+    let user_id = extract_user_id(&headers)?;
+
+    // Whitelisted — the value is passed to date_trunc as a bind parameter
+    let bucket = query.bucket.as_deref().unwrap_or("minute");
+    if !matches!(bucket, "minute" | "hour") {
+        return Err(AppError::Validation("bucket must be 'minute' or 'hour'".into()));
+    }
+
+    // Verify ownership — organizer_id is read-mostly, served from the event cache
+    let owned = events.get(event_id).await
+        .map_err(AppError::Database)?
+        .is_some_and(|e| e.organizer_id == user_id);
+
+    if !owned {
+        return Err(AppError::NotFound("Event not found or not owned by you".into()));
+    }
+
+    // scan_log.scanned_at is the row's creation time (DEFAULT NOW())
+    let rows = sqlx::query(
+        r#"SELECT date_trunc($2, scanned_at) AS period, COUNT(*) AS scans
+           FROM scan_log
+           WHERE event_id = $1 AND scanned_at IS NOT NULL
+           GROUP BY period
+           ORDER BY period ASC"#,
     )
     .bind(event_id)
-    .bind(user_id)
-    .fetch_optional(&pool)
+    .bind(bucket)
+    .fetch_all(&pool)
     .await
-    .map_err(AppError::Database)?
-    .ok_or_else(|| AppError::NotFound("Event not found or not owned by you".into()))?;
+    .map_err(AppError::Database)?;
+
+    let timeline: Vec<Value> = rows.iter().map(|r| {
+        json!({
+            "period": crate::timestamp::format(&r.get::<chrono::DateTime<chrono::Utc>, _>("period")),
+            "scans":  r.get::<i64, _>("scans"),
+        })
+    }).collect();
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": {
+            "event_id": event_id,
+            "bucket": bucket,
+            "timeline": timeline,
+        }
+    })))
+}
+
+/**
+ * Get Checkout Funnel
+ * 
+ * How many checkouts turn into paid tickets — the abandonment view.
+ * A checkout is a purchase that went to a payment provider (free claims
+ * and comps never do). Each one is counted once, by order, not seat:
+ * - paid: a payment_transactions row reached 'success' (later refunds still count)
+ * - expired: closed without payment (expired, failed or cancelled unpaid)
+ * - awaiting_payment: neither yet
+ * 
+ * @param pool - Database connection pool
+ * @param events - Event cache (ownership check)
+ * @param event_id - Event ID (must be owned by caller)
+ * @returns { reserved, paid, expired, awaiting_payment, conversion_rate }
+ */
+pub async fn get_checkout_funnel(
+    State(pool): State<PgPool>,
+    State(events): State<Arc<EventCache>>,
+    headers: HeaderMap,
+    Path(event_id): Path<Uuid>,
+) -> Result<Json<Value>> {
+    let user_id = extract_user_id(&headers)?;
+
+    let owned = events.get(event_id).await
+        .map_err(AppError::Database)?
+        .is_some_and(|e| e.organizer_id == user_id);
 
-    // Extract event data
-    let title: String = event.get("title");
-    let total_tickets: i32 = event.get("total_tickets");
-    let available_tickets: i32 = event.get("available_tickets");
-    let currency: String = event.get("currency");
+    if !owned {
+        return Err(AppError::NotFound("Event not found or not owned by you".into()));
+    }
 
-    // Aggregate ticket statistics
-    let ticket_stats = sqlx::query(
+    let row = sqlx::query(
         r#"SELECT
-            COUNT(*) as total_sold,
-            COALESCE(SUM(total_price), 0) as total_revenue,
-            COALESCE(AVG(excitement_rating::float8), 0) as avg_rating,
-            COUNT(CASE WHEN status = 'used' THEN 1 END) as scanned
-        FROM tickets WHERE event_id = $1"#,
+               COUNT(*) AS reserved,
+               COUNT(*) FILTER (WHERE paid) AS paid,
+               COUNT(*) FILTER (WHERE NOT paid AND status IN ('expired', 'failed', 'cancelled')) AS expired
+           FROM (
+               SELECT t.status,
+                      EXISTS (SELECT 1 FROM payment_transactions p
+                              WHERE p.ticket_id = t.id AND p.status IN ('success', 'refunded')) AS paid
+               FROM tickets t
+               WHERE t.event_id = $1 AND t.payment_provider IS NOT NULL
+           ) checkouts"#,
     )
     .bind(event_id)
     .fetch_one(&pool)
     .await
     .map_err(AppError::Database)?;
 
-    // Extract statistics
-    let _total_sold: i64 = ticket_stats.get("total_sold");
-    let total_revenue: rust_decimal::Decimal = ticket_stats.get("total_revenue");
-    let avg_rating: f64 = ticket_stats.get("avg_rating");
-    let scanned: i64 = ticket_stats.get("scanned");
+    let reserved: i64 = row.get("reserved");
+    let paid: i64 = row.get("paid");
+    let expired: i64 = row.get("expired");
 
-    // Calculate sold tickets
-    let sold = total_tickets - available_tickets;
-
-    // Return analytics data
     Ok(Json(json!({
         "status": "success",
         "data": {
             "event_id": event_id,
-            "title": title,
-            "total_tickets": total_tickets,
-            "sold_tickets": sold,
-            "scanned_tickets": scanned,
-            "available_tickets": available_tickets,
-            "total_revenue": total_revenue,
-            "currency": currency,
-            "average_rating": avg_rating,
+            "reserved": reserved,
+            "paid": paid,
+            "expired": expired,
+            "awaiting_payment": reserved - paid - expired,
+            "conversion_rate": percentage(paid, reserved),
+        }
+    })))
+}
+
+// part / whole as a percentage to 2dp; 0 when there is nothing to divide by.
+fn percentage(part: i64, whole: i64) -> f64 {
+    if whole <= 0 {
+        return 0.0;
+    }
+    (part as f64 / whole as f64 * 100.0 * 100.0).round() / 100.0
+}
+
+/**
+ * Get Gate Activity
+ * 
+ * Scanner throughput leaderboard across every event the organizer owns —
+ * which gates carried the load, for staffing the next stop of a tour.
+ * A gate is a scanner (scan_log.scanned_by) plus the access code it
+ * signed in with, when one was recorded.
+ * 
+ * Only admission attempts count: a scan let in (valid) or turned away
+ * (invalid, already_used, wrong_location, at_capacity, blacklisted).
+ * Undo and check-out rows are logged too but aren't someone trying to
+ * get in, so they are left out of scans, admitted and rejected alike.
+ * 
+ * @param pool - Database connection pool
+ * @param organizer_id - Organizer ID (must be the caller)
+ * @returns { gates: [{ scanner_id, scanner_name, access_code, events, scans, admitted, rejected, last_scan_at }] } busiest first
+ */
+pub async fn get_gate_activity(
+    State(pool): State<PgPool>,
+    headers: HeaderMap,
+    Path(organizer_id): Path<Uuid>,
+) -> Result<Json<Value>> {
+    let user_id = extract_user_id(&headers)?;
+
+    if organizer_id != user_id {
+        return Err(AppError::Forbidden);
+    }
+
+    let rows = sqlx::query(
+        r#"SELECT sl.scanned_by, u.name AS scanner_name, sl.access_code,
+                  COUNT(DISTINCT sl.event_id) AS events,
+                  COUNT(*) AS scans,
+                  COUNT(*) FILTER (WHERE sl.result = 'valid') AS admitted,
+                  COUNT(*) FILTER (WHERE sl.result IN ('invalid', 'already_used', 'wrong_location',
+                                                       'at_capacity', 'blacklisted')) AS rejected,
+                  MAX(sl.scanned_at) AS last_scan_at
+           FROM scan_log sl
+           JOIN events e ON e.id = sl.event_id
+           LEFT JOIN users u ON u.id = sl.scanned_by
+           WHERE e.organizer_id = $1
+             AND sl.result IN ('valid', 'invalid', 'already_used', 'wrong_location', 'at_capacity', 'blacklisted')
+           GROUP BY sl.scanned_by, u.name, sl.access_code
+           ORDER BY scans DESC, last_scan_at DESC"#,
+    )
+    .bind(organizer_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::Database)?;
+
+    let gates: Vec<Value> = rows.iter().map(|r| {
+        json!({
+            "scanner_id":   r.get::<Option<Uuid>, _>("scanned_by"),
+            "scanner_name": r.get::<Option<String>, _>("scanner_name"),
+            "access_code":  r.get::<Option<String>, _>("access_code"),
+            "events":       r.get::<i64, _>("events"),
+            "scans":        r.get::<i64, _>("scans"),
+            "admitted":     r.get::<i64, _>("admitted"),
+            "rejected":     r.get::<i64, _>("rejected"),
+            "last_scan_at": r.get::<Option<chrono::DateTime<chrono::Utc>>, _>("last_scan_at")
+                .map(|t| crate::timestamp::format(&t)),
+        })
+    }).collect();
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": {
+            "organizer_id": organizer_id,
+            "gates": gates,
+        }
+    })))
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct PayoutQuery {
+    pub event_id: Option<Uuid>,    // One event; all of the organizer's events when absent
+}
+
+/**
+ * Get Estimated Payout
+ * 
+ * What the organizer should expect to receive, from payment_transactions —
+ * the record of money that actually moved — not from ticket prices.
+ * 
+ * Per currency (never summed across currencies):
+ * - gross: every payment that succeeded, including ones later refunded
+ * - refunds: the refunded part of gross — refunds queued against each
+ *   payment's reference (ticket.refund_requested), since a refunded
+ *   payment's own status never changes
+ * - platform_fees / bukrshield_fees: fees on payments that still stand, i.e.
+ *   weren't refunded in full (an overpayment refund leaves the rest standing)
+ * - net_payout: the organizer's share recorded on each payment that still
+ *   stands (organizer_payout) — right for both fee modes, unlike gross
+ *   minus fees, which overstates it when fees were passed to the buyer
+ *
+ * Resale checkouts (BUKR-RSL- references) pay the seller, not the
+ * organizer, and are left out.
+ * 
+ * An estimate: settlement timing and provider charges aren't modelled.
+ * 
+ * @param pool - Database connection pool
+ * @param events - Event cache (ownership check when event_id is given)
+ * @param organizer_id - Organizer ID (must be the caller)
+ * @param query - Optional event_id filter
+ * @returns { organizer_id, event_id, payouts: [{ currency, gross, refunds, platform_fees, bukrshield_fees, net_payout, payments, refunded_payments }] }
+ */
+pub async fn get_organizer_payout(
+    State(pool): State<PgPool>,
+    State(events): State<Arc<EventCache>>,
+    headers: HeaderMap,
+    Path(organizer_id): Path<Uuid>,
+    Query(query): Query<PayoutQuery>,
+) -> Result<Json<Value>> {
+    let user_id = extract_user_id(&headers)?;
+
+    if organizer_id != user_id {
+        return Err(AppError::Forbidden);
+    }
+
+    if let Some(event_id) = query.event_id {
+        let owned = events.get(event_id).await
+            .map_err(AppError::Database)?
+            .is_some_and(|e| e.organizer_id == organizer_id);
+        if !owned {
+            return Err(AppError::NotFound("Event not found or not owned by you".into()));
+        }
+    }
+
+    let rows = sqlx::query(
+        r#"SELECT pt.currency,
+                  COALESCE(SUM(pt.amount), 0)                                          AS gross,
+                  COALESCE(SUM(LEAST(r.amount, pt.amount)), 0)                         AS refunds,
+                  COALESCE(SUM(pt.platform_fee) FILTER (WHERE r.amount IS NULL OR r.amount < pt.amount), 0)
+                                                                                       AS platform_fees,
+                  COALESCE(SUM(pt.bukrshield_fee) FILTER (WHERE r.amount IS NULL OR r.amount < pt.amount), 0)
+                                                                                       AS bukrshield_fees,
+                  COALESCE(SUM(pt.organizer_payout) FILTER (WHERE r.amount IS NULL OR r.amount < pt.amount), 0)
+                                                                                       AS net_payout,
+                  COUNT(*)                                                             AS payments,
+                  COUNT(*) FILTER (WHERE r.amount >= pt.amount)                        AS refunded_payments
+           FROM payment_transactions pt
+           JOIN tickets t ON t.id = pt.ticket_id
+           JOIN events e ON e.id = t.event_id
+           LEFT JOIN LATERAL (
+               SELECT SUM((o.payload->>'amount')::NUMERIC) AS amount
+               FROM outbox_events o
+               WHERE o.aggregate_id = t.id
+                 AND o.event_type = 'ticket.refund_requested'
+                 AND o.payload->>'payment_ref' = pt.provider_ref
+           ) r ON TRUE
+           WHERE e.organizer_id = $1
+             AND ($2::uuid IS NULL OR e.id = $2)
+             AND pt.status = 'success'
+             AND pt.provider_ref NOT LIKE $3
+           GROUP BY pt.currency
+           ORDER BY pt.currency ASC"#,
+    )
+    .bind(organizer_id)
+    .bind(query.event_id)
+    .bind(RESALE_REF_LIKE)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::Database)?;
+
+    let payouts: Vec<Value> = rows.iter().map(|r| {
+        json!({
+            "currency":          r.get::<String, _>("currency"),
+            "gross":             normalize_amount(r.get("gross")),
+            "refunds":           normalize_amount(r.get("refunds")),
+            "platform_fees":     normalize_amount(r.get("platform_fees")),
+            "bukrshield_fees":   normalize_amount(r.get("bukrshield_fees")),
+            "net_payout":        normalize_amount(r.get("net_payout")),
+            "payments":          r.get::<i64, _>("payments"),
+            "refunded_payments": r.get::<i64, _>("refunded_payments"),
+        })
+    }).collect();
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": {
+            "organizer_id": organizer_id,
+            "event_id": query.event_id,
+            "payouts": payouts,
         }
     })))
 }
 
+/**
+ * Export Event Attendance (CSV)
+ * 
+ * One row per sold ticket with its check-in history, for reconciling
+ * against venue capacity records after the event.
+ * 
+ * Columns: ticket_id, holder_name, ticket_type, first_scan_time, scan_count
+ * - first_scan_time: earliest admitted scan (RFC3339), empty if never scanned
+ * - scan_count: admitted scans minus undone ones
+ * 
+ * Cancelled and refunded tickets are left out — they never held a seat.
+ * 
+ * @param pool - Database connection pool
+ * @param events - Event cache (ownership check)
+ * @param event_id - Event ID (must be owned by caller)
+ * @returns text/csv attachment
+ */
+pub async fn get_attendance_csv(
+    State(pool): State<PgPool>,
+    State(events): State<Arc<EventCache>>,
+    headers: HeaderMap,
+    Path(event_id): Path<Uuid>,
+) -> Result<Response> {
+    let user_id = extract_user_id(&headers)?;
+
+    let owned = events.get(event_id).await
+        .map_err(AppError::Database)?
+        .is_some_and(|e| e.organizer_id == user_id);
+
+    if !owned {
+        return Err(AppError::NotFound("Event not found or not owned by you".into()));
+    }
+
+    let rows = sqlx::query(
+        r#"SELECT t.ticket_id, u.name AS holder_name, t.ticket_type,
+                  MIN(s.scanned_at) FILTER (WHERE s.result = 'valid') AS first_scan_time,
+                  GREATEST(
+                      COUNT(s.id) FILTER (WHERE s.result = 'valid')
+                        - COUNT(s.id) FILTER (WHERE s.result = 'undo'),
+                      0
+                  ) AS scan_count
+           FROM tickets t
+           JOIN users u ON u.id = t.user_id
+           LEFT JOIN scan_log s ON s.ticket_id = t.id
+           WHERE t.event_id = $1 AND t.status NOT IN ('cancelled', 'refunded')
+           GROUP BY t.id, t.ticket_id, u.name, t.ticket_type, t.purchase_date
+           ORDER BY t.purchase_date ASC"#,
+    )
+    .bind(event_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::Database)?;
+
+    let mut csv = String::from("ticket_id,holder_name,ticket_type,first_scan_time,scan_count\n");
+    for r in &rows {
+        let first_scan = r.get::<Option<chrono::DateTime<chrono::Utc>>, _>("first_scan_time")
+            .map(|t| crate::timestamp::format(&t))
+            .unwrap_or_default();
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            csv_field(r.get("ticket_id")),
+            csv_field(r.get("holder_name")),
+            csv_field(r.get("ticket_type")),
+            first_scan,
+            r.get::<i64, _>("scan_count"),
+        ));
+    }
+
+    let disposition = format!("attachment; filename=\"attendance-{}.csv\"", event_id);
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        csv,
+    ).into_response())
+}
+
+// Quote a CSV field (RFC 4180). Leading =, +, - or @ gets a ' prefix so a
+// holder name can't run as a formula when the export is opened in a spreadsheet.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
 /**
  * Get Platform Metrics
  *
@@ -137,6 +653,7 @@ pub async fn get_event_analytics(
  * - payment_success_rate: successful payments / total payment attempts
  * - failed_payments_last_24h: count of failed transactions in last 24 hours
  * - top_events_by_revenue: top 5 events by total ticket revenue
+ * - tickets_by_status: all-time valid/used/cancelled/refunded counts
  *
  * Use case: ops dashboard, alerting, capacity planning.
  */
@@ -162,7 +679,7 @@ pub async fn get_platform_metrics(
         json!({
             "day":          r.get::<chrono::NaiveDate, _>("day").to_string(),
             "tickets_sold": r.get::<i64, _>("tickets_sold"),
-            "revenue":      r.get::<rust_decimal::Decimal, _>("revenue"),
+            "revenue":      normalize_amount(r.get("revenue")),
         })
     }).collect();
 
@@ -238,11 +755,31 @@ pub async fn get_platform_metrics(
             "event_id":     r.get::<String, _>("id"),
             "title":        r.get::<String, _>("title"),
             "tickets_sold": r.get::<i64, _>("tickets_sold"),
-            "revenue":      r.get::<rust_decimal::Decimal, _>("revenue"),
+            "revenue":      normalize_amount(r.get("revenue")),
             "currency":     r.get::<String, _>("currency"),
         })
     }).collect();
 
+    // Ticket lifecycle snapshot — all time, every event
+    let status_row = sqlx::query(
+        r#"SELECT
+               COUNT(*) FILTER (WHERE status = 'valid')     AS valid,
+               COUNT(*) FILTER (WHERE status = 'used')      AS used,
+               COUNT(*) FILTER (WHERE status = 'cancelled') AS cancelled,
+               COUNT(*) FILTER (WHERE status = 'refunded')  AS refunded
+           FROM tickets"#,
+    )
+    .fetch_one(&pool)
+    .await
+    .map_err(AppError::Database)?;
+
+    let tickets_by_status = json!({
+        "valid":     status_row.get::<i64, _>("valid"),
+        "used":      status_row.get::<i64, _>("used"),
+        "cancelled": status_row.get::<i64, _>("cancelled"),
+        "refunded":  status_row.get::<i64, _>("refunded"),
+    });
+
     Ok(Json(json!({
         "status": "success",
         "data": {
@@ -252,6 +789,101 @@ pub async fn get_platform_metrics(
             "payment_success_rate_pct": payment_success_rate,
             "failed_payments_last_24h": failed_24h,
             "top_events_by_revenue":  top_events,
+            "tickets_by_status":      tickets_by_status,
+        }
+    })))
+}
+
+/**
+ * Get Revenue by Provider
+ *
+ * Payment mix for finance: successful payment_transactions grouped by
+ * provider and currency. Currencies are never summed together — each
+ * provider/currency pair is its own row. Admin-only: platform revenue.
+ *
+ * @param pool - Database connection pool
+ * @param headers - HTTP headers with admin auth
+ * @returns { breakdown: [{ provider, currency, total_amount, transactions }] }
+ */
+pub async fn get_revenue_by_provider(
+    State(pool): State<PgPool>,
+    headers: HeaderMap,
+) -> Result<Json<Value>> {
+    require_admin(&headers)?;
+
+    let rows = sqlx::query(
+        r#"SELECT
+               provider,
+               currency,
+               COALESCE(SUM(amount), 0) AS total_amount,
+               COUNT(*)                 AS transactions
+           FROM payment_transactions
+           WHERE status = 'success'
+           GROUP BY provider, currency
+           ORDER BY provider ASC, currency ASC"#,
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::Database)?;
+
+    let breakdown: Vec<Value> = rows.iter().map(|r| {
+        json!({
+            "provider":     r.get::<String, _>("provider"),
+            "currency":     r.get::<String, _>("currency"),
+            "total_amount": normalize_amount(r.get("total_amount")),
+            "transactions": r.get::<i64, _>("transactions"),
+        })
+    }).collect();
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": {
+            "breakdown": breakdown,
         }
     })))
 }
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+
+    use super::{batch_event_ids, csv_field, percentage, MAX_BATCH_EVENTS};
+
+    #[test]
+    fn conversion_rate_rounds_to_two_places() {
+        assert_eq!(percentage(2, 3), 66.67);
+        assert_eq!(percentage(5, 5), 100.0);
+    }
+
+    #[test]
+    fn conversion_rate_without_checkouts_is_zero() {
+        assert_eq!(percentage(0, 0), 0.0);
+    }
+
+    #[test]
+    fn batch_ids_are_deduped_in_request_order() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        assert_eq!(batch_event_ids(vec![b, a, b]).unwrap(), vec![b, a]);
+    }
+
+    #[test]
+    fn batch_must_be_non_empty_and_capped() {
+        assert!(batch_event_ids(vec![]).is_err());
+        let many: Vec<Uuid> = (0..=MAX_BATCH_EVENTS).map(|_| Uuid::new_v4()).collect();
+        assert!(batch_event_ids(many).is_err());
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_when_needed() {
+        assert_eq!(csv_field("Ada Obi"), "Ada Obi");
+        assert_eq!(csv_field("Obi, Ada"), "\"Obi, Ada\"");
+        assert_eq!(csv_field("The \"VIP\""), "\"The \"\"VIP\"\"\"");
+    }
+
+    #[test]
+    fn formula_prefixes_are_neutralised() {
+        assert_eq!(csv_field("=SUM(A1)"), "'=SUM(A1)");
+        assert_eq!(csv_field("@cmd"), "'@cmd");
+    }
+}
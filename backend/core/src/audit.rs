@@ -0,0 +1,147 @@
+// Audit trail for organizer and admin mutations.
+//
+// One row per state change: who (actor_id), what (action, e.g. "promo.create"),
+// on what (entity + entity_id), and any context worth keeping (metadata).
+// Rows are append-only — nothing here updates or deletes.
+//
+// Writes that belong to a transaction go through record() on the transaction,
+// so the audit row commits or rolls back with the change. Paths that mutate
+// without a transaction call record_logged() after the change succeeds: the
+// change already happened, so failing the request would only mislead.
+//
+// GET /api/v1/admin/audit?entity=&from=&to=&limit=&offset= reads it back.
+
+use axum::{extract::State, http::HeaderMap};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::{PgExecutor, PgPool, Row};
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::extract::{Json, Query};
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+
+pub async fn record<'e, E: PgExecutor<'e>>(
+    exec: E,
+    actor_id: Option<Uuid>,
+    action: &str,
+    entity: &str,
+    entity_id: impl ToString,
+    metadata: Value,
+) -> std::result::Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO audit_log (actor_id, action, entity, entity_id, metadata)
+         VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(actor_id)
+    .bind(action)
+    .bind(entity)
+    .bind(entity_id.to_string())
+    .bind(metadata)
+    .execute(exec)
+    .await?;
+    Ok(())
+}
+
+/// record() for changes that are already committed — a failed write is logged, not returned.
+pub async fn record_logged(
+    pool: &PgPool,
+    actor_id: Option<Uuid>,
+    action: &str,
+    entity: &str,
+    entity_id: impl ToString,
+    metadata: Value,
+) {
+    let entity_id = entity_id.to_string();
+    if let Err(e) = record(pool, actor_id, action, entity, &entity_id, metadata).await {
+        tracing::warn!("Audit write failed for {} on {} {}: {}", action, entity, entity_id, e);
+    }
+}
+
+/// The caller's user id as forwarded by the gateway, when present.
+pub fn actor_from_headers(headers: &HeaderMap) -> Option<Uuid> {
+    headers
+        .get("x-user-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| Uuid::parse_str(s).ok())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuditQuery {
+    pub entity: Option<String>,
+    pub from: Option<DateTime<Utc>>,         // inclusive
+    pub to: Option<DateTime<Utc>>,           // exclusive
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuditEntry {
+    pub id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub action: String,
+    pub entity: String,
+    pub entity_id: Option<String>,
+    pub metadata: Value,
+    #[serde(with = "crate::timestamp")]
+    pub created_at: DateTime<Utc>,
+}
+
+// GET /api/v1/admin/audit — newest first
+pub async fn list_audit(
+    State(pool): State<PgPool>,
+    headers: HeaderMap,
+    Query(query): Query<AuditQuery>,
+) -> Result<Json<Value>> {
+    if actor_from_headers(&headers).is_none() {
+        return Err(AppError::Unauthorized);
+    }
+    if headers.get("x-user-type").and_then(|v| v.to_str().ok()) != Some("admin") {
+        return Err(AppError::Forbidden);
+    }
+    if let (Some(from), Some(to)) = (query.from, query.to) {
+        if from >= to {
+            return Err(AppError::Validation("from must be before to".into()));
+        }
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let rows = sqlx::query(
+        r#"SELECT id, actor_id, action, entity, entity_id, metadata, created_at,
+                  COUNT(*) OVER () AS total
+           FROM audit_log
+           WHERE ($1::text IS NULL OR entity = $1)
+             AND ($2::timestamptz IS NULL OR created_at >= $2)
+             AND ($3::timestamptz IS NULL OR created_at <  $3)
+           ORDER BY created_at DESC, id DESC
+           LIMIT $4 OFFSET $5"#,
+    )
+    .bind(&query.entity)
+    .bind(query.from)
+    .bind(query.to)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::Database)?;
+
+    let total: i64 = rows.first().map(|r| r.get("total")).unwrap_or(0);
+    let entries: Vec<AuditEntry> = rows.iter().map(|r| AuditEntry {
+        id: r.get("id"),
+        actor_id: r.get("actor_id"),
+        action: r.get("action"),
+        entity: r.get("entity"),
+        entity_id: r.get("entity_id"),
+        metadata: r.get("metadata"),
+        created_at: r.get("created_at"),
+    }).collect();
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": { "entries": entries, "total": total, "limit": limit, "offset": offset }
+    })))
+}
@@ -0,0 +1,181 @@
+// Shared Redis handle with graceful degradation.
+//
+// Redis is an accelerator, never a dependency: if it is unset, unreachable at
+// boot, or drops mid-flight, every operation falls back (cache miss, no-op,
+// or "lock unavailable") and the caller continues on the DB path. Ticket
+// sales and scanning must keep working through a Redis outage.
+//
+// `cache_enabled` tracks connectivity. It flips off on the first failed
+// operation (one warning, not one per request) and a background health task
+// reconnects / PINGs every HEALTH_CHECK_SECS to flip it back on. While off,
+// operations short-circuit immediately instead of waiting on a dead socket.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+// Per-operation ceiling — a slow Redis must not add latency to a gate scan.
+const OP_TIMEOUT: Duration = Duration::from_millis(250);
+const HEALTH_CHECK_SECS: u64 = 15;
+
+pub struct Cache {
+    url: String,
+    conn: RwLock<Option<ConnectionManager>>,
+    enabled: AtomicBool,
+}
+
+impl Cache {
+    /// Connect to `redis_url` (from Config). Never fails: an empty URL gives a
+    /// permanently disabled cache; a failed connect starts disabled and the
+    /// health task keeps retrying.
+    pub async fn connect(redis_url: &str) -> Arc<Self> {
+        let cache = Arc::new(Self {
+            url: redis_url.to_string(),
+            conn: RwLock::new(None),
+            enabled: AtomicBool::new(false),
+        });
+
+        if redis_url.is_empty() {
+            tracing::warn!("REDIS_URL not set — cache disabled, using DB-only paths");
+            return cache;
+        }
+
+        match open(redis_url).await {
+            Ok(mgr) => {
+                *cache.conn.write().unwrap() = Some(mgr);
+                cache.enabled.store(true, Ordering::Relaxed);
+                tracing::info!("Redis connected — cache enabled");
+            }
+            Err(e) => tracing::warn!("Redis unreachable at startup: {} — cache disabled, will retry", e),
+        }
+
+        let health = cache.clone();
+        tokio::spawn(async move { health.health_loop().await });
+        cache
+    }
+
+    /// A cache that is always off. For services constructed without Redis.
+    pub fn disabled() -> Arc<Self> {
+        Arc::new(Self {
+            url: String::new(),
+            conn: RwLock::new(None),
+            enabled: AtomicBool::new(false),
+        })
+    }
+
+    /// True if a REDIS_URL was given, whether or not it is reachable right now.
+    pub fn is_configured(&self) -> bool {
+        !self.url.is_empty()
+    }
+
+    /// True while Redis is configured and answering.
+    pub fn cache_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// GET. Miss on any failure.
+    pub async fn get(&self, key: &str) -> Option<String> {
+        let mut conn = self.live_conn()?;
+        self.guard(async move { conn.get::<_, Option<String>>(key).await })
+            .await
+            .flatten()
+    }
+
+    /// SET with TTL. Best-effort.
+    pub async fn set_ex(&self, key: &str, value: &str, ttl_secs: u64) {
+        let Some(mut conn) = self.live_conn() else { return; };
+        self.guard(async move { conn.set_ex::<_, _, ()>(key, value, ttl_secs).await }).await;
+    }
+
+    /// DEL. Best-effort.
+    pub async fn del(&self, key: &str) {
+        let Some(mut conn) = self.live_conn() else { return; };
+        self.guard(async move { conn.del::<_, ()>(key).await }).await;
+    }
+
+    /// SET NX EX. Some(true) = acquired, Some(false) = held by someone else,
+    /// None = Redis unavailable (caller decides the fallback).
+    pub async fn set_nx_ex(&self, key: &str, ttl_secs: u64) -> Option<bool> {
+        let mut conn = self.live_conn()?;
+        let opts = redis::SetOptions::default()
+            .conditional_set(redis::ExistenceCheck::NX)
+            .get(false)
+            .with_expiration(redis::SetExpiry::EX(ttl_secs));
+        self.guard(async move { conn.set_options::<_, _, Option<String>>(key, "1", opts).await })
+            .await
+            .map(|r| r.is_some())
+    }
+
+    /// LPUSH. Unlike the cache helpers this reports failure — queue producers
+    /// (the outbox) must retry rather than drop.
+    pub async fn lpush(&self, key: &str, value: &str) -> Result<(), String> {
+        let mut conn = self.live_conn().ok_or_else(|| "Redis unavailable".to_string())?;
+        self.guard(async move { conn.lpush::<_, _, i64>(key, value).await })
+            .await
+            .map(|_| ())
+            .ok_or_else(|| "Redis LPUSH failed".to_string())
+    }
+
+    fn live_conn(&self) -> Option<ConnectionManager> {
+        if !self.cache_enabled() {
+            return None;
+        }
+        self.conn.read().unwrap().clone()
+    }
+
+    // Run one Redis op under OP_TIMEOUT. Any failure disables the cache until
+    // the health task sees Redis answer again.
+    async fn guard<T>(&self, op: impl std::future::Future<Output = redis::RedisResult<T>>) -> Option<T> {
+        let err = match tokio::time::timeout(OP_TIMEOUT, op).await {
+            Ok(Ok(v)) => return Some(v),
+            Ok(Err(e)) => e.to_string(),
+            Err(_) => format!("timed out after {}ms", OP_TIMEOUT.as_millis()),
+        };
+        if self.enabled.swap(false, Ordering::Relaxed) {
+            tracing::warn!("Redis operation failed: {} — cache disabled, falling back to DB", err);
+        }
+        None
+    }
+
+    async fn health_loop(&self) {
+        let mut tick = tokio::time::interval(Duration::from_secs(HEALTH_CHECK_SECS));
+        loop {
+            tick.tick().await;
+
+            let existing = self.conn.read().unwrap().clone();
+            let healthy = match existing {
+                Some(mut conn) => matches!(
+                    tokio::time::timeout(OP_TIMEOUT, redis::cmd("PING").query_async::<String>(&mut conn)).await,
+                    Ok(Ok(_))
+                ),
+                None => match open(&self.url).await {
+                    Ok(mgr) => {
+                        *self.conn.write().unwrap() = Some(mgr);
+                        true
+                    }
+                    Err(_) => false,
+                },
+            };
+
+            let was = self.enabled.swap(healthy, Ordering::Relaxed);
+            match (was, healthy) {
+                (false, true) => tracing::info!("Redis reachable again — cache re-enabled"),
+                (true, false) => tracing::warn!("Redis health check failed — cache disabled"),
+                _ => {}
+            }
+        }
+    }
+}
+
+async fn open(url: &str) -> Result<ConnectionManager, String> {
+    let client = redis::Client::open(url).map_err(|e| e.to_string())?;
+    match tokio::time::timeout(CONNECT_TIMEOUT, ConnectionManager::new(client)).await {
+        Ok(Ok(mgr)) => Ok(mgr),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(_) => Err(format!("connect timed out after {}s", CONNECT_TIMEOUT.as_secs())),
+    }
+}
@@ -17,6 +17,58 @@ pub struct Config {
     pub jwt_secret: String,
     pub qr_hmac_secret: String,
     pub gateway_secret: String,
+    /// Mount prefix when served behind a proxy under a sub-path, e.g. "/bukr".
+    /// None = routes served from root.
+    pub base_path: Option<String>,
+    /// Outbox consumer target. When unset, outbox rows are pushed to Redis instead.
+    pub outbox_webhook_url: Option<String>,
+    /// Allowed promo code length range (PROMO_CODE_MIN_LEN / PROMO_CODE_MAX_LEN).
+    pub promo_code_min_len: usize,
+    pub promo_code_max_len: usize,
+    /// Most promo codes one event may have (MAX_PROMO_CODES_PER_EVENT).
+    pub max_promo_codes_per_event: i64,
+    /// Per-currency provider minimum overrides (PAYMENT_MIN_CHARGE="NGN=100,USD=1.50").
+    pub min_charge_overrides: std::collections::HashMap<String, rust_decimal::Decimal>,
+    /// How long after a scan the same scanner may undo it (SCAN_UNDO_WINDOW_SECS).
+    pub scan_undo_window_secs: i64,
+    /// Simulated provider failure (BUKR_FAULT_INJECT). Always None unless BUKR_DEV_MODE=1.
+    pub payment_fault: Option<crate::fault::PaymentFault>,
+    /// Hosts user-configured webhooks may reach (WEBHOOK_ALLOW_HOSTS, comma-separated).
+    /// Empty = any public host.
+    pub webhook_allow_hosts: Vec<String>,
+    /// Hosts user-configured webhooks may never reach (WEBHOOK_DENY_HOSTS). Wins over the allowlist.
+    pub webhook_deny_hosts: Vec<String>,
+    /// Total per-request timeout for outbound webhooks (WEBHOOK_TIMEOUT_SECS).
+    pub webhook_timeout_secs: u64,
+    /// Hosts a payment callback_url may point at (CALLBACK_ALLOWED_HOSTS, comma-separated).
+    /// Unset = the https hosts of ALLOWED_ORIGINS, i.e. the frontend. Never open to any host.
+    pub callback_allowed_hosts: Vec<String>,
+    /// gzip/brotli response compression (COMPRESSION_ENABLED). On unless set to "0" or "false".
+    pub compression_enabled: bool,
+    /// BUKR_MAINTENANCE=1: writes answer 503, reads and payment webhooks keep working.
+    pub maintenance: bool,
+    /// Reuse client connections across requests (HTTP_KEEP_ALIVE). On unless "0" or "false".
+    pub http_keep_alive: bool,
+    /// How long a client may take to send request headers (HTTP_HEADER_READ_TIMEOUT_SECS).
+    pub http_header_read_timeout_secs: u64,
+    /// Per-connection read buffer cap in bytes (HTTP_MAX_BUF_SIZE) — bounds header size.
+    pub http_max_buf_size: usize,
+    /// Days after an event's last day that attendee PII is kept (PII_RETENTION_DAYS).
+    pub pii_retention_days: i64,
+    /// Salt for the holder hash a scrub leaves behind (PII_HASH_SECRET). Its own
+    /// secret: a leaked QR key must not also unmask scrubbed holders.
+    pub pii_hash_secret: String,
+    /// How often the scheduled PII scrub runs (PII_SCRUB_INTERVAL_SECS). 0 = admin endpoint only.
+    pub pii_scrub_interval_secs: u64,
+    /// Minutes an unpaid checkout holds its seats, promo uses and promo budget
+    /// before the sweep cancels it (PENDING_TICKET_TTL_MINUTES).
+    pub pending_ticket_ttl_minutes: i64,
+    /// How often stale pending tickets are swept (PENDING_SWEEP_INTERVAL_SECS). 0 = never.
+    pub pending_sweep_interval_secs: u64,
+    /// Browser origins allowed by CORS (ALLOWED_ORIGINS, comma-separated).
+    pub allowed_origins: Vec<String>,
+    /// Methods allowed by CORS (CORS_ALLOWED_METHODS). Defaults to the ones the API routes use.
+    pub cors_allowed_methods: Vec<axum::http::Method>,
 }
 
 impl Config {
@@ -33,8 +85,95 @@ impl Config {
             jwt_secret: std::env::var("APP_JWT_SECRET").unwrap_or_default(),
             qr_hmac_secret: std::env::var("QR_HMAC_SECRET").unwrap_or_default(),
             gateway_secret: std::env::var("GATEWAY_SECRET").unwrap_or_default(),
+            base_path: normalize_base_path(&std::env::var("BUKR_BASE_PATH").unwrap_or_default()),
+            outbox_webhook_url: std::env::var("OUTBOX_WEBHOOK_URL").ok().filter(|s| !s.trim().is_empty()),
+            promo_code_min_len: std::env::var("PROMO_CODE_MIN_LEN")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(3)
+                .max(1),
+            promo_code_max_len: std::env::var("PROMO_CODE_MAX_LEN")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(32),
+            max_promo_codes_per_event: std::env::var("MAX_PROMO_CODES_PER_EVENT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(1000)
+                .max(1),
+            min_charge_overrides: parse_min_charges(&std::env::var("PAYMENT_MIN_CHARGE").unwrap_or_default()),
+            scan_undo_window_secs: std::env::var("SCAN_UNDO_WINDOW_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(120)
+                .max(0),
+            payment_fault: crate::fault::PaymentFault::from_env(),
+            webhook_allow_hosts: parse_list(&std::env::var("WEBHOOK_ALLOW_HOSTS").unwrap_or_default()),
+            webhook_deny_hosts: parse_list(&std::env::var("WEBHOOK_DENY_HOSTS").unwrap_or_default()),
+            webhook_timeout_secs: std::env::var("WEBHOOK_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5)
+                .clamp(1, 30),
+            callback_allowed_hosts: callback_hosts(
+                &std::env::var("CALLBACK_ALLOWED_HOSTS").unwrap_or_default(),
+                &std::env::var("ALLOWED_ORIGINS").unwrap_or_else(|_| "http://localhost:5173".to_string()),
+            ),
+            compression_enabled: !matches!(
+                std::env::var("COMPRESSION_ENABLED").unwrap_or_default().trim(),
+                "0" | "false"
+            ),
+            maintenance: matches!(
+                std::env::var("BUKR_MAINTENANCE").unwrap_or_default().trim(),
+                "1" | "true"
+            ),
+            http_keep_alive: !matches!(
+                std::env::var("HTTP_KEEP_ALIVE").unwrap_or_default().trim(),
+                "0" | "false"
+            ),
+            http_header_read_timeout_secs: std::env::var("HTTP_HEADER_READ_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30)
+                .clamp(1, 300),
+            http_max_buf_size: std::env::var("HTTP_MAX_BUF_SIZE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(64 * 1024),
+            pii_retention_days: std::env::var("PII_RETENTION_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(365)
+                .max(0),
+            pii_hash_secret: std::env::var("PII_HASH_SECRET").unwrap_or_default(),
+            pii_scrub_interval_secs: std::env::var("PII_SCRUB_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            pending_ticket_ttl_minutes: std::env::var("PENDING_TICKET_TTL_MINUTES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60)
+                .max(1),
+            pending_sweep_interval_secs: std::env::var("PENDING_SWEEP_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(300),
+            allowed_origins: parse_list(
+                &std::env::var("ALLOWED_ORIGINS").unwrap_or_else(|_| "http://localhost:5173".to_string()),
+            ),
+            cors_allowed_methods: parse_methods(
+                &std::env::var("CORS_ALLOWED_METHODS").unwrap_or_else(|_| "GET,POST,PUT,PATCH,DELETE".to_string()),
+            ),
         };
 
+        if cfg.promo_code_max_len < cfg.promo_code_min_len {
+            panic!(
+                "FATAL: PROMO_CODE_MAX_LEN ({}) is below PROMO_CODE_MIN_LEN ({})",
+                cfg.promo_code_max_len, cfg.promo_code_min_len
+            );
+        }
+
         // Fail loud at boot in production — an empty secret is worse than a crash.
         if std::env::var("APP_ENV").unwrap_or_default() == "production" {
             let required: &[(&str, &str)] = &[
@@ -44,6 +183,7 @@ impl Config {
                 ("PAYSTACK_SECRET_KEY", &cfg.paystack_secret_key),
                 ("PAYSTACK_WEBHOOK_SECRET", &cfg.paystack_webhook_secret),
                 ("GATEWAY_SECRET", &cfg.gateway_secret),
+                ("PII_HASH_SECRET", &cfg.pii_hash_secret),
             ];
             for (name, val) in required {
                 if val.is_empty() {
@@ -55,3 +195,75 @@ impl Config {
         cfg
     }
 }
+
+/// "bukr", "/bukr" and "/bukr/" all become Some("/bukr"). Empty or "/" means root.
+fn normalize_base_path(raw: &str) -> Option<String> {
+    let trimmed = raw.trim().trim_matches('/');
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(format!("/{}", trimmed))
+    }
+}
+
+/// "a.com, b.com" → ["a.com", "b.com"]. Blank entries are dropped.
+fn parse_list(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// CALLBACK_ALLOWED_HOSTS, or the hosts of the frontend origins when unset —
+/// buyers are only ever sent back to our own pages. Origins HostPolicy would
+/// refuse anyway (plain http, localhost) are left out, so the local-dev
+/// default yields no hosts and callbacks fail with "no callback hosts are
+/// configured" instead of naming a host that can never pass.
+fn callback_hosts(raw: &str, origins: &str) -> Vec<String> {
+    let hosts = parse_list(raw);
+    if !hosts.is_empty() {
+        return hosts;
+    }
+    let policy = crate::webhook::HostPolicy::new(Vec::new(), Vec::new());
+    let hosts: Vec<String> = parse_list(origins)
+        .iter()
+        .filter_map(|origin| policy.check_url(origin).ok())
+        .filter_map(|url| url.host_str().map(str::to_string))
+        .collect();
+    if hosts.is_empty() {
+        tracing::warn!("No https frontend origin to derive callback hosts from — set CALLBACK_ALLOWED_HOSTS to accept payments");
+    }
+    hosts
+}
+
+/// "get, POST" → [GET, POST]. Unknown names are skipped with a warning.
+fn parse_methods(raw: &str) -> Vec<axum::http::Method> {
+    parse_list(raw)
+        .into_iter()
+        .filter_map(|name| {
+            let method = name.to_uppercase().parse::<axum::http::Method>().ok();
+            if method.is_none() {
+                tracing::warn!("Ignoring malformed CORS_ALLOWED_METHODS entry '{}'", name);
+            }
+            method
+        })
+        .collect()
+}
+
+/// "NGN=100, usd=1.50" → {NGN: 100, USD: 1.50}. Malformed entries are skipped with a warning.
+fn parse_min_charges(raw: &str) -> std::collections::HashMap<String, rust_decimal::Decimal> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let parsed = entry
+                .split_once('=')
+                .and_then(|(code, min)| Some((code.trim().to_uppercase(), min.trim().parse::<rust_decimal::Decimal>().ok()?)));
+            if parsed.is_none() {
+                tracing::warn!("Ignoring malformed PAYMENT_MIN_CHARGE entry '{}'", entry);
+            }
+            parsed
+        })
+        .collect()
+}
@@ -0,0 +1,95 @@
+// ISO 4217 currency codes.
+//
+// Event and ticket currencies are stored as free text and forwarded to payment
+// providers as-is, so a typo like "NAIRA" used to surface as a cryptic provider
+// error. Every path that prices or charges in a currency runs it through
+// normalize() first: trimmed, uppercased, and checked against the active
+// ISO 4217 list below. Fund codes, precious metals and testing codes (XAU,
+// XTS, XXX, ...) are deliberately absent — nobody buys a ticket in them.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::error::{AppError, Result};
+
+/// Active ISO 4217 alphabetic codes, sorted for binary search.
+const ISO_4217: &[&str] = &[
+    "AED", "AFN", "ALL", "AMD", "AOA", "ARS", "AUD", "AWG", "AZN", "BAM", "BBD", "BDT",
+    "BGN", "BHD", "BIF", "BMD", "BND", "BOB", "BRL", "BSD", "BTN", "BWP", "BYN", "BZD",
+    "CAD", "CDF", "CHF", "CLP", "CNY", "COP", "CRC", "CUP", "CVE", "CZK", "DJF", "DKK",
+    "DOP", "DZD", "EGP", "ERN", "ETB", "EUR", "FJD", "FKP", "GBP", "GEL", "GHS", "GIP",
+    "GMD", "GNF", "GTQ", "GYD", "HKD", "HNL", "HTG", "HUF", "IDR", "ILS", "INR", "IQD",
+    "IRR", "ISK", "JMD", "JOD", "JPY", "KES", "KGS", "KHR", "KMF", "KPW", "KRW", "KWD",
+    "KYD", "KZT", "LAK", "LBP", "LKR", "LRD", "LSL", "LYD", "MAD", "MDL", "MGA", "MKD",
+    "MMK", "MNT", "MOP", "MRU", "MUR", "MVR", "MWK", "MXN", "MYR", "MZN", "NAD", "NGN",
+    "NIO", "NOK", "NPR", "NZD", "OMR", "PAB", "PEN", "PGK", "PHP", "PKR", "PLN", "PYG",
+    "QAR", "RON", "RSD", "RUB", "RWF", "SAR", "SBD", "SCR", "SDG", "SEK", "SGD", "SHP",
+    "SLE", "SOS", "SRD", "SSP", "STN", "SVC", "SYP", "SZL", "THB", "TJS", "TMT", "TND",
+    "TOP", "TRY", "TTD", "TWD", "TZS", "UAH", "UGX", "USD", "UYU", "UZS", "VED", "VES",
+    "VND", "VUV", "WST", "XAF", "XCD", "XCG", "XOF", "XPF", "YER", "ZAR", "ZMW", "ZWG",
+];
+
+/// Trim + uppercase, then require a known ISO 4217 code.
+pub fn normalize(raw: &str) -> Result<String> {
+    let code = raw.trim().to_uppercase();
+    if ISO_4217.binary_search(&code.as_str()).is_err() {
+        return Err(AppError::Validation(format!(
+            "'{}' is not a supported ISO 4217 currency code (e.g. NGN, USD, GHS)",
+            raw.trim()
+        )));
+    }
+    Ok(code)
+}
+
+/// A currency code that has been through normalize() — holding one means
+/// the code is valid, so prices can't carry an unchecked string around.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(transparent)]
+pub struct Currency(String);
+
+impl Currency {
+    pub fn parse(raw: &str) -> Result<Self> {
+        normalize(raw).map(Self)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_is_sorted_and_unique() {
+        assert!(ISO_4217.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn known_codes_are_normalized_to_uppercase() {
+        assert_eq!(normalize("NGN").unwrap(), "NGN");
+        assert_eq!(normalize(" usd ").unwrap(), "USD");
+        assert_eq!(normalize("Ghs").unwrap(), "GHS");
+    }
+
+    #[test]
+    fn currency_parse_normalizes() {
+        assert_eq!(Currency::parse(" ngn").unwrap().as_str(), "NGN");
+        assert!(Currency::parse("NAIRA").is_err());
+    }
+
+    #[test]
+    fn typos_and_non_currencies_are_rejected() {
+        for bad in ["NAIRA", "NG", "", "N G N", "XXX", "XAU", "USDT"] {
+            assert!(normalize(bad).is_err(), "{} should be rejected", bad);
+        }
+    }
+}
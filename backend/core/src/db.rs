@@ -19,7 +19,11 @@
 use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 use sqlx::PgPool;
 use std::str::FromStr;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+const MIN_CONNECTIONS: u32 = 1;
+// Warm-up is best-effort: after this the service starts serving regardless.
+const WARM_UP_TIMEOUT: Duration = Duration::from_secs(5);
 
 pub async fn create_pool(database_url: &str) -> PgPool {
     if database_url.is_empty() {
@@ -35,15 +39,63 @@ pub async fn create_pool(database_url: &str) -> PgPool {
             .expect("Invalid DATABASE_URL")
             .statement_cache_capacity(0);
 
-        PgPoolOptions::new()
+        let pool = PgPoolOptions::new()
             .max_connections(5)
-            .min_connections(1)
+            .min_connections(MIN_CONNECTIONS)
             // Give more headroom on cold starts (Render DB may need to wake up).
             .acquire_timeout(Duration::from_secs(10))
             .max_lifetime(Duration::from_secs(1800))
             .idle_timeout(Duration::from_secs(300))
             .connect_with(connect_opts)
             .await
-            .expect("Failed to connect to database")
+            .expect("Failed to connect to database");
+
+        warm_up(&pool).await;
+        pool
+    }
+}
+
+/// Open MIN_CONNECTIONS connections and round-trip a `SELECT 1` on each, so
+/// the first requests after a deploy don't pay for TCP/TLS/auth. Concurrent
+/// acquires, because sequential ones would just reuse the same connection.
+/// Failures and timeouts are logged, never fatal.
+async fn warm_up(pool: &PgPool) {
+    let started = Instant::now();
+    let mut pings = tokio::task::JoinSet::new();
+    for _ in 0..MIN_CONNECTIONS {
+        let pool = pool.clone();
+        pings.spawn(async move {
+            let mut conn = pool.acquire().await?;
+            sqlx::query("SELECT 1").execute(&mut *conn).await.map(|_| ())
+        });
+    }
+
+    let collect = async {
+        let mut ready = 0;
+        let mut last_err = None;
+        while let Some(joined) = pings.join_next().await {
+            match joined {
+                Ok(Ok(())) => ready += 1,
+                Ok(Err(e)) => last_err = Some(e.to_string()),
+                Err(e) => last_err = Some(e.to_string()),
+            }
+        }
+        (ready, last_err)
+    };
+
+    match tokio::time::timeout(WARM_UP_TIMEOUT, collect).await {
+        Ok((ready, None)) => tracing::info!(
+            "Database ready: {} connection(s) warmed in {} ms",
+            ready,
+            started.elapsed().as_millis()
+        ),
+        Ok((ready, Some(e))) => tracing::warn!(
+            "Database warm-up: {}/{} connections ready ({})",
+            ready, MIN_CONNECTIONS, e
+        ),
+        Err(_) => tracing::warn!(
+            "Database warm-up timed out after {}s — serving anyway",
+            WARM_UP_TIMEOUT.as_secs()
+        ),
     }
 }
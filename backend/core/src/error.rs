@@ -51,7 +51,16 @@ pub enum AppError {
     PromoInvalid(String),                // 400 - Fake discount detected
 
     #[error("payment failed: {0}")]
-    PaymentFailed(String),               // 402 - Money problems
+    PaymentFailed(String),               // 402 - Money problems (declined, bad request) - don't retry
+
+    #[error("payment provider unavailable: {0}")]
+    PaymentProviderUnavailable(String),  // 503 - Provider timed out / unreachable - retry
+
+    #[error("rate limited: {0}")]
+    RateLimited(String),                 // 429 - Slow down, try again later
+
+    #[error("maintenance: {0}")]
+    Maintenance(String),                 // 503 - Writes paused for maintenance (BUKR_MAINTENANCE)
 
     #[error("ticket already used")]
     TicketAlreadyUsed,                   // 409 - Can't scan twice
@@ -130,10 +139,24 @@ impl IntoResponse for AppError {
             AppError::TicketAlreadyUsed => 
                 (StatusCode::CONFLICT, "TICKET_ALREADY_USED", "Ticket has already been scanned".to_string()),
             
+            // 429 error - caller is retrying too fast
+            AppError::RateLimited(msg) => 
+                (StatusCode::TOO_MANY_REQUESTS, "RATE_LIMITED", msg.clone()),
+            
             // 402 error - payment required
             AppError::PaymentFailed(msg) => 
                 (StatusCode::PAYMENT_REQUIRED, "PAYMENT_FAILED", msg.clone()),
             
+            // 503 error - provider outage, the same request may succeed later
+            AppError::PaymentProviderUnavailable(msg) => {
+                tracing::warn!("Payment provider unavailable: {}", msg);
+                (StatusCode::SERVICE_UNAVAILABLE, "PAYMENT_PROVIDER_UNAVAILABLE", msg.clone())
+            }
+            
+            // 503 error - maintenance window, reads still work
+            AppError::Maintenance(msg) => 
+                (StatusCode::SERVICE_UNAVAILABLE, "MAINTENANCE", msg.clone()),
+            
             // 500 errors - server problems
             // Log these because they're unexpected
             AppError::Database(err) => {
@@ -169,3 +192,61 @@ impl IntoResponse for AppError {
  * Rust convention - makes code cleaner
  */
 pub type Result<T> = std::result::Result<T, AppError>;
+
+/// Postgres SQLSTATE for unique_violation.
+const PG_UNIQUE_VIOLATION: &str = "23505";
+
+/**
+ * is_unique_violation: Did this write lose to a unique constraint?
+ * 
+ * Checks the SQLSTATE, not the message text - messages change with the
+ * server's lc_messages and between driver versions, the code doesn't.
+ * Callers map a true result to AppError::Conflict with a specific message.
+ */
+pub fn is_unique_violation(err: &sqlx::Error) -> bool {
+    match err {
+        sqlx::Error::Database(db) => db.code().as_deref() == Some(PG_UNIQUE_VIOLATION),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::is_unique_violation;
+
+    // Minimal stand-in for a driver error. The message is a non-English
+    // server locale on purpose: only the SQLSTATE may matter.
+    #[derive(Debug)]
+    struct FakeDbError(&'static str);
+
+    impl std::fmt::Display for FakeDbError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "SQLSTATE {}", self.0)
+        }
+    }
+
+    impl std::error::Error for FakeDbError {}
+
+    impl sqlx::error::DatabaseError for FakeDbError {
+        fn message(&self) -> &str { "doppelter Schlüsselwert" }
+        fn code(&self) -> Option<Cow<'_, str>> { Some(Cow::Borrowed(self.0)) }
+        fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) { self }
+        fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) { self }
+        fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> { self }
+        fn kind(&self) -> sqlx::error::ErrorKind { sqlx::error::ErrorKind::Other }
+    }
+
+    #[test]
+    fn unique_violation_is_recognised_by_sqlstate_not_message() {
+        let err = sqlx::Error::Database(Box::new(FakeDbError("23505")));
+        assert!(is_unique_violation(&err));
+    }
+
+    #[test]
+    fn other_database_errors_are_not_unique_violations() {
+        assert!(!is_unique_violation(&sqlx::Error::Database(Box::new(FakeDbError("23503")))));
+        assert!(!is_unique_violation(&sqlx::Error::RowNotFound));
+    }
+}
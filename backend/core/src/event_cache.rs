@@ -0,0 +1,93 @@
+// Event lookup cache — read-mostly event fields keyed by event id.
+//
+// Only for non-critical reads (titles and dates in responses, ownership
+// checks). Anything that decides inventory or money — available_tickets,
+// the price actually charged — must still come from the locked row inside
+// the transaction, never from here.
+//
+// In-process (moka) with a short TTL, so a stale entry lives at most
+// EVENT_CACHE_TTL_SECS. Event edits are owned by the gateway; its event
+// service calls POST /admin/events/{id}/cache/invalidate after an update or
+// delete so changes show immediately on this instance.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use moka::future::Cache;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::event_time::{self, LocalStart};
+
+const EVENT_CACHE_TTL_SECS: u64 = 30;
+const EVENT_CACHE_CAPACITY: u64 = 10_000;
+
+#[derive(Debug, Clone)]
+pub struct CachedEvent {
+    pub organizer_id: Uuid,
+    pub title: String,
+    pub date: String,
+    pub time: String,
+    pub location: String,
+    pub emoji: Option<String>,
+    pub image_url: Option<String>,
+    pub timezone: String,
+}
+
+impl CachedEvent {
+    /// date/time in the event's timezone, plus the UTC start.
+    pub fn local_start(&self) -> LocalStart {
+        event_time::localize(&self.date, &self.time, &self.timezone)
+    }
+}
+
+pub struct EventCache {
+    pool: PgPool,
+    entries: Cache<Uuid, Arc<CachedEvent>>,
+}
+
+impl EventCache {
+    pub fn new(pool: PgPool) -> Self {
+        let entries = Cache::builder()
+            .max_capacity(EVENT_CACHE_CAPACITY)
+            .time_to_live(Duration::from_secs(EVENT_CACHE_TTL_SECS))
+            .build();
+        Self { pool, entries }
+    }
+
+    /// Cached event, loading it on a miss. Missing events are not cached, so
+    /// a freshly created event is visible on the next call.
+    pub async fn get(&self, event_id: Uuid) -> Result<Option<Arc<CachedEvent>>, sqlx::Error> {
+        if let Some(hit) = self.entries.get(&event_id).await {
+            return Ok(Some(hit));
+        }
+
+        let row = sqlx::query(
+            r#"SELECT organizer_id, title, date::text AS date, time::text AS time,
+                      location, emoji, thumbnail_url AS image_url, timezone
+               FROM events WHERE id = $1"#,
+        )
+        .bind(event_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some(r) = row else { return Ok(None) };
+        let event = Arc::new(CachedEvent {
+            organizer_id: r.get("organizer_id"),
+            title: r.get("title"),
+            date: r.get("date"),
+            time: r.get("time"),
+            location: r.get("location"),
+            emoji: r.get("emoji"),
+            image_url: r.get("image_url"),
+            timezone: r.get("timezone"),
+        });
+        self.entries.insert(event_id, event.clone()).await;
+        Ok(Some(event))
+    }
+
+    /// Drop one event so the next read goes to the DB.
+    pub async fn invalidate(&self, event_id: Uuid) {
+        self.entries.invalidate(&event_id).await;
+    }
+}
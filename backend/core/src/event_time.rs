@@ -0,0 +1,81 @@
+// Event start in the event's own timezone — pure, no I/O.
+//
+// events.date + events.time hold the start as the organizer entered it: a
+// wall clock in events.timezone (the create form sends the local value of
+// its date and time inputs). Responses keep that date/time and carry the
+// UTC instant alongside for clients that do their own formatting. SQL that
+// needs the instant — resale and transfer cut-offs, refund deadlines,
+// reminders — reads (date + time) AT TIME ZONE timezone, same as here.
+//
+// Existing events default to Africa/Lagos (060_event_timezone.sql). An
+// unknown zone falls back to UTC rather than failing the response.
+
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalStart {
+    pub date: String,                       // YYYY-MM-DD in the event's zone
+    pub time: String,                       // HH:MM:SS in the event's zone
+    pub starts_at: Option<DateTime<Utc>>,   // None when the stored values don't parse
+}
+
+/// Date and time as the DB returns them (`date::text`, `time::text`), read
+/// as a wall clock in `timezone`. Values that don't parse are passed through
+/// unchanged. A time skipped by a DST jump has no instant; one repeated by
+/// the fall-back takes the earlier of the two.
+pub fn localize(date: &str, time: &str, timezone: &str) -> LocalStart {
+    let parsed = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .zip(NaiveTime::parse_from_str(time, "%H:%M:%S%.f").ok());
+    let Some((d, t)) = parsed else {
+        return LocalStart { date: date.to_string(), time: time.to_string(), starts_at: None };
+    };
+
+    let local = d.and_time(t);
+    let tz: Tz = timezone.parse().unwrap_or(Tz::UTC);
+    LocalStart {
+        date: local.format("%Y-%m-%d").to_string(),
+        time: local.format("%H:%M:%S").to_string(),
+        starts_at: tz.from_local_datetime(&local).earliest().map(|s| s.with_timezone(&Utc)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stored_values_are_the_local_start() {
+        let start = localize("2026-10-16", "23:30:00", "Africa/Lagos");
+        assert_eq!((start.date.as_str(), start.time.as_str()), ("2026-10-16", "23:30:00"));
+        assert_eq!(crate::timestamp::format(&start.starts_at.unwrap()), "2026-10-16T22:30:00.000Z");
+    }
+
+    #[test]
+    fn zones_behind_utc_start_later_in_utc() {
+        let start = localize("2026-10-16", "22:00:00", "America/New_York");
+        assert_eq!((start.date.as_str(), start.time.as_str()), ("2026-10-16", "22:00:00"));
+        assert_eq!(crate::timestamp::format(&start.starts_at.unwrap()), "2026-10-17T02:00:00.000Z");
+    }
+
+    #[test]
+    fn unknown_zone_reads_as_utc() {
+        let start = localize("2026-10-16", "19:00:00.5", "Mars/Olympus");
+        assert_eq!((start.date.as_str(), start.time.as_str()), ("2026-10-16", "19:00:00"));
+        assert_eq!(crate::timestamp::format(&start.starts_at.unwrap()), "2026-10-16T19:00:00.500Z");
+    }
+
+    #[test]
+    fn time_skipped_by_dst_has_no_instant() {
+        let start = localize("2026-03-08", "02:30:00", "America/New_York");
+        assert_eq!(start.time, "02:30:00");
+        assert_eq!(start.starts_at, None);
+    }
+
+    #[test]
+    fn unparseable_values_pass_through() {
+        let start = localize("TBA", "19:00", "Africa/Lagos");
+        assert_eq!(start, LocalStart { date: "TBA".into(), time: "19:00".into(), starts_at: None });
+    }
+}
@@ -0,0 +1,212 @@
+/*!
+ * INFRASTRUCTURE LAYER - Request Extractors
+ *
+ * Drop-in replacements for axum's Path, Query and Json extractors.
+ *
+ * Axum's own rejections answer with a plain-text 400/422 that doesn't match
+ * our error envelope. These wrappers run the stock extractor and convert any
+ * rejection into AppError::Validation, naming the offending field, so every
+ * 4xx a client sees has the same {status, error: {code, message}} shape.
+ *
+ * Usage: import from here instead of axum — `use crate::extract::{Json, Path, Query};`
+ * Json also implements IntoResponse, so handler return types are unchanged.
+ */
+
+use axum::{
+    async_trait,
+    extract::{
+        path::ErrorKind,
+        rejection::{JsonRejection, PathRejection, QueryRejection},
+        FromRequest, FromRequestParts, RawPathParams, Request,
+    },
+    http::{header, request::Parts},
+    response::{IntoResponse, Response},
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::error::AppError;
+
+/// Path parameters, rejected as VALIDATION_ERROR with the parameter name.
+#[derive(Debug)]
+pub struct Path<T>(pub T);
+
+/// Query string, rejected as VALIDATION_ERROR with serde's field message.
+#[derive(Debug)]
+pub struct Query<T>(pub T);
+
+/// JSON body, rejected as VALIDATION_ERROR with the failing field path.
+#[derive(Debug)]
+pub struct Json<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Path<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::extract::Path::<T>::from_request_parts(parts, state).await {
+            Ok(axum::extract::Path(value)) => Ok(Path(value)),
+            Err(rejection) => {
+                // Single-param routes report no key — recover it from the raw params
+                let only_key = RawPathParams::from_request_parts(parts, state)
+                    .await
+                    .ok()
+                    .and_then(|raw| {
+                        let keys: Vec<String> = raw.iter().map(|(k, _)| k.to_string()).collect();
+                        if keys.len() == 1 { keys.into_iter().next() } else { None }
+                    });
+                Err(path_rejection(rejection, only_key))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Query<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        axum::extract::Query::<T>::from_request_parts(parts, state)
+            .await
+            .map(|axum::extract::Query(value)| Query(value))
+            .map_err(query_rejection)
+    }
+}
+
+#[async_trait]
+impl<T, S> FromRequest<S> for Json<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        // Kept for the error message — the request is consumed below
+        let content_type = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        axum::Json::<T>::from_request(req, state)
+            .await
+            .map(|axum::Json(value)| Json(value))
+            .map_err(|rejection| json_rejection(rejection, content_type.as_deref()))
+    }
+}
+
+impl<T: Serialize> IntoResponse for Json<T> {
+    fn into_response(self) -> Response {
+        axum::Json(self.0).into_response()
+    }
+}
+
+fn path_rejection(rejection: PathRejection, only_key: Option<String>) -> AppError {
+    let PathRejection::FailedToDeserializePathParams(err) = rejection else {
+        return AppError::Validation(rejection.body_text());
+    };
+    let message = match err.kind() {
+        ErrorKind::ParseErrorAtKey { key, value, expected_type } => {
+            invalid_param(key, value, expected_type)
+        }
+        ErrorKind::ParseError { value, expected_type } => match only_key {
+            Some(key) => invalid_param(&key, value, expected_type),
+            None => format!("Invalid path parameter '{}': expected {}", value, describe_type(expected_type)),
+        },
+        ErrorKind::ParseErrorAtIndex { index, value, expected_type } => {
+            format!("Invalid path parameter #{} '{}': expected {}", index, value, describe_type(expected_type))
+        }
+        _ => format!("Invalid path parameters: {}", err.body_text()),
+    };
+    AppError::Validation(message)
+}
+
+fn invalid_param(key: &str, value: &str, expected_type: &str) -> String {
+    format!("Invalid path parameter '{}': expected {}, got '{}'", key, describe_type(expected_type), value)
+}
+
+// "uuid::Uuid" → "a UUID"; other types by their last path segment.
+fn describe_type(expected_type: &str) -> String {
+    let name = expected_type.rsplit("::").next().unwrap_or(expected_type);
+    match name {
+        "Uuid" => "a UUID".into(),
+        "i32" | "i64" | "u32" | "u64" | "usize" => "an integer".into(),
+        other => other.into(),
+    }
+}
+
+fn query_rejection(rejection: QueryRejection) -> AppError {
+    // serde_urlencoded messages already name the field, e.g. "missing field `date`"
+    let text = rejection.body_text();
+    let detail = text.strip_prefix("Failed to deserialize query string: ").unwrap_or(&text);
+    AppError::Validation(format!("Invalid query string: {}", detail))
+}
+
+fn json_rejection(rejection: JsonRejection, content_type: Option<&str>) -> AppError {
+    let message = match &rejection {
+        // body_text carries the field path, e.g. "quantity: invalid type: string ..."
+        JsonRejection::JsonDataError(_) => {
+            let text = rejection.body_text();
+            let detail = text
+                .strip_prefix("Failed to deserialize the JSON body into the target type: ")
+                .unwrap_or(&text);
+            format!("Invalid request body: {}", detail)
+        }
+        JsonRejection::JsonSyntaxError(_) => format!("Malformed JSON: {}", rejection.body_text()),
+        // Form posts and bare bodies land here instead of a bare 415
+        JsonRejection::MissingJsonContentType(_) => match content_type {
+            Some(ct) => format!("expected application/json body, got Content-Type '{}'", ct),
+            None => "expected application/json body, no Content-Type was sent".to_string(),
+        },
+        _ => rejection.body_text(),
+    };
+    AppError::Validation(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Purchase {
+        quantity: i32,
+    }
+
+    async fn extract(content_type: Option<&str>, body: &'static str) -> Result<Json<Purchase>, AppError> {
+        let mut req = Request::builder().method("POST").uri("/");
+        if let Some(ct) = content_type {
+            req = req.header(header::CONTENT_TYPE, ct);
+        }
+        Json::<Purchase>::from_request(req.body(Body::from(body)).unwrap(), &()).await
+    }
+
+    #[tokio::test]
+    async fn json_body_with_charset_is_accepted() {
+        let Json(p) = extract(Some("application/json; charset=utf-8"), r#"{"quantity":2}"#).await.unwrap();
+        assert_eq!(p.quantity, 2);
+    }
+
+    #[tokio::test]
+    async fn form_body_is_a_validation_error_naming_the_content_type() {
+        let err = extract(Some("application/x-www-form-urlencoded"), "quantity=2").await.unwrap_err();
+        let AppError::Validation(message) = err else { panic!("expected Validation, got {:?}", err) };
+        assert_eq!(message, "expected application/json body, got Content-Type 'application/x-www-form-urlencoded'");
+    }
+
+    #[tokio::test]
+    async fn wrong_field_type_reports_the_field_path() {
+        let err = extract(Some("application/json"), r#"{"quantity":"two"}"#).await.unwrap_err();
+        let AppError::Validation(message) = err else { panic!("expected Validation, got {:?}", err) };
+        assert!(message.starts_with("Invalid request body: quantity:"), "{}", message);
+    }
+}
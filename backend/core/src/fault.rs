@@ -0,0 +1,90 @@
+// Dev-only failure injection — lets QA drive the frontend's error paths
+// without taking a real provider down.
+//
+// BUKR_FAULT_INJECT="payment:<kind>" makes every provider initialization fail
+// the same way, deterministically:
+//   payment:timeout      waits out the provider timeout, then 503
+//   payment:unavailable  immediate 503 (provider 5xx)
+//   payment:declined     immediate 402 (provider refused the charge)
+//
+// Inert unless BUKR_DEV_MODE=1. A fault spec without dev mode is logged and
+// ignored, so a stray env var in production can never break checkout.
+
+use std::time::Duration;
+
+use crate::error::AppError;
+
+/// How long the simulated timeout hangs — matches the payment HTTP client.
+pub const SIMULATED_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaymentFault {
+    Timeout,
+    Unavailable,
+    Declined,
+}
+
+impl PaymentFault {
+    /// Resolve BUKR_FAULT_INJECT against BUKR_DEV_MODE.
+    pub fn from_env() -> Option<Self> {
+        let spec = std::env::var("BUKR_FAULT_INJECT").unwrap_or_default();
+        if spec.trim().is_empty() {
+            return None;
+        }
+        if std::env::var("BUKR_DEV_MODE").as_deref() != Ok("1") {
+            tracing::warn!("BUKR_FAULT_INJECT is set but BUKR_DEV_MODE != 1 — ignoring");
+            return None;
+        }
+        let fault = Self::parse(&spec);
+        match fault {
+            Some(f) => tracing::warn!("Fault injection active: payment providers will fail with {:?}", f),
+            None => tracing::warn!("Ignoring unrecognised BUKR_FAULT_INJECT '{}'", spec),
+        }
+        fault
+    }
+
+    /// "payment:timeout" → Timeout. Other targets and kinds → None.
+    pub fn parse(spec: &str) -> Option<Self> {
+        let (target, kind) = spec.trim().split_once(':')?;
+        if !target.trim().eq_ignore_ascii_case("payment") {
+            return None;
+        }
+        match kind.trim().to_ascii_lowercase().as_str() {
+            "timeout" => Some(Self::Timeout),
+            "unavailable" => Some(Self::Unavailable),
+            "declined" => Some(Self::Declined),
+            _ => None,
+        }
+    }
+
+    /// The error a real failure of this kind surfaces as.
+    pub async fn trigger(self, provider: &str) -> AppError {
+        match self {
+            Self::Timeout => {
+                tokio::time::sleep(SIMULATED_TIMEOUT).await;
+                AppError::PaymentProviderUnavailable(format!("{} unavailable: operation timed out (injected)", provider))
+            }
+            Self::Unavailable => AppError::PaymentProviderUnavailable(format!("{} returned 503 Service Unavailable (injected)", provider)),
+            Self::Declined => AppError::PaymentFailed(format!("{}: payment declined (injected)", provider)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_payment_faults() {
+        assert_eq!(PaymentFault::parse("payment:timeout"), Some(PaymentFault::Timeout));
+        assert_eq!(PaymentFault::parse(" Payment:Unavailable "), Some(PaymentFault::Unavailable));
+        assert_eq!(PaymentFault::parse("payment:declined"), Some(PaymentFault::Declined));
+    }
+
+    #[test]
+    fn rejects_unknown_specs() {
+        assert_eq!(PaymentFault::parse("payment"), None);
+        assert_eq!(PaymentFault::parse("payment:explode"), None);
+        assert_eq!(PaymentFault::parse("scanner:timeout"), None);
+    }
+}
@@ -15,7 +15,7 @@
 ///
 /// Paystack 1.5% always comes off the gross — never absorbed by Bukr.
 
-use rust_decimal::Decimal;
+use rust_decimal::{Decimal, RoundingStrategy};
 use rust_decimal::prelude::FromPrimitive;
 use serde::{Deserialize, Serialize};
 
@@ -46,18 +46,25 @@ impl Default for FeeMode {
 #[derive(Debug, Clone, Serialize)]
 pub struct FeeBreakdown {
     /// What the buyer actually pays (per ticket × quantity)
+    #[serde(with = "rust_decimal::serde::str")]
     pub buyer_price_per_ticket: Decimal,
     /// Total buyer pays for all tickets
+    #[serde(with = "rust_decimal::serde::str")]
     pub buyer_total: Decimal,
     /// Bukr's 2% platform fee on the total
+    #[serde(with = "rust_decimal::serde::str")]
     pub platform_fee: Decimal,
     /// BukrShield flat fee (₦75 or ₦100 × quantity)
+    #[serde(with = "rust_decimal::serde::str")]
     pub bukrshield_fee: Decimal,
     /// What Paystack takes (1.5% of buyer_total) — informational only
+    #[serde(with = "rust_decimal::serde::str")]
     pub paystack_fee: Decimal,
     /// What the organizer actually receives
+    #[serde(with = "rust_decimal::serde::str")]
     pub organizer_payout: Decimal,
     /// Bukr's net profit (platform_fee + bukrshield_fee)
+    #[serde(with = "rust_decimal::serde::str")]
     pub bukr_net: Decimal,
 }
 
@@ -152,6 +159,45 @@ pub fn compute_fees(
     }
 }
 
+/// compute_fees for an order whose tickets aren't all priced alike — a
+/// bundle's free tickets, or those a partial promo code no longer covers.
+/// Each `(desired_payout_per_ticket, quantity)` line is priced on its own,
+/// since the shield tier and rounding are per ticket, and the breakdowns are
+/// summed. buyer_price_per_ticket is then the order's average.
+pub fn compute_order_fees(lines: &[(Decimal, i32)], fee_mode: &FeeMode) -> FeeBreakdown {
+    let mut order = compute_fees(Decimal::ZERO, 0, fee_mode);
+    let mut quantity = 0;
+    for &(payout, qty) in lines.iter().filter(|(_, qty)| *qty > 0) {
+        let line = compute_fees(payout, qty, fee_mode);
+        order.buyer_total += line.buyer_total;
+        order.platform_fee += line.platform_fee;
+        order.bukrshield_fee += line.bukrshield_fee;
+        order.paystack_fee += line.paystack_fee;
+        order.organizer_payout += line.organizer_payout;
+        order.bukr_net += line.bukr_net;
+        quantity += qty;
+    }
+    if quantity > 0 {
+        order.buyer_price_per_ticket = (order.buyer_total / Decimal::from(quantity)).round_dp(2);
+    }
+    order
+}
+
+/// Fee and payout split of a ticket order, as the purchase records it and
+/// every checkout or replay of the order re-derives it. Ticket lines go
+/// through compute_order_fees; add-ons are charged at list price with no
+/// ticket fees, so all of `add_ons_total` is the organizer's.
+pub struct OrderSplit {
+    pub tickets: FeeBreakdown,
+    pub organizer_payout: Decimal,
+}
+
+pub fn split_order(lines: &[(Decimal, i32)], add_ons_total: Decimal, fee_mode: &FeeMode) -> OrderSplit {
+    let tickets = compute_order_fees(lines, fee_mode);
+    let organizer_payout = tickets.organizer_payout + add_ons_total;
+    OrderSplit { tickets, organizer_payout }
+}
+
 /// Validate that a paid ticket price meets the ₦500 minimum.
 /// Returns Err with a user-friendly message if below minimum.
 pub fn validate_min_price(unit_price: Decimal) -> Result<(), String> {
@@ -165,11 +211,208 @@ pub fn validate_min_price(unit_price: Decimal) -> Result<(), String> {
     Ok(())
 }
 
+/// Booking fee breakdown: percentage part + flat part.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BookingFee {
+    /// round(order_total × pct / 100) — rounded half-up to 2dp
+    #[serde(with = "rust_decimal::serde::str")]
+    pub percentage_fee: Decimal,
+    /// Flat per-order component
+    #[serde(with = "rust_decimal::serde::str")]
+    pub flat_fee: Decimal,
+    /// percentage_fee + flat_fee — what gets stored on the ticket
+    #[serde(with = "rust_decimal::serde::str")]
+    pub total: Decimal,
+}
+
+/// Compute a combined "pct% + flat" booking fee on an order total.
+///
+/// `pct` is a percentage (2.9 = 2.9%). The percentage part is rounded to the
+/// nearest kobo before the flat part is added, so the stored fee always
+/// matches the displayed breakdown. Free orders pay no booking fee.
+pub fn compute_booking_fee(order_total: Decimal, pct: Decimal, flat: Decimal) -> BookingFee {
+    if order_total <= Decimal::ZERO {
+        return BookingFee {
+            percentage_fee: Decimal::ZERO,
+            flat_fee: Decimal::ZERO,
+            total: Decimal::ZERO,
+        };
+    }
+    let percentage_fee = (order_total * pct / Decimal::from(100))
+        .round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero);
+    BookingFee {
+        percentage_fee,
+        flat_fee: flat,
+        total: normalize_amount(percentage_fee + flat),
+    }
+}
+
+/// Tax split of a post-discount subtotal.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TaxBreakdown {
+    /// Amount before tax
+    #[serde(with = "rust_decimal::serde::str")]
+    pub net: Decimal,
+    /// Tax portion, rounded half-up to 2dp
+    #[serde(with = "rust_decimal::serde::str")]
+    pub tax_amount: Decimal,
+    /// What the buyer pays for the tickets (net + tax)
+    #[serde(with = "rust_decimal::serde::str")]
+    pub gross: Decimal,
+}
+
+/// Compute per-event tax (e.g. VAT) on a post-discount subtotal.
+///
+/// - Exclusive (`inclusive = false`): subtotal is net, tax is added on top.
+/// - Inclusive (`inclusive = true`): subtotal already contains tax, which is
+///   backed out — the buyer total does not change.
+///
+/// A zero rate or zero subtotal yields no tax.
+pub fn compute_tax(subtotal: Decimal, tax_percentage: Decimal, inclusive: bool) -> TaxBreakdown {
+    if subtotal <= Decimal::ZERO || tax_percentage <= Decimal::ZERO {
+        return TaxBreakdown { net: subtotal, tax_amount: Decimal::ZERO, gross: subtotal };
+    }
+    let rate = tax_percentage / Decimal::from(100);
+    if inclusive {
+        let net = (subtotal / (Decimal::ONE + rate))
+            .round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero);
+        TaxBreakdown { net, tax_amount: subtotal - net, gross: subtotal }
+    } else {
+        let tax_amount = (subtotal * rate)
+            .round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero);
+        TaxBreakdown { net: subtotal, tax_amount, gross: subtotal + tax_amount }
+    }
+}
+
+/// Normalize a money amount read from the DB to exactly 2 decimal places.
+/// NUMERIC columns can come back with storage scale (e.g. `1000.0000`) — this
+/// keeps every amount entering the domain at the same scale (`1000.00`).
+pub fn normalize_amount(amount: Decimal) -> Decimal {
+    let mut a = amount.round_dp(2);
+    a.rescale(2);
+    a
+}
+
+/// Display string for an amount, e.g. `₦15,000.00`. Server-side so every
+/// client shows the same thing regardless of locale. Unknown currencies fall
+/// back to the ISO code: `XOF 1,500.00`. Responses keep the raw Decimal too.
+pub fn format_amount(amount: Decimal, currency: &str) -> String {
+    let amount = normalize_amount(amount);
+    let text = amount.abs().to_string();
+    let (whole, frac) = text.split_once('.').unwrap_or((&text, "00"));
+
+    // Thousands separators on the whole part
+    let mut grouped = String::with_capacity(whole.len() + whole.len() / 3);
+    for (i, ch) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(ch);
+    }
+
+    let sign = if amount.is_sign_negative() && !amount.is_zero() { "-" } else { "" };
+    match currency_symbol(currency) {
+        Some(symbol) => format!("{}{}{}.{}", sign, symbol, grouped, frac),
+        None => format!("{}{} {}.{}", sign, currency.to_uppercase(), grouped, frac),
+    }
+}
+
+fn currency_symbol(currency: &str) -> Option<&'static str> {
+    match currency.to_uppercase().as_str() {
+        "NGN" => Some("₦"),
+        "USD" => Some("$"),
+        "GBP" => Some("£"),
+        "EUR" => Some("€"),
+        "GHS" => Some("GH₵"),
+        "KES" => Some("KSh"),
+        "ZAR" => Some("R"),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use rust_decimal_macros::dec;
 
+    #[test]
+    fn formats_with_symbol_and_thousands_separators() {
+        assert_eq!(format_amount(dec!(15000), "NGN"), "₦15,000.00");
+        assert_eq!(format_amount(dec!(1234567.5), "usd"), "$1,234,567.50");
+        assert_eq!(format_amount(dec!(999.999), "GBP"), "£1,000.00");
+    }
+
+    #[test]
+    fn formats_small_negative_and_unknown_currency() {
+        assert_eq!(format_amount(dec!(0), "NGN"), "₦0.00");
+        assert_eq!(format_amount(dec!(-250), "ZAR"), "-R250.00");
+        assert_eq!(format_amount(dec!(1500), "XOF"), "XOF 1,500.00");
+    }
+
+    #[test]
+    fn booking_fee_combines_percentage_and_flat() {
+        let f = compute_booking_fee(dec!(10000), dec!(2.9), dec!(100));
+        assert_eq!(f.percentage_fee, dec!(290.00));
+        assert_eq!(f.flat_fee, dec!(100));
+        assert_eq!(f.total, dec!(390.00));
+    }
+
+    #[test]
+    fn booking_fee_percentage_rounds_half_up_to_kobo() {
+        // 1234.50 × 2.9% = 35.8005 → 35.80; 1850 × 1.5% = 27.75 exact;
+        // 1001 × 0.25% = 2.5025 → 2.50; 5 × 2.5% = 0.125 → 0.13 (half-up)
+        assert_eq!(compute_booking_fee(dec!(1234.50), dec!(2.9), dec!(0)).percentage_fee, dec!(35.80));
+        assert_eq!(compute_booking_fee(dec!(1850), dec!(1.5), dec!(0)).percentage_fee, dec!(27.75));
+        assert_eq!(compute_booking_fee(dec!(1001), dec!(0.25), dec!(0)).percentage_fee, dec!(2.50));
+        assert_eq!(compute_booking_fee(dec!(5), dec!(2.5), dec!(0)).percentage_fee, dec!(0.13));
+    }
+
+    #[test]
+    fn booking_fee_total_is_two_decimal_places() {
+        let f = compute_booking_fee(dec!(5300), dec!(0), dec!(100));
+        assert_eq!(f.total.to_string(), "100.00");
+    }
+
+    #[test]
+    fn free_order_has_no_booking_fee() {
+        let f = compute_booking_fee(dec!(0), dec!(2.9), dec!(100));
+        assert_eq!(f.total, dec!(0));
+    }
+
+    #[test]
+    fn exclusive_tax_is_added_on_top() {
+        // 7.5% VAT on ₦5,300 → ₦397.50, buyer pays ₦5,697.50
+        let t = compute_tax(dec!(5300), dec!(7.5), false);
+        assert_eq!(t.net, dec!(5300));
+        assert_eq!(t.tax_amount, dec!(397.50));
+        assert_eq!(t.gross, dec!(5697.50));
+    }
+
+    #[test]
+    fn inclusive_tax_is_backed_out_of_the_price() {
+        // ₦5,375 including 7.5% VAT → net ₦5,000, tax ₦375; total unchanged
+        let t = compute_tax(dec!(5375), dec!(7.5), true);
+        assert_eq!(t.net, dec!(5000.00));
+        assert_eq!(t.tax_amount, dec!(375.00));
+        assert_eq!(t.gross, dec!(5375));
+    }
+
+    #[test]
+    fn inclusive_tax_rounds_and_parts_sum_to_gross() {
+        // 5300 / 1.075 = 4930.2325... → net 4930.23, tax 369.77
+        let t = compute_tax(dec!(5300), dec!(7.5), true);
+        assert_eq!(t.net, dec!(4930.23));
+        assert_eq!(t.tax_amount, dec!(369.77));
+        assert_eq!(t.net + t.tax_amount, t.gross);
+    }
+
+    #[test]
+    fn zero_rate_or_free_order_has_no_tax() {
+        assert_eq!(compute_tax(dec!(5300), dec!(0), false).tax_amount, dec!(0));
+        assert_eq!(compute_tax(dec!(0), dec!(7.5), false).tax_amount, dec!(0));
+        assert_eq!(compute_tax(dec!(0), dec!(7.5), true).gross, dec!(0));
+    }
+
     #[test]
     fn free_event_zero_fees() {
         let b = compute_fees(dec!(0), 1, &FeeMode::PassToBuyer);
@@ -221,4 +464,50 @@ mod tests {
         assert_eq!(double.buyer_total, single.buyer_total * dec!(2));
         assert_eq!(double.bukrshield_fee, single.bukrshield_fee * dec!(2));
     }
+
+    #[test]
+    fn free_line_adds_no_fees_to_an_order() {
+        // Buy 3 get 1 free at ₦5,000: three ₦5,300 tickets and one at ₦0
+        let order = compute_order_fees(&[(dec!(0), 1), (dec!(5000), 3)], &FeeMode::PassToBuyer);
+        let paid = compute_fees(dec!(5000), 3, &FeeMode::PassToBuyer);
+        assert_eq!(order.buyer_total, dec!(15900));
+        assert_eq!(order.bukrshield_fee, paid.bukrshield_fee);
+        assert_eq!(order.organizer_payout, paid.organizer_payout);
+    }
+
+    #[test]
+    fn order_lines_are_priced_separately() {
+        let order = compute_order_fees(&[(dec!(4500), 2), (dec!(5000), 3)], &FeeMode::PassToBuyer);
+        let discounted = compute_fees(dec!(4500), 2, &FeeMode::PassToBuyer);
+        let full = compute_fees(dec!(5000), 3, &FeeMode::PassToBuyer);
+        assert_eq!(order.buyer_total, discounted.buyer_total + full.buyer_total);
+        assert_eq!(order.platform_fee, discounted.platform_fee + full.platform_fee);
+    }
+
+    #[test]
+    fn add_ons_pass_to_the_organizer_without_fees() {
+        let lines = [(dec!(5000), 2)];
+        let split = split_order(&lines, dec!(3000), &FeeMode::PassToBuyer);
+        let tickets = compute_order_fees(&lines, &FeeMode::PassToBuyer);
+        assert_eq!(split.tickets.platform_fee, tickets.platform_fee);
+        assert_eq!(split.organizer_payout, tickets.organizer_payout + dec!(3000));
+    }
+
+    #[test]
+    fn money_fields_serialize_as_strings_and_round_trip() {
+        // Beyond 2^53 — a JS number would silently lose the last digits.
+        let big = dec!(12345678901234567.89);
+        let b = TaxBreakdown { net: big, tax_amount: dec!(0.10), gross: big + dec!(0.10) };
+        let v = serde_json::to_value(&b).unwrap();
+        assert_eq!(v["net"], "12345678901234567.89");
+        assert_eq!(v["tax_amount"], "0.10");
+        let back: Decimal = v["gross"].as_str().unwrap().parse().unwrap();
+        assert_eq!(back, b.gross);
+    }
+
+    #[test]
+    fn ad_hoc_json_amounts_are_strings_too() {
+        // Analytics builds payloads with json!(), which goes through Decimal's own Serialize.
+        assert_eq!(serde_json::json!({ "revenue": dec!(1500.00) })["revenue"], "1500.00");
+    }
 }
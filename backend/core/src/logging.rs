@@ -0,0 +1,151 @@
+// Log output format — LOG_FORMAT=json|pretty.
+//
+// Aggregators in production want one JSON object per line; a terminal wants
+// the readable default. When LOG_FORMAT is unset, dev mode (BUKR_DEV_MODE=1)
+// gets pretty and everything else gets JSON, so a forgotten variable in
+// production still produces parseable logs.
+//
+// JSON lines are written by a small FormatEvent here rather than
+// tracing-subscriber's "json" feature, which would pull in tracing-serde
+// for what is a dozen lines of serde_json.
+
+use std::fmt;
+
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::EnvFilter;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    Json,
+    Pretty,
+}
+
+impl LogFormat {
+    /// Explicit LOG_FORMAT wins; otherwise pretty only in dev mode.
+    /// An unrecognised value falls back to the default rather than failing boot.
+    pub fn resolve(log_format: Option<&str>, dev_mode: bool) -> Self {
+        match log_format.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+            Some("json") => Self::Json,
+            Some("pretty") => Self::Pretty,
+            _ if dev_mode => Self::Pretty,
+            _ => Self::Json,
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::resolve(
+            std::env::var("LOG_FORMAT").ok().as_deref(),
+            std::env::var("BUKR_DEV_MODE").as_deref() == Ok("1"),
+        )
+    }
+}
+
+/// Install the global subscriber in the chosen format.
+pub fn init(format: LogFormat) {
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| "bukr_core=info,tower_http=info".into());
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+
+    match format {
+        LogFormat::Pretty => builder.init(),
+        LogFormat::Json => builder.with_ansi(false).event_format(JsonLines).init(),
+    }
+}
+
+/// One JSON object per event: timestamp, level, target, message, fields, spans.
+struct JsonLines;
+
+impl<S, N> FormatEvent<S, N> for JsonLines
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(&self, ctx: &FmtContext<'_, S, N>, mut writer: Writer<'_>, event: &Event<'_>) -> fmt::Result {
+        let meta = event.metadata();
+        let mut fields = FieldMap::default();
+        event.record(&mut fields);
+
+        let mut line = Map::new();
+        line.insert("timestamp".into(), chrono::Utc::now().to_rfc3339().into());
+        line.insert("level".into(), meta.level().as_str().into());
+        line.insert("target".into(), meta.target().into());
+        line.insert("message".into(), fields.message.unwrap_or_default().into());
+        if !fields.values.is_empty() {
+            line.insert("fields".into(), Value::Object(fields.values));
+        }
+        if let Some(scope) = ctx.event_scope() {
+            let spans: Vec<Value> = scope.from_root().map(|span| span.name().into()).collect();
+            line.insert("spans".into(), spans.into());
+        }
+
+        writeln!(writer, "{}", Value::Object(line))
+    }
+}
+
+#[derive(Default)]
+struct FieldMap {
+    message: Option<String>,
+    values: Map<String, Value>,
+}
+
+impl FieldMap {
+    fn insert(&mut self, field: &Field, value: Value) {
+        if field.name() == "message" {
+            self.message = Some(match value {
+                Value::String(s) => s,
+                other => other.to_string(),
+            });
+        } else {
+            self.values.insert(field.name().to_string(), value);
+        }
+    }
+}
+
+impl Visit for FieldMap {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.insert(field, format!("{:?}", value).into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explicit_format_wins() {
+        assert_eq!(LogFormat::resolve(Some("json"), true), LogFormat::Json);
+        assert_eq!(LogFormat::resolve(Some(" Pretty "), false), LogFormat::Pretty);
+    }
+
+    #[test]
+    fn unset_or_unknown_follows_dev_mode() {
+        assert_eq!(LogFormat::resolve(None, true), LogFormat::Pretty);
+        assert_eq!(LogFormat::resolve(None, false), LogFormat::Json);
+        assert_eq!(LogFormat::resolve(Some("xml"), false), LogFormat::Json);
+    }
+}
@@ -7,7 +7,7 @@
  * Responsibility: Application bootstrap, dependency injection, routing
  * 
  * Startup Flow:
- * 1. Initialize logging (tracing)
+ * 1. Load .env, initialize logging (tracing)
  * 2. Load configuration from environment
  * 3. Create database connection pool
  * 4. Initialize repositories
@@ -22,33 +22,65 @@
  * - Router composes all handlers
  * 
  * Modules:
+ * - cache: Redis handle that degrades to no-ops when Redis is down
  * - config: Configuration management
+ * - currency: ISO 4217 currency code validation
  * - db: Database connection pooling
  * - error: Error handling
+ * - event_cache: Short-TTL cache of read-mostly event fields
+ * - extract: Path/Query/Json extractors with enveloped rejections
+ * - fault: Dev-only payment provider failure injection
+ * - logging: LOG_FORMAT=json|pretty subscriber setup
+ * - maintenance: BUKR_MAINTENANCE write freeze (reads and webhooks stay up)
+ * - meta: Server time, build and enabled features for clients
+ * - money: Amount + currency pair that refuses cross-currency arithmetic
  * - tickets: Ticket purchase and management
  * - promos: Promo code management
+ * - qr_bucket: Time-bucketed QR signatures for rotating_qr events
  * - scanner: Ticket scanning and validation
+ * - server: HTTP accept loop with keep-alive and header limits
  * - payments: Payment processing
  * - analytics: Analytics and reporting
+ * - outbox: Transactional outbox for reliable side effects
+ * - audit: Audit trail of organizer/admin mutations
+ * - webhook: SSRF-guarded HTTP client for user-configured webhooks
  */
 
+mod cache;
 mod config;
+mod currency;
 mod db;
 mod error;
+mod event_cache;
+mod event_time;
+mod extract;
+mod fault;
 mod fees;
+mod logging;
+mod maintenance;
+mod meta;
+mod money;
 mod notifications;
+mod openapi;
+mod outbox;
+mod payment_ref;
 mod tickets;
 mod promos;
+mod qr_bucket;
 mod scanner;
+mod server;
+mod timestamp;
 mod payments;
 mod analytics;
+mod audit;
 mod vendors;
+mod webhook;
 
 use crate::error::{AppError, Result};
 use std::sync::Arc;
 use axum::{
     extract::{FromRef, State},
-    routing::{get, patch, post, delete},
+    routing::{get, patch, post, put, delete},
     Router,
     middleware,
     response::Response,
@@ -56,6 +88,7 @@ use axum::{
     http::Request,
 };
 use sqlx::PgPool;
+use tower_http::compression::{predicate::DefaultPredicate, CompressionLayer};
 use tower_http::cors::CorsLayer;
 use tower_http::trace::TraceLayer;
 
@@ -72,6 +105,9 @@ struct AppState {
     pool:            PgPool,
     arc_pool:        Arc<PgPool>,
     gateway_secret:  String,
+    cache:           Arc<cache::Cache>,
+    event_cache:     Arc<event_cache::EventCache>,
+    meta:            Arc<meta::ServerMeta>,
 }
 
 impl FromRef<AppState> for Arc<tickets::service::TicketService> {
@@ -100,6 +136,16 @@ impl FromRef<AppState> for Arc<PgPool> {
 impl FromRef<AppState> for String {
     fn from_ref(s: &AppState) -> Self { s.gateway_secret.clone() }
 }
+impl FromRef<AppState> for Arc<cache::Cache> {
+    fn from_ref(s: &AppState) -> Self { s.cache.clone() }
+}
+/// analytics ownership checks use `State(events): State<Arc<EventCache>>`
+impl FromRef<AppState> for Arc<event_cache::EventCache> {
+    fn from_ref(s: &AppState) -> Self { s.event_cache.clone() }
+}
+impl FromRef<AppState> for Arc<meta::ServerMeta> {
+    fn from_ref(s: &AppState) -> Self { s.meta.clone() }
+}
 
 /**
  * Main Entry Point
@@ -108,17 +154,12 @@ impl FromRef<AppState> for String {
  */
 #[tokio::main]
 async fn main() {
-    // Initialize structured logging with tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "bukr_core=info,tower_http=info".into()),
-        )
-        .init();
-
-    // Load environment variables from .env file
+    // Load environment variables from .env file (before logging, so LOG_FORMAT there applies)
     dotenvy::dotenv().ok();
 
+    // Initialize structured logging with tracing — JSON lines or pretty (LOG_FORMAT)
+    logging::init(logging::LogFormat::from_env());
+
     // Load configuration from environment
     let cfg = config::Config::from_env();
 
@@ -131,6 +172,7 @@ async fn main() {
     };
 
     // Start HTTP server
+    let http = server::HttpSettings::from_config(&cfg);
     let addr = format!("0.0.0.0:{}", cfg.port);
     tracing::info!("Bukr Core starting on {}", addr);
 
@@ -141,12 +183,13 @@ async fn main() {
         // Health-only mode when database unavailable
         Router::new()
             .route("/health", get(health))
-            .layer(CorsLayer::permissive())
+            .fallback(route_not_found)
+            .layer(cors_layer(&cfg))
     };
 
     // Bind and serve
     let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    server::serve(listener, app, http).await;
 }
 
 /**
@@ -166,6 +209,11 @@ async fn main() {
  * @returns Configured Axum router
  */
 async fn build_router(pool: PgPool, cfg: config::Config) -> Router {
+    // MIDDLEWARE LAYER: Configure CORS — restrict to known origins in production.
+    // Built first, before config fields are moved into the services.
+    let cors = cors_layer(&cfg);
+    let server_meta = Arc::new(meta::ServerMeta::from_config(&cfg));
+
     // REPOSITORY LAYER
     let promo_repo  = promos::repository::PromoRepository::new(pool.clone());
     let ticket_repo = tickets::repository::TicketRepository::new(pool.clone());
@@ -173,18 +221,64 @@ async fn build_router(pool: PgPool, cfg: config::Config) -> Router {
     // SERVICE LAYER
     // qr_hmac_secret is passed explicitly — services must not read env vars directly.
     // Config.from_env() is the single validation point; if it's empty in production, we never reach here.
-    let ticket_service  = Arc::new(tickets::service::TicketService::new(ticket_repo, promo_repo.clone(), cfg.qr_hmac_secret.clone()));
-    let promo_service   = Arc::new(promos::service::PromoService::new(promo_repo));
-    let scanner_service = Arc::new(scanner::service::ScannerService::new_with_redis(pool.clone(), cfg.qr_hmac_secret.clone()).await);
+    let event_cache     = Arc::new(event_cache::EventCache::new(pool.clone()));
+    let ticket_service  = Arc::new(tickets::service::TicketService::new(
+        ticket_repo,
+        promo_repo.clone(),
+        cfg.qr_hmac_secret.clone(),
+        event_cache.clone(),
+        cfg.pii_retention_days,
+        cfg.pii_hash_secret.clone(),
+    ));
+    let promo_service   = Arc::new(promos::service::PromoService::new(
+        promo_repo,
+        cfg.promo_code_min_len,
+        cfg.promo_code_max_len,
+        cfg.max_promo_codes_per_event,
+    ));
+    // CACHE — never fails; an unreachable Redis just starts disabled and reconnects in the background.
+    let cache = cache::Cache::connect(&cfg.redis_url).await;
+
+    let scanner_service = Arc::new(scanner::service::ScannerService::new_with_cache(
+        pool.clone(),
+        cfg.qr_hmac_secret.clone(),
+        cache.clone(),
+        cfg.scan_undo_window_secs,
+        webhook::WebhookClient::new(
+            webhook::HostPolicy::new(cfg.webhook_allow_hosts.clone(), cfg.webhook_deny_hosts.clone()),
+            std::time::Duration::from_secs(cfg.webhook_timeout_secs),
+        ),
+    ));
     let payment_service = Arc::new(payments::service::PaymentService::new(
         pool.clone(),
         cfg.paystack_secret_key,
         cfg.paystack_webhook_secret,
+        cfg.min_charge_overrides,
+        cfg.payment_fault,
+        webhook::HostPolicy::new(cfg.callback_allowed_hosts.clone(), Vec::new()),
     ));
     let vendor_service = Arc::new(vendors::service::VendorService::new(
         vendors::repository::VendorRepository::new(pool.clone()),
     ));
 
+    // OUTBOX CONSUMER — drains outbox_events to the configured sink in the background.
+    match outbox::OutboxSink::from_config(cfg.outbox_webhook_url.clone(), cache.clone()) {
+        Some(sink) => outbox::spawn_consumer(pool.clone(), sink),
+        None => tracing::warn!("Outbox consumer disabled — set OUTBOX_WEBHOOK_URL or REDIS_URL"),
+    }
+
+    // RETENTION — scheduled attendee PII scrub; off unless PII_SCRUB_INTERVAL_SECS is set.
+    if cfg.pii_scrub_interval_secs > 0 {
+        tickets::retention::spawn_scrub_job(ticket_service.clone(), cfg.pii_scrub_interval_secs);
+    }
+
+    // CHECKOUT EXPIRY — abandoned pending tickets give their seats back.
+    if cfg.pending_sweep_interval_secs > 0 {
+        tickets::checkout_expiry::spawn_pending_sweep(
+            ticket_service.clone(), cfg.pending_sweep_interval_secs, cfg.pending_ticket_ttl_minutes,
+        );
+    }
+
     let state = AppState {
         ticket_service,
         scanner_service,
@@ -194,6 +288,9 @@ async fn build_router(pool: PgPool, cfg: config::Config) -> Router {
         arc_pool: Arc::new(pool.clone()),
         pool,
         gateway_secret: cfg.gateway_secret,
+        cache,
+        event_cache,
+        meta: server_meta,
     };
 
     // ROUTE GROUPS — NO .with_state() per router.
@@ -203,34 +300,77 @@ async fn build_router(pool: PgPool, cfg: config::Config) -> Router {
 
     let ticket_routes = Router::new()
         .route("/purchase", post(tickets::handler::purchase_ticket))
+        .route("/guest-purchase", post(tickets::handler::guest_purchase))
         .route("/me", get(tickets::handler::get_my_tickets))
         .route("/event/:event_id", get(tickets::handler::get_event_tickets))
+        .route("/event/:event_id/hold", put(tickets::handler::set_event_hold))
+        .route("/event/:event_id/qr-batch.zip", get(tickets::handler::get_qr_batch_zip))
+        .route("/event/:event_id/comp", post(tickets::handler::issue_comp_ticket))
+        .route("/event/:event_id/refund-deadline", put(tickets::handler::set_refund_deadline))
+        .route("/event/:event_id/overbook-allowance", put(tickets::handler::set_overbook_allowance))
+        .route("/event/:event_id/max-inside", put(tickets::handler::set_max_concurrent_inside))
+        .route("/event/:event_id/low-stock-threshold", put(tickets::handler::set_low_stock_threshold))
+        .route("/event/:event_id/default-ticket-type", put(tickets::handler::set_default_ticket_type))
+        .route("/event/:event_id/self-cancel-deadline", put(tickets::handler::set_self_cancel_deadline))
+        .route("/event/:event_id/add-ons", get(tickets::handler::list_add_ons).post(tickets::handler::create_add_on))
         .route("/claim-free", post(tickets::handler::claim_free_ticket))
+        .route("/order/:order_id", get(tickets::handler::get_order_tickets))
         .route("/:ticket_id/qr", get(tickets::handler::get_dynamic_qr))
+        .route("/:ticket_id/qr.png", get(tickets::handler::get_qr_png))
+        .route("/:ticket_id/barcode.png", get(tickets::handler::get_barcode_png))
         .route("/:ticket_id/transfer", post(tickets::transfer::transfer_ticket))
+        .route("/:ticket_id/cancel", post(tickets::handler::cancel_ticket))
+        .route("/:ticket_id/resend-confirmation", post(tickets::handler::resend_confirmation))
+        .route("/:ticket_id/resume-payment", get(payments::handler::resume_payment))
+        .route("/:ticket_id/resale", post(tickets::resale::list_for_resale).delete(tickets::resale::cancel_resale))
+        .route("/event/:event_id/resale", get(tickets::resale::list_event_resales))
+        .route("/resale/:listing_id/buy", post(tickets::resale::buy_resale))
         .route("/:ticket_id/renew", post(scanner::handler::renew_ticket));
 
+    let event_routes = Router::new()
+        .route("/:event_id/ticket-types", get(tickets::handler::list_ticket_types))
+        .route("/:event_id/cancel-preview", get(tickets::handler::cancel_preview))
+        .route("/:event_id/status", patch(tickets::handler::set_event_status));
+
     let promo_routes = Router::new()
         .route("/events/:event_id/promos", get(promos::handler::list_promos).post(promos::handler::create_promo))
+        .route("/events/:event_id/promos/by-code/:code", get(promos::handler::get_promo_by_code))
+        .route("/events/:event_id/promos/bulk-toggle", post(promos::handler::bulk_toggle_promos))
+        .route("/events/:event_id/promo-budget", get(promos::handler::get_promo_budget).put(promos::handler::set_promo_budget))
         .route("/events/:event_id/promos/:promo_id", delete(promos::handler::delete_promo))
         .route("/events/:event_id/promos/:promo_id/toggle", patch(promos::handler::toggle_promo))
-        .route("/promos/validate", post(promos::handler::validate_promo));
+        .route("/promos/validate", post(promos::handler::validate_promo))
+        .route("/promos/validate-batch", post(promos::handler::validate_promo_batch));
 
     let scanner_routes = Router::new()
         .route("/verify-access", post(scanner::handler::verify_access))
         .route("/validate", post(scanner::handler::validate_ticket))
+        .route("/sync", post(scanner::handler::sync_scans))
         .route("/manual-validate", post(scanner::handler::manual_validate))
         .route("/mark-used/:ticket_id", patch(scanner::handler::mark_used))
-        .route("/:event_id/stats", get(scanner::handler::get_stats));
+        .route("/:ticket_id/undo", post(scanner::handler::undo_scan))
+        .route("/:ticket_id/check-out", post(scanner::handler::check_out))
+        .route("/:event_id/stats", get(scanner::handler::get_stats))
+        .route("/:event_id/duplicates", get(scanner::handler::get_duplicates));
 
     let payment_routes = Router::new()
         .route("/initialize", post(payments::handler::initialize_payment))
-        .route("/webhook/paystack", post(payments::handler::paystack_webhook))
         .route("/:reference/verify", get(payments::handler::verify_payment));
 
+    // Provider callbacks — kept out of the compression layer below.
+    let webhook_routes = Router::new()
+        .route("/webhook/paystack", post(payments::handler::paystack_webhook));
+
     let analytics_routes = Router::new()
+        .route("/events/batch", post(analytics::handler::get_batch_event_analytics))
         .route("/events/:event_id", get(analytics::handler::get_event_analytics))
-        .route("/dashboard", get(analytics::handler::get_platform_metrics));
+        .route("/events/:event_id/scan-timeline", get(analytics::handler::get_scan_timeline))
+        .route("/events/:event_id/funnel", get(analytics::handler::get_checkout_funnel))
+        .route("/events/:event_id/attendance.csv", get(analytics::handler::get_attendance_csv))
+        .route("/organizer/:organizer_id/gate-activity", get(analytics::handler::get_gate_activity))
+        .route("/organizer/:organizer_id/payout", get(analytics::handler::get_organizer_payout))
+        .route("/dashboard", get(analytics::handler::get_platform_metrics))
+        .route("/revenue-by-provider", get(analytics::handler::get_revenue_by_provider));
 
     let vendor_profile_routes = Router::new()
         .route("/", get(vendors::handler::search_vendors).post(vendors::handler::register_vendor))
@@ -253,29 +393,27 @@ async fn build_router(pool: PgPool, cfg: config::Config) -> Router {
     let vendor_me_routes = Router::new()
         .route("/hires", get(vendors::handler::get_my_hires));
 
-    // MIDDLEWARE LAYER: Configure CORS — restrict to known origins in production
-    let allowed_origin = std::env::var("ALLOWED_ORIGINS")
-        .unwrap_or_else(|_| "http://localhost:5173".to_string());
-
-    let cors = CorsLayer::new()
-        .allow_origin(
-            allowed_origin
-                .split(',')
-                .filter_map(|s| s.trim().parse::<axum::http::HeaderValue>().ok())
-                .collect::<Vec<_>>(),
-        )
-        .allow_methods([axum::http::Method::GET, axum::http::Method::POST, axum::http::Method::PUT, axum::http::Method::PATCH, axum::http::Method::DELETE])
-        .allow_headers([axum::http::header::AUTHORIZATION, axum::http::header::CONTENT_TYPE, "x-user-id".parse().unwrap()])
-        // Browsers cache the preflight response for 1 hour.
-        // Without this, every POST/PATCH/DELETE to Rust triggers an OPTIONS
-        // preflight before the real request — doubling request count.
-        .max_age(std::time::Duration::from_secs(3600));
+    // Admin-only operations. Role is enforced per handler via X-User-Type.
+    let admin_routes = Router::new()
+        .route("/tickets/bulk-cancel", post(tickets::handler::bulk_cancel_tickets))
+        .route("/tickets/:ticket_id/reassign", post(tickets::handler::reassign_ticket))
+        .route("/tickets/:ticket_id/blacklist", post(tickets::handler::blacklist_ticket).delete(tickets::handler::unblacklist_ticket))
+        .route("/audit", get(audit::list_audit))
+        .route("/promos", get(promos::handler::list_global_promos).post(promos::handler::create_global_promo))
+        .route("/promos/:promo_id", delete(promos::handler::delete_global_promo))
+        .route("/promos/:promo_id/toggle", patch(promos::handler::toggle_global_promo))
+        .route("/payments", get(payments::handler::list_payments))
+        .route("/payments/reconcile", get(payments::handler::reconcile_payments))
+        .route("/events/:event_id/cache/invalidate", post(tickets::handler::invalidate_event_cache))
+        .route("/events/:event_id/scrub-pii", post(tickets::handler::scrub_event_pii));
 
     // COMPOSE — one .with_state(state) at the very end.
     // Each prefix appears exactly once; sub-routers carry only relative paths.
-    Router::new()
-        .route("/health", get(health))
+    let api = Router::new()
+        .route("/api/v1/openapi.json", get(openapi::spec))
+        .route("/api/v1/meta", get(meta::meta))
         .nest("/api/v1/tickets",          ticket_routes)
+        .nest("/api/v1/events",           event_routes)
         .nest("/api/v1/scanner",          scanner_routes)
         .nest("/api/v1/payments",         payment_routes)
         .nest("/api/v1/analytics",        analytics_routes)
@@ -284,13 +422,74 @@ async fn build_router(pool: PgPool, cfg: config::Config) -> Router {
         .nest("/api/v1/vendor-reviews",   vendor_review_routes)
         .nest("/api/v1/vendor-invitations", vendor_invitation_routes)
         .nest("/api/v1/vendor/me",        vendor_me_routes)
-        .nest("/api/v1",                  promo_routes)
+        .nest("/api/v1/admin",            admin_routes)
+        .nest("/api/v1",                  promo_routes);
+
+    // Compression negotiates via Accept-Encoding and streams through the encoder,
+    // so large lists and CSV exports are never buffered whole. Routes added after
+    // this point (webhooks) are not wrapped.
+    let api = if cfg.compression_enabled { api.layer(compression_layer()) } else { api };
+    // Maintenance mode wraps everything above; webhooks are nested after it on
+    // purpose so provider callbacks still land during the freeze.
+    let api = if cfg.maintenance {
+        tracing::warn!("BUKR_MAINTENANCE is on — rejecting writes with 503");
+        api.layer(middleware::from_fn(maintenance::reject_writes))
+    } else {
+        api
+    };
+    let api = api.nest("/api/v1/payments", webhook_routes);
+
+    // BUKR_BASE_PATH mounts the API under a prefix (e.g. /bukr/api/v1/...).
+    // /health stays on root as well so existing probes keep working.
+    let app = Router::new().route("/health", get(health_with_cache));
+    let app = match cfg.base_path {
+        Some(ref base) => app
+            .route(&format!("{}/health", base), get(health_with_cache))
+            .nest(base, api),
+        None => app.merge(api),
+    };
+
+    app
+        .fallback(route_not_found)
         .layer(middleware::from_fn_with_state(state.clone(), check_gateway_secret))
         .layer(cors)
         .layer(TraceLayer::new_for_http())
         .with_state(state)
 }
 
+/// Request headers a browser may send cross-origin: the ones handlers read,
+/// plus the purchase idempotency key and the provider signature header.
+const CORS_ALLOWED_HEADERS: [&str; 5] = [
+    "authorization",
+    "content-type",
+    "x-user-id",
+    "idempotency-key",
+    "x-paystack-signature",
+];
+
+/// CORS from config: ALLOWED_ORIGINS, CORS_ALLOWED_METHODS and the fixed header list.
+fn cors_layer(cfg: &config::Config) -> CorsLayer {
+    CorsLayer::new()
+        .allow_origin(
+            cfg.allowed_origins
+                .iter()
+                .filter_map(|s| s.parse::<axum::http::HeaderValue>().ok())
+                .collect::<Vec<_>>(),
+        )
+        .allow_methods(cfg.cors_allowed_methods.clone())
+        .allow_headers(CORS_ALLOWED_HEADERS.map(axum::http::HeaderName::from_static))
+        // Browsers cache the preflight response for 1 hour.
+        // Without this, every POST/PATCH/DELETE to Rust triggers an OPTIONS
+        // preflight before the real request — doubling request count.
+        .max_age(std::time::Duration::from_secs(3600))
+}
+
+/// gzip/brotli for everything except images (the QR PNG is already compressed),
+/// SSE, gRPC and bodies under 32 bytes — DefaultPredicate covers all of those.
+fn compression_layer() -> CompressionLayer<DefaultPredicate> {
+    CompressionLayer::new().gzip(true).br(true).compress_when(DefaultPredicate::new())
+}
+
 /**
  * Gateway Secret Middleware
  * 
@@ -316,6 +515,16 @@ async fn check_gateway_secret(
     Ok(next.run(req).await)
 }
 
+/**
+ * Fallback for unmatched routes
+ * 
+ * Axum's default miss is an empty 404; this keeps misses in the standard
+ * {status, error:{code, message}} envelope like every other error.
+ */
+async fn route_not_found() -> AppError {
+    AppError::NotFound("route not found".into())
+}
+
 /**
  * Health Check Endpoint
  * 
@@ -329,3 +538,19 @@ async fn health() -> axum::Json<serde_json::Value> {
         "service": "bukr-core"
     }))
 }
+
+/**
+ * Health Check with Cache State
+ * 
+ * Same as health, plus whether Redis is currently in use. A false
+ * cache_enabled is degraded, not down — the service keeps serving from the DB.
+ * 
+ * @returns JSON with service status and cache_enabled
+ */
+async fn health_with_cache(State(cache): State<Arc<cache::Cache>>) -> axum::Json<serde_json::Value> {
+    axum::Json(serde_json::json!({
+        "status": "ok",
+        "service": "bukr-core",
+        "cache_enabled": cache.cache_enabled(),
+    }))
+}
@@ -0,0 +1,52 @@
+// Maintenance mode (BUKR_MAINTENANCE=1) — reads stay up, writes get a 503.
+//
+// Meant for migrations: operators restart with the flag set, buyers can still
+// browse events and open their tickets, but nothing that changes state runs.
+// Payment provider webhooks are deliberately NOT behind this layer — main.rs
+// nests them after it is applied — so charges that complete during the window
+// still activate tickets instead of piling up as provider retries.
+//
+// Scans are writes too (they mark tickets used); door staff see the 503 body.
+
+use axum::{
+    body::Body,
+    http::{Method, Request},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::error::{AppError, Result};
+
+/// Methods that change state. Everything else (GET, HEAD, OPTIONS) passes.
+pub fn is_write(method: &Method) -> bool {
+    matches!(*method, Method::POST | Method::PUT | Method::PATCH | Method::DELETE)
+}
+
+/// Layer body: refuse writes while maintenance mode is on.
+pub async fn reject_writes(req: Request<Body>, next: Next) -> Result<Response> {
+    if is_write(req.method()) {
+        return Err(AppError::Maintenance(
+            "Bukr is undergoing maintenance — changes are paused, please try again shortly".into(),
+        ));
+    }
+    Ok(next.run(req).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mutating_methods_are_writes() {
+        for m in [Method::POST, Method::PUT, Method::PATCH, Method::DELETE] {
+            assert!(is_write(&m), "{}", m);
+        }
+    }
+
+    #[test]
+    fn reads_pass_through() {
+        for m in [Method::GET, Method::HEAD, Method::OPTIONS] {
+            assert!(!is_write(&m), "{}", m);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+// Server meta — GET /api/v1/meta.
+//
+// Scanner and client apps read this before syncing offline bundles or
+// checking time-bucketed QRs: server_time is the clock to measure skew
+// against, version/git_sha identify the build, and features lists what this
+// deployment has switched on so clients can hide what isn't there.
+//
+// git_sha is baked in at compile time from VERGEN_GIT_SHA (set by the build
+// pipeline or a vergen build script); null when the build didn't set it.
+
+use std::sync::Arc;
+
+use axum::extract::State;
+use chrono::Utc;
+use serde_json::{json, Value};
+
+use crate::cache::Cache;
+use crate::config::Config;
+use crate::extract::Json;
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const GIT_SHA: Option<&str> = option_env!("VERGEN_GIT_SHA");
+
+/// Config-derived feature flags, fixed for the life of the process.
+pub struct ServerMeta {
+    features: Vec<&'static str>,
+}
+
+impl ServerMeta {
+    pub fn from_config(cfg: &Config) -> Self {
+        let flags = [
+            ("paystack", !cfg.paystack_secret_key.is_empty()),
+            ("outbox_webhook", cfg.outbox_webhook_url.is_some()),
+            ("compression", cfg.compression_enabled),
+            ("maintenance", cfg.maintenance),
+            ("pii_scrub", cfg.pii_scrub_interval_secs > 0),
+            ("pending_sweep", cfg.pending_sweep_interval_secs > 0),
+            ("fault_injection", cfg.payment_fault.is_some()),
+        ];
+        Self { features: flags.into_iter().filter(|(_, on)| *on).map(|(name, _)| name).collect() }
+    }
+}
+
+/// GET /api/v1/meta — server clock, build and enabled features. Redis comes
+/// and goes at runtime, so "cache" is read per request.
+pub async fn meta(State(meta): State<Arc<ServerMeta>>, State(cache): State<Arc<Cache>>) -> Json<Value> {
+    let mut features = meta.features.clone();
+    if cache.cache_enabled() {
+        features.push("cache");
+    }
+    Json(json!({
+        "status": "success",
+        "data": {
+            "server_time": crate::timestamp::format(&Utc::now()),
+            "version": VERSION,
+            "git_sha": GIT_SHA,
+            "features": features,
+        }
+    }))
+}
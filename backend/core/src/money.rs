@@ -0,0 +1,108 @@
+// Money — an amount that always travels with its currency.
+//
+// Prices used to move around as a Decimal plus a separate currency String,
+// and nothing stopped an NGN subtotal being added to a USD fee or charged
+// under the wrong code. Money pairs the two; arithmetic between different
+// currencies is an error instead of a silent wrong number.
+//
+// Amounts are kept at 2dp (fees::normalize_amount). Serialized as
+// {"amount": "1500.00", "currency": "NGN"} — the amount as a string, like
+// every other Decimal in our responses.
+
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+use crate::currency::Currency;
+use crate::error::{AppError, Result};
+use crate::fees::{format_amount, normalize_amount};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Money {
+    #[serde(with = "rust_decimal::serde::str")]
+    amount: Decimal,
+    currency: Currency,
+}
+
+impl Money {
+    pub fn new(amount: Decimal, currency: Currency) -> Self {
+        Self { amount: normalize_amount(amount), currency }
+    }
+
+    pub fn amount(&self) -> Decimal {
+        self.amount
+    }
+
+    pub fn currency(&self) -> &Currency {
+        &self.currency
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.amount > Decimal::ZERO
+    }
+
+    /// Sum of two amounts in the same currency.
+    pub fn checked_add(&self, other: &Money) -> Result<Money> {
+        self.same_currency(other)?;
+        Ok(Money::new(self.amount + other.amount, self.currency.clone()))
+    }
+
+    /// An amount in this money's currency, e.g. a fee computed from it.
+    pub fn with_amount(&self, amount: Decimal) -> Money {
+        Money::new(amount, self.currency.clone())
+    }
+
+    /// Amount in the provider's minor unit (kobo, cents): 2dp × 100.
+    /// Paystack takes every currency this way, including zero-decimal ones.
+    pub fn minor_units(&self) -> i64 {
+        (self.amount * Decimal::from(100)).trunc().try_into().unwrap_or(0)
+    }
+
+    /// Display string, e.g. "₦15,000.00".
+    pub fn formatted(&self) -> String {
+        format_amount(self.amount, self.currency.as_str())
+    }
+
+    fn same_currency(&self, other: &Money) -> Result<()> {
+        if self.currency != other.currency {
+            // Both sides come from our own rows — a mismatch is a data fault, not bad input.
+            return Err(AppError::Internal(format!(
+                "currency mismatch: cannot combine {} with {}",
+                self.currency, other.currency
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn ngn(amount: Decimal) -> Money {
+        Money::new(amount, Currency::parse("NGN").unwrap())
+    }
+
+    #[test]
+    fn same_currency_adds() {
+        assert_eq!(ngn(dec!(1000)).checked_add(&ngn(dec!(250.5))).unwrap().amount(), dec!(1250.50));
+    }
+
+    #[test]
+    fn different_currencies_refuse_to_combine() {
+        let usd = Money::new(dec!(10), Currency::parse("USD").unwrap());
+        assert!(ngn(dec!(1000)).checked_add(&usd).is_err());
+    }
+
+    #[test]
+    fn minor_units_handle_fractional_amounts() {
+        assert_eq!(ngn(dec!(15000.50)).minor_units(), 1_500_050);
+        assert_eq!(ngn(dec!(0)).minor_units(), 0);
+    }
+
+    #[test]
+    fn serializes_as_amount_and_currency() {
+        let json = serde_json::to_value(ngn(dec!(1500))).unwrap();
+        assert_eq!(json, serde_json::json!({ "amount": "1500.00", "currency": "NGN" }));
+    }
+}
@@ -0,0 +1,346 @@
+// OpenAPI contract — GET /api/v1/openapi.json.
+//
+// Schemas come from the DTO structs themselves, not from a hand-kept doc:
+// `api_schema!` names a struct's fields and
+//   - reads each field's type through a closure, so the JSON type follows the
+//     Rust type (Option → nullable and not required, Decimal → string, ...);
+//   - destructures the struct without `..`, so adding, removing or renaming a
+//     field fails to compile until the schema line is updated.
+// Field names are the wire names — these DTOs don't rename. The macro can't
+// see serde attributes, so a field's line repeats the ones that change the
+// contract: `#[default]` for #[serde(default)] (may be omitted) and
+// `#[alias = "..."]` for #[serde(alias)] (listed as a second property).
+//
+// utoipa would derive the same thing but isn't available to this build; the
+// macro covers the handful of DTOs the frontend and gateway consume.
+//
+// Paths are the one listed part: method, path, and which schema goes in and
+// comes back inside the usual { status, data } envelope.
+
+use std::sync::OnceLock;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde_json::{json, Map, Value};
+use uuid::Uuid;
+
+use crate::extract::Json;
+use crate::promos::dto::{CreatePromoRequest, PromoResponse, PromoSplit, ValidatePromoRequest, ValidatePromoResponse, BundleQuote};
+use crate::scanner::service::{
+    AccessVerifyResponse, EventSummary, ManualValidateRequest, ScanAddOn, ScanResult, ScanTicketInfo,
+    ValidateTicketRequest, VerifyAccessRequest,
+};
+use crate::tickets::dto::{
+    AddOnSelection, PaymentInitResponse, PurchaseResponse, PurchaseTicketRequest, TicketAddOn, TicketResponse,
+};
+
+/// JSON schema for a field type.
+pub trait SchemaType {
+    fn schema() -> Value;
+
+    /// Whether the field must be present. Option and Vec fields may be
+    /// omitted (requests default them; responses skip empty lists).
+    fn required() -> bool {
+        true
+    }
+}
+
+/// A named schema under components/schemas.
+pub trait Component {
+    const NAME: &'static str;
+    fn component() -> Value;
+}
+
+macro_rules! primitive {
+    ($($ty:ty => $schema:tt),* $(,)?) => {
+        $(impl SchemaType for $ty {
+            fn schema() -> Value { json!($schema) }
+        })*
+    };
+}
+
+primitive! {
+    String => { "type": "string" },
+    bool => { "type": "boolean" },
+    i32 => { "type": "integer", "format": "int32" },
+    i64 => { "type": "integer", "format": "int64" },
+    u64 => { "type": "integer", "format": "int64", "minimum": 0 },
+    f64 => { "type": "number", "format": "double" },
+    Uuid => { "type": "string", "format": "uuid" },
+    Decimal => { "type": "string", "format": "decimal" },
+    DateTime<Utc> => { "type": "string", "format": "date-time" },
+}
+
+impl<T: SchemaType> SchemaType for Option<T> {
+    fn schema() -> Value {
+        let mut s = T::schema();
+        if let Some(obj) = s.as_object_mut() {
+            if obj.contains_key("$ref") {
+                // A $ref can't carry siblings in OpenAPI 3.0
+                s = json!({ "allOf": [s], "nullable": true });
+            } else {
+                obj.insert("nullable".into(), true.into());
+            }
+        }
+        s
+    }
+
+    fn required() -> bool {
+        false
+    }
+}
+
+impl<T: SchemaType> SchemaType for Vec<T> {
+    fn schema() -> Value {
+        json!({ "type": "array", "items": T::schema() })
+    }
+
+    fn required() -> bool {
+        false
+    }
+}
+
+// Type inference does the work: `field(|v: &Dto| &v.name)` picks T from the field.
+fn field<S, T: SchemaType>(_: impl Fn(&S) -> &T) -> (Value, bool) {
+    (T::schema(), T::required())
+}
+
+// One `#[...]` marker from an api_schema! field line; true when it makes
+// the field optional.
+macro_rules! field_marker {
+    ($properties:ident, $field:ident, default) => {
+        true
+    };
+    ($properties:ident, $field:ident, alias = $alias:literal) => {{
+        let schema = alias_of(&$properties[stringify!($field)], stringify!($field));
+        $properties.insert($alias.into(), schema);
+        false
+    }};
+}
+
+/// Schema for a serde alias: the field's own, marked as the other spelling.
+fn alias_of(schema: &Value, field: &str) -> Value {
+    let mut s = if schema.get("$ref").is_some() { json!({ "allOf": [schema] }) } else { schema.clone() };
+    s["description"] = format!("Alias of {}", field).into();
+    s
+}
+
+macro_rules! api_schema {
+    ($ty:ident { $($(#[$marker:ident $(= $arg:literal)?])* $field:ident),* $(,)? }) => {
+        impl SchemaType for $ty {
+            fn schema() -> Value {
+                json!({ "$ref": concat!("#/components/schemas/", stringify!($ty)) })
+            }
+        }
+
+        impl Component for $ty {
+            const NAME: &'static str = stringify!($ty);
+
+            fn component() -> Value {
+                // Exhaustive on purpose — see the module comment.
+                #[allow(dead_code)]
+                fn every_field_listed(v: &$ty) {
+                    let $ty { $($field: _),* } = v;
+                }
+
+                let mut properties = Map::new();
+                let mut required: Vec<&str> = Vec::new();
+                $(
+                    let (schema, is_required) = field(|v: &$ty| &v.$field);
+                    properties.insert(stringify!($field).into(), schema);
+                    let defaulted = false $(| field_marker!(properties, $field, $marker $(= $arg)?))*;
+                    if is_required && !defaulted {
+                        required.push(stringify!($field));
+                    }
+                )*
+                json!({ "type": "object", "properties": properties, "required": required })
+            }
+        }
+    };
+}
+
+// ─── Tickets ──────────────────────────────────────────────────────────────────
+
+api_schema!(PurchaseTicketRequest {
+    event_id, quantity, ticket_type, promo_code, excitement_rating, payment_provider,
+    referral_code, #[alias = "intent_id"] idempotency_key, usage_model, usage_total, valid_from, valid_until,
+    is_renewable, add_ons, chosen_price, guest_email, callback_url,
+});
+api_schema!(AddOnSelection { add_on_id, quantity });
+api_schema!(PurchaseResponse { ticket, payment, add_ons, promo_split });
+api_schema!(TicketResponse {
+    id, ticket_id, event_id, event_title, event_date, event_time, event_starts_at, event_timezone,
+    event_location, event_emoji, event_image_url, ticket_type, quantity, usage_limit, usage_count,
+    unit_price, discount_applied, total_price, total_price_formatted, currency, status,
+    qr_code_data, valid_from, valid_until, receipt_number, order_id, purchase_date,
+});
+api_schema!(PaymentInitResponse {
+    provider, authorization_url, checkout_url, reference, amount, amount_formatted,
+    booking_fee, tax_amount, currency, platform_fee, bukrshield_fee, organizer_payout,
+});
+api_schema!(TicketAddOn { add_on_id, name, quantity, unit_price });
+
+// ─── Promos ───────────────────────────────────────────────────────────────────
+
+api_schema!(CreatePromoRequest {
+    code, promo_type, #[default] discount_percentage, buy_quantity, free_quantity, ticket_limit, expires_at,
+    #[default] allow_partial, activates_at,
+});
+api_schema!(PromoResponse {
+    id, event_id, code, promo_type, discount_percentage, buy_quantity, free_quantity,
+    ticket_limit, used_count, is_active, allow_partial, activates_at, expires_at, created_at,
+});
+api_schema!(ValidatePromoRequest { event_id, code, quantity });
+api_schema!(ValidatePromoResponse { valid, promo_type, discount_percentage, remaining_uses, bundle, partial });
+api_schema!(BundleQuote { buy_quantity, free_quantity, description, quantity, free_units });
+api_schema!(PromoSplit { discounted_units, full_price_units, discount_percentage, effective_discount });
+
+// ─── Scanner ──────────────────────────────────────────────────────────────────
+
+api_schema!(VerifyAccessRequest { event_id, event_key, access_code });
+api_schema!(AccessVerifyResponse { verified, event, gate_label });
+api_schema!(EventSummary { id, title, date, starts_at, timezone });
+api_schema!(ValidateTicketRequest { ticket_id, event_key, qr_data, lat, lon, admit });
+api_schema!(ManualValidateRequest { ticket_id, event_id, event_key, lat, lon, admit });
+api_schema!(ScanResult { result, ticket, message, new_qr_data, usage_left });
+api_schema!(ScanTicketInfo {
+    ticket_id, user_name, ticket_type, quantity, scanned_at, scanned_by, scanned_by_name,
+    usage_left, usage_total, admitted, remaining, add_ons,
+});
+api_schema!(ScanAddOn { name, quantity });
+
+fn components() -> Map<String, Value> {
+    let mut schemas = Map::new();
+    macro_rules! register {
+        ($($ty:ty),* $(,)?) => {
+            $(schemas.insert(<$ty as Component>::NAME.into(), <$ty as Component>::component());)*
+        };
+    }
+    register!(
+        PurchaseTicketRequest, AddOnSelection, PurchaseResponse, TicketResponse, PaymentInitResponse, TicketAddOn,
+        CreatePromoRequest, PromoResponse, ValidatePromoRequest, ValidatePromoResponse, BundleQuote,
+        PromoSplit, VerifyAccessRequest, AccessVerifyResponse, EventSummary, ValidateTicketRequest, ManualValidateRequest,
+        ScanResult, ScanTicketInfo, ScanAddOn,
+    );
+    schemas
+}
+
+// (method, path, summary, request body, response data)
+type Operation = (&'static str, &'static str, &'static str, Option<&'static str>, Option<Value>);
+
+fn operations() -> Vec<Operation> {
+    vec![
+        ("post", "/api/v1/tickets/purchase", "Buy tickets",
+            Some(PurchaseTicketRequest::NAME), Some(PurchaseResponse::schema())),
+        ("post", "/api/v1/tickets/guest-purchase", "Buy tickets without an account",
+            Some(PurchaseTicketRequest::NAME), Some(PurchaseResponse::schema())),
+        ("post", "/api/v1/events/{event_id}/promos", "Create a promo code",
+            Some(CreatePromoRequest::NAME), Some(PromoResponse::schema())),
+        ("get", "/api/v1/events/{event_id}/promos", "List an event's promo codes",
+            None, Some(Vec::<PromoResponse>::schema())),
+        ("post", "/api/v1/promos/validate", "Check a promo code",
+            Some(ValidatePromoRequest::NAME), Some(ValidatePromoResponse::schema())),
+        ("post", "/api/v1/scanner/verify-access", "Verify a scanner access code",
+            Some(VerifyAccessRequest::NAME), Some(AccessVerifyResponse::schema())),
+        ("post", "/api/v1/scanner/validate", "Validate a scanned QR or barcode",
+            Some(ValidateTicketRequest::NAME), Some(ScanResult::schema())),
+        ("post", "/api/v1/scanner/manual-validate", "Validate a typed ticket id",
+            Some(ManualValidateRequest::NAME), Some(ScanResult::schema())),
+    ]
+}
+
+/// The whole document. Pure, so it can be built once and checked in tests.
+pub fn document() -> Value {
+    let mut paths = Map::new();
+    for (method, path, summary, request, data) in operations() {
+        let mut op = json!({
+            "summary": summary,
+            "responses": {
+                "200": {
+                    "description": "Success",
+                    "content": { "application/json": { "schema": {
+                        "type": "object",
+                        "properties": {
+                            "status": { "type": "string", "enum": ["success"] },
+                            "data": data.unwrap_or_else(|| json!({})),
+                        },
+                        "required": ["status", "data"],
+                    }}}
+                }
+            }
+        });
+        if let Some(name) = request {
+            op["requestBody"] = json!({
+                "required": true,
+                "content": { "application/json": { "schema": {
+                    "$ref": format!("#/components/schemas/{}", name)
+                }}}
+            });
+        }
+        let entry = paths.entry(path.to_string()).or_insert_with(|| json!({}));
+        entry[method] = op;
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": { "title": "Bukr Core API", "version": env!("CARGO_PKG_VERSION") },
+        "paths": paths,
+        "components": { "schemas": components() },
+    })
+}
+
+/// GET /api/v1/openapi.json — built on first request, then served from memory.
+pub async fn spec() -> Json<Value> {
+    static DOC: OnceLock<Value> = OnceLock::new();
+    Json(DOC.get_or_init(document).clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn optional_fields_are_not_required() {
+        let purchase = PurchaseTicketRequest::component();
+        let required: Vec<&str> = purchase["required"].as_array().unwrap()
+            .iter().filter_map(Value::as_str).collect();
+        assert!(required.contains(&"event_id"));
+        assert!(required.contains(&"payment_provider"));
+        assert!(!required.contains(&"promo_code"));
+        assert_eq!(purchase["properties"]["promo_code"]["nullable"], true);
+    }
+
+    #[test]
+    fn serde_defaults_are_not_required_and_aliases_are_listed() {
+        let promo = CreatePromoRequest::component();
+        let required: Vec<&str> = promo["required"].as_array().unwrap()
+            .iter().filter_map(Value::as_str).collect();
+        assert!(required.contains(&"code"));
+        assert!(!required.contains(&"discount_percentage"));
+        assert!(!required.contains(&"allow_partial"));
+
+        let purchase = PurchaseTicketRequest::component();
+        assert_eq!(purchase["properties"]["intent_id"]["type"], "string");
+        assert_eq!(purchase["properties"]["intent_id"]["description"], "Alias of idempotency_key");
+    }
+
+    #[test]
+    fn decimals_are_strings_and_nested_dtos_are_refs() {
+        let ticket = TicketResponse::component();
+        assert_eq!(ticket["properties"]["total_price"]["type"], "string");
+        let purchase = PurchaseResponse::component();
+        assert_eq!(purchase["properties"]["ticket"]["$ref"], "#/components/schemas/TicketResponse");
+        assert_eq!(purchase["properties"]["add_ons"]["items"]["$ref"], "#/components/schemas/TicketAddOn");
+    }
+
+    #[test]
+    fn every_ref_resolves() {
+        let doc = document();
+        let text = doc.to_string();
+        let schemas = doc["components"]["schemas"].as_object().unwrap();
+        for part in text.split("#/components/schemas/").skip(1) {
+            let name: String = part.chars().take_while(|c| c.is_ascii_alphanumeric()).collect();
+            assert!(schemas.contains_key(&name), "dangling $ref {}", name);
+        }
+    }
+}
@@ -0,0 +1,190 @@
+// Transactional outbox writer.
+// Inserts into outbox_events inside the caller's transaction — the row commits
+// or rolls back together with the state change that produced it.
+// Unlike notifications::queue, errors ARE propagated: losing a refund request
+// silently is worse than failing the whole operation.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Row, Transaction};
+use uuid::Uuid;
+
+use crate::cache::Cache;
+
+pub async fn enqueue(
+    tx: &mut Transaction<'_, Postgres>,
+    event_type: &str,
+    aggregate_id: Uuid,
+    payload: serde_json::Value,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO outbox_events (event_type, aggregate_id, payload)
+         VALUES ($1, $2, $3)",
+    )
+    .bind(event_type)
+    .bind(aggregate_id)
+    .bind(payload)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+// ─── Consumer ─────────────────────────────────────────────────────────────────
+//
+// At-least-once delivery. Rows are claimed by pushing next_attempt_at forward by
+// a lease (FOR UPDATE SKIP LOCKED, so several instances never claim the same
+// row), delivered outside any transaction, then marked processed. A crash
+// between delivery and the processed_at write re-delivers after the lease —
+// receivers must dedupe on the "id" field of the envelope.
+
+const POLL_INTERVAL_SECS: u64 = 2;
+const BATCH_SIZE: i64 = 50;
+const CLAIM_LEASE_SECS: i64 = 60;
+const MAX_ATTEMPTS: i32 = 10;
+const BASE_BACKOFF_SECS: i64 = 5;
+const MAX_BACKOFF_SECS: i64 = 3600;
+const REDIS_QUEUE_KEY: &str = "bukr:outbox";
+
+/// Where drained outbox rows go. Webhook wins when both are configured.
+pub enum OutboxSink {
+    Webhook { client: reqwest::Client, url: String },
+    Redis(Arc<Cache>),
+}
+
+impl OutboxSink {
+    /// Build from config. None = no sink configured, consumer stays off and rows
+    /// simply accumulate until one is.
+    pub fn from_config(webhook_url: Option<String>, cache: Arc<Cache>) -> Option<Self> {
+        if let Some(url) = webhook_url {
+            // Operator-set target, often internal — TLS/timeout/no-redirect
+            // hardening only, not the user-webhook address policy.
+            let client = crate::webhook::base_client(std::time::Duration::from_secs(10));
+            return Some(OutboxSink::Webhook { client, url });
+        }
+        // The shared cache reconnects on its own; while Redis is down LPUSH
+        // fails and rows are retried with backoff like any other sink error.
+        if cache.is_configured() {
+            return Some(OutboxSink::Redis(cache));
+        }
+        None
+    }
+
+    async fn deliver(&mut self, envelope: &serde_json::Value) -> Result<(), String> {
+        match self {
+            OutboxSink::Webhook { client, url } => {
+                let resp = client
+                    .post(url.as_str())
+                    .json(envelope)
+                    .send()
+                    .await
+                    .map_err(|e| e.to_string())?;
+                if resp.status().is_success() {
+                    Ok(())
+                } else {
+                    Err(format!("webhook returned {}", resp.status()))
+                }
+            }
+            OutboxSink::Redis(cache) => cache.lpush(REDIS_QUEUE_KEY, &envelope.to_string()).await,
+        }
+    }
+}
+
+/// Spawn the consumer loop. Called once from main after the pool is up.
+pub fn spawn_consumer(pool: PgPool, sink: OutboxSink) {
+    tokio::spawn(async move {
+        let mut sink = sink;
+        let mut tick = tokio::time::interval(std::time::Duration::from_secs(POLL_INTERVAL_SECS));
+        loop {
+            tick.tick().await;
+            if let Err(e) = drain_batch(&pool, &mut sink).await {
+                tracing::error!("Outbox consumer poll failed: {}", e);
+            }
+        }
+    });
+}
+
+async fn drain_batch(pool: &PgPool, sink: &mut OutboxSink) -> Result<(), sqlx::Error> {
+    let rows = sqlx::query(
+        "UPDATE outbox_events
+         SET next_attempt_at = NOW() + make_interval(secs => $1)
+         WHERE id IN (
+             SELECT id FROM outbox_events
+             WHERE processed_at IS NULL
+               AND dead_lettered_at IS NULL
+               AND next_attempt_at <= NOW()
+             ORDER BY next_attempt_at
+             LIMIT $2
+             FOR UPDATE SKIP LOCKED
+         )
+         RETURNING id, event_type, aggregate_id, payload, attempts, created_at",
+    )
+    .bind(CLAIM_LEASE_SECS as f64)
+    .bind(BATCH_SIZE)
+    .fetch_all(pool)
+    .await?;
+
+    for row in rows {
+        let id: Uuid = row.get("id");
+        let event_type: String = row.get("event_type");
+        let attempts: i32 = row.get("attempts");
+        let envelope = serde_json::json!({
+            "id": id,
+            "event_type": event_type,
+            "aggregate_id": row.get::<Option<Uuid>, _>("aggregate_id"),
+            "payload": row.get::<serde_json::Value, _>("payload"),
+            "created_at": crate::timestamp::format(&row.get::<DateTime<Utc>, _>("created_at")),
+        });
+
+        match sink.deliver(&envelope).await {
+            Ok(()) => {
+                sqlx::query("UPDATE outbox_events SET processed_at = NOW(), attempts = attempts + 1, last_error = NULL WHERE id = $1")
+                    .bind(id)
+                    .execute(pool)
+                    .await?;
+            }
+            Err(err) => record_failure(pool, id, &event_type, attempts + 1, &err).await?,
+        }
+    }
+    Ok(())
+}
+
+async fn record_failure(
+    pool: &PgPool,
+    id: Uuid,
+    event_type: &str,
+    attempts: i32,
+    err: &str,
+) -> Result<(), sqlx::Error> {
+    if attempts >= MAX_ATTEMPTS {
+        tracing::error!("Outbox {} ({}) dead-lettered after {} attempts: {}", id, event_type, attempts, err);
+        sqlx::query("UPDATE outbox_events SET attempts = $2, last_error = $3, dead_lettered_at = NOW() WHERE id = $1")
+            .bind(id)
+            .bind(attempts)
+            .bind(err)
+            .execute(pool)
+            .await?;
+        return Ok(());
+    }
+
+    let delay = backoff_secs(attempts);
+    tracing::warn!("Outbox {} ({}) attempt {} failed, retrying in {}s: {}", id, event_type, attempts, delay, err);
+    sqlx::query(
+        "UPDATE outbox_events
+         SET attempts = $2, last_error = $3, next_attempt_at = NOW() + make_interval(secs => $4)
+         WHERE id = $1",
+    )
+    .bind(id)
+    .bind(attempts)
+    .bind(err)
+    .bind(delay as f64)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 5s, 10s, 20s, ... capped at one hour.
+fn backoff_secs(attempts: i32) -> i64 {
+    let exp = attempts.clamp(1, 20) as u32 - 1;
+    (BASE_BACKOFF_SECS.saturating_mul(1i64 << exp)).min(MAX_BACKOFF_SECS)
+}
@@ -0,0 +1,92 @@
+// Payment references — the id we hand Paystack and match webhooks on.
+//
+// `{prefix}{unix_millis}-{seq}-{rand}`. The millisecond clock orders refs
+// across restarts, the per-process counter separates refs minted in the same
+// millisecond on this instance, and the random tail separates instances.
+// The old `{unix_secs}-{u32}` form could repeat within a busy second.
+//
+// Uniqueness is still the database's call: payment_transactions.provider_ref
+// and ticket_resale_listings.payment_ref are UNIQUE. `reserve` checks a fresh
+// ref against every table that carries one inside the caller's transaction
+// and mints another on a hit, but it can't see another transaction's
+// uncommitted insert — callers must still check the insert that claims the
+// ref and mint again when it loses.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crate::error::{AppError, Result};
+
+pub const TICKET_PREFIX: &str = "BUKR-PAY-";
+
+/// Fresh refs tried before giving up.
+pub(crate) const MAX_ATTEMPTS: usize = 3;
+
+static SEQ: AtomicU32 = AtomicU32::new(0);
+
+/// Mint a reference. Safe to call from any thread; never blocks.
+pub fn next(prefix: &str) -> String {
+    let seq = SEQ.fetch_add(1, Ordering::Relaxed);
+    format!(
+        "{}{}-{:08x}-{:06x}",
+        prefix,
+        chrono::Utc::now().timestamp_millis(),
+        seq,
+        rand::random::<u32>() & 0x00ff_ffff
+    )
+}
+
+/// Mint a reference no ticket, resale listing or payment already uses.
+pub async fn reserve(tx: &mut sqlx::Transaction<'_, sqlx::Postgres>, prefix: &str) -> Result<String> {
+    for _ in 0..MAX_ATTEMPTS {
+        let reference = next(prefix);
+        let taken: bool = sqlx::query_scalar(
+            r#"SELECT EXISTS(SELECT 1 FROM tickets WHERE payment_ref = $1)
+                   OR EXISTS(SELECT 1 FROM payment_transactions WHERE provider_ref = $1)
+                   OR EXISTS(SELECT 1 FROM ticket_resale_listings WHERE payment_ref = $1)"#,
+        )
+        .bind(&reference)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(AppError::Database)?;
+
+        if !taken {
+            return Ok(reference);
+        }
+        tracing::warn!("Payment reference {} already in use — minting another", reference);
+    }
+    Err(AppError::Internal("Could not mint a unique payment reference".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn tight_loop_never_collides() {
+        let refs: HashSet<String> = (0..100_000).map(|_| next(TICKET_PREFIX)).collect();
+        assert_eq!(refs.len(), 100_000);
+    }
+
+    #[test]
+    fn threads_never_collide() {
+        let handles: Vec<_> = (0..8)
+            .map(|_| std::thread::spawn(|| (0..10_000).map(|_| next(TICKET_PREFIX)).collect::<Vec<_>>()))
+            .collect();
+        let mut refs = HashSet::new();
+        for h in handles {
+            for r in h.join().unwrap() {
+                assert!(refs.insert(r), "duplicate payment reference");
+            }
+        }
+        assert_eq!(refs.len(), 80_000);
+    }
+
+    #[test]
+    fn refs_stay_paystack_safe() {
+        let r = next(TICKET_PREFIX);
+        assert!(r.starts_with(TICKET_PREFIX));
+        assert!(r.len() <= 100);
+        assert!(r.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'));
+    }
+}
@@ -1,14 +1,14 @@
 use axum::{
     body::Bytes,
-    extract::{Path, State},
+    extract::State,
     http::HeaderMap,
-    Json,
 };
 use serde_json::{json, Value};
 use uuid::Uuid;
 
 use crate::error::{AppError, Result};
-use super::service::{PaymentService, InitializePaymentRequest, PaystackWebhookPayload};
+use crate::extract::{Json, Path, Query};
+use super::service::{PaymentService, InitializePaymentRequest, PaymentListQuery, PaystackWebhookPayload, ReconcileQuery, ResumePaymentQuery};
 use std::sync::Arc;
 
 fn extract_user_id(headers: &HeaderMap) -> Result<Uuid> {
@@ -19,6 +19,19 @@ fn extract_user_id(headers: &HeaderMap) -> Result<Uuid> {
         .ok_or(AppError::Unauthorized)
 }
 
+// Admin routes: the gateway forwards X-User-Type from the verified JWT.
+fn require_admin(headers: &HeaderMap) -> Result<Uuid> {
+    let user_id = extract_user_id(headers)?;
+    let user_type = headers
+        .get("x-user-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    if user_type != "admin" {
+        return Err(AppError::Forbidden);
+    }
+    Ok(user_id)
+}
+
 pub async fn initialize_payment(
     State(service): State<Arc<PaymentService>>,
     headers: HeaderMap,
@@ -29,6 +42,18 @@ pub async fn initialize_payment(
     Ok(Json(json!({ "status": "success", "data": result })))
 }
 
+// GET /api/v1/tickets/:ticket_id/resume-payment?callback_url=
+pub async fn resume_payment(
+    State(service): State<Arc<PaymentService>>,
+    headers: HeaderMap,
+    Path(ticket_id): Path<String>,
+    Query(query): Query<ResumePaymentQuery>,
+) -> Result<Json<Value>> {
+    let user_id = extract_user_id(&headers)?;
+    let result = service.resume(user_id, &ticket_id, query.callback_url).await?;
+    Ok(Json(json!({ "status": "success", "data": result })))
+}
+
 pub async fn paystack_webhook(
     State(service): State<Arc<PaymentService>>,
     headers: HeaderMap,
@@ -39,6 +64,10 @@ pub async fn paystack_webhook(
         .and_then(|v| v.to_str().ok())
         .unwrap_or("");
 
+    // An empty body can't be a real event — say so instead of a bare 401.
+    if body.is_empty() {
+        return Err(AppError::Validation("empty webhook body".into()));
+    }
     if !service.verify_paystack_signature(&body, signature) {
         return Err(AppError::Unauthorized);
     }
@@ -57,3 +86,25 @@ pub async fn verify_payment(
     let result = service.verify_payment(&reference).await?;
     Ok(Json(json!({ "status": "success", "data": result })))
 }
+
+// GET /api/v1/admin/payments?provider=&status=&from=&to=&limit=&offset=
+pub async fn list_payments(
+    State(service): State<Arc<PaymentService>>,
+    headers: HeaderMap,
+    Query(query): Query<PaymentListQuery>,
+) -> Result<Json<Value>> {
+    require_admin(&headers)?;
+    let page = service.list_transactions(query).await?;
+    Ok(Json(json!({ "status": "success", "data": page })))
+}
+
+// GET /api/v1/admin/payments/reconcile?date=YYYY-MM-DD
+pub async fn reconcile_payments(
+    State(service): State<Arc<PaymentService>>,
+    headers: HeaderMap,
+    Query(query): Query<ReconcileQuery>,
+) -> Result<Json<Value>> {
+    require_admin(&headers)?;
+    let report = service.reconcile(query.date).await?;
+    Ok(Json(json!({ "status": "success", "data": report })))
+}
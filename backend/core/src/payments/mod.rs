@@ -11,3 +11,6 @@
 
 pub mod handler;
 pub mod service;
+
+#[cfg(test)]
+mod service_test;
@@ -19,27 +19,47 @@
  * 3. Verify webhook signature -> verify_paystack_signature()
  * 4. Update payment status -> handle_paystack_webhook()
  * 5. Mark ticket as valid -> ticket status update
+ *
+ * Split payments: a ticket may be paid in parts (card + bank transfer on a
+ * corporate order). Each part is its own payment_transactions row with its
+ * own reference; the ticket turns valid once its successful parts cover
+ * total_price.
  * 
  * Supported Providers:
  * - Paystack (African markets)
  * - Stripe (Global markets)
  */
 
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
 use hmac::{Hmac, Mac};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sha2::Sha512;
 use sqlx::{PgPool, Row};
+use subtle::ConstantTimeEq;
 use uuid::Uuid;
 
 use crate::error::{AppError, Result};
-use crate::fees::{compute_fees, FeeMode};
+use crate::fault::PaymentFault;
+use crate::tickets::resale::{self, BuyResaleRequest, RESALE_HOLD_MINUTES, RESALE_REF_LIKE, RESALE_REF_PREFIX};
+use crate::tickets::status::TicketStatus;
+use crate::webhook::HostPolicy;
+use crate::currency::Currency;
+use crate::fees::{split_order, FeeMode};
+use crate::promos::service::PromoPricing;
+use crate::money::Money;
+use crate::payment_ref;
 
 #[derive(Debug, Deserialize)]
 pub struct InitializePaymentRequest {
     pub ticket_id: Uuid,
     pub provider: String,
     pub callback_url: String,
+    #[serde(default)]
+    pub amount: Option<Decimal>,             // Part of a split payment; default is everything still owed
 }
 
 #[derive(Debug, Serialize)]
@@ -47,6 +67,23 @@ pub struct PaymentInitResponse {
     pub provider: String,
     pub authorization_url: Option<String>,
     pub reference: String,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub amount: Decimal,
+    pub amount_formatted: String,            // Display string, e.g. "₦15,000.00"
+    pub currency: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResumePaymentQuery {
+    pub callback_url: String,                // Used only if a new provider session is needed
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResumePaymentResponse {
+    pub reference: String,
+    pub status: String,                      // pending | success | refunded | failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment: Option<PaymentInitResponse>, // Checkout to continue; only while pending
 }
 
 #[derive(Debug, Deserialize)]
@@ -63,12 +100,120 @@ pub struct PaystackWebhookData {
     pub currency: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ReconcileQuery {
+    pub date: chrono::NaiveDate, // YYYY-MM-DD, UTC day of the local transaction
+}
+
+// GET /admin/payments filters. All optional; from is inclusive, to exclusive.
+#[derive(Debug, Deserialize)]
+pub struct PaymentListQuery {
+    pub provider: Option<String>,
+    pub status: Option<String>,
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PaymentTransaction {
+    pub id: Uuid,
+    pub ticket_id: Option<Uuid>,
+    pub user_id: Uuid,
+    pub provider: String,
+    pub provider_ref: String,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub amount: Decimal,
+    pub currency: String,
+    pub status: Option<String>,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub platform_fee: Decimal,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub bukrshield_fee: Decimal,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub organizer_payout: Decimal,
+    #[serde(with = "crate::timestamp::option")]
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(with = "crate::timestamp::option")]
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PaymentListResponse {
+    pub payments: Vec<PaymentTransaction>,
+    pub total: i64,                          // Rows matching the filters, ignoring limit/offset
+    pub limit: i64,
+    pub offset: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReconcileReport {
+    pub date: chrono::NaiveDate,
+    pub checked: usize,                      // Local 'success' rows looked up at the provider
+    pub matched: usize,                      // Provider agrees
+    pub discrepancies: Vec<ReconcileEntry>,  // Provider says something other than success
+    pub unchecked: Vec<ReconcileEntry>,      // Provider lookup failed — retry later
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReconcileEntry {
+    pub reference: String,
+    pub provider: String,
+    pub ticket_id: Option<Uuid>,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub amount: Decimal,
+    pub currency: String,
+    pub local_status: String,
+    pub provider_status: Option<String>,
+    pub error: Option<String>,
+}
+
+// Smallest charge Paystack accepts per currency (major units). Anything below
+// is rejected by the provider with an opaque error, so we check first.
+// Overridable per currency via PAYMENT_MIN_CHARGE, e.g. "NGN=100,USD=1.50".
+const DEFAULT_MIN_CHARGES: &[(&str, &str)] = &[
+    ("NGN", "50.00"),
+    ("GHS", "0.10"),
+    ("ZAR", "1.00"),
+    ("KES", "3.00"),
+    ("USD", "2.00"),
+];
+
+// payment_transactions.status CHECK constraint values.
+const PAYMENT_STATUSES: &[&str] = &["pending", "success", "failed", "refunded"];
+
+// Admin payment listing page size.
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+
+// How long an unpaid checkout holds its share of a ticket's balance. Older
+// pending parts are treated as abandoned; if one is paid after all, the
+// webhook queues a refund for whatever it paid beyond the total.
+const PART_HOLD_MINUTES: i64 = 30;
+
+// Concurrent provider lookups per batch — keeps us well under Paystack's rate limit.
+const RECONCILE_BATCH_SIZE: usize = 10;
+
+// The frontend polls verify every second or so after the provider redirect;
+// a few seconds of cache turns that burst into one lookup per reference.
+const VERIFY_CACHE_TTL_SECS: u64 = 3;
+const VERIFY_CACHE_CAPACITY: u64 = 10_000;
+
 pub struct PaymentService {
     pool: PgPool,
     paystack_secret: String,
     paystack_webhook_secret: String,
     // Shared client — connection pool reused across all Paystack calls.
     http: reqwest::Client,
+    // Currency code → provider minimum charge (defaults + config overrides)
+    min_charges: HashMap<String, Decimal>,
+    // Dev-only simulated provider failure (see fault.rs)
+    fault: Option<PaymentFault>,
+    // Where the provider may send the buyer back to (CALLBACK_ALLOWED_HOSTS)
+    callback_policy: HostPolicy,
+    // Recent verify results by reference; concurrent misses share one lookup
+    verifications: moka::future::Cache<String, serde_json::Value>,
 }
 
 impl PaymentService {
@@ -76,7 +221,16 @@ impl PaymentService {
         pool: PgPool,
         paystack_secret: String,
         paystack_webhook_secret: String,
+        min_charge_overrides: HashMap<String, Decimal>,
+        fault: Option<PaymentFault>,
+        callback_policy: HostPolicy,
     ) -> Self {
+        let mut min_charges: HashMap<String, Decimal> = DEFAULT_MIN_CHARGES
+            .iter()
+            .map(|(code, min)| (code.to_string(), min.parse().expect("valid default minimum")))
+            .collect();
+        min_charges.extend(min_charge_overrides);
+
         Self {
             pool,
             paystack_secret,
@@ -85,6 +239,26 @@ impl PaymentService {
                 .timeout(std::time::Duration::from_secs(10))
                 .build()
                 .expect("reqwest client build failed"),
+            min_charges,
+            fault,
+            callback_policy,
+            verifications: verification_cache(),
+        }
+    }
+
+    /// Reject amounts the provider would refuse. Zero-amount comp tickets never
+    /// reach the provider, and currencies without a configured minimum pass.
+    fn check_min_charge(&self, charge: &Money) -> Result<()> {
+        if !charge.is_positive() {
+            return Ok(());
+        }
+        let (amount, currency) = (charge.amount(), charge.currency());
+        match self.min_charges.get(currency.as_str()) {
+            Some(min) if amount < *min => Err(AppError::Validation(format!(
+                "amount below provider minimum: {} {} is less than the minimum charge of {} {}",
+                amount, currency, min, currency
+            ))),
+            _ => Ok(()),
         }
     }
 
@@ -92,98 +266,423 @@ impl PaymentService {
      * Initialize Payment: Start payment process with provider
      * 
      * Flow:
-     * 1. Fetch ticket details (price, currency, user email)
-     * 2. Generate or reuse payment reference
-     * 3. Call provider API (Paystack or Stripe)
-     * 4. Record transaction in database
+     * 1. Lock the ticket and fetch its details (price, currency, user email,
+     *    amount already paid, parts still in flight), check the event's
+     *    allowed providers
+     * 2. Size the charge (a split part or the rest of the balance), generate or
+     *    reuse payment reference, check provider minimum
+     * 3. Record the pending transaction and release the lock, so the next
+     *    checkout for this ticket counts it
+     * 4. Call provider API (Paystack or Stripe); on failure the unsent
+     *    transaction is removed again
      * 5. Return authorization URL
      * 
+     * A pending part younger than PART_HOLD_MINUTES holds its share of the
+     * balance. While the ticket's own whole-total checkout is open, no part
+     * can be started — paying both would charge the buyer twice.
+     * 
      * @param user_id - User making payment
      * @param req - Payment initialization request
      * @returns Payment URL and reference
      */
     pub async fn initialize(&self, user_id: Uuid, req: InitializePaymentRequest) -> Result<PaymentInitResponse> {
+        // Forwarded to the provider, which redirects the buyer there — vet it first.
+        let callback_url = check_callback_url(&self.callback_policy, &req.callback_url)?;
+
         // Fetch ticket with user email (needed for payment gateway).
         // Also fetch unit_price, quantity, discount_applied, and fee_mode so the
         // fee breakdown matches exactly what was computed at purchase time.
         // fee_mode is stored on the event at purchase; we read it from the ticket's
         // joined event row so Absorb-mode events compute correctly on retry.
+        // Resale charges paid a seller, not this ticket's balance.
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
         let ticket = sqlx::query(
             r#"SELECT t.id, t.total_price, t.unit_price, t.quantity, t.currency,
-                      t.payment_ref, t.discount_applied, u.email,
-                      COALESCE(e.fee_mode, 'pass_to_buyer') as fee_mode
+                      t.payment_ref, t.discount_applied, t.promo_units, t.promo_rate, u.email,
+                      COALESCE(e.fee_mode, 'pass_to_buyer') as fee_mode, e.allowed_providers,
+                      (SELECT COALESCE(SUM(a.unit_price * a.quantity), 0) FROM ticket_add_ons a
+                        WHERE a.ticket_id = t.id) AS add_ons_total,
+                      (SELECT COALESCE(SUM(p.amount), 0) FROM payment_transactions p
+                        WHERE p.ticket_id = t.id AND p.status = 'success'
+                          AND p.provider_ref NOT LIKE $3) AS amount_paid,
+                      (SELECT COALESCE(SUM(p.amount), 0) FROM payment_transactions p
+                        WHERE p.ticket_id = t.id AND p.status = 'pending'
+                          AND p.provider_ref NOT LIKE $3
+                          AND p.provider_ref IS DISTINCT FROM t.payment_ref
+                          AND p.created_at > NOW() - make_interval(mins => $4)) AS amount_held,
+                      EXISTS(SELECT 1 FROM payment_transactions p
+                        WHERE p.provider_ref = t.payment_ref AND p.status = 'pending'
+                          AND p.created_at > NOW() - make_interval(mins => $4)) AS whole_open
                FROM tickets t
                JOIN users u ON t.user_id = u.id
                JOIN events e ON t.event_id = e.id
-               WHERE t.id = $1 AND t.user_id = $2"#,
+               WHERE t.id = $1 AND t.user_id = $2
+               FOR UPDATE OF t"#,
         )
         .bind(req.ticket_id)
         .bind(user_id)
-        .fetch_optional(&self.pool)
+        .bind(RESALE_REF_LIKE)
+        .bind(PART_HOLD_MINUTES as i32)
+        .fetch_optional(&mut *tx)
         .await
         .map_err(AppError::Database)?
         .ok_or_else(|| AppError::NotFound("Ticket not found".into()))?;
 
-        let total_price: Decimal  = ticket.get("total_price");
+        let currency = Currency::parse(ticket.get("currency"))?;
+        let total = Money::new(ticket.get("total_price"), currency.clone());
         let unit_price: Decimal   = ticket.get("unit_price");
         let quantity: i32         = ticket.get("quantity");
-        let currency: String      = ticket.get("currency");
         let email: String         = ticket.get("email");
         let payment_ref: Option<String> = ticket.get("payment_ref");
-        let discount_applied: Decimal = ticket.get("discount_applied");
+        let pricing = PromoPricing::stored(
+            quantity, ticket.get("discount_applied"), ticket.get("promo_units"), ticket.get("promo_rate"),
+        );
         let fee_mode_str: String  = ticket.get("fee_mode");
+        let allowed_providers: Option<Vec<String>> = ticket.get("allowed_providers");
+        let add_ons_total: Decimal = ticket.get("add_ons_total");
+        let amount_paid: Decimal  = ticket.get("amount_paid");
+        let amount_held: Decimal  = ticket.get("amount_held");
+        let whole_open: bool      = ticket.get("whole_open");
 
-        let reference = payment_ref.unwrap_or_else(|| {
-            format!("BUKR-PAY-{}-{:06x}", chrono::Utc::now().timestamp(), rand::random::<u32>())
-        });
+        // Organizer's payout routing — checked before any provider call
+        check_allowed_provider(&req.provider, allowed_providers.as_deref())?;
+        check_provider_currency(&req.provider, currency.as_str(), allowed_providers.as_deref())?;
+        if req.provider != "paystack" {
+            return Err(AppError::Validation("Only 'paystack' is supported".into()));
+        }
+
+        let charge = total.with_amount(split_charge(total.amount(), amount_paid, amount_held, req.amount)?);
+        // The ticket's own reference is only ever a charge for the whole total,
+        // so its success alone activates the ticket. Split parts get their own.
+        let whole = amount_paid.is_zero() && amount_held.is_zero() && charge == total;
+        if !whole && whole_open {
+            return Err(AppError::Conflict(
+                "A checkout for the full amount is still open — complete it, or split the payment once it lapses".into(),
+            ));
+        }
+        let mut reference = match payment_ref {
+            Some(r) if whole => r,
+            None if whole => payment_ref::next(payment_ref::TICKET_PREFIX),
+            _ => payment_ref::reserve(&mut tx, payment_ref::TICKET_PREFIX).await?,
+        };
 
         // ─── FEE COMPUTATION — mirrors purchase path exactly ─────────────────
         // Use the same fee_mode the event had at purchase time.
         // PassToBuyer: unit_price is organizer desired payout — apply discount first.
         // Absorb: unit_price is the buyer-facing price — pass directly.
+        // Discounted and full-price tickets are priced as separate lines.
         let fee_mode = if fee_mode_str == "absorb" { FeeMode::Absorb } else { FeeMode::PassToBuyer };
-        let discount_multiplier = (Decimal::from(100) - discount_applied) / Decimal::from(100);
-        let desired_payout = unit_price * discount_multiplier;
-        let fees = compute_fees(desired_payout, quantity, &fee_mode);
-        let platform_fee   = fees.platform_fee;
-        let bukrshield_fee = fees.bukrshield_fee;
-        let organizer_payout = fees.organizer_payout;
+        let split = split_order(&pricing.lines(unit_price, quantity), add_ons_total, &fee_mode);
+        // A split part carries its share, so the parts add up to the ticket's fees
+        let share = |fee: Decimal| prorate(fee, charge.amount(), total.amount());
+        let platform_fee   = share(split.tickets.platform_fee);
+        let bukrshield_fee = share(split.tickets.bukrshield_fee);
+        let organizer_payout = share(split.organizer_payout);
         // ─────────────────────────────────────────────────────────────────────
 
-        match req.provider.as_str() {
-            "paystack" => {
-                let amount_kobo = (total_price * Decimal::from(100)).to_string().parse::<i64>().unwrap_or(0);
-                let init_resp = self.init_paystack(&email, amount_kobo, &currency, &reference, &req.callback_url).await?;
-
-                let _ = sqlx::query(
-                    r#"INSERT INTO payment_transactions
-                       (ticket_id, user_id, provider, provider_ref, amount, currency, status,
-                        platform_fee, bukrshield_fee, organizer_payout)
-                    VALUES ($1, $2, 'paystack', $3, $4, $5, 'pending', $6, $7, $8)
-                    ON CONFLICT (provider_ref) DO NOTHING"#,
+        self.check_min_charge(&charge)?;
+
+        let fees = CheckoutFees { platform_fee, bukrshield_fee, organizer_payout };
+        let mut attempts = 1;
+        while !insert_checkout(&mut tx, req.ticket_id, user_id, &reference, &charge, &fees).await? {
+            // A retry of the whole-total checkout finds its own row already there.
+            // Anything else lost the reference to another checkout.
+            let owner: Option<Uuid> = sqlx::query_scalar("SELECT ticket_id FROM payment_transactions WHERE provider_ref = $1")
+                .bind(&reference)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(AppError::Database)?
+                .flatten();
+            if whole && owner == Some(req.ticket_id) {
+                break;
+            }
+            if attempts >= payment_ref::MAX_ATTEMPTS {
+                return Err(AppError::Internal("Could not mint a unique payment reference".into()));
+            }
+            attempts += 1;
+            tracing::warn!("Payment reference {} already in use — minting another", reference);
+            reference = payment_ref::next(payment_ref::TICKET_PREFIX);
+            if whole {
+                // Webhooks activate the ticket by its own reference, so it must follow
+                sqlx::query("UPDATE tickets SET payment_ref = $2 WHERE id = $1")
+                    .bind(req.ticket_id)
+                    .bind(&reference)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(AppError::Database)?;
+            }
+        }
+        tx.commit().await.map_err(AppError::Database)?;
+
+        let init_resp = match self.init_paystack(&email, &charge, &reference, &callback_url).await {
+            Ok(url) => url,
+            Err(e) => {
+                // Never handed to the buyer, so it must not keep holding the balance
+                if let Err(db) = sqlx::query(
+                    "DELETE FROM payment_transactions WHERE provider_ref = $1 AND status = 'pending' AND provider_response IS NULL",
                 )
-                .bind(req.ticket_id)
-                .bind(user_id)
                 .bind(&reference)
-                .bind(total_price)
-                .bind(&currency)
-                .bind(platform_fee)
-                .bind(bukrshield_fee)
-                .bind(organizer_payout)
                 .execute(&self.pool)
-                .await;
+                .await
+                {
+                    tracing::error!("Could not release unsent payment {}: {}", reference, db);
+                }
+                return Err(e);
+            }
+        };
 
-                Ok(PaymentInitResponse {
-                    provider: "paystack".to_string(),
-                    authorization_url: Some(init_resp),
-                    reference,
-                })
+        sqlx::query(
+            r#"UPDATE payment_transactions
+               SET provider_response = jsonb_build_object('authorization_url', $2::text), updated_at = NOW()
+               WHERE provider_ref = $1"#,
+        )
+        .bind(&reference)
+        .bind(&init_resp)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+
+        Ok(PaymentInitResponse {
+            provider: "paystack".to_string(),
+            authorization_url: Some(init_resp),
+            reference,
+            amount: charge.amount(),
+            amount_formatted: charge.formatted(),
+            currency: currency.to_string(),
+        })
+    }
+
+    /**
+     * Start checkout for a resale listing
+     *
+     * Holds the listing for this buyer for RESALE_HOLD_MINUTES. A repeat call by
+     * the same buyer inside the hold reuses the reference; anyone else gets a
+     * Conflict until the hold lapses. Ownership moves on charge.success
+     * (tickets::resale::settle), not here.
+     *
+     * Resales carry no platform or BukrShield fee — the ticket was already
+     * charged for at primary sale.
+     *
+     * @param buyer_id - User buying the ticket
+     * @param listing_id - Listing to buy
+     * @param req - Provider and callback URL
+     * @returns Payment URL and reference
+     */
+    pub async fn initialize_resale(&self, buyer_id: Uuid, listing_id: Uuid, req: BuyResaleRequest) -> Result<PaymentInitResponse> {
+        let callback_url = check_callback_url(&self.callback_policy, &req.callback_url)?;
+
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+        let row = sqlx::query(
+            r#"SELECT l.ticket_id, l.seller_id, l.buyer_id, l.price, l.currency, l.status,
+                      l.payment_ref, l.reserved_until < NOW() AS hold_lapsed,
+                      t.status AS ticket_status, t.usage_count, t.scanned_at,
+                      EXISTS(SELECT 1 FROM ticket_blacklist b WHERE b.ticket_id = t.ticket_id) AS blacklisted,
+                      e.allowed_providers, u.email
+               FROM ticket_resale_listings l
+               JOIN tickets t ON t.id = l.ticket_id
+               JOIN events e ON e.id = l.event_id
+               JOIN users u ON u.id = $2
+               WHERE l.id = $1
+               FOR UPDATE OF l"#,
+        )
+        .bind(listing_id)
+        .bind(buyer_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::Database)?
+        .ok_or_else(|| AppError::NotFound("Resale listing not found".into()))?;
+
+        let ticket_id: Uuid = row.get("ticket_id");
+        let currency = Currency::parse(row.get("currency"))?;
+        let price = Money::new(row.get("price"), currency.clone());
+        let email: String = row.get("email");
+        let status: String = row.get("status");
+        let holder: Option<Uuid> = row.get("buyer_id");
+        let hold_lapsed: Option<bool> = row.get("hold_lapsed");
+
+        if row.get::<Uuid, _>("seller_id") == buyer_id {
+            return Err(AppError::Validation("Cannot buy your own listing".into()));
+        }
+        let reference = match status.as_str() {
+            "listed" => None,
+            "pending" if holder == Some(buyer_id) && hold_lapsed != Some(true) => row.get("payment_ref"),
+            "pending" if hold_lapsed == Some(true) => None,
+            "pending" => return Err(AppError::Conflict("Another buyer is checking out this ticket".into())),
+            _ => return Err(AppError::BadRequest("Listing is no longer available".into())),
+        };
+        resale::check_resellable(
+            TicketStatus::from_db(row.get("ticket_status"))?,
+            row.get("usage_count"),
+            row.get::<Option<chrono::DateTime<chrono::Utc>>, _>("scanned_at").is_some(),
+        )?;
+        crate::tickets::transfer::check_not_blacklisted(row.get("blacklisted"), "resell")?;
+
+        let allowed_providers: Option<Vec<String>> = row.get("allowed_providers");
+        check_allowed_provider(&req.provider, allowed_providers.as_deref())?;
+        check_provider_currency(&req.provider, currency.as_str(), allowed_providers.as_deref())?;
+        if req.provider != "paystack" {
+            return Err(AppError::Validation("Only 'paystack' is supported".into()));
+        }
+        self.check_min_charge(&price)?;
+
+        // The payment row claims a new reference before the listing does, so
+        // the provider_ref constraint settles any race between two buyers.
+        let reference = match reference {
+            Some(r) => r,
+            None => {
+                let mut reference = payment_ref::reserve(&mut tx, RESALE_REF_PREFIX).await?;
+                let mut attempts = 1;
+                while !insert_checkout(&mut tx, ticket_id, buyer_id, &reference, &price, &CheckoutFees::default()).await? {
+                    if attempts >= payment_ref::MAX_ATTEMPTS {
+                        return Err(AppError::Internal("Could not mint a unique payment reference".into()));
+                    }
+                    attempts += 1;
+                    tracing::warn!("Payment reference {} already in use — minting another", reference);
+                    reference = payment_ref::next(RESALE_REF_PREFIX);
+                }
+                reference
             }
-            _ => Err(AppError::Validation("Only 'paystack' is supported".into())),
+        };
+        sqlx::query(
+            r#"UPDATE ticket_resale_listings
+               SET status = 'pending', buyer_id = $2, payment_ref = $3,
+                   reserved_until = NOW() + make_interval(mins => $4)
+               WHERE id = $1"#,
+        )
+        .bind(listing_id)
+        .bind(buyer_id)
+        .bind(&reference)
+        .bind(RESALE_HOLD_MINUTES as i32)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::Database)?;
+        tx.commit().await.map_err(AppError::Database)?;
+
+        let authorization_url = self.init_paystack(&email, &price, &reference, &callback_url).await?;
+
+        Ok(PaymentInitResponse {
+            provider: "paystack".to_string(),
+            authorization_url: Some(authorization_url),
+            reference,
+            amount: price.amount(),
+            amount_formatted: price.formatted(),
+            currency: currency.to_string(),
+        })
+    }
+
+    /**
+     * Resume an abandoned checkout
+     *
+     * No new ticket, no inventory taken — the seat was reserved at
+     * purchase. While the payment is pending the checkout URL saved at
+     * initialization is handed back under the same reference. A row with
+     * no saved URL predates URL storage; the provider already knows its
+     * reference, so the session is initialized under a fresh one. A
+     * payment that already settled just reports its status.
+     *
+     * @param user_id - Caller; must own the ticket
+     * @param ticket_id - Human-readable ticket ID
+     * @param callback_url - Where the provider sends the buyer afterwards
+     * @returns Payment status, plus the checkout to continue while pending
+     */
+    pub async fn resume(&self, user_id: Uuid, ticket_id: &str, callback_url: String) -> Result<ResumePaymentResponse> {
+        let row = sqlx::query(
+            r#"SELECT t.id, t.status, t.payment_ref, t.payment_provider, t.total_price,
+                      p.status AS payment_status, p.amount, p.currency,
+                      p.provider_response->>'authorization_url' AS authorization_url
+               FROM tickets t
+               LEFT JOIN payment_transactions p ON p.provider_ref = t.payment_ref
+               WHERE t.ticket_id = $1 AND t.user_id = $2"#,
+        )
+        .bind(ticket_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::Database)?
+        .ok_or_else(|| AppError::NotFound("Ticket not found".into()))?;
+
+        let reference: Option<String> = row.get("payment_ref");
+        let provider: Option<String> = row.get("payment_provider");
+        let total_price: Decimal = row.get("total_price");
+        let (reference, provider) = match (reference, provider) {
+            (Some(r), Some(p)) if p != "free" && total_price > Decimal::ZERO => (r, p),
+            _ => return Err(AppError::BadRequest("This ticket has no payment to resume".into())),
+        };
+
+        let payment_status: Option<String> = row.get("payment_status");
+        if let Some(status) = payment_status.as_deref().filter(|s| is_settled_payment(s)) {
+            return Ok(ResumePaymentResponse { reference, status: status.to_string(), payment: None });
+        }
+
+        let ticket_status = TicketStatus::from_db(row.get("status"))?;
+        if !ticket_status.is_live() {
+            return Err(AppError::BadRequest(format!("Ticket is {} — start a new checkout", ticket_status)));
+        }
+
+        let payment = match row.get::<Option<String>, _>("authorization_url") {
+            Some(url) => {
+                let charge = Money::new(row.get("amount"), Currency::parse(row.get("currency"))?);
+                PaymentInitResponse {
+                    provider,
+                    authorization_url: Some(url),
+                    reference: reference.clone(),
+                    amount: charge.amount(),
+                    amount_formatted: charge.formatted(),
+                    currency: charge.currency().to_string(),
+                }
+            }
+            None => {
+                // Saved before checkout URLs were stored: the provider already
+                // has this reference and won't initialize it twice. Retire it
+                // (a late success still settles and refunds any overpayment)
+                // and open the checkout under a fresh one.
+                let ticket_db_id: Uuid = row.get("id");
+                self.retire_reference(ticket_db_id, &reference).await?;
+                self.initialize(user_id, InitializePaymentRequest {
+                    ticket_id: ticket_db_id,
+                    provider,
+                    callback_url,
+                    amount: None,
+                }).await?
+            }
+        };
+
+        Ok(ResumePaymentResponse { reference: payment.reference.clone(), status: "pending".into(), payment: Some(payment) })
+    }
+
+    /// Swap a ticket's payment_ref for a fresh one and fail the unsent
+    /// checkout row behind the old one, so it no longer holds the balance.
+    async fn retire_reference(&self, ticket_db_id: Uuid, reference: &str) -> Result<()> {
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+        let fresh = payment_ref::reserve(&mut tx, payment_ref::TICKET_PREFIX).await?;
+        let swapped = sqlx::query("UPDATE tickets SET payment_ref = $3 WHERE id = $1 AND payment_ref = $2")
+            .bind(ticket_db_id)
+            .bind(reference)
+            .bind(&fresh)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::Database)?
+            .rows_affected();
+        if swapped == 0 {
+            // A concurrent resume got here first; initialize uses its reference
+            return Ok(());
         }
+        sqlx::query(
+            r#"UPDATE payment_transactions SET status = 'failed', updated_at = NOW()
+               WHERE provider_ref = $1 AND status = 'pending' AND provider_response IS NULL"#,
+        )
+        .bind(reference)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::Database)?;
+        tx.commit().await.map_err(AppError::Database)
     }
 
-    async fn init_paystack(&self, email: &str, amount_kobo: i64, currency: &str, reference: &str, callback_url: &str) -> Result<String> {
+    async fn init_paystack(&self, email: &str, charge: &Money, reference: &str, callback_url: &str) -> Result<String> {
+        // Checked before the mock path so QA can exercise failures without keys.
+        if let Some(fault) = self.fault {
+            return Err(fault.trigger("Paystack").await);
+        }
         if self.paystack_secret.is_empty() {
             return Ok(format!("https://checkout.paystack.com/mock/{}", reference));
         }
@@ -193,19 +692,25 @@ impl PaymentService {
             .header("Authorization", format!("Bearer {}", self.paystack_secret))
             .json(&serde_json::json!({
                 "email": email,
-                "amount": amount_kobo,
-                "currency": currency,
+                "amount": charge.minor_units(),
+                "currency": charge.currency(),
                 "reference": reference,
                 "callback_url": callback_url,
             }))
             .send()
             .await
-            .map_err(|e| AppError::PaymentFailed(format!("Paystack request failed: {}", e)))?;
+            .map_err(|e| provider_transport_error("Paystack", e))?;
 
+        let status = resp.status();
         let body: serde_json::Value = resp
             .json()
             .await
-            .map_err(|e| AppError::PaymentFailed(format!("Paystack response parse failed: {}", e)))?;
+            .map_err(|e| provider_transport_error("Paystack", e))?;
+
+        if !status.is_success() {
+            let message = body["message"].as_str().unwrap_or("request rejected");
+            return Err(provider_status_error("Paystack", status, message));
+        }
 
         body["data"]["authorization_url"]
             .as_str()
@@ -220,11 +725,7 @@ impl PaymentService {
             tracing::warn!("PAYSTACK_WEBHOOK_SECRET is empty — rejecting webhook");
             return false;
         }
-        let mut mac = Hmac::<Sha512>::new_from_slice(self.paystack_webhook_secret.as_bytes())
-            .expect("HMAC can take key of any size");
-        mac.update(body);
-        let expected = hex::encode(mac.finalize().into_bytes());
-        expected == signature
+        paystack_signature_matches(&self.paystack_webhook_secret, body, signature)
     }
 
     /**
@@ -234,8 +735,14 @@ impl PaymentService {
      * 
      * Flow:
      * 1. Check event type (only process charge.success)
-     * 2. Update payment transaction status
-     * 3. Mark ticket as valid
+     * 2. Update payment transaction status; if this charge takes the ticket
+     *    past its total (a lapsed checkout paid after all), queue a refund
+     *    of the excess in the same transaction. A ticket cancelled while
+     *    the checkout was open gets the whole charge refunded instead.
+     *    Otherwise the charge's platform and BukrShield fees go into the
+     *    platform_revenue ledger, in that same transaction
+     * 3. Mark ticket as valid once paid in full — by its own reference, or
+     *    by split parts whose successful total covers total_price
      * 4. Log success
      * 
      * @param payload - Webhook payload from Paystack
@@ -247,23 +754,116 @@ impl PaymentService {
         }
 
         let reference = &payload.data.reference;
+        let resale = reference.starts_with(RESALE_REF_PREFIX);
+
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+        // Parts of one ticket settle one at a time, so each sees the others' totals
+        if !resale {
+            sqlx::query(
+                r#"SELECT t.id FROM tickets t
+                   JOIN payment_transactions p ON p.ticket_id = t.id
+                   WHERE p.provider_ref = $1
+                   FOR UPDATE OF t"#,
+            )
+            .bind(reference)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(AppError::Database)?;
+        }
 
         // Update payment transaction to success
-        sqlx::query(
+        let settled = sqlx::query(
             r#"UPDATE payment_transactions SET status = 'success', provider_response = $2
-            WHERE provider_ref = $1"#,
+            WHERE provider_ref = $1 AND status IS DISTINCT FROM 'success'"#,
         )
         .bind(reference)
         .bind(serde_json::to_value(&payload.data).unwrap_or_default())
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await
-        .map_err(AppError::Database)?;
+        .map_err(AppError::Database)?
+        .rows_affected() > 0;
+
+        if settled && !resale {
+            let over = sqlx::query(
+                r#"SELECT t.id, t.ticket_id, t.currency, t.status, p.amount,
+                          LEAST(p.amount, (SELECT COALESCE(SUM(q.amount), 0) FROM payment_transactions q
+                                            WHERE q.ticket_id = t.id AND q.status = 'success'
+                                              AND q.provider_ref NOT LIKE $2) - t.total_price) AS excess
+                   FROM payment_transactions p
+                   JOIN tickets t ON t.id = p.ticket_id
+                   WHERE p.provider_ref = $1"#,
+            )
+            .bind(reference)
+            .bind(RESALE_REF_LIKE)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(AppError::Database)?;
+
+            // A ticket cancelled while its checkout was still open (by the
+            // buyer, support, or with its event) never activates — the whole
+            // charge goes back. Otherwise only what overshoots the total does.
+            let refund = match over {
+                Some(row) if refunds_late_charge(TicketStatus::from_db(row.get("status"))?) => {
+                    let amount: Decimal = row.get("amount");
+                    Some((row, amount, "charged_after_cancel"))
+                }
+                Some(row) => {
+                    let excess: Decimal = row.get("excess");
+                    Some((row, excess, "overpaid")).filter(|_| excess > Decimal::ZERO)
+                }
+                None => None,
+            };
+            // Fees are revenue once the charge is kept — not at purchase, when
+            // the buyer may still walk away from the checkout.
+            if !matches!(refund, Some((_, _, "charged_after_cancel"))) {
+                record_fee_revenue(&mut tx, reference).await?;
+            }
+            if let Some((row, amount, reason)) = refund {
+                let ticket_id: String = row.get("ticket_id");
+                crate::outbox::enqueue(
+                    &mut tx,
+                    "ticket.refund_requested",
+                    row.get("id"),
+                    serde_json::json!({
+                        "ticket_id": ticket_id,
+                        "payment_ref": reference,
+                        "payment_provider": "paystack",
+                        "amount": amount,
+                        "currency": row.get::<String, _>("currency"),
+                        "reason": reason,
+                        "requested_by": serde_json::Value::Null,
+                    }),
+                ).await.map_err(AppError::Database)?;
+                tracing::warn!("Payment {} on ticket {} not kept ({}) — refund of {} queued", reference, ticket_id, reason, amount);
+            }
+        }
+        tx.commit().await.map_err(AppError::Database)?;
+        // Pollers see the success now, not when the cached pending expires
+        self.verifications.invalidate(reference).await;
 
-        // Activate ticket (mark as valid for scanning)
+        // Resale checkouts move an existing ticket instead of activating one.
+        if resale {
+            resale::settle(&self.pool, reference).await?;
+            tracing::info!("Paystack webhook processed: {} -> resale settled", reference);
+            return Ok(());
+        }
+
+        // Activate ticket (mark as valid for scanning). Only from statuses that
+        // may legally become valid — a cancelled or used ticket stays put.
+        // A split part activates only when it completes the total.
         sqlx::query(
-            r#"UPDATE tickets SET status = 'valid' WHERE payment_ref = $1 AND status != 'used'"#,
+            r#"UPDATE tickets t SET status = $2
+               WHERE t.status = ANY($3)
+                 AND (t.payment_ref = $1
+                      OR (t.id = (SELECT ticket_id FROM payment_transactions WHERE provider_ref = $1)
+                          AND (SELECT COALESCE(SUM(p.amount), 0) FROM payment_transactions p
+                                WHERE p.ticket_id = t.id AND p.status = 'success'
+                                  AND p.provider_ref NOT LIKE $4) >= t.total_price))"#,
         )
         .bind(reference)
+        .bind(TicketStatus::Valid.as_str())
+        .bind(TicketStatus::Valid.sources())
+        .bind(RESALE_REF_LIKE)
         .execute(&self.pool)
         .await
         .map_err(AppError::Database)?;
@@ -272,7 +872,14 @@ impl PaymentService {
         Ok(())
     }
 
+    /// Payment status for the post-redirect poll. Cached for a few seconds,
+    /// and concurrent calls for one reference share a single lookup — if the
+    /// caller running it disconnects, the next waiter takes it over.
     pub async fn verify_payment(&self, reference: &str) -> Result<serde_json::Value> {
+        coalesced(&self.verifications, reference, self.load_verification(reference)).await
+    }
+
+    async fn load_verification(&self, reference: &str) -> Result<serde_json::Value> {
         // Fetch payment transaction
         let txn = sqlx::query(
             r#"SELECT provider, provider_ref, amount, currency, status
@@ -300,4 +907,474 @@ impl PaymentService {
             "status": status,
         }))
     }
+
+    /**
+     * List payment transactions for admin review, newest first
+     *
+     * Filters are ANDed; unset filters match everything. Unknown provider or
+     * status values are rejected rather than silently returning nothing.
+     *
+     * @param query - Filters and page window
+     * @returns One page of transactions plus the total match count
+     */
+    pub async fn list_transactions(&self, query: PaymentListQuery) -> Result<PaymentListResponse> {
+        check_payment_filters(&query)?;
+        let (limit, offset) = page_window(query.limit, query.offset);
+
+        let rows = sqlx::query(
+            r#"SELECT id, ticket_id, user_id, provider, provider_ref, amount, currency, status,
+                      platform_fee, bukrshield_fee, organizer_payout, created_at, updated_at,
+                      COUNT(*) OVER () AS total
+               FROM payment_transactions
+               WHERE ($1::text IS NULL OR provider = $1)
+                 AND ($2::text IS NULL OR status = $2)
+                 AND ($3::timestamptz IS NULL OR created_at >= $3)
+                 AND ($4::timestamptz IS NULL OR created_at <  $4)
+               ORDER BY created_at DESC, id DESC
+               LIMIT $5 OFFSET $6"#,
+        )
+        .bind(&query.provider)
+        .bind(&query.status)
+        .bind(query.from)
+        .bind(query.to)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+
+        // COUNT(*) OVER () rides along on every row; a page past the end has no
+        // rows to carry it, so fall back to a plain count.
+        let total = match rows.first() {
+            Some(r) => r.get("total"),
+            None => sqlx::query_scalar(
+                r#"SELECT COUNT(*) FROM payment_transactions
+                   WHERE ($1::text IS NULL OR provider = $1)
+                     AND ($2::text IS NULL OR status = $2)
+                     AND ($3::timestamptz IS NULL OR created_at >= $3)
+                     AND ($4::timestamptz IS NULL OR created_at <  $4)"#,
+            )
+            .bind(&query.provider)
+            .bind(&query.status)
+            .bind(query.from)
+            .bind(query.to)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(AppError::Database)?,
+        };
+
+        let payments = rows.iter().map(|r| PaymentTransaction {
+            id: r.get("id"),
+            ticket_id: r.get("ticket_id"),
+            user_id: r.get("user_id"),
+            provider: r.get("provider"),
+            provider_ref: r.get("provider_ref"),
+            amount: r.get("amount"),
+            currency: r.get("currency"),
+            status: r.get("status"),
+            platform_fee: r.get("platform_fee"),
+            bukrshield_fee: r.get("bukrshield_fee"),
+            organizer_payout: r.get("organizer_payout"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+        }).collect();
+
+        Ok(PaymentListResponse { payments, total, limit, offset })
+    }
+
+    /**
+     * Reconcile Payments: Compare local successes with the provider
+     *
+     * Finance tool. Every transaction we marked 'success' on the given UTC day
+     * is re-checked live at the provider, RECONCILE_BATCH_SIZE at a time.
+     *
+     * Flow:
+     * 1. Load local 'success' transactions for the day
+     * 2. Fetch provider status for each (batched, concurrent)
+     * 3. Split into matched / discrepancies / unchecked
+     *
+     * @param date - UTC day to reconcile
+     * @returns Report with both statuses for every disagreement
+     */
+    pub async fn reconcile(&self, date: chrono::NaiveDate) -> Result<ReconcileReport> {
+        let rows = sqlx::query(
+            r#"SELECT provider, provider_ref, ticket_id, amount, currency, status
+               FROM payment_transactions
+               WHERE status = 'success'
+                 AND created_at >= $1::date
+                 AND created_at <  $1::date + INTERVAL '1 day'
+               ORDER BY created_at"#,
+        )
+        .bind(date)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+
+        let entries: Vec<ReconcileEntry> = rows.iter().map(|r| ReconcileEntry {
+            reference: r.get("provider_ref"),
+            provider: r.get("provider"),
+            ticket_id: r.get("ticket_id"),
+            amount: r.get("amount"),
+            currency: r.get("currency"),
+            local_status: r.get("status"),
+            provider_status: None,
+            error: None,
+        }).collect();
+
+        let mut report = ReconcileReport {
+            date,
+            checked: entries.len(),
+            matched: 0,
+            discrepancies: Vec::new(),
+            unchecked: Vec::new(),
+        };
+
+        let mut pending = entries.into_iter();
+        loop {
+            let batch: Vec<ReconcileEntry> = pending.by_ref().take(RECONCILE_BATCH_SIZE).collect();
+            if batch.is_empty() {
+                break;
+            }
+
+            let mut set = tokio::task::JoinSet::new();
+            for mut entry in batch {
+                let http = self.http.clone();
+                let secret = self.paystack_secret.clone();
+                set.spawn(async move {
+                    let result = match entry.provider.as_str() {
+                        "paystack" => fetch_paystack_status(&http, &secret, &entry.reference).await,
+                        other => Err(format!("reconciliation not supported for provider '{}'", other)),
+                    };
+                    match result {
+                        Ok(status) => entry.provider_status = Some(status),
+                        Err(e) => entry.error = Some(e),
+                    }
+                    entry
+                });
+            }
+
+            while let Some(joined) = set.join_next().await {
+                let entry = joined.map_err(|e| AppError::Internal(format!("reconcile task failed: {}", e)))?;
+                match entry.provider_status.as_deref() {
+                    None => report.unchecked.push(entry),
+                    Some(s) if s == entry.local_status => report.matched += 1,
+                    Some(_) => report.discrepancies.push(entry),
+                }
+            }
+        }
+
+        tracing::info!(
+            "Reconcile {}: {} checked, {} matched, {} discrepancies, {} unchecked",
+            date, report.checked, report.matched, report.discrepancies.len(), report.unchecked.len()
+        );
+        Ok(report)
+    }
+}
+
+/// Fee split stored on a pending checkout row. Resale charges carry none.
+#[derive(Default)]
+struct CheckoutFees {
+    platform_fee: Decimal,
+    bukrshield_fee: Decimal,
+    organizer_payout: Decimal,
+}
+
+/// Insert a pending checkout row under `reference`. provider_ref is UNIQUE and
+/// this insert is what actually claims it: `payment_ref::reserve` can't see
+/// another transaction's uncommitted row. Returns false when the reference
+/// was already taken and nothing was written.
+async fn insert_checkout(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ticket_id: Uuid,
+    user_id: Uuid,
+    reference: &str,
+    charge: &Money,
+    fees: &CheckoutFees,
+) -> Result<bool> {
+    let inserted = sqlx::query(
+        r#"INSERT INTO payment_transactions
+           (ticket_id, user_id, provider, provider_ref, amount, currency, status,
+            platform_fee, bukrshield_fee, organizer_payout)
+        VALUES ($1, $2, 'paystack', $3, $4, $5, 'pending', $6, $7, $8)
+        ON CONFLICT (provider_ref) DO NOTHING"#,
+    )
+    .bind(ticket_id)
+    .bind(user_id)
+    .bind(reference)
+    .bind(charge.amount())
+    .bind(charge.currency().as_str())
+    .bind(fees.platform_fee)
+    .bind(fees.bukrshield_fee)
+    .bind(fees.organizer_payout)
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::Database)?
+    .rows_affected();
+    Ok(inserted == 1)
+}
+
+/// Ledger the fees recorded on a settled ticket charge. A split part carries
+/// its share of the ticket's fees, so the parts add up to the whole.
+async fn record_fee_revenue(tx: &mut sqlx::Transaction<'_, sqlx::Postgres>, reference: &str) -> Result<()> {
+    sqlx::query(
+        r#"INSERT INTO platform_revenue (source, reference_id, organizer_id, amount, currency, meta)
+           SELECT f.source, t.id, e.organizer_id, f.amount, p.currency,
+                  jsonb_build_object('quantity', t.quantity, 'unit_price', t.unit_price, 'payment_ref', p.provider_ref)
+           FROM payment_transactions p
+           JOIN tickets t ON t.id = p.ticket_id
+           JOIN events e ON e.id = t.event_id
+           CROSS JOIN LATERAL (VALUES ('ticket_fee', p.platform_fee),
+                                      ('bukrshield_fee', p.bukrshield_fee)) AS f(source, amount)
+           WHERE p.provider_ref = $1 AND f.amount > 0"#,
+    )
+    .bind(reference)
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::Database)?;
+    Ok(())
+}
+
+/// Reject filter values that can never match, and inverted date ranges.
+pub(crate) fn check_payment_filters(query: &PaymentListQuery) -> Result<()> {
+    if let Some(p) = query.provider.as_deref() {
+        if !SUPPORTED_PROVIDERS.contains(&p) {
+            return Err(AppError::Validation(format!(
+                "unknown provider '{}' (expected one of: {})", p, SUPPORTED_PROVIDERS.join(", ")
+            )));
+        }
+    }
+    if let Some(s) = query.status.as_deref() {
+        if !PAYMENT_STATUSES.contains(&s) {
+            return Err(AppError::Validation(format!(
+                "unknown status '{}' (expected one of: {})", s, PAYMENT_STATUSES.join(", ")
+            )));
+        }
+    }
+    if let (Some(from), Some(to)) = (query.from, query.to) {
+        if from >= to {
+            return Err(AppError::Validation("from must be before to".into()));
+        }
+    }
+    Ok(())
+}
+
+/// Clamp limit to 1..=MAX_PAGE_SIZE (default DEFAULT_PAGE_SIZE) and offset to >= 0.
+pub(crate) fn page_window(limit: Option<i64>, offset: Option<i64>) -> (i64, i64) {
+    (
+        limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE),
+        offset.unwrap_or(0).max(0),
+    )
+}
+
+/// Every provider the platform knows about — the default when an event sets no allowlist.
+pub const SUPPORTED_PROVIDERS: &[&str] = &["paystack", "stripe"];
+
+/// Providers a checkout can actually be opened with today. The rest of
+/// SUPPORTED_PROVIDERS may be allowlisted by organizers but aren't wired up.
+pub const CHECKOUT_PROVIDERS: &[&str] = &["paystack"];
+
+/// Check a requested provider against the event's allowed_providers.
+/// None (column unset) allows every supported provider.
+pub(crate) fn check_allowed_provider(requested: &str, allowed: Option<&[String]>) -> Result<()> {
+    let permitted = match allowed {
+        Some(list) => list.iter().any(|p| p == requested),
+        None => SUPPORTED_PROVIDERS.contains(&requested),
+    };
+    if permitted {
+        return Ok(());
+    }
+    let allowed_list = match allowed {
+        Some(list) => list.join(", "),
+        None => SUPPORTED_PROVIDERS.join(", "),
+    };
+    Err(AppError::Validation(format!(
+        "payment provider '{}' is not accepted for this event (allowed: {})",
+        requested, allowed_list
+    )))
+}
+
+/// Currencies each provider can charge in (ISO 4217, uppercase). A charge
+/// routed to a provider that can't settle its currency fails at the provider
+/// with an opaque error, so it is caught before any provider call.
+pub const PROVIDER_CURRENCIES: &[(&str, &[&str])] = &[
+    ("paystack", &["GHS", "KES", "NGN", "USD", "ZAR"]),
+    ("stripe", &[
+        "AUD", "CAD", "CHF", "DKK", "EUR", "GBP", "HKD", "JPY", "KES", "NGN",
+        "NOK", "NZD", "SEK", "SGD", "USD", "ZAR",
+    ]),
+];
+
+/// What a checkout charges: `requested` (one part of a split payment) or,
+/// by default, everything still owed on the ticket. `held` is what checkouts
+/// still in flight may yet pay; it isn't owed, but it isn't free either.
+pub(crate) fn split_charge(total: Decimal, paid: Decimal, held: Decimal, requested: Option<Decimal>) -> Result<Decimal> {
+    if total - paid <= Decimal::ZERO {
+        return Err(AppError::BadRequest("Ticket is already paid in full".into()));
+    }
+    let owed = total - paid - held;
+    if owed <= Decimal::ZERO {
+        return Err(AppError::Conflict("The rest of this ticket is covered by payments still in progress".into()));
+    }
+    let Some(amount) = requested else { return Ok(owed) };
+    if amount <= Decimal::ZERO || amount > owed {
+        return Err(AppError::Validation(format!("amount must be more than 0 and at most {} (still owed)", owed)));
+    }
+    if amount.normalize().scale() > 2 {
+        return Err(AppError::Validation("amount can have at most 2 decimal places".into()));
+    }
+    Ok(amount)
+}
+
+/// A split part's share of a ticket-level fee, to the cent.
+pub(crate) fn prorate(fee: Decimal, part: Decimal, total: Decimal) -> Decimal {
+    if part >= total || total.is_zero() {
+        return fee;
+    }
+    (fee * part / total).round_dp(2)
+}
+
+/// A charge that lands on a ticket which can no longer become valid —
+/// cancelled while its checkout was open, or already refunded or expired —
+/// is owed back in full.
+pub(crate) fn refunds_late_charge(status: TicketStatus) -> bool {
+    matches!(status, TicketStatus::Cancelled | TicketStatus::Refunded | TicketStatus::Expired)
+}
+
+/// Payment outcomes a resume can only report: paid, paid then refunded, or declined.
+pub(crate) fn is_settled_payment(status: &str) -> bool {
+    matches!(status, "success" | "refunded" | "failed")
+}
+
+/// HMAC-SHA512 of the body, hex-encoded, compared in constant time so the
+/// response time doesn't reveal how much of a forged signature was right.
+pub(crate) fn paystack_signature_matches(secret: &str, body: &[u8], signature: &str) -> bool {
+    let mut mac = Hmac::<Sha512>::new_from_slice(secret.as_bytes())
+        .expect("HMAC can take key of any size");
+    mac.update(body);
+    let expected = hex::encode(mac.finalize().into_bytes());
+    expected.as_bytes().ct_eq(signature.trim().to_ascii_lowercase().as_bytes()).into()
+}
+
+/// Check the provider can charge in `currency`. The error names the
+/// providers that can — only ones a checkout can be opened with, limited to
+/// the event's allowlist when it has one.
+pub(crate) fn check_provider_currency(provider: &str, currency: &str, allowed: Option<&[String]>) -> Result<()> {
+    let supports = |p: &str| {
+        PROVIDER_CURRENCIES
+            .iter()
+            .any(|(name, codes)| *name == p && codes.contains(&currency))
+    };
+    if supports(provider) {
+        return Ok(());
+    }
+    let alternatives: Vec<&str> = PROVIDER_CURRENCIES
+        .iter()
+        .map(|(name, _)| *name)
+        .filter(|p| supports(p) && CHECKOUT_PROVIDERS.contains(p))
+        .filter(|p| allowed.is_none_or(|list| list.iter().any(|a| a == p)))
+        .collect();
+    let hint = if alternatives.is_empty() {
+        "no accepted provider supports it for this event".to_string()
+    } else {
+        format!("use {} instead", alternatives.join(" or "))
+    };
+    Err(AppError::Validation(format!(
+        "payment provider '{}' does not support {} — {}",
+        provider, currency, hint
+    )))
+}
+
+/// Validate a client-supplied callback_url: well-formed https, public host,
+/// and on the allowlist. No allowlist refuses every URL — an open one would
+/// let anyone bounce a buyer through the provider to any site.
+/// Returns the normalized URL.
+pub(crate) fn check_callback_url(policy: &HostPolicy, raw: &str) -> Result<String> {
+    if !policy.has_allowlist() {
+        return Err(AppError::Validation("callback_url rejected: no callback hosts are configured".into()));
+    }
+    policy
+        .check_url(raw)
+        .map(|url| url.to_string())
+        .map_err(|reason| AppError::Validation(format!("callback_url rejected: {}", reason)))
+}
+
+/// Map a reqwest failure talking to a provider. Timeouts, refused/dropped
+/// connections and unreadable bodies are outages (503, retry); anything else
+/// is treated as a rejection of this request (402).
+fn provider_transport_error(provider: &str, e: reqwest::Error) -> AppError {
+    if e.is_timeout() || e.is_connect() || e.is_request() || e.is_body() || e.is_decode() {
+        AppError::PaymentProviderUnavailable(format!("{} unavailable: {}", provider, e))
+    } else {
+        AppError::PaymentFailed(format!("{} request failed: {}", provider, e))
+    }
+}
+
+pub(crate) fn verification_cache() -> moka::future::Cache<String, serde_json::Value> {
+    moka::future::Cache::builder()
+        .max_capacity(VERIFY_CACHE_CAPACITY)
+        .time_to_live(Duration::from_secs(VERIFY_CACHE_TTL_SECS))
+        .build()
+}
+
+/// Cached value for `key`, or run `load` once for every caller waiting on it.
+/// Errors are handed to all of those callers and not cached.
+pub(crate) async fn coalesced(
+    cache: &moka::future::Cache<String, serde_json::Value>,
+    key: &str,
+    load: impl std::future::Future<Output = Result<serde_json::Value>>,
+) -> Result<serde_json::Value> {
+    cache.try_get_with_by_ref(key, load).await.map_err(|shared: Arc<AppError>| {
+        // Only the caller that ran the lookup owns the error; the rest get a copy
+        Arc::try_unwrap(shared).unwrap_or_else(|e| match &*e {
+            AppError::NotFound(msg) => AppError::NotFound(msg.clone()),
+            other => AppError::Internal(other.to_string()),
+        })
+    })
+}
+
+/// Map a non-2xx provider response. 5xx and 429 are the provider's problem
+/// (503, retry); 4xx means the provider refused this payment (402).
+pub(crate) fn provider_status_error(provider: &str, status: reqwest::StatusCode, message: &str) -> AppError {
+    if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        AppError::PaymentProviderUnavailable(format!("{} returned {}", provider, status))
+    } else {
+        AppError::PaymentFailed(format!("{}: {}", provider, message))
+    }
+}
+
+/// Live status lookup via Paystack's verify endpoint.
+/// A reference Paystack has never seen is reported as "not_found", which is
+/// itself a discrepancy against a local success.
+async fn fetch_paystack_status(
+    http: &reqwest::Client,
+    secret: &str,
+    reference: &str,
+) -> std::result::Result<String, String> {
+    if secret.is_empty() {
+        return Err("PAYSTACK_SECRET_KEY not configured".into());
+    }
+
+    let resp = http
+        .get(format!("https://api.paystack.co/transaction/verify/{}", reference))
+        .header("Authorization", format!("Bearer {}", secret))
+        .send()
+        .await
+        .map_err(|e| format!("Paystack request failed: {}", e))?;
+
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok("not_found".into());
+    }
+    if !resp.status().is_success() {
+        return Err(format!("Paystack returned {}", resp.status()));
+    }
+
+    let body: serde_json::Value = resp
+        .json()
+        .await
+        .map_err(|e| format!("Paystack response parse failed: {}", e))?;
+
+    body["data"]["status"]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| "Paystack response missing data.status".into())
 }
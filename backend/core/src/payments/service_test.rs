@@ -0,0 +1,248 @@
+// Unit tests for pure pieces of the payment path.
+
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+
+use rust_decimal::Decimal;
+
+use crate::error::AppError;
+use crate::tickets::status::TicketStatus;
+use crate::webhook::HostPolicy;
+use super::service::{
+    coalesced, verification_cache, check_allowed_provider, check_callback_url, check_provider_currency, check_payment_filters, is_settled_payment, page_window, paystack_signature_matches, prorate, provider_status_error, refunds_late_charge, split_charge, PaymentListQuery,
+};
+
+fn status_of(err: AppError) -> StatusCode {
+    err.into_response().status()
+}
+
+#[test]
+fn provider_outage_is_503() {
+    let err = provider_status_error("Paystack", reqwest::StatusCode::BAD_GATEWAY, "");
+    assert!(matches!(err, AppError::PaymentProviderUnavailable(_)));
+    assert_eq!(status_of(err), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[test]
+fn provider_rate_limit_is_retryable() {
+    let err = provider_status_error("Paystack", reqwest::StatusCode::TOO_MANY_REQUESTS, "");
+    assert_eq!(status_of(err), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[test]
+fn provider_rejection_is_402_with_message() {
+    let err = provider_status_error("Paystack", reqwest::StatusCode::BAD_REQUEST, "Invalid email");
+    match &err {
+        AppError::PaymentFailed(msg) => assert!(msg.contains("Invalid email")),
+        other => panic!("expected PaymentFailed, got {:?}", other),
+    }
+    assert_eq!(status_of(err), StatusCode::PAYMENT_REQUIRED);
+}
+
+// ─── Per-event allowed providers ──────────────────────────────────────────────
+
+#[test]
+fn unset_allowlist_accepts_every_supported_provider() {
+    assert!(check_allowed_provider("paystack", None).is_ok());
+    assert!(check_allowed_provider("stripe", None).is_ok());
+    assert!(check_allowed_provider("flutterwave", None).is_err());
+}
+
+#[test]
+fn provider_outside_event_allowlist_lists_allowed_set() {
+    let allowed = vec!["stripe".to_string()];
+    match check_allowed_provider("paystack", Some(&allowed)) {
+        Err(AppError::Validation(msg)) => assert!(msg.contains("allowed: stripe"), "{}", msg),
+        other => panic!("expected Validation, got {:?}", other),
+    }
+    assert!(check_allowed_provider("stripe", Some(&allowed)).is_ok());
+}
+
+// ─── Provider currency matrix ─────────────────────────────────────────────────
+
+#[test]
+fn provider_accepts_its_own_currencies() {
+    assert!(check_provider_currency("paystack", "NGN", None).is_ok());
+    assert!(check_provider_currency("stripe", "EUR", None).is_ok());
+}
+
+#[test]
+fn unsupported_currency_suggests_the_provider_that_can_charge_it() {
+    match check_provider_currency("stripe", "GHS", None) {
+        Err(AppError::Validation(msg)) => assert!(msg.contains("use paystack instead"), "{}", msg),
+        other => panic!("expected Validation, got {:?}", other),
+    }
+}
+
+#[test]
+fn currency_suggestion_skips_providers_without_a_checkout() {
+    // Stripe can charge EUR but no checkout can be opened with it yet
+    match check_provider_currency("paystack", "EUR", None) {
+        Err(AppError::Validation(msg)) => {
+            assert!(!msg.contains("stripe"), "{}", msg);
+            assert!(msg.contains("no accepted provider"), "{}", msg);
+        }
+        other => panic!("expected Validation, got {:?}", other),
+    }
+}
+
+#[test]
+fn currency_suggestion_respects_event_allowlist() {
+    let allowed = vec!["paystack".to_string()];
+    match check_provider_currency("paystack", "GBP", Some(&allowed)) {
+        Err(AppError::Validation(msg)) => assert!(msg.contains("no accepted provider"), "{}", msg),
+        other => panic!("expected Validation, got {:?}", other),
+    }
+}
+
+#[test]
+fn callback_url_must_be_https_on_an_allowed_host() {
+    let policy = HostPolicy::new(vec!["bukr.app".into()], Vec::new());
+    assert!(check_callback_url(&policy, "https://bukr.app/payment/return").is_ok());
+    assert!(check_callback_url(&policy, "https://www.bukr.app/return").is_ok());
+
+    for bad in ["http://bukr.app/return", "https://evil.example/return", "not a url", "https://10.0.0.5/"] {
+        let err = check_callback_url(&policy, bad).unwrap_err();
+        assert_eq!(status_of(err), StatusCode::BAD_REQUEST);
+    }
+}
+
+#[test]
+fn callback_url_is_refused_without_an_allowlist() {
+    let open = HostPolicy::new(Vec::new(), Vec::new());
+    let err = check_callback_url(&open, "https://evil.example/return").unwrap_err();
+    assert_eq!(status_of(err), StatusCode::BAD_REQUEST);
+}
+
+fn list_query() -> PaymentListQuery {
+    PaymentListQuery { provider: None, status: None, from: None, to: None, limit: None, offset: None }
+}
+
+#[test]
+fn payment_list_page_window_is_clamped() {
+    assert_eq!(page_window(None, None), (50, 0));
+    assert_eq!(page_window(Some(0), Some(-5)), (1, 0));
+    assert_eq!(page_window(Some(10_000), Some(40)), (200, 40));
+}
+
+#[test]
+fn payment_list_rejects_unknown_filters() {
+    assert!(check_payment_filters(&list_query()).is_ok());
+    assert!(check_payment_filters(&PaymentListQuery { provider: Some("paystack".into()), status: Some("success".into()), ..list_query() }).is_ok());
+    assert!(check_payment_filters(&PaymentListQuery { provider: Some("paypal".into()), ..list_query() }).is_err());
+    assert!(check_payment_filters(&PaymentListQuery { status: Some("done".into()), ..list_query() }).is_err());
+
+    let now = chrono::Utc::now();
+    let inverted = PaymentListQuery { from: Some(now), to: Some(now), ..list_query() };
+    assert!(matches!(check_payment_filters(&inverted), Err(AppError::Validation(_))));
+}
+
+fn paystack_sign(secret: &str, body: &[u8]) -> String {
+    use hmac::{Hmac, Mac};
+    let mut mac = Hmac::<sha2::Sha512>::new_from_slice(secret.as_bytes()).unwrap();
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[test]
+fn paystack_signature_accepts_the_matching_hmac() {
+    let body = br#"{"event":"charge.success"}"#;
+    let sig = paystack_sign("whsec", body);
+    assert!(paystack_signature_matches("whsec", body, &sig));
+    assert!(paystack_signature_matches("whsec", body, &sig.to_uppercase()));
+}
+
+#[test]
+fn paystack_signature_rejects_forgeries() {
+    let body = br#"{"event":"charge.success"}"#;
+    let sig = paystack_sign("whsec", body);
+    assert!(!paystack_signature_matches("other", body, &sig));
+    assert!(!paystack_signature_matches("whsec", b"{}", &sig));
+    assert!(!paystack_signature_matches("whsec", body, &sig[..64]));
+    assert!(!paystack_signature_matches("whsec", body, ""));
+}
+
+#[test]
+fn only_pending_payments_can_be_resumed() {
+    assert!(!is_settled_payment("pending"));
+    for settled in ["success", "refunded", "failed"] {
+        assert!(is_settled_payment(settled), "{} is settled", settled);
+    }
+}
+
+// The post-redirect polling burst: many verify calls for one reference at
+// once must become a single lookup, and every caller gets its result.
+#[tokio::test]
+async fn concurrent_verifies_share_one_lookup() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let cache = verification_cache();
+    let lookups = Arc::new(AtomicUsize::new(0));
+    let calls: Vec<_> = (0..20).map(|_| {
+        let (cache, lookups) = (cache.clone(), Arc::clone(&lookups));
+        tokio::spawn(async move {
+            coalesced(&cache, "BUKR-REF-1", async {
+                lookups.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                Ok(serde_json::json!({ "status": "pending" }))
+            }).await
+        })
+    }).collect();
+
+    for call in calls {
+        assert_eq!(call.await.unwrap().unwrap()["status"], "pending");
+    }
+    assert_eq!(lookups.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn failed_verify_is_not_cached() {
+    let cache = verification_cache();
+    let missing = coalesced(&cache, "BUKR-REF-2", async { Err(AppError::NotFound("Payment not found".into())) }).await;
+    assert!(matches!(missing, Err(AppError::NotFound(_))));
+
+    let found = coalesced(&cache, "BUKR-REF-2", async { Ok(serde_json::json!({ "status": "success" })) }).await;
+    assert_eq!(found.unwrap()["status"], "success");
+}
+
+#[test]
+fn split_part_must_fit_in_what_is_still_owed() {
+    let total = Decimal::new(250_000, 0);
+    let paid = Decimal::new(100_000, 0);
+    let none = Decimal::ZERO;
+    assert_eq!(split_charge(total, paid, none, None).unwrap(), Decimal::new(150_000, 0));
+    assert_eq!(split_charge(total, paid, none, Some(Decimal::new(50_000, 0))).unwrap(), Decimal::new(50_000, 0));
+    assert!(matches!(split_charge(total, paid, none, Some(Decimal::new(150_001, 0))), Err(AppError::Validation(_))));
+    assert!(matches!(split_charge(total, paid, none, Some(Decimal::ZERO)), Err(AppError::Validation(_))));
+    assert!(matches!(split_charge(total, paid, none, Some(Decimal::new(1_001, 3))), Err(AppError::Validation(_))));
+    assert!(matches!(split_charge(total, total, none, None), Err(AppError::BadRequest(_))));
+}
+
+#[test]
+fn parts_in_flight_hold_their_share_of_the_balance() {
+    let total = Decimal::new(250_000, 0);
+    let paid = Decimal::new(100_000, 0);
+    let held = Decimal::new(100_000, 0);
+    assert_eq!(split_charge(total, paid, held, None).unwrap(), Decimal::new(50_000, 0));
+    assert!(matches!(split_charge(total, paid, held, Some(Decimal::new(50_001, 0))), Err(AppError::Validation(_))));
+    assert!(matches!(split_charge(total, paid, Decimal::new(150_000, 0), None), Err(AppError::Conflict(_))));
+}
+
+#[test]
+fn split_parts_carry_their_share_of_the_fees() {
+    let fee = Decimal::new(1_000, 0);
+    let total = Decimal::new(300, 0);
+    assert_eq!(prorate(fee, Decimal::new(100, 0), total), Decimal::new(33333, 2));
+    assert_eq!(prorate(fee, total, total), fee);
+}
+
+#[test]
+fn charge_on_a_cancelled_ticket_is_refunded_in_full() {
+    for status in [TicketStatus::Cancelled, TicketStatus::Refunded, TicketStatus::Expired] {
+        assert!(refunds_late_charge(status), "{}", status);
+    }
+    for status in [TicketStatus::Pending, TicketStatus::Failed, TicketStatus::Valid, TicketStatus::Used] {
+        assert!(!refunds_late_charge(status), "{}", status);
+    }
+}
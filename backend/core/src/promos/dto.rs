@@ -16,9 +16,16 @@ use uuid::Uuid;
 #[derive(Debug, Deserialize)]
 pub struct CreatePromoRequest {
     pub code: String,                      // Promo code (e.g., "SUMMER2024")
-    pub discount_percentage: Decimal,      // Discount (10.00 = 10%)
+    pub promo_type: Option<String>,        // "percentage" (default) | "bundle"
+    #[serde(default)]
+    pub discount_percentage: Decimal,      // Discount (10.00 = 10%); percentage codes only
+    pub buy_quantity: Option<i32>,         // Bundle codes: buy N...
+    pub free_quantity: Option<i32>,        // ...get M free
     pub ticket_limit: i32,                 // Max uses (0 = unlimited)
     pub expires_at: Option<DateTime<Utc>>, // Expiration date
+    #[serde(default)]
+    pub allow_partial: bool,               // Discount the uses left when a basket is larger
+    pub activates_at: Option<DateTime<Utc>>, // Scheduled start — unusable before it
 }
 
 // Request to update promo code (unused but defined for future)
@@ -36,19 +43,66 @@ pub struct UpdatePromoRequest {
 pub struct ValidatePromoRequest {
     pub event_id: Uuid,    // Event to validate for
     pub code: String,      // Promo code to check
+    pub quantity: Option<i32>,  // Tickets in the basket — lets bundle codes quote free units
+}
+
+// Request to validate several promo codes in one call
+#[derive(Debug, Deserialize)]
+pub struct ValidatePromoBatchRequest {
+    pub event_id: Uuid,
+    pub codes: Vec<String>,  // Codes as typed, order preserved in the response
+}
+
+// Request to switch many of an event's codes on or off at once
+#[derive(Debug, Deserialize)]
+pub struct BulkTogglePromoRequest {
+    pub promo_ids: Vec<Uuid>,  // Codes of this event; others are ignored
+    pub active: bool,          // Target state, not a flip
+}
+
+// Result of a bulk toggle
+#[derive(Debug, Serialize)]
+pub struct BulkTogglePromoResponse {
+    pub active: bool,
+    pub updated: u64,          // Codes whose state actually changed
+}
+
+// Request to set or clear an event's total promo discount budget
+#[derive(Debug, Deserialize)]
+pub struct SetPromoBudgetRequest {
+    pub promo_budget: Option<Decimal>,     // Most discount all codes may give (None = no cap)
+}
+
+// An event's promo budget and how much of it is spent
+#[derive(Debug, Serialize)]
+pub struct PromoBudgetResponse {
+    pub event_id: Uuid,
+    pub promo_budget: Option<Decimal>,
+    pub discount_given: Decimal,           // Taken off by codes so far
+    pub remaining: Option<Decimal>,        // None when there is no budget
 }
 
 // Promo code response
 #[derive(Debug, Serialize)]
 pub struct PromoResponse {
     pub id: Uuid,
-    pub event_id: Uuid,
+    pub event_id: Option<Uuid>,        // None = platform-wide
     pub code: String,
+    pub promo_type: String,            // percentage | bundle
     pub discount_percentage: Decimal,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buy_quantity: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub free_quantity: Option<i32>,
     pub ticket_limit: i32,
     pub used_count: i32,               // How many times used
     pub is_active: bool,               // Enabled/disabled
+    pub allow_partial: bool,
+    #[serde(with = "crate::timestamp::option")]
+    pub activates_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::timestamp::option")]
     pub expires_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::timestamp")]
     pub created_at: DateTime<Utc>,
 }
 
@@ -56,19 +110,57 @@ pub struct PromoResponse {
 #[derive(Debug, Serialize)]
 pub struct ValidatePromoResponse {
     pub valid: bool,                       // Is code valid?
-    pub discount_percentage: Decimal,      // Discount amount
+    pub promo_type: String,                // percentage | bundle
+    pub discount_percentage: Decimal,      // Discount amount (bundles: effective % for the quantity, else 0)
     pub remaining_uses: Option<i32>,       // Uses left (None = unlimited)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bundle: Option<BundleQuote>,       // Bundle codes only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial: Option<PromoSplit>,       // Basket larger than the uses left (allow_partial codes)
+}
+
+// How a partially available code splits a basket
+#[derive(Debug, Clone, Serialize)]
+pub struct PromoSplit {
+    pub discounted_units: i32,             // Tickets the code still covers
+    pub full_price_units: i32,             // The rest of the basket
+    pub discount_percentage: Decimal,      // The code's rate on the discounted tickets
+    pub effective_discount: Decimal,       // Blended rate over the whole basket
+}
+
+// How a bundle code applies to a basket
+#[derive(Debug, Serialize)]
+pub struct BundleQuote {
+    pub buy_quantity: i32,
+    pub free_quantity: i32,
+    pub description: String,               // "Buy 3, get 1 free"
+    pub quantity: Option<i32>,             // Echo of the requested quantity
+    pub free_units: Option<i32>,           // Tickets not charged for at that quantity
+}
+
+// Per-code result of a batch validation
+#[derive(Debug, Serialize)]
+pub struct PromoBatchResult {
+    pub code: String,                          // Code as submitted
+    pub valid: bool,
+    pub discount_percentage: Option<Decimal>,  // Set only when valid
+    pub reason: Option<String>,                // not_found | inactive | expired | not_yet_active | usage_limit_reached
 }
 
 // Database model for promo code
 pub struct PromoCode {
     pub id: Uuid,
-    pub event_id: Uuid,
+    pub event_id: Option<Uuid>,        // None = platform-wide
     pub code: String,
-    pub discount_percentage: Decimal,
+    pub promo_type: String,            // percentage | bundle
+    pub discount_percentage: Decimal,  // 0 for bundle codes
+    pub buy_quantity: Option<i32>,     // Bundle codes only
+    pub free_quantity: Option<i32>,
     pub ticket_limit: i32,
     pub used_count: i32,
     pub is_active: bool,
+    pub allow_partial: bool,
+    pub activates_at: Option<DateTime<Utc>>,
     pub expires_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
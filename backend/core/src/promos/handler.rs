@@ -9,10 +9,16 @@
  * 
  * Endpoints:
  * - GET /events/{event_id}/promos: List all promo codes for event
+ * - GET /events/{event_id}/promos/by-code/{code}: One promo code, any state (editing)
  * - POST /events/{event_id}/promos: Create new promo code
  * - DELETE /events/{event_id}/promos/{promo_id}: Delete promo code
  * - PATCH /events/{event_id}/promos/{promo_id}/toggle: Enable/disable promo
+ * - POST /events/{event_id}/promos/bulk-toggle: Enable/disable many promos at once
+ * - GET/PUT /events/{event_id}/promo-budget: Total discount cap across all codes
  * - POST /promos/validate: Validate promo code for ticket purchase
+ * - POST /promos/validate-batch: Validate up to 50 codes in one call (event organizer)
+ * - GET/POST /admin/promos: Platform-wide codes (admin)
+ * - DELETE /admin/promos/{promo_id}, PATCH /admin/promos/{promo_id}/toggle
  * 
  * Use Cases:
  * 1. Event organizers create discount codes
@@ -21,17 +27,32 @@
  */
 
 use axum::{
-    extract::{Path, State},
-    Json,
+    extract::State,
+    http::HeaderMap,
 };
 use serde_json::{json, Value};
 use uuid::Uuid;
 
-use crate::error::Result;
-use super::dto::{CreatePromoRequest, ValidatePromoRequest};
+use crate::audit::actor_from_headers;
+use crate::error::{AppError, Result};
+use crate::extract::{Json, Path};
+use super::dto::{BulkTogglePromoRequest, CreatePromoRequest, SetPromoBudgetRequest, ValidatePromoBatchRequest, ValidatePromoRequest};
 use super::service::PromoService;
 use std::sync::Arc;
 
+// Admin routes: the gateway forwards X-User-Type from the verified JWT.
+fn require_admin(headers: &HeaderMap) -> Result<Uuid> {
+    let user_id = actor_from_headers(headers).ok_or(AppError::Unauthorized)?;
+    let user_type = headers
+        .get("x-user-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    if user_type != "admin" {
+        return Err(AppError::Forbidden);
+    }
+    Ok(user_id)
+}
+
 /**
  * List Promo Codes
  * 
@@ -53,6 +74,30 @@ pub async fn list_promos(
     })))
 }
 
+/**
+ * Get Promo Code by Code
+ * 
+ * Current values of one code for the edit form — returned whether
+ * active, disabled, expired or used up. Event owner only.
+ * 
+ * @param service - Promo service instance
+ * @param event_id - Event ID
+ * @param code - Promo code (case-insensitive)
+ * @returns Promo code
+ */
+pub async fn get_promo_by_code(
+    State(service): State<Arc<PromoService>>,
+    headers: HeaderMap,
+    Path((event_id, code)): Path<(Uuid, String)>,
+) -> Result<Json<Value>> {
+    let organizer_id = actor_from_headers(&headers).ok_or(AppError::Unauthorized)?;
+    let promo = service.get_by_code(organizer_id, event_id, &code).await?;
+    Ok(Json(json!({
+        "status": "success",
+        "data": promo
+    })))
+}
+
 /**
  * Create Promo Code
  * 
@@ -71,10 +116,11 @@ pub async fn list_promos(
  */
 pub async fn create_promo(
     State(service): State<Arc<PromoService>>,
+    headers: HeaderMap,
     Path(event_id): Path<Uuid>,
     Json(req): Json<CreatePromoRequest>,
 ) -> Result<Json<Value>> {
-    let promo = service.create(event_id, req).await?;
+    let promo = service.create(actor_from_headers(&headers), Some(event_id), req).await?;
     Ok(Json(json!({
         "status": "success",
         "data": promo
@@ -94,9 +140,10 @@ pub async fn create_promo(
  */
 pub async fn delete_promo(
     State(service): State<Arc<PromoService>>,
+    headers: HeaderMap,
     Path((event_id, promo_id)): Path<(Uuid, Uuid)>,
 ) -> Result<Json<Value>> {
-    service.delete(promo_id, event_id).await?;
+    service.delete(actor_from_headers(&headers), promo_id, Some(event_id)).await?;
     Ok(Json(json!({
         "status": "success",
         "data": { "message": "Promo code deleted" }
@@ -116,15 +163,90 @@ pub async fn delete_promo(
  */
 pub async fn toggle_promo(
     State(service): State<Arc<PromoService>>,
+    headers: HeaderMap,
     Path((event_id, promo_id)): Path<(Uuid, Uuid)>,
 ) -> Result<Json<Value>> {
-    let promo = service.toggle_active(promo_id, event_id).await?;
+    let promo = service.toggle_active(actor_from_headers(&headers), promo_id, Some(event_id)).await?;
     Ok(Json(json!({
         "status": "success",
         "data": promo
     })))
 }
 
+/**
+ * Bulk Toggle Promo Codes
+ * 
+ * Set many of an event's codes active or inactive in one statement
+ * Organizer only - the caller must own the event
+ * 
+ * @param service - Promo service instance
+ * @param event_id - Event ID
+ * @param req - Promo ids and target state
+ * @returns Number of codes changed
+ */
+pub async fn bulk_toggle_promos(
+    State(service): State<Arc<PromoService>>,
+    headers: HeaderMap,
+    Path(event_id): Path<Uuid>,
+    Json(req): Json<BulkTogglePromoRequest>,
+) -> Result<Json<Value>> {
+    let organizer_id = actor_from_headers(&headers).ok_or(AppError::Unauthorized)?;
+    let result = service.bulk_set_active(organizer_id, event_id, req).await?;
+    Ok(Json(json!({
+        "status": "success",
+        "data": result
+    })))
+}
+
+/**
+ * Get Promo Budget
+ *
+ * The event's total discount cap and how much of it codes have given.
+ * Event owner only.
+ *
+ * @param service - Promo service instance
+ * @param event_id - Event ID
+ * @returns promo_budget, discount_given, remaining
+ */
+pub async fn get_promo_budget(
+    State(service): State<Arc<PromoService>>,
+    headers: HeaderMap,
+    Path(event_id): Path<Uuid>,
+) -> Result<Json<Value>> {
+    let organizer_id = actor_from_headers(&headers).ok_or(AppError::Unauthorized)?;
+    let result = service.budget(organizer_id, event_id).await?;
+    Ok(Json(json!({
+        "status": "success",
+        "data": result
+    })))
+}
+
+/**
+ * Set Promo Budget
+ *
+ * Cap the total discount all of the event's codes may give
+ * (null removes the cap). Checkout refuses a code once an order's
+ * discount would go past it.
+ *
+ * @param service - Promo service instance
+ * @param event_id - Event ID
+ * @param req - promo_budget
+ * @returns promo_budget, discount_given, remaining
+ */
+pub async fn set_promo_budget(
+    State(service): State<Arc<PromoService>>,
+    headers: HeaderMap,
+    Path(event_id): Path<Uuid>,
+    Json(req): Json<SetPromoBudgetRequest>,
+) -> Result<Json<Value>> {
+    let organizer_id = actor_from_headers(&headers).ok_or(AppError::Unauthorized)?;
+    let result = service.set_budget(organizer_id, event_id, req.promo_budget).await?;
+    Ok(Json(json!({
+        "status": "success",
+        "data": result
+    })))
+}
+
 /**
  * Validate Promo Code
  * 
@@ -151,3 +273,110 @@ pub async fn validate_promo(
         "data": result
     })))
 }
+
+/**
+ * Validate Promo Codes (batch)
+ *
+ * Per-code verdicts for a list of codes; invalid codes are reported with a
+ * reason instead of failing the request. Only the event's organizer (or an
+ * admin) may call it — the reasons would otherwise let anyone enumerate an
+ * event's codes 50 at a time.
+ *
+ * @param service - Promo service instance
+ * @param headers - HTTP headers with user auth
+ * @param req - Event ID and codes
+ * @returns results: [{code, valid, discount_percentage, reason}]
+ */
+pub async fn validate_promo_batch(
+    State(service): State<Arc<PromoService>>,
+    headers: HeaderMap,
+    Json(req): Json<ValidatePromoBatchRequest>,
+) -> Result<Json<Value>> {
+    let actor_id = actor_from_headers(&headers).ok_or(AppError::Unauthorized)?;
+    let is_admin = headers
+        .get("x-user-type")
+        .and_then(|v| v.to_str().ok())
+        == Some("admin");
+    let results = service.validate_batch(actor_id, is_admin, req).await?;
+    Ok(Json(json!({
+        "status": "success",
+        "data": { "results": results }
+    })))
+}
+
+/**
+ * GET /api/v1/admin/promos
+ * 
+ * Platform-wide promo codes (no event), valid at checkout for every event
+ * 
+ * @param service - Promo service instance
+ * @param headers - HTTP headers with admin auth
+ * @returns List of global promo codes
+ */
+pub async fn list_global_promos(
+    State(service): State<Arc<PromoService>>,
+    headers: HeaderMap,
+) -> Result<Json<Value>> {
+    require_admin(&headers)?;
+    let promos = service.list_global().await?;
+    Ok(Json(json!({
+        "status": "success",
+        "data": { "promos": promos }
+    })))
+}
+
+/**
+ * POST /api/v1/admin/promos
+ * 
+ * Create a platform-wide promo code (e.g. "NEWUSER10")
+ * An event's own code with the same text takes precedence for that event.
+ * 
+ * @param service - Promo service instance
+ * @param headers - HTTP headers with admin auth
+ * @param req - Promo creation request
+ * @returns Created promo code
+ */
+pub async fn create_global_promo(
+    State(service): State<Arc<PromoService>>,
+    headers: HeaderMap,
+    Json(req): Json<CreatePromoRequest>,
+) -> Result<Json<Value>> {
+    let admin_id = require_admin(&headers)?;
+    let promo = service.create(Some(admin_id), None, req).await?;
+    Ok(Json(json!({
+        "status": "success",
+        "data": promo
+    })))
+}
+
+/**
+ * DELETE /api/v1/admin/promos/:promo_id
+ */
+pub async fn delete_global_promo(
+    State(service): State<Arc<PromoService>>,
+    headers: HeaderMap,
+    Path(promo_id): Path<Uuid>,
+) -> Result<Json<Value>> {
+    let admin_id = require_admin(&headers)?;
+    service.delete(Some(admin_id), promo_id, None).await?;
+    Ok(Json(json!({
+        "status": "success",
+        "data": { "message": "Promo code deleted" }
+    })))
+}
+
+/**
+ * PATCH /api/v1/admin/promos/:promo_id/toggle
+ */
+pub async fn toggle_global_promo(
+    State(service): State<Arc<PromoService>>,
+    headers: HeaderMap,
+    Path(promo_id): Path<Uuid>,
+) -> Result<Json<Value>> {
+    let admin_id = require_admin(&headers)?;
+    let promo = service.toggle_active(Some(admin_id), promo_id, None).await?;
+    Ok(Json(json!({
+        "status": "success",
+        "data": promo
+    })))
+}
@@ -15,3 +15,6 @@ pub mod handler;
 pub mod service;
 pub mod repository;
 pub mod dto;
+
+#[cfg(test)]
+mod service_test;
@@ -12,20 +12,33 @@
  * - id: UUID primary key
  * - event_id: Foreign key to events
  * - code: Promo code string (unique per event)
- * - discount_percentage: Decimal discount
- * - ticket_limit: Max uses (0 = unlimited)
- * - used_count: Current usage count
+ * - promo_type: percentage | bundle
+ * - discount_percentage: Decimal discount (percentage codes)
+ * - buy_quantity, free_quantity: "buy N get M free" (bundle codes)
+ * - ticket_limit: Max uses in tickets (0 = unlimited)
+ * - used_count: Paid-for tickets sold with the code so far
  * - is_active: Enable/disable flag
+ * - allow_partial: Discount the uses left when a basket is larger
+ * - activates_at: Optional scheduled start (unusable before it)
  * - expires_at: Optional expiration
  * - created_at, updated_at: Timestamps
  */
 
-use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use sqlx::{PgPool, Row};
 use uuid::Uuid;
 
 use super::dto::PromoCode;
+use super::service::NewPromo;
+
+// Money taken off by promo codes on an event's tickets. Tickets that never
+// paid or were paid back don't count against the budget.
+const DISCOUNT_GIVEN_SQL: &str = r#"
+    SELECT COALESCE(SUM(unit_price * COALESCE(promo_units, quantity)
+                        * COALESCE(promo_rate, discount_applied) / 100), 0)::NUMERIC(14,2)
+    FROM tickets
+    WHERE event_id = $1 AND promo_code_id IS NOT NULL
+      AND status NOT IN ('failed', 'cancelled', 'refunded')"#;
 
 /**
  * PromoRepository: Database access for promo codes
@@ -50,6 +63,10 @@ impl PromoRepository {
         Self { pool }
     }
 
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
     /**
      * List Promo Codes by Event
      * 
@@ -62,7 +79,8 @@ impl PromoRepository {
     pub async fn list_by_event(&self, event_id: Uuid) -> Result<Vec<PromoCode>, sqlx::Error> {
         let rows = sqlx::query(
             r#"SELECT id, event_id, code, discount_percentage, ticket_limit,
-                      used_count, is_active, expires_at, created_at, updated_at
+                      used_count, is_active, expires_at, created_at, updated_at,
+                      promo_type, buy_quantity, free_quantity, allow_partial, activates_at
             FROM promo_codes WHERE event_id = $1 ORDER BY created_at DESC"#,
         )
         .bind(event_id)
@@ -73,44 +91,80 @@ impl PromoRepository {
         Ok(rows.iter().map(row_to_promo).collect())
     }
 
+    /**
+     * List Platform-Wide Promo Codes (event_id NULL), newest first
+     */
+    pub async fn list_global(&self) -> Result<Vec<PromoCode>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"SELECT id, event_id, code, discount_percentage, ticket_limit,
+                      used_count, is_active, expires_at, created_at, updated_at,
+                      promo_type, buy_quantity, free_quantity, allow_partial, activates_at
+            FROM promo_codes WHERE event_id IS NULL ORDER BY created_at DESC"#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(row_to_promo).collect())
+    }
+
     /**
      * Create Promo Code
      * 
      * Insert new promo code into database
-     * Unique constraint on (event_id, code)
+     * Unique per event, and separately unique among global codes
      * 
-     * @param event_id - Event ID
-     * @param code - Promo code string
-     * @param discount_percentage - Discount percentage
-     * @param ticket_limit - Max uses (0 = unlimited)
-     * @param expires_at - Optional expiration date
+     * @param promo - Code, terms, limit and window (already validated);
+     *                event_id None for a platform-wide code
      * @returns Created promo code
      */
     pub async fn create(
         &self,
-        event_id: Uuid,
-        code: &str,
-        discount_percentage: Decimal,
-        ticket_limit: i32,
-        expires_at: Option<DateTime<Utc>>,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        promo: &NewPromo,
     ) -> Result<PromoCode, sqlx::Error> {
         let row = sqlx::query(
-            r#"INSERT INTO promo_codes (event_id, code, discount_percentage, ticket_limit, expires_at)
-            VALUES ($1, $2, $3, $4, $5)
+            r#"INSERT INTO promo_codes
+                (event_id, code, discount_percentage, ticket_limit, expires_at,
+                 promo_type, buy_quantity, free_quantity, allow_partial, activates_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
             RETURNING id, event_id, code, discount_percentage, ticket_limit,
-                      used_count, is_active, expires_at, created_at, updated_at"#,
+                      used_count, is_active, expires_at, created_at, updated_at,
+                      promo_type, buy_quantity, free_quantity, allow_partial, activates_at"#,
         )
-        .bind(event_id)
-        .bind(code)
-        .bind(discount_percentage)
-        .bind(ticket_limit)
-        .bind(expires_at)
-        .fetch_one(&self.pool)
+        .bind(promo.event_id)
+        .bind(&promo.code)
+        .bind(promo.terms.discount_percentage())
+        .bind(promo.ticket_limit)
+        .bind(promo.window.expires_at)
+        .bind(promo.terms.promo_type())
+        .bind(promo.terms.bundle().map(|(buy, _)| buy))
+        .bind(promo.terms.bundle().map(|(_, free)| free))
+        .bind(promo.allow_partial)
+        .bind(promo.window.activates_at)
+        .fetch_one(&mut **tx)
         .await?;
 
         Ok(row_to_promo(&row))
     }
 
+    /// Codes on an event, active or not — what the per-event cap counts.
+    /// Locks the event row first, so concurrent creates count one at a time
+    /// and can't both slip in under the cap.
+    pub async fn count_for_event_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query("SELECT id FROM events WHERE id = $1 FOR UPDATE")
+            .bind(event_id)
+            .execute(&mut **tx)
+            .await?;
+        sqlx::query_scalar("SELECT COUNT(*) FROM promo_codes WHERE event_id = $1")
+            .bind(event_id)
+            .fetch_one(&mut **tx)
+            .await
+    }
+
     /**
      * Delete Promo Code
      * 
@@ -118,11 +172,11 @@ impl PromoRepository {
      * Requires both promo_id and event_id (authorization)
      * 
      * @param promo_id - Promo code ID
-     * @param event_id - Event ID
+     * @param event_id - Event ID, or None for a platform-wide code
      * @returns true if deleted, false if not found
      */
-    pub async fn delete(&self, promo_id: Uuid, event_id: Uuid) -> Result<bool, sqlx::Error> {
-        let result = sqlx::query("DELETE FROM promo_codes WHERE id = $1 AND event_id = $2")
+    pub async fn delete(&self, promo_id: Uuid, event_id: Option<Uuid>) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM promo_codes WHERE id = $1 AND event_id IS NOT DISTINCT FROM $2")
             .bind(promo_id)
             .bind(event_id)
             .execute(&self.pool)
@@ -138,15 +192,16 @@ impl PromoRepository {
      * Useful for pausing codes without deleting
      * 
      * @param promo_id - Promo code ID
-     * @param event_id - Event ID
+     * @param event_id - Event ID, or None for a platform-wide code
      * @returns Updated promo code or None if not found
      */
-    pub async fn toggle_active(&self, promo_id: Uuid, event_id: Uuid) -> Result<Option<PromoCode>, sqlx::Error> {
+    pub async fn toggle_active(&self, promo_id: Uuid, event_id: Option<Uuid>) -> Result<Option<PromoCode>, sqlx::Error> {
         let row = sqlx::query(
             r#"UPDATE promo_codes SET is_active = NOT is_active
-            WHERE id = $1 AND event_id = $2
+            WHERE id = $1 AND event_id IS NOT DISTINCT FROM $2
             RETURNING id, event_id, code, discount_percentage, ticket_limit,
-                      used_count, is_active, expires_at, created_at, updated_at"#,
+                      used_count, is_active, expires_at, created_at, updated_at,
+                      promo_type, buy_quantity, free_quantity, allow_partial, activates_at"#,
         )
         .bind(promo_id)
         .bind(event_id)
@@ -156,15 +211,101 @@ impl PromoRepository {
         Ok(row.as_ref().map(row_to_promo))
     }
 
+    /**
+     * Set Active Status for Many Codes
+     * 
+     * One statement for the whole list. Only the organizer's own codes on
+     * this event are touched; codes already in the target state don't count.
+     * 
+     * @param event_id - Event the codes belong to
+     * @param organizer_id - Caller; must own the event
+     * @param promo_ids - Codes to update
+     * @param active - Target state
+     * @returns Number of codes that changed
+     */
+    pub async fn set_active_bulk(&self, event_id: Uuid, organizer_id: Uuid, promo_ids: &[Uuid], active: bool) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"UPDATE promo_codes p SET is_active = $1
+            FROM events e
+            WHERE p.id = ANY($2) AND p.event_id = $3
+              AND e.id = p.event_id AND e.organizer_id = $4
+              AND p.is_active IS DISTINCT FROM $1"#,
+        )
+        .bind(active)
+        .bind(promo_ids)
+        .bind(event_id)
+        .bind(organizer_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// True when the event exists and belongs to the organizer.
+    pub async fn is_event_organizer(&self, event_id: Uuid, organizer_id: Uuid) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM events WHERE id = $1 AND organizer_id = $2)")
+            .bind(event_id)
+            .bind(organizer_id)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    /**
+     * Set Event Promo Budget
+     *
+     * @param event_id - Event ID
+     * @param budget - Total discount cap, None to remove it
+     */
+    pub async fn set_budget(&self, event_id: Uuid, budget: Option<Decimal>) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE events SET promo_budget = $2 WHERE id = $1")
+            .bind(event_id)
+            .bind(budget)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// The event's promo budget (None = no cap).
+    pub async fn budget(&self, event_id: Uuid) -> Result<Option<Decimal>, sqlx::Error> {
+        sqlx::query_scalar("SELECT promo_budget FROM events WHERE id = $1")
+            .bind(event_id)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    /// Discount given so far on the event's tickets, across all codes.
+    pub async fn discount_given(&self, event_id: Uuid) -> Result<Decimal, sqlx::Error> {
+        sqlx::query_scalar(DISCOUNT_GIVEN_SQL)
+            .bind(event_id)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    /// Same as discount_given, inside checkout — call with the event row
+    /// locked so no other sale can add to it before this one commits.
+    pub async fn discount_given_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: Uuid,
+    ) -> Result<Decimal, sqlx::Error> {
+        sqlx::query_scalar(DISCOUNT_GIVEN_SQL)
+            .bind(event_id)
+            .fetch_one(&mut **tx)
+            .await
+    }
+
     /**
      * Validate Promo Code
      * 
      * Check if promo code is valid for use
      * 
      * Validation Checks (in SQL):
-     * 1. Code exists for event
+     * 1. Code exists for the event, or globally (event_id NULL). When both
+     *    exist the event's own code is the one checked — even if it is
+     *    inactive or spent, it shadows the global code
      * 2. is_active = true
      * 3. Not expired (expires_at IS NULL OR expires_at > NOW())
+     *    and already started (activates_at IS NULL OR activates_at <= NOW())
      * 4. Usage limit not reached (ticket_limit = 0 OR used_count < ticket_limit)
      * 
      * @param event_id - Event ID
@@ -173,11 +314,18 @@ impl PromoRepository {
      */
     pub async fn validate(&self, event_id: Uuid, code: &str) -> Result<Option<PromoCode>, sqlx::Error> {
         let row = sqlx::query(
-            r#"SELECT id, event_id, code, discount_percentage, ticket_limit,
-                      used_count, is_active, expires_at, created_at, updated_at
-            FROM promo_codes
-            WHERE event_id = $1 AND code = $2 AND is_active = true
+            r#"SELECT * FROM (
+                SELECT id, event_id, code, discount_percentage, ticket_limit,
+                       used_count, is_active, expires_at, created_at, updated_at,
+                      promo_type, buy_quantity, free_quantity, allow_partial, activates_at
+                FROM promo_codes
+                WHERE (event_id = $1 OR event_id IS NULL) AND UPPER(code) = UPPER($2)
+                ORDER BY event_id IS NULL
+                LIMIT 1
+            ) p
+            WHERE is_active = true
               AND (expires_at IS NULL OR expires_at > NOW())
+              AND (activates_at IS NULL OR activates_at <= NOW())
               AND (ticket_limit = 0 OR used_count < ticket_limit)"#,
         )
         .bind(event_id)
@@ -187,6 +335,93 @@ impl PromoRepository {
 
         Ok(row.as_ref().map(row_to_promo))
     }
+
+    /**
+     * Lock a Promo's Usage
+     *
+     * Re-reads ticket_limit and used_count under FOR UPDATE inside checkout,
+     * so two baskets can't both be granted the last uses. Taken after the
+     * event row lock, same order as the usage trigger.
+     *
+     * used_count only moves once a ticket is paid for, so tickets still
+     * pending payment are added here — their uses are held, not free.
+     *
+     * @param promo_id - Promo code ID
+     * @returns (ticket_limit, uses taken or held), None if the code was deleted
+     */
+    pub async fn lock_usage_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        promo_id: Uuid,
+    ) -> Result<Option<(i32, i32)>, sqlx::Error> {
+        let row = sqlx::query(
+            r#"SELECT ticket_limit,
+                      used_count + (SELECT COALESCE(SUM(quantity), 0)::INT FROM tickets
+                                     WHERE promo_code_id = $1 AND status = 'pending') AS used_count
+               FROM promo_codes WHERE id = $1 FOR UPDATE"#,
+        )
+        .bind(promo_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+        Ok(row.map(|r| (r.get("ticket_limit"), r.get("used_count"))))
+    }
+
+    /**
+     * Find One Event Promo Code by Code (editing)
+     *
+     * Case-insensitive, no validity filter — inactive, expired and used-up
+     * codes come back too. Only the event's own codes, and only when the
+     * event belongs to `organizer_id`.
+     *
+     * @param event_id - Event ID
+     * @param organizer_id - Caller; must own the event
+     * @param code - Promo code string
+     * @returns Promo code, None if absent or not the caller's
+     */
+    pub async fn find_for_organizer(&self, event_id: Uuid, organizer_id: Uuid, code: &str) -> Result<Option<PromoCode>, sqlx::Error> {
+        let row = sqlx::query(
+            r#"SELECT p.id, p.event_id, p.code, p.discount_percentage, p.ticket_limit,
+                      p.used_count, p.is_active, p.expires_at, p.created_at, p.updated_at,
+                      p.promo_type, p.buy_quantity, p.free_quantity, p.allow_partial, p.activates_at
+            FROM promo_codes p
+            JOIN events e ON e.id = p.event_id
+            WHERE p.event_id = $1 AND e.organizer_id = $2 AND UPPER(p.code) = UPPER($3)"#,
+        )
+        .bind(event_id)
+        .bind(organizer_id)
+        .bind(code)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.as_ref().map(row_to_promo))
+    }
+
+    /**
+     * Find Promo Codes by Code
+     *
+     * Case-insensitive lookup of many codes in one query. No validity
+     * filter — the caller decides why each code is or isn't usable.
+     * Global codes are included; a code may come back twice (event + global).
+     *
+     * @param event_id - Event ID
+     * @param codes - Uppercased promo codes
+     * @returns Matching promo codes (missing codes are simply absent)
+     */
+    pub async fn find_by_codes(&self, event_id: Uuid, codes: &[String]) -> Result<Vec<PromoCode>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"SELECT id, event_id, code, discount_percentage, ticket_limit,
+                      used_count, is_active, expires_at, created_at, updated_at,
+                      promo_type, buy_quantity, free_quantity, allow_partial, activates_at
+            FROM promo_codes
+            WHERE (event_id = $1 OR event_id IS NULL) AND UPPER(code) = ANY($2)"#,
+        )
+        .bind(event_id)
+        .bind(codes)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(row_to_promo).collect())
+    }
 }
 
 /**
@@ -203,10 +438,15 @@ fn row_to_promo(row: &sqlx::postgres::PgRow) -> PromoCode {
         id: row.get("id"),
         event_id: row.get("event_id"),
         code: row.get("code"),
+        promo_type: row.get("promo_type"),
         discount_percentage: row.get("discount_percentage"),
+        buy_quantity: row.get("buy_quantity"),
+        free_quantity: row.get("free_quantity"),
         ticket_limit: row.get("ticket_limit"),
         used_count: row.get("used_count"),
         is_active: row.get("is_active"),
+        allow_partial: row.get("allow_partial"),
+        activates_at: row.get("activates_at"),
         expires_at: row.get("expires_at"),
         created_at: row.get("created_at"),
         updated_at: row.get("updated_at"),
@@ -8,10 +8,14 @@
  * Responsibility: Promo code validation, creation, lifecycle management
  * 
  * Business Rules:
- * 1. Unique codes per event (no duplicates)
+ * 1. Unique codes per event (no duplicates); platform-wide codes (no event)
+ *    apply everywhere, but an event's own code with the same text wins
  * 2. Usage limits (prevent over-redemption)
  * 3. Expiration dates (time-bound offers)
  * 4. Active/inactive states (pause without deleting)
+ * 5. Two discount strategies: a percentage off every ticket, or a bundle
+ *    ("buy N get M free") that is turned into the equivalent percentage
+ *    for the order at purchase time
  * 
  * Validation Logic:
  * - Code must be active
@@ -20,12 +24,114 @@
  * - Belongs to correct event
  */
 
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use uuid::Uuid;
 
-use crate::error::{AppError, Result};
+use crate::error::{is_unique_violation, AppError, Result};
 use super::dto::*;
 use super::repository::PromoRepository;
 
+// Upper bound on codes per batch validation — keeps the ANY($2) array small.
+pub const MAX_BATCH_CODES: usize = 50;
+
+// A bundle can't be bigger than one order (purchase caps quantity at 10),
+// otherwise it could never apply.
+pub const MAX_BUNDLE_SIZE: i32 = 10;
+
+/// What a code takes off: a percentage of each ticket, or free tickets per bundle.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PromoTerms {
+    Percentage(Decimal),
+    Bundle { buy: i32, free: i32 },
+}
+
+impl PromoTerms {
+    /// Validated terms from a create request. promo_type defaults to "percentage".
+    pub fn from_request(req: &CreatePromoRequest) -> Result<Self> {
+        match req.promo_type.as_deref().unwrap_or("percentage") {
+            "percentage" => {
+                let pct = req.discount_percentage;
+                if pct <= Decimal::ZERO || pct > Decimal::from(100) {
+                    return Err(AppError::Validation("discount_percentage must be above 0 and at most 100".into()));
+                }
+                Ok(PromoTerms::Percentage(pct))
+            }
+            "bundle" => {
+                let (Some(buy), Some(free)) = (req.buy_quantity, req.free_quantity) else {
+                    return Err(AppError::Validation("Bundle codes need buy_quantity and free_quantity".into()));
+                };
+                if buy < 1 || free < 1 {
+                    return Err(AppError::Validation("buy_quantity and free_quantity must be at least 1".into()));
+                }
+                if buy + free > MAX_BUNDLE_SIZE {
+                    return Err(AppError::Validation(format!(
+                        "buy_quantity + free_quantity cannot exceed {} (the per-order ticket limit)", MAX_BUNDLE_SIZE
+                    )));
+                }
+                Ok(PromoTerms::Bundle { buy, free })
+            }
+            other => Err(AppError::Validation(format!(
+                "Unknown promo_type '{}' — use 'percentage' or 'bundle'", other
+            ))),
+        }
+    }
+
+    pub fn promo_type(&self) -> &'static str {
+        match self {
+            PromoTerms::Percentage(_) => "percentage",
+            PromoTerms::Bundle { .. } => "bundle",
+        }
+    }
+
+    /// Stored discount_percentage — bundles store 0 and are priced per order.
+    pub fn discount_percentage(&self) -> Decimal {
+        match self {
+            PromoTerms::Percentage(pct) => *pct,
+            PromoTerms::Bundle { .. } => Decimal::ZERO,
+        }
+    }
+
+    pub fn bundle(&self) -> Option<(i32, i32)> {
+        match self {
+            PromoTerms::Bundle { buy, free } => Some((*buy, *free)),
+            PromoTerms::Percentage(_) => None,
+        }
+    }
+}
+
+/// A validated code ready to insert.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewPromo {
+    pub event_id: Option<Uuid>,      // None for a platform-wide code
+    pub code: String,                // Normalized
+    pub terms: PromoTerms,
+    pub ticket_limit: i32,           // 0 = unlimited
+    pub window: PromoWindow,
+    pub allow_partial: bool,         // Discount the uses left when a basket is larger
+}
+
+/// When a code may be used: from activates_at until expires_at, either end open.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PromoWindow {
+    pub activates_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl PromoWindow {
+    /// Validated window from a create request — it must not end before it starts.
+    pub fn from_request(req: &CreatePromoRequest) -> Result<Self> {
+        if let (Some(start), Some(end)) = (req.activates_at, req.expires_at) {
+            if start >= end {
+                return Err(AppError::Validation("activates_at must be before expires_at".into()));
+            }
+        }
+        Ok(PromoWindow { activates_at: req.activates_at, expires_at: req.expires_at })
+    }
+}
+
 /**
  * PromoService: The discount manager
  * 
@@ -37,14 +143,20 @@ use super::repository::PromoRepository;
  */
 pub struct PromoService {
     repo: PromoRepository,    // Database operations
+    code_min_len: usize,      // Inclusive bounds for new codes (from Config)
+    code_max_len: usize,
+    max_codes_per_event: i64, // Cap on codes per event (from Config)
 }
 
 impl PromoService {
     /**
      * Constructor: Initialize promo service
+     *
+     * @param code_min_len / code_max_len - Allowed length range for new codes
+     * @param max_codes_per_event - Most promo codes one event may have
      */
-    pub fn new(repo: PromoRepository) -> Self {
-        Self { repo }
+    pub fn new(repo: PromoRepository, code_min_len: usize, code_max_len: usize, max_codes_per_event: i64) -> Self {
+        Self { repo, code_min_len, code_max_len, max_codes_per_event }
     }
 
     /**
@@ -60,64 +172,94 @@ impl PromoService {
         let promos = self.repo.list_by_event(event_id).await.map_err(AppError::Database)?;
 
         // Map database models to response DTOs
-        Ok(promos.into_iter().map(|p| PromoResponse {
-            id: p.id,
-            event_id: p.event_id,
-            code: p.code,
-            discount_percentage: p.discount_percentage,
-            ticket_limit: p.ticket_limit,
-            used_count: p.used_count,
-            is_active: p.is_active,
-            expires_at: p.expires_at,
-            created_at: p.created_at,
-        }).collect())
+        Ok(promos.into_iter().map(promo_response).collect())
+    }
+
+    /**
+     * Get Promo Code by Code (editing)
+     *
+     * Full current values whatever the code's state — unlike validate,
+     * which only answers for codes usable at checkout.
+     *
+     * @param organizer_id - Caller; must own the event
+     * @param event_id - Event ID
+     * @param code - Promo code, any case
+     * @returns Promo code
+     */
+    pub async fn get_by_code(&self, organizer_id: Uuid, event_id: Uuid, code: &str) -> Result<PromoResponse> {
+        let promo = self.repo.find_for_organizer(event_id, organizer_id, code.trim())
+            .await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Promo code not found".into()))?;
+        Ok(promo_response(promo))
+    }
+
+    /// Platform-wide codes (admin view).
+    pub async fn list_global(&self) -> Result<Vec<PromoResponse>> {
+        let promos = self.repo.list_global().await.map_err(AppError::Database)?;
+        Ok(promos.into_iter().map(promo_response).collect())
     }
 
     /**
      * Create Promo Code
      * 
      * Business logic:
-     * 1. Validate code is not empty
-     * 2. Create in database
+     * 1. Normalize and validate code (uppercase, A-Z 0-9 _ -, length bounds)
+     *    and the active window (activates_at before expires_at)
+     * 2. Count the event's codes against the cap and create, with the event
+     *    row locked so concurrent creates can't both fit under it
      * 3. Handle duplicate code errors
      * 
-     * @param event_id - Event ID
+     * @param actor_id - Organizer (or admin) making the change (audit)
+     * @param event_id - Event ID, or None for a platform-wide code (admin only)
      * @param req - Promo creation request
      * @returns Created promo code
      */
-    pub async fn create(&self, event_id: Uuid, req: CreatePromoRequest) -> Result<PromoResponse> {
-        // Validation: code cannot be empty
-        if req.code.is_empty() {
-            return Err(AppError::Validation("Promo code is required".into()));
+    pub async fn create(&self, actor_id: Option<Uuid>, event_id: Option<Uuid>, req: CreatePromoRequest) -> Result<PromoResponse> {
+        // Validation: spaces/emoji break share URLs and receipts downstream
+        let new = NewPromo {
+            event_id,
+            code: normalize_code(&req.code, self.code_min_len, self.code_max_len)?,
+            terms: PromoTerms::from_request(&req)?,
+            ticket_limit: req.ticket_limit,
+            window: PromoWindow::from_request(&req)?,
+            allow_partial: req.allow_partial,
+        };
+
+        let mut tx = self.repo.pool().begin().await.map_err(AppError::Database)?;
+
+        // Per-event cap — platform-wide codes aren't tied to a listing
+        if let Some(event_id) = event_id {
+            let existing = self.repo.count_for_event_with_tx(&mut tx, event_id).await.map_err(AppError::Database)?;
+            check_code_cap(existing, self.max_codes_per_event)?;
         }
 
         // Create promo code
-        let promo = self.repo.create(
-            event_id,
-            &req.code,
-            req.discount_percentage,
-            req.ticket_limit,
-            req.expires_at,
-        ).await.map_err(|e| {
+        let promo = self.repo.create(&mut tx, &new).await.map_err(|e| {
             // Handle duplicate code error
-            if e.to_string().contains("unique") || e.to_string().contains("duplicate") {
-                AppError::Conflict("Promo code already exists for this event".into())
+            if is_unique_violation(&e) {
+                AppError::Conflict(match event_id {
+                    Some(_) => "Promo code already exists for this event".into(),
+                    None => "A platform-wide promo code with this code already exists".into(),
+                })
             } else {
                 AppError::Database(e)
             }
         })?;
+        tx.commit().await.map_err(AppError::Database)?;
+
+        crate::audit::record_logged(self.repo.pool(), actor_id, "promo.create", "promo", promo.id, serde_json::json!({
+            "event_id": event_id,
+            "code": promo.code,
+            "promo_type": promo.promo_type,
+            "discount_percentage": promo.discount_percentage,
+            "buy_quantity": promo.buy_quantity,
+            "free_quantity": promo.free_quantity,
+            "ticket_limit": promo.ticket_limit,
+            "allow_partial": promo.allow_partial,
+        })).await;
 
-        Ok(PromoResponse {
-            id: promo.id,
-            event_id: promo.event_id,
-            code: promo.code,
-            discount_percentage: promo.discount_percentage,
-            ticket_limit: promo.ticket_limit,
-            used_count: promo.used_count,
-            is_active: promo.is_active,
-            expires_at: promo.expires_at,
-            created_at: promo.created_at,
-        })
+        Ok(promo_response(promo))
     }
 
     /**
@@ -126,15 +268,18 @@ impl PromoService {
      * Remove promo code from event
      * Does not affect tickets already purchased with code
      * 
+     * @param actor_id - Organizer making the change (audit)
      * @param promo_id - Promo code ID
-     * @param event_id - Event ID (for authorization)
+     * @param event_id - Event ID (for authorization), None for a platform-wide code
      * @returns Success or NotFound error
      */
-    pub async fn delete(&self, promo_id: Uuid, event_id: Uuid) -> Result<()> {
+    pub async fn delete(&self, actor_id: Option<Uuid>, promo_id: Uuid, event_id: Option<Uuid>) -> Result<()> {
         let deleted = self.repo.delete(promo_id, event_id).await.map_err(AppError::Database)?;
         if !deleted {
             return Err(AppError::NotFound("Promo code not found".into()));
         }
+        crate::audit::record_logged(self.repo.pool(), actor_id, "promo.delete", "promo", promo_id,
+            serde_json::json!({ "event_id": event_id })).await;
         Ok(())
     }
 
@@ -144,27 +289,93 @@ impl PromoService {
      * Enable or disable promo code
      * Useful for pausing codes without deleting them
      * 
+     * @param actor_id - Organizer making the change (audit)
      * @param promo_id - Promo code ID
-     * @param event_id - Event ID (for authorization)
+     * @param event_id - Event ID (for authorization), None for a platform-wide code
      * @returns Updated promo code
      */
-    pub async fn toggle_active(&self, promo_id: Uuid, event_id: Uuid) -> Result<PromoResponse> {
+    pub async fn toggle_active(&self, actor_id: Option<Uuid>, promo_id: Uuid, event_id: Option<Uuid>) -> Result<PromoResponse> {
         let promo = self.repo.toggle_active(promo_id, event_id)
             .await
             .map_err(AppError::Database)?
             .ok_or_else(|| AppError::NotFound("Promo code not found".into()))?;
 
-        Ok(PromoResponse {
-            id: promo.id,
-            event_id: promo.event_id,
-            code: promo.code,
-            discount_percentage: promo.discount_percentage,
-            ticket_limit: promo.ticket_limit,
-            used_count: promo.used_count,
-            is_active: promo.is_active,
-            expires_at: promo.expires_at,
-            created_at: promo.created_at,
-        })
+        crate::audit::record_logged(self.repo.pool(), actor_id, "promo.toggle", "promo", promo_id,
+            serde_json::json!({ "event_id": event_id, "is_active": promo.is_active })).await;
+
+        Ok(promo_response(promo))
+    }
+
+    /**
+     * Bulk Set Active Status
+     * 
+     * Pause or resume a whole campaign in one call. `active` is the target
+     * state, so repeating the request is harmless. Ids that aren't this
+     * event's codes are skipped rather than failing the batch.
+     * 
+     * @param organizer_id - Caller; must own the event
+     * @param event_id - Event the codes belong to
+     * @param req - Up to MAX_BATCH_CODES promo ids and the target state
+     * @returns How many codes changed
+     */
+    pub async fn bulk_set_active(&self, organizer_id: Uuid, event_id: Uuid, req: BulkTogglePromoRequest) -> Result<BulkTogglePromoResponse> {
+        check_bulk_ids(&req.promo_ids)?;
+        if !self.repo.is_event_organizer(event_id, organizer_id).await.map_err(AppError::Database)? {
+            return Err(AppError::NotFound("Event not found".into()));
+        }
+
+        let updated = self.repo.set_active_bulk(event_id, organizer_id, &req.promo_ids, req.active)
+            .await
+            .map_err(AppError::Database)?;
+
+        crate::audit::record_logged(self.repo.pool(), Some(organizer_id), "promo.bulk_toggle", "event", event_id,
+            serde_json::json!({ "promo_ids": req.promo_ids, "is_active": req.active, "updated": updated })).await;
+
+        Ok(BulkTogglePromoResponse { active: req.active, updated })
+    }
+
+    /**
+     * Set Event Promo Budget
+     *
+     * Caps the total discount all of an event's codes may give. Lowering it
+     * below what has already been given is allowed — it just stops further
+     * discounts.
+     *
+     * @param organizer_id - Caller; must own the event
+     * @param event_id - Event ID
+     * @param budget - Total discount cap, None to remove it
+     * @returns Budget and discount given so far
+     */
+    pub async fn set_budget(&self, organizer_id: Uuid, event_id: Uuid, budget: Option<Decimal>) -> Result<PromoBudgetResponse> {
+        if budget.is_some_and(|b| b < Decimal::ZERO) {
+            return Err(AppError::Validation("promo_budget cannot be negative".into()));
+        }
+        if !self.repo.is_event_organizer(event_id, organizer_id).await.map_err(AppError::Database)? {
+            return Err(AppError::NotFound("Event not found".into()));
+        }
+
+        self.repo.set_budget(event_id, budget).await.map_err(AppError::Database)?;
+        crate::audit::record_logged(self.repo.pool(), Some(organizer_id), "event.promo_budget_set", "event", event_id,
+            serde_json::json!({ "promo_budget": budget })).await;
+
+        let given = self.repo.discount_given(event_id).await.map_err(AppError::Database)?;
+        Ok(budget_response(event_id, budget, given))
+    }
+
+    /**
+     * Get Event Promo Budget
+     *
+     * @param organizer_id - Caller; must own the event
+     * @param event_id - Event ID
+     * @returns Budget, discount given so far and what is left
+     */
+    pub async fn budget(&self, organizer_id: Uuid, event_id: Uuid) -> Result<PromoBudgetResponse> {
+        if !self.repo.is_event_organizer(event_id, organizer_id).await.map_err(AppError::Database)? {
+            return Err(AppError::NotFound("Event not found".into()));
+        }
+        let budget = self.repo.budget(event_id).await.map_err(AppError::Database)?;
+        let given = self.repo.discount_given(event_id).await.map_err(AppError::Database)?;
+        Ok(budget_response(event_id, budget, given))
     }
 
     /**
@@ -178,8 +389,11 @@ impl PromoService {
      * 3. Not expired
      * 4. Usage limit not reached (if set)
      * 
-     * @param req - Validation request (event_id, code)
-     * @returns Discount percentage and remaining uses
+     * Bundle codes explain themselves: with a quantity, the response says how
+     * many tickets come free and the effective discount for that basket.
+     * 
+     * @param req - Validation request (event_id, code, optional quantity)
+     * @returns Discount percentage, remaining uses and any bundle quote
      */
     pub async fn validate(&self, req: ValidatePromoRequest) -> Result<ValidatePromoResponse> {
         // Query promo code with all validation checks
@@ -196,13 +410,346 @@ impl PromoService {
                     None    // Unlimited uses
                 };
 
+                if req.quantity.is_some_and(|q| q < 1) {
+                    return Err(AppError::Validation("quantity must be at least 1".into()));
+                }
+                let bundle = match (p.buy_quantity, p.free_quantity) {
+                    (Some(buy), Some(free)) if p.promo_type == "bundle" => Some(BundleQuote {
+                        buy_quantity: buy,
+                        free_quantity: free,
+                        description: format!("Buy {}, get {} free", buy, free),
+                        quantity: req.quantity,
+                        free_units: req.quantity.map(|q| bundle_free_units(q, buy, free)),
+                    }),
+                    _ => None,
+                };
+                // With a basket: refuse one larger than the uses left, or
+                // quote the split when the code allows partial use.
+                let (discount_percentage, partial) = match req.quantity {
+                    Some(q) => {
+                        let (discount, _, partial) = apply_usage_limit(&p, q)?;
+                        (discount, partial)
+                    }
+                    None => (p.discount_percentage, None),
+                };
+
                 Ok(ValidatePromoResponse {
                     valid: true,
-                    discount_percentage: p.discount_percentage,
+                    promo_type: p.promo_type,
+                    discount_percentage,
                     remaining_uses: remaining,
+                    bundle,
+                    partial,
                 })
             }
             None => Err(AppError::PromoInvalid("Promo code is invalid, expired, or has reached its usage limit".into())),
         }
     }
+
+    /**
+     * Validate Many Promo Codes
+     *
+     * One query for the whole list; each code gets its own verdict and a
+     * reason when it can't be used. Lets an organizer check a pasted list
+     * without one request per code. Restricted to the event's organizer:
+     * per-code reasons are an enumeration oracle in anyone else's hands.
+     *
+     * @param actor_id - Caller
+     * @param is_admin - Admins may check any event
+     * @param req - Event ID and up to MAX_BATCH_CODES codes
+     * @returns One result per submitted code, in request order
+     */
+    pub async fn validate_batch(&self, actor_id: Uuid, is_admin: bool, req: ValidatePromoBatchRequest) -> Result<Vec<PromoBatchResult>> {
+        if !is_admin && !self.repo.is_event_organizer(req.event_id, actor_id).await.map_err(AppError::Database)? {
+            return Err(AppError::NotFound("Event not found".into()));
+        }
+        if req.codes.is_empty() {
+            return Err(AppError::Validation("codes must not be empty".into()));
+        }
+        if req.codes.len() > MAX_BATCH_CODES {
+            return Err(AppError::Validation(format!(
+                "At most {} codes can be validated per request", MAX_BATCH_CODES
+            )));
+        }
+
+        let mut lookup: Vec<String> = req.codes.iter().map(|c| c.trim().to_uppercase()).collect();
+        lookup.sort();
+        lookup.dedup();
+
+        let found = prefer_event_codes(
+            self.repo.find_by_codes(req.event_id, &lookup).await.map_err(AppError::Database)?,
+        );
+
+        let now = Utc::now();
+        Ok(req.codes.into_iter().map(|code| {
+            let promo = found.get(&code.trim().to_uppercase());
+            let reason = promo_rejection(promo, now);
+            PromoBatchResult {
+                code,
+                valid: reason.is_none(),
+                discount_percentage: promo.filter(|_| reason.is_none()).map(|p| p.discount_percentage),
+                reason: reason.map(str::to_string),
+            }
+        }).collect())
+    }
+}
+
+/// A bulk toggle names at least one code and no more than a batch validation takes.
+pub fn check_bulk_ids(promo_ids: &[Uuid]) -> Result<()> {
+    if promo_ids.is_empty() {
+        return Err(AppError::Validation("promo_ids must not be empty".into()));
+    }
+    if promo_ids.len() > MAX_BATCH_CODES {
+        return Err(AppError::Validation(format!(
+            "At most {} codes can be toggled per request", MAX_BATCH_CODES
+        )));
+    }
+    Ok(())
+}
+
+/**
+ * Normalize a new promo code: trim + uppercase, then require
+ * ^[A-Z0-9_-]{min,max}$
+ *
+ * @param raw - Code as typed by the organizer
+ * @returns Uppercased code, or a Validation error naming the rule
+ */
+pub fn normalize_code(raw: &str, min_len: usize, max_len: usize) -> Result<String> {
+    let code = raw.trim().to_uppercase();
+    if code.is_empty() {
+        return Err(AppError::Validation("Promo code is required".into()));
+    }
+
+    let allowed = code.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_' || c == '-');
+    let len = code.chars().count();
+    if !allowed || len < min_len || len > max_len {
+        return Err(AppError::Validation(format!(
+            "Promo code must be {}-{} characters using only letters A-Z, digits 0-9, '_' or '-'",
+            min_len, max_len
+        )));
+    }
+    Ok(code)
+}
+
+/// Free tickets a "buy `buy` get `free` free" bundle gives on `quantity`
+/// tickets: `free` for every complete group of buy + free.
+pub(crate) fn bundle_free_units(quantity: i32, buy: i32, free: i32) -> i32 {
+    let group = buy + free;
+    if quantity <= 0 || buy < 1 || free < 1 {
+        return 0;
+    }
+    (quantity / group) * free
+}
+
+/**
+ * Discount percentage a promo gives on an order of `quantity` tickets
+ *
+ * Percentage codes: their stored percentage. Bundle codes: the free share of
+ * the order, e.g. 1 free of 4 = 25.00, rounded to 2 places to fit
+ * tickets.discount_applied. This is the headline figure only — fees price
+ * each ticket, so charges come from promo_pricing instead.
+ */
+pub(crate) fn effective_discount(promo: &PromoCode, quantity: i32) -> Decimal {
+    match (promo.promo_type.as_str(), promo.buy_quantity, promo.free_quantity) {
+        ("bundle", Some(buy), Some(free)) if quantity > 0 => {
+            let free_units = bundle_free_units(quantity, buy, free);
+            (Decimal::from(free_units * 100) / Decimal::from(quantity)).round_dp(2)
+        }
+        ("bundle", _, _) => Decimal::ZERO,
+        _ => promo.discount_percentage,
+    }
+}
+
+/// How a promo prices an order: `units` tickets at `rate` percent off, the
+/// rest at list price. Stored on the ticket as promo_units / promo_rate so
+/// checkout retries and replays price it the same way.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PromoPricing {
+    pub units: i32,
+    pub rate: Decimal,
+}
+
+impl PromoPricing {
+    pub const NONE: PromoPricing = PromoPricing { units: 0, rate: Decimal::ZERO };
+
+    /// Per-ticket payouts for compute_order_fees: the discounted units, then
+    /// the full-price ones. A free ticket is a ₦0 line, so it carries no fees.
+    pub fn lines(&self, unit_price: Decimal, quantity: i32) -> Vec<(Decimal, i32)> {
+        let units = self.units.clamp(0, quantity);
+        vec![
+            (unit_price * (Decimal::from(100) - self.rate) / Decimal::from(100), units),
+            (unit_price, quantity - units),
+        ]
+    }
+
+    /// Money the promo takes off the order's list price.
+    pub fn discount_amount(&self, unit_price: Decimal) -> Decimal {
+        discount_amount(unit_price, self.units, self.rate)
+    }
+
+    /// Pricing stored on a ticket. Tickets sold before promo_units existed
+    /// took discount_applied off every unit.
+    pub fn stored(quantity: i32, discount_applied: Decimal, units: Option<i32>, rate: Option<Decimal>) -> Self {
+        match (units, rate) {
+            (Some(units), Some(rate)) => PromoPricing { units, rate },
+            _ => PromoPricing { units: quantity, rate: discount_applied },
+        }
+    }
+}
+
+/// Pricing a promo gives on `quantity` tickets: a bundle's free tickets at
+/// 100% off, or a percentage off every ticket.
+pub(crate) fn promo_pricing(promo: &PromoCode, quantity: i32) -> PromoPricing {
+    match (promo.promo_type.as_str(), promo.buy_quantity, promo.free_quantity) {
+        ("bundle", Some(buy), Some(free)) => PromoPricing {
+            units: bundle_free_units(quantity, buy, free),
+            rate: Decimal::from(100),
+        },
+        ("bundle", _, _) => PromoPricing::NONE,
+        _ => PromoPricing { units: quantity, rate: promo.discount_percentage },
+    }
+}
+
+/**
+ * Price a basket against the uses a code has left.
+ *
+ * Uses are tickets. When the basket fits, this is effective_discount. When it
+ * doesn't, allow_partial codes discount the tickets still covered and charge
+ * list price for the rest — the pricing covers only those tickets, so each
+ * part goes through the fees at its own price. The blended rate is the
+ * covered tickets' discount spread over the whole basket, shown to the buyer
+ * but not charged. Other codes refuse the basket.
+ *
+ * @returns Discount for tickets.discount_applied, the pricing to charge,
+ *          and the split when partial
+ */
+pub(crate) fn apply_usage_limit(promo: &PromoCode, quantity: i32) -> Result<(Decimal, PromoPricing, Option<PromoSplit>)> {
+    let remaining = if promo.ticket_limit > 0 {
+        (promo.ticket_limit - promo.used_count).max(0)
+    } else {
+        i32::MAX
+    };
+    if quantity <= remaining {
+        return Ok((effective_discount(promo, quantity), promo_pricing(promo, quantity), None));
+    }
+    if !promo.allow_partial || remaining == 0 {
+        return Err(AppError::PromoInvalid(format!(
+            "Promo code has {} use(s) left, not enough for {} tickets",
+            remaining, quantity
+        )));
+    }
+
+    let covered_rate = effective_discount(promo, remaining);
+    let blended = (covered_rate * Decimal::from(remaining) / Decimal::from(quantity)).round_dp(2);
+    let pricing = promo_pricing(promo, remaining);
+    Ok((blended, pricing, Some(PromoSplit {
+        discounted_units: remaining,
+        full_price_units: quantity - remaining,
+        discount_percentage: covered_rate,
+        effective_discount: blended,
+    })))
+}
+
+/// Money a promo takes off an order: `discount` percent of the list price.
+pub(crate) fn discount_amount(unit_price: Decimal, quantity: i32, discount: Decimal) -> Decimal {
+    (unit_price * Decimal::from(quantity) * discount / Decimal::from(100)).round_dp(2)
+}
+
+/**
+ * Refuse a discount that would take the event past its promo budget.
+ *
+ * @param budget - Event's total discount cap, None = no cap
+ * @param given - Discount already given on the event's tickets
+ * @param discount - What this order would take off
+ */
+pub(crate) fn check_promo_budget(budget: Option<Decimal>, given: Decimal, discount: Decimal) -> Result<()> {
+    let Some(budget) = budget else { return Ok(()) };
+    if discount <= Decimal::ZERO || given + discount <= budget {
+        return Ok(());
+    }
+    let left = (budget - given).max(Decimal::ZERO);
+    Err(AppError::PromoInvalid(if left.is_zero() {
+        "This event's promo budget has been used up — promo codes no longer apply".into()
+    } else {
+        format!("This event's promo budget has {} left, not enough for a {} discount", left, discount)
+    }))
+}
+
+/**
+ * Refuse a new code once the event has as many as it may hold.
+ *
+ * @param existing - Codes the event already has, active or not
+ * @param cap - MAX_PROMO_CODES_PER_EVENT
+ */
+pub(crate) fn check_code_cap(existing: i64, cap: i64) -> Result<()> {
+    if existing < cap {
+        return Ok(());
+    }
+    Err(AppError::Conflict(format!(
+        "Event already has {} promo codes (limit {}) — delete unused codes to add more",
+        existing, cap
+    )))
+}
+
+fn budget_response(event_id: Uuid, budget: Option<Decimal>, given: Decimal) -> PromoBudgetResponse {
+    PromoBudgetResponse {
+        event_id,
+        promo_budget: budget,
+        discount_given: given,
+        remaining: budget.map(|b| (b - given).max(Decimal::ZERO)),
+    }
+}
+
+/// Key codes by uppercase text; when an event code and a global code share
+/// the text, the event's own code wins (same precedence as validate()).
+pub(crate) fn prefer_event_codes(promos: Vec<PromoCode>) -> HashMap<String, PromoCode> {
+    let mut found = HashMap::new();
+    for p in promos {
+        let key = p.code.to_uppercase();
+        if p.event_id.is_some() || !found.contains_key(&key) {
+            found.insert(key, p);
+        }
+    }
+    found
+}
+
+fn promo_response(promo: PromoCode) -> PromoResponse {
+    PromoResponse {
+        id: promo.id,
+        event_id: promo.event_id,
+        code: promo.code,
+        promo_type: promo.promo_type,
+        discount_percentage: promo.discount_percentage,
+        buy_quantity: promo.buy_quantity,
+        free_quantity: promo.free_quantity,
+        ticket_limit: promo.ticket_limit,
+        used_count: promo.used_count,
+        allow_partial: promo.allow_partial,
+        is_active: promo.is_active,
+        activates_at: promo.activates_at,
+        expires_at: promo.expires_at,
+        created_at: promo.created_at,
+    }
+}
+
+/**
+ * Why a promo code can't be used right now — same rules as the SQL in
+ * PromoRepository::validate, evaluated in Rust so batch results can say which
+ * rule failed.
+ *
+ * @returns None if usable, otherwise a stable reason code
+ */
+pub(crate) fn promo_rejection(promo: Option<&PromoCode>, now: DateTime<Utc>) -> Option<&'static str> {
+    let Some(p) = promo else { return Some("not_found") };
+    if !p.is_active {
+        Some("inactive")
+    } else if p.expires_at.is_some_and(|exp| exp <= now) {
+        Some("expired")
+    } else if p.activates_at.is_some_and(|start| start > now) {
+        Some("not_yet_active")
+    } else if p.ticket_limit > 0 && p.used_count >= p.ticket_limit {
+        Some("usage_limit_reached")
+    } else {
+        None
+    }
 }
@@ -0,0 +1,320 @@
+// Unit tests for the DB-free parts of the promo service: code normalization
+// and terms for create(), the per-code rules behind validate_batch(), and
+// bundle pricing.
+
+use chrono::{Duration, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use super::dto::{CreatePromoRequest, PromoCode};
+use super::service::{apply_usage_limit, bundle_free_units, check_bulk_ids, check_code_cap, check_promo_budget, discount_amount, effective_discount, normalize_code, prefer_event_codes, promo_pricing, promo_rejection, PromoPricing, PromoTerms, PromoWindow};
+use crate::fees::{compute_fees, compute_order_fees, FeeMode};
+
+fn promo(is_active: bool, ticket_limit: i32, used_count: i32) -> PromoCode {
+    let now = Utc::now();
+    PromoCode {
+        id: Uuid::new_v4(),
+        event_id: Some(Uuid::new_v4()),
+        code: "SUMMER".into(),
+        promo_type: "percentage".into(),
+        discount_percentage: Decimal::new(10, 0),
+        buy_quantity: None,
+        free_quantity: None,
+        ticket_limit,
+        used_count,
+        is_active,
+        allow_partial: false,
+        activates_at: None,
+        expires_at: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+#[test]
+fn code_is_trimmed_and_uppercased() {
+    assert_eq!(normalize_code("  summer_24-vip ", 3, 32).unwrap(), "SUMMER_24-VIP");
+}
+
+#[test]
+fn spaces_and_emoji_are_rejected() {
+    assert!(normalize_code("SUMMER SALE", 3, 32).is_err());
+    assert!(normalize_code("PARTY🎉", 3, 32).is_err());
+    assert!(normalize_code("ÉTÉ2024", 3, 32).is_err());
+}
+
+#[test]
+fn length_bounds_are_configurable() {
+    assert!(normalize_code("AB", 3, 32).is_err());
+    assert!(normalize_code("ABC", 3, 32).is_ok());
+    assert!(normalize_code(&"A".repeat(33), 3, 32).is_err());
+    assert!(normalize_code(&"A".repeat(32), 3, 32).is_ok());
+    assert!(normalize_code("AB", 2, 4).is_ok());
+    assert!(normalize_code("ABCDE", 2, 4).is_err());
+}
+
+#[test]
+fn batch_rejection_reasons() {
+    let now = Utc::now();
+    assert_eq!(promo_rejection(None, now), Some("not_found"));
+    assert_eq!(promo_rejection(Some(&promo(false, 0, 0)), now), Some("inactive"));
+    assert_eq!(promo_rejection(Some(&promo(true, 5, 5)), now), Some("usage_limit_reached"));
+
+    let mut expired = promo(true, 0, 0);
+    expired.expires_at = Some(now - Duration::minutes(1));
+    assert_eq!(promo_rejection(Some(&expired), now), Some("expired"));
+
+    let mut scheduled = promo(true, 0, 0);
+    scheduled.activates_at = Some(now + Duration::hours(1));
+    assert_eq!(promo_rejection(Some(&scheduled), now), Some("not_yet_active"));
+}
+
+#[test]
+fn batch_accepts_usable_codes() {
+    let now = Utc::now();
+    assert_eq!(promo_rejection(Some(&promo(true, 0, 100)), now), None);
+    assert_eq!(promo_rejection(Some(&promo(true, 5, 4)), now), None);
+
+    let mut future = promo(true, 0, 0);
+    future.expires_at = Some(now + Duration::days(1));
+    assert_eq!(promo_rejection(Some(&future), now), None);
+
+    let mut started = promo(true, 0, 0);
+    started.activates_at = Some(now);
+    assert_eq!(promo_rejection(Some(&started), now), None);
+}
+
+#[test]
+fn event_code_shadows_global_code_with_same_text() {
+    let event_code = promo(false, 0, 0);
+    let event_code_id = event_code.id;
+    let global = PromoCode { event_id: None, ..promo(true, 0, 0) };
+    let global_id = global.id;
+
+    // Order from the DB is not guaranteed — precedence must not depend on it.
+    let found = prefer_event_codes(vec![global, event_code]);
+    assert_eq!(found["SUMMER"].id, event_code_id);
+
+    let only_global = prefer_event_codes(vec![PromoCode { id: global_id, event_id: None, ..promo(true, 0, 0) }]);
+    assert_eq!(only_global["SUMMER"].id, global_id);
+}
+
+fn bundle(buy: i32, free: i32) -> PromoCode {
+    PromoCode {
+        promo_type: "bundle".into(),
+        discount_percentage: Decimal::ZERO,
+        buy_quantity: Some(buy),
+        free_quantity: Some(free),
+        ..promo(true, 0, 0)
+    }
+}
+
+fn create_request(promo_type: Option<&str>, pct: i64, buy: Option<i32>, free: Option<i32>) -> CreatePromoRequest {
+    CreatePromoRequest {
+        code: "BUNDLE".into(),
+        promo_type: promo_type.map(str::to_string),
+        discount_percentage: Decimal::new(pct, 0),
+        buy_quantity: buy,
+        free_quantity: free,
+        ticket_limit: 0,
+        expires_at: None,
+        allow_partial: false,
+        activates_at: None,
+    }
+}
+
+#[test]
+fn bundle_gives_free_units_per_complete_group() {
+    // Buy 3 get 1 free: every 4th ticket is free
+    assert_eq!(bundle_free_units(3, 3, 1), 0);
+    assert_eq!(bundle_free_units(4, 3, 1), 1);
+    assert_eq!(bundle_free_units(7, 3, 1), 1);
+    assert_eq!(bundle_free_units(8, 3, 1), 2);
+    // Buy 2 get 2 free
+    assert_eq!(bundle_free_units(4, 2, 2), 2);
+    assert_eq!(bundle_free_units(0, 3, 1), 0);
+}
+
+#[test]
+fn bundle_becomes_the_equivalent_order_percentage() {
+    assert_eq!(effective_discount(&bundle(3, 1), 4), Decimal::new(2500, 2));
+    assert_eq!(effective_discount(&bundle(3, 1), 3), Decimal::ZERO);
+    // 1 free of 6 = 16.666…% → 16.67 to fit discount_applied
+    assert_eq!(effective_discount(&bundle(3, 1), 6), Decimal::new(1667, 2));
+}
+
+#[test]
+fn bundle_charges_paid_tickets_in_full_and_free_ones_nothing() {
+    // Buy 3 get 1 at ₦5,000: the buyer pays for three tickets exactly
+    let unit = Decimal::new(5_000, 0);
+    let pricing = promo_pricing(&bundle(3, 1), 4);
+    assert_eq!(pricing, PromoPricing { units: 1, rate: Decimal::ONE_HUNDRED });
+    let order = compute_order_fees(&pricing.lines(unit, 4), &FeeMode::PassToBuyer);
+    let three = compute_fees(unit, 3, &FeeMode::PassToBuyer);
+    assert_eq!(order.buyer_total, three.buyer_total);
+    assert_eq!(order.organizer_payout, three.organizer_payout);
+    assert_eq!(pricing.discount_amount(unit), unit);
+}
+
+#[test]
+fn legacy_tickets_price_every_unit_at_discount_applied() {
+    let pricing = PromoPricing::stored(4, Decimal::new(10, 0), None, None);
+    assert_eq!(pricing, PromoPricing { units: 4, rate: Decimal::new(10, 0) });
+    let stored = PromoPricing::stored(4, Decimal::new(25, 0), Some(1), Some(Decimal::ONE_HUNDRED));
+    assert_eq!(stored, promo_pricing(&bundle(3, 1), 4));
+}
+
+#[test]
+fn percentage_code_ignores_quantity() {
+    let p = promo(true, 0, 0);
+    assert_eq!(effective_discount(&p, 1), Decimal::new(10, 0));
+    assert_eq!(effective_discount(&p, 9), Decimal::new(10, 0));
+}
+
+#[test]
+fn promo_type_defaults_to_percentage() {
+    let terms = PromoTerms::from_request(&create_request(None, 15, None, None)).unwrap();
+    assert_eq!(terms, PromoTerms::Percentage(Decimal::new(15, 0)));
+    assert!(PromoTerms::from_request(&create_request(None, 0, None, None)).is_err());
+}
+
+#[test]
+fn bundle_terms_need_both_quantities_within_order_limit() {
+    let ok = PromoTerms::from_request(&create_request(Some("bundle"), 0, Some(3), Some(1))).unwrap();
+    assert_eq!(ok.bundle(), Some((3, 1)));
+    assert_eq!(ok.discount_percentage(), Decimal::ZERO);
+    assert!(PromoTerms::from_request(&create_request(Some("bundle"), 0, Some(3), None)).is_err());
+    assert!(PromoTerms::from_request(&create_request(Some("bundle"), 0, Some(0), Some(1))).is_err());
+    assert!(PromoTerms::from_request(&create_request(Some("bundle"), 0, Some(8), Some(3))).is_err());
+    assert!(PromoTerms::from_request(&create_request(Some("bogo"), 0, Some(1), Some(1))).is_err());
+}
+
+#[test]
+fn bulk_toggle_needs_at_least_one_id() {
+    assert!(check_bulk_ids(&[]).is_err());
+    assert!(check_bulk_ids(&[Uuid::new_v4()]).is_ok());
+}
+
+#[test]
+fn bulk_toggle_is_capped_at_the_batch_size() {
+    let ids: Vec<Uuid> = (0..super::service::MAX_BATCH_CODES).map(|_| Uuid::new_v4()).collect();
+    assert!(check_bulk_ids(&ids).is_ok());
+    let mut over = ids.clone();
+    over.push(Uuid::new_v4());
+    assert!(check_bulk_ids(&over).is_err());
+}
+
+#[test]
+fn basket_within_remaining_uses_gets_the_full_rate() {
+    let (discount, _, split) = apply_usage_limit(&promo(true, 10, 5), 5).unwrap();
+    assert_eq!(discount, Decimal::new(10, 0));
+    assert!(split.is_none());
+}
+
+#[test]
+fn basket_over_remaining_uses_is_refused_without_allow_partial() {
+    assert!(matches!(apply_usage_limit(&promo(true, 10, 8), 5), Err(AppError::PromoInvalid(_))));
+}
+
+#[test]
+fn partial_code_discounts_only_the_uses_left() {
+    let p = PromoCode { allow_partial: true, ..promo(true, 10, 8) };
+    let (discount, _, split) = apply_usage_limit(&p, 5).unwrap();
+    let split = split.unwrap();
+    assert_eq!((split.discounted_units, split.full_price_units), (2, 3));
+    assert_eq!(split.discount_percentage, Decimal::new(10, 0));
+    // 10% on 2 of 5 tickets = 4% over the basket
+    assert_eq!(discount, Decimal::new(400, 2));
+    assert_eq!(split.effective_discount, discount);
+
+    // The buyer pays for 2 × 9,000 and 3 × 10,000, each through the fees
+    let unit = Decimal::new(10_000, 0);
+    let (_, pricing, _) = apply_usage_limit(&p, 5).unwrap();
+    let order = compute_order_fees(&pricing.lines(unit, 5), &FeeMode::PassToBuyer);
+    let discounted = compute_fees(Decimal::new(9_000, 0), 2, &FeeMode::PassToBuyer);
+    let full = compute_fees(unit, 3, &FeeMode::PassToBuyer);
+    assert_eq!(order.buyer_total, discounted.buyer_total + full.buyer_total);
+    assert_eq!(pricing.lines(unit, 5), vec![(Decimal::new(9_000, 0), 2), (unit, 3)]);
+    assert_eq!(pricing.discount_amount(unit), Decimal::new(2_000, 0));
+}
+
+#[test]
+fn partial_code_charges_exactly_where_the_blend_would_round() {
+    // 10% on 1 of 3 blends to 3.33%, but the charge isn't rounded through it
+    let p = PromoCode { allow_partial: true, ..promo(true, 1, 0) };
+    let (_, pricing, _) = apply_usage_limit(&p, 3).unwrap();
+    let unit = Decimal::new(10_000, 0);
+    let order = compute_order_fees(&pricing.lines(unit, 3), &FeeMode::PassToBuyer);
+    assert_eq!(pricing.lines(unit, 3), vec![(Decimal::new(9_000, 0), 1), (unit, 2)]);
+    let discounted = compute_fees(Decimal::new(9_000, 0), 1, &FeeMode::PassToBuyer);
+    let full = compute_fees(unit, 2, &FeeMode::PassToBuyer);
+    assert_eq!(order.buyer_total, discounted.buyer_total + full.buyer_total);
+}
+
+#[test]
+fn partial_blend_rounding_is_bounded() {
+    // 10% on 1 of 3 = 3.333...% → 3.33; off by at most half a basis point of the basket
+    let p = PromoCode { allow_partial: true, ..promo(true, 1, 0) };
+    let (discount, _, _) = apply_usage_limit(&p, 3).unwrap();
+    assert_eq!(discount, Decimal::new(333, 2));
+    let unit = Decimal::new(10_000, 0);
+    let blended = unit * (Decimal::ONE_HUNDRED - discount) / Decimal::ONE_HUNDRED * Decimal::from(3);
+    let exact = unit * Decimal::new(9, 1) + unit * Decimal::from(2);
+    assert!((blended - exact).abs() <= unit * Decimal::from(3) * Decimal::new(5, 5));
+}
+
+#[test]
+fn partial_bundle_counts_free_units_within_the_covered_tickets() {
+    // Buy 1 get 1 with 4 uses left, basket of 6: 2 free of the covered 4
+    let p = PromoCode { allow_partial: true, ticket_limit: 4, ..bundle(1, 1) };
+    let (discount, pricing, split) = apply_usage_limit(&p, 6).unwrap();
+    assert_eq!(split.unwrap().discount_percentage, Decimal::new(50, 0));
+    assert_eq!(discount, Decimal::new(3333, 2));
+    // 2 free, 4 at list price
+    assert_eq!(pricing, PromoPricing { units: 2, rate: Decimal::ONE_HUNDRED });
+}
+
+#[test]
+fn scheduled_window_must_start_before_it_ends() {
+    let now = Utc::now();
+    let mut req = create_request(None, 10, None, None);
+    req.activates_at = Some(now + Duration::days(1));
+    req.expires_at = Some(now + Duration::days(2));
+    assert!(PromoWindow::from_request(&req).is_ok());
+
+    req.expires_at = req.activates_at;
+    assert!(matches!(PromoWindow::from_request(&req), Err(AppError::Validation(_))));
+}
+
+#[test]
+fn budget_refuses_the_order_that_would_go_past_it() {
+    let budget = Some(Decimal::new(500_000, 0));
+    // 20% off 2 × 50,000 = 20,000
+    let discount = discount_amount(Decimal::new(50_000, 0), 2, Decimal::new(20, 0));
+    assert_eq!(discount, Decimal::new(20_000, 0));
+    assert!(check_promo_budget(budget, Decimal::new(480_000, 0), discount).is_ok());
+    assert!(matches!(
+        check_promo_budget(budget, Decimal::new(480_001, 0), discount),
+        Err(AppError::PromoInvalid(_))
+    ));
+}
+
+#[test]
+fn no_budget_or_no_discount_always_passes() {
+    assert!(check_promo_budget(None, Decimal::new(1_000_000, 0), Decimal::ONE).is_ok());
+    assert!(check_promo_budget(Some(Decimal::ZERO), Decimal::ZERO, Decimal::ZERO).is_ok());
+}
+
+#[test]
+fn code_cap_allows_up_to_the_limit() {
+    assert!(check_code_cap(0, 1000).is_ok());
+    assert!(check_code_cap(999, 1000).is_ok());
+}
+
+#[test]
+fn code_cap_names_count_and_limit() {
+    let Err(AppError::Conflict(msg)) = check_code_cap(1000, 1000) else { panic!("expected Conflict") };
+    assert!(msg.contains("1000 promo codes") && msg.contains("limit 1000"), "{}", msg);
+    assert!(matches!(check_code_cap(1200, 1000), Err(AppError::Conflict(_))));
+}
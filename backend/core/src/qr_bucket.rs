@@ -0,0 +1,95 @@
+// Time-bucketed QR signatures — for events with rotating_qr enabled.
+//
+// The payload carries `bucket = unix_ts / ttl` and `sig = HMAC(ticket_id:ttl:bucket)`.
+// The scanner never trusts the embedded bucket: it recomputes the signature
+// for the current and the previous bucket and accepts either, which absorbs
+// clock skew between phone and server plus the time it takes to walk up to
+// the gate. Anything older fails — a screenshot shared ahead of time is dead
+// after at most 2 × ttl.
+//
+// Pure functions, no I/O. TicketService signs, ScannerService verifies, both
+// with the same injected QR secret.
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Bucket index for a unix timestamp.
+pub fn bucket_for(unix_ts: i64, ttl_secs: i64) -> i64 {
+    unix_ts.div_euclid(ttl_secs.max(1))
+}
+
+/// Hex HMAC over ticket id, ttl and bucket. The ttl is signed too, so a
+/// payload minted for a long window can't be replayed as a short one.
+pub fn sign(secret: &str, ticket_id: &str, ttl_secs: i64, bucket: i64) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts any key size");
+    mac.update(format!("{}:{}:{}", ticket_id, ttl_secs, bucket).as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// QR JSON for a rotating ticket at `now`.
+pub fn build_payload(secret: &str, ticket_id: &str, event_key: &str, ttl_secs: i64, now: i64) -> String {
+    let bucket = bucket_for(now, ttl_secs);
+    serde_json::json!({
+        "ticketId": ticket_id,
+        "eventKey": event_key,
+        "bucket": bucket,
+        "ttl": ttl_secs,
+        "sig": sign(secret, ticket_id, ttl_secs, bucket),
+        "ts": now
+    })
+    .to_string()
+}
+
+/// True if `sig` matches the current or the previous bucket at `now`.
+pub fn verify(secret: &str, ticket_id: &str, ttl_secs: i64, sig: &str, now: i64) -> bool {
+    let current = bucket_for(now, ttl_secs);
+    [current, current - 1]
+        .iter()
+        .any(|&b| constant_time_eq(&sign(secret, ticket_id, ttl_secs, b), sig))
+}
+
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes().zip(b.bytes()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "test-secret";
+
+    #[test]
+    fn accepts_current_and_previous_bucket() {
+        let minted_at = 1_700_000_000;
+        let sig = sign(SECRET, "BUKR-0001-abcd", 30, bucket_for(minted_at, 30));
+        assert!(verify(SECRET, "BUKR-0001-abcd", 30, &sig, minted_at));
+        assert!(verify(SECRET, "BUKR-0001-abcd", 30, &sig, minted_at + 30));
+    }
+
+    #[test]
+    fn rejects_two_buckets_old() {
+        let minted_at = 1_700_000_000;
+        let sig = sign(SECRET, "BUKR-0001-abcd", 30, bucket_for(minted_at, 30));
+        assert!(!verify(SECRET, "BUKR-0001-abcd", 30, &sig, minted_at + 60));
+    }
+
+    #[test]
+    fn rejects_other_ticket_or_ttl() {
+        let now = 1_700_000_000;
+        let sig = sign(SECRET, "BUKR-0001-abcd", 30, bucket_for(now, 30));
+        assert!(!verify(SECRET, "BUKR-0002-abcd", 30, &sig, now));
+        assert!(!verify(SECRET, "BUKR-0001-abcd", 60, &sig, now));
+    }
+
+    #[test]
+    fn payload_round_trips_through_verify() {
+        let now = 1_700_000_000;
+        let payload: serde_json::Value =
+            serde_json::from_str(&build_payload(SECRET, "BUKR-0001-abcd", "evt", 30, now)).unwrap();
+        let sig = payload["sig"].as_str().unwrap();
+        assert_eq!(payload["bucket"], bucket_for(now, 30));
+        assert!(verify(SECRET, "BUKR-0001-abcd", 30, sig, now + 5));
+    }
+}
@@ -0,0 +1,102 @@
+// Scan geofencing — was the ticket scanned at the venue?
+//
+// Scanner apps may send the device's lat/lon with each scan. The point is
+// stored on the scan_log row either way; when the event has venue
+// coordinates, a scan farther than geofence_radius_m from them is rejected
+// as "wrong_location" (multi-venue tours: tonight's scanner at last night's
+// venue). Events without coordinates, and scans without a location, are
+// never rejected here.
+//
+// Pure functions, no I/O.
+
+/// Mean Earth radius in metres (IUGG).
+const EARTH_RADIUS_M: f64 = 6_371_008.8;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoPoint {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+impl GeoPoint {
+    /// Both halves or neither; each within its valid range.
+    pub fn from_parts(lat: Option<f64>, lon: Option<f64>) -> Result<Option<Self>, String> {
+        match (lat, lon) {
+            (None, None) => Ok(None),
+            (Some(lat), Some(lon)) => {
+                if !(-90.0..=90.0).contains(&lat) {
+                    return Err("lat must be between -90 and 90".into());
+                }
+                if !(-180.0..=180.0).contains(&lon) {
+                    return Err("lon must be between -180 and 180".into());
+                }
+                Ok(Some(Self { lat, lon }))
+            }
+            _ => Err("lat and lon must be sent together".into()),
+        }
+    }
+}
+
+/// Great-circle distance in metres (haversine).
+pub fn distance_m(a: GeoPoint, b: GeoPoint) -> f64 {
+    let (lat1, lat2) = (a.lat.to_radians(), b.lat.to_radians());
+    let dlat = lat2 - lat1;
+    let dlon = (b.lon - a.lon).to_radians();
+    let h = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_M * h.sqrt().min(1.0).asin()
+}
+
+/// Some(distance) when `scan` is outside `radius_m` of `venue`, None when inside
+/// (the boundary itself counts as inside).
+pub fn outside(venue: GeoPoint, radius_m: f64, scan: GeoPoint) -> Option<f64> {
+    let d = distance_m(venue, scan);
+    (d > radius_m).then_some(d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Eko Hotel, Victoria Island, Lagos
+    const VENUE: GeoPoint = GeoPoint { lat: 6.4281, lon: 3.4219 };
+
+    #[test]
+    fn same_point_is_zero_metres() {
+        assert!(distance_m(VENUE, VENUE) < 1e-6);
+    }
+
+    #[test]
+    fn one_degree_of_latitude_is_about_111_km() {
+        let north = GeoPoint { lat: VENUE.lat + 1.0, lon: VENUE.lon };
+        let d = distance_m(VENUE, north);
+        assert!((d - 111_195.0).abs() < 100.0, "got {}", d);
+    }
+
+    #[test]
+    fn scan_inside_radius_is_accepted() {
+        // ~0.001° latitude ≈ 111 m
+        let near = GeoPoint { lat: VENUE.lat + 0.001, lon: VENUE.lon };
+        assert_eq!(outside(VENUE, 500.0, near), None);
+    }
+
+    #[test]
+    fn scan_outside_radius_reports_distance() {
+        // Lagos → Abuja, roughly 525 km
+        let abuja = GeoPoint { lat: 9.0765, lon: 7.3986 };
+        let d = outside(VENUE, 500.0, abuja).expect("far outside");
+        assert!(d > 500_000.0 && d < 550_000.0, "got {}", d);
+    }
+
+    #[test]
+    fn lat_without_lon_is_rejected() {
+        assert!(GeoPoint::from_parts(Some(6.4), None).is_err());
+        assert!(GeoPoint::from_parts(None, Some(3.4)).is_err());
+        assert_eq!(GeoPoint::from_parts(None, None), Ok(None));
+    }
+
+    #[test]
+    fn out_of_range_coordinates_are_rejected() {
+        assert!(GeoPoint::from_parts(Some(91.0), Some(0.0)).is_err());
+        assert!(GeoPoint::from_parts(Some(0.0), Some(-180.5)).is_err());
+    }
+}
@@ -1,17 +1,18 @@
 // Scanner HTTP handlers — thin layer, all logic in ScannerService.
 
 use axum::{
-    extract::{Path, State},
+    extract::State,
     http::HeaderMap,
-    Json,
 };
 use serde_json::{json, Value};
 use uuid::Uuid;
 
 use crate::error::{AppError, Result};
+use crate::extract::{Json, Path, Query};
 use super::service::{
     ScannerService, VerifyAccessRequest, ValidateTicketRequest,
-    ManualValidateRequest, RenewTicketRequest,
+    ManualValidateRequest, MarkUsedQuery, RenewTicketRequest, CheckOutQuery, DuplicatesQuery,
+    SyncScansRequest,
 };
 use std::sync::Arc;
 
@@ -22,6 +23,17 @@ fn extract_user_id(headers: &HeaderMap) -> Option<Uuid> {
         .and_then(|s| Uuid::parse_str(s).ok())
 }
 
+/// Gate access code the scanner app signed in with, recorded on scan_log
+/// so gate reports can tell gates apart.
+fn extract_access_code(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get("x-scanner-code")
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
 pub async fn verify_access(
     State(service): State<Arc<ScannerService>>,
     Json(req): Json<VerifyAccessRequest>,
@@ -32,9 +44,23 @@ pub async fn verify_access(
 
 pub async fn validate_ticket(
     State(service): State<Arc<ScannerService>>,
+    headers: HeaderMap,
     Json(req): Json<ValidateTicketRequest>,
 ) -> Result<Json<Value>> {
-    let result = service.validate_ticket(req).await?;
+    // Optional — recorded as scanned_by when the scanner app is signed in,
+    // which is what lets that scanner undo the scan.
+    let scanned_by = extract_user_id(&headers);
+    let result = service.validate_ticket(req, scanned_by, extract_access_code(&headers)).await?;
+    Ok(Json(json!({ "status": "success", "data": result })))
+}
+
+pub async fn sync_scans(
+    State(service): State<Arc<ScannerService>>,
+    headers: HeaderMap,
+    Json(req): Json<SyncScansRequest>,
+) -> Result<Json<Value>> {
+    let scanned_by = extract_user_id(&headers);
+    let result = service.sync_scans(req, scanned_by, extract_access_code(&headers)).await?;
     Ok(Json(json!({ "status": "success", "data": result })))
 }
 
@@ -46,16 +72,61 @@ pub async fn manual_validate(
     let scanned_by = extract_user_id(&headers)
         .ok_or(AppError::Unauthorized)?;
     
-    let result = service.manual_validate(req, scanned_by).await?;
+    let result = service.manual_validate(req, scanned_by, extract_access_code(&headers)).await?;
     Ok(Json(json!({ "status": "success", "data": result })))
 }
 
 pub async fn mark_used(
     State(service): State<Arc<ScannerService>>,
     Path(ticket_id): Path<String>,
+    Query(query): Query<MarkUsedQuery>,
 ) -> Result<Json<Value>> {
-    service.mark_used(&ticket_id, None).await?;
-    Ok(Json(json!({ "status": "success", "data": { "message": "Ticket marked as used" } })))
+    let result = service.mark_used(&ticket_id, None, query.admit).await?;
+    if result.result != "valid" {
+        // Already used, at capacity, cancelled… — nothing was marked
+        return Err(AppError::Conflict(
+            result.message.unwrap_or_else(|| format!("Ticket not marked as used: {}", result.result)),
+        ));
+    }
+    let ticket = result.ticket.as_ref();
+    let remaining = ticket.and_then(|t| t.remaining);
+    let message = match remaining {
+        Some(left) if left > 0 => "Seats admitted, ticket still valid for the rest",
+        _ => "Ticket marked as used",
+    };
+    Ok(Json(json!({
+        "status": "success",
+        "data": {
+            "message": message,
+            "admitted": ticket.and_then(|t| t.admitted),
+            "remaining": remaining,
+        }
+    })))
+}
+
+pub async fn undo_scan(
+    State(service): State<Arc<ScannerService>>,
+    headers: HeaderMap,
+    Path(ticket_id): Path<String>,
+) -> Result<Json<Value>> {
+    let scanner_id = extract_user_id(&headers)
+        .ok_or(AppError::Unauthorized)?;
+
+    let result = service.undo_scan(&ticket_id, scanner_id, extract_access_code(&headers).as_deref()).await?;
+    Ok(Json(json!({ "status": "success", "data": result })))
+}
+
+pub async fn check_out(
+    State(service): State<Arc<ScannerService>>,
+    headers: HeaderMap,
+    Path(ticket_id): Path<String>,
+    Query(query): Query<CheckOutQuery>,
+) -> Result<Json<Value>> {
+    let scanner_id = extract_user_id(&headers)
+        .ok_or(AppError::Unauthorized)?;
+
+    let result = service.check_out(&ticket_id, query.event_id, scanner_id, extract_access_code(&headers)).await?;
+    Ok(Json(json!({ "status": "success", "data": result })))
 }
 
 pub async fn get_stats(
@@ -66,6 +137,19 @@ pub async fn get_stats(
     Ok(Json(json!({ "status": "success", "data": stats })))
 }
 
+pub async fn get_duplicates(
+    State(service): State<Arc<ScannerService>>,
+    headers: HeaderMap,
+    Path(event_id): Path<Uuid>,
+    Query(query): Query<DuplicatesQuery>,
+) -> Result<Json<Value>> {
+    let organizer_id = extract_user_id(&headers)
+        .ok_or(AppError::Unauthorized)?;
+
+    let report = service.duplicate_scans(organizer_id, event_id, query.window_secs).await?;
+    Ok(Json(json!({ "status": "success", "data": report })))
+}
+
 pub async fn renew_ticket(
     State(service): State<Arc<ScannerService>>,
     headers: HeaderMap,
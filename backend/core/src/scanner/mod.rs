@@ -7,8 +7,13 @@
  * Exports:
  * - handler: Controller layer (HTTP endpoints)
  * - service: Use case layer (business logic)
+ * - geofence: Venue radius check for located scans
  */
 
+pub mod geofence;
 pub mod handler;
 pub mod service;
 pub mod usage_engine;
+
+#[cfg(test)]
+mod service_test;
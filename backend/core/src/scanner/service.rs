@@ -3,20 +3,32 @@
 // Security layers (in order):
 // 1. Access code verification — only authorised scanners can scan
 // 2. HMAC-signed QR nonce — screenshots invalid after first scan
-// 3. Redis atomic lock — prevents simultaneous double-scan race
+//    (rotating_qr events: time-bucketed signature, current or previous bucket only)
+// 3. Redis atomic lock — prevents simultaneous double-scan race (skipped if Redis is down)
 // 4. UsageEngine — handles all ticket models (single/multi/consumable/time_bound/renewable)
 // 5. DB atomic UPDATE WHERE status='valid' — final safety net
-// 6. Audit log — every attempt recorded
+//    (single-use: the row is locked FOR UPDATE, so the first scan to commit
+//    wins and every later one is told who scanned it and when)
+// 6. Audit log — every attempt recorded, with the device location when sent
+//
+// Scans that carry lat/lon are also checked against the venue geofence
+// (see geofence.rs) before any of the above. Events with max_concurrent_inside
+// refuse admissions past that headcount until someone checks out.
+
+use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
 use hmac::{Hmac, Mac};
-use redis::AsyncCommands;
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
 use sqlx::{PgPool, Row};
 use uuid::Uuid;
 
+use crate::cache::Cache;
 use crate::error::{AppError, Result};
+use crate::tickets::status::TicketStatus;
+use crate::webhook::{HostPolicy, WebhookClient};
+use super::geofence::{self, GeoPoint};
 use super::usage_engine::{UsageDecision, UsageEngine};
 
 // ─── Request DTOs ─────────────────────────────────────────────────────────────
@@ -28,11 +40,17 @@ pub struct VerifyAccessRequest {
     pub access_code: String,
 }
 
+// Either field may carry the scan. Hardware that only emits the raw BUKR-... id
+// can send it alone; qr_data may be the signed JSON payload or the raw id too.
 #[derive(Debug, Deserialize)]
 pub struct ValidateTicketRequest {
-    pub ticket_id: String,
+    #[serde(default)]
+    pub ticket_id: Option<String>,
     pub event_key: String,
     pub qr_data: Option<String>,
+    pub lat: Option<f64>,            // Scanner device location — optional, both or neither
+    pub lon: Option<f64>,
+    pub admit: Option<i32>,          // Group tickets: seats to let in now (default: all remaining)
 }
 
 #[derive(Debug, Deserialize)]
@@ -40,6 +58,43 @@ pub struct ManualValidateRequest {
     pub ticket_id: String,
     pub event_id: Option<Uuid>,
     pub event_key: Option<String>,
+    pub lat: Option<f64>,
+    pub lon: Option<f64>,
+    pub admit: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MarkUsedQuery {
+    pub admit: Option<i32>,
+}
+
+// Scans a device queued while offline, uploaded in one go.
+#[derive(Debug, Deserialize)]
+pub struct SyncScansRequest {
+    pub event_key: String,
+    pub scans: Vec<SyncScan>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SyncScan {
+    #[serde(default)]
+    pub ticket_id: Option<String>,
+    pub qr_data: Option<String>,
+    pub scanned_at: DateTime<Utc>,   // Device clock when it was scanned
+    pub lat: Option<f64>,
+    pub lon: Option<f64>,
+    pub admit: Option<i32>,
+}
+
+// ?event_id= picks the session for grouped events; defaults to the ticket's own event.
+#[derive(Debug, Deserialize)]
+pub struct CheckOutQuery {
+    pub event_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DuplicatesQuery {
+    pub window_secs: Option<i64>,    // Max gap between scans at different gates (default 300)
 }
 
 #[derive(Debug, Deserialize)]
@@ -48,11 +103,29 @@ pub struct RenewTicketRequest {
     pub user_id: Uuid,
 }
 
+// Where and when a scan was made: the device location when sent, the gate
+// access code the scanner app signed in with (X-Scanner-Code), and for
+// offline uploads the device's own scan time. All go to scan_log; a code
+// that isn't one of the event's is recorded as NULL.
+#[derive(Debug, Clone, Default)]
+pub struct ScanSite {
+    pub location: Option<GeoPoint>,
+    pub access_code: Option<String>,
+    pub at: Option<DateTime<Utc>>,
+}
+
+impl ScanSite {
+    /// The moment the scan is judged at — the device's, for a synced scan.
+    pub fn time(&self) -> DateTime<Utc> {
+        self.at.unwrap_or_else(Utc::now)
+    }
+}
+
 // ─── Response DTOs ────────────────────────────────────────────────────────────
 
 #[derive(Debug, Serialize)]
 pub struct ScanResult {
-    pub result: String,              // "valid" | "already_used" | "invalid" | "expired" | "depleted_renewable"
+    pub result: String,              // "valid" | "already_used" | "invalid" | "expired" | "depleted_renewable" | "wrong_location" | "at_capacity" | "blacklisted"
     pub ticket: Option<ScanTicketInfo>,
     pub message: Option<String>,
     pub new_qr_data: Option<String>,
@@ -65,9 +138,22 @@ pub struct ScanTicketInfo {
     pub user_name: String,
     pub ticket_type: String,
     pub quantity: i32,
-    pub scanned_at: Option<String>,
+    #[serde(with = "crate::timestamp::option")]
+    pub scanned_at: Option<DateTime<Utc>>,
+    pub scanned_by: Option<Uuid>,           // Winning scanner on an already_used result
+    pub scanned_by_name: Option<String>,
     pub usage_left: Option<i32>,
     pub usage_total: Option<i32>,
+    pub admitted: Option<i32>,       // Single-use: seats let in by this scan
+    pub remaining: Option<i32>,      // Single-use: seats still to come on this ticket
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub add_ons: Vec<ScanAddOn>,     // Extras to hand over at the gate
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScanAddOn {
+    pub name: String,
+    pub quantity: i32,
 }
 
 #[derive(Debug, Serialize)]
@@ -81,7 +167,10 @@ pub struct AccessVerifyResponse {
 pub struct EventSummary {
     pub id: Uuid,
     pub title: String,
-    pub date: String,
+    pub date: String,                // Local date in the event's timezone
+    #[serde(with = "crate::timestamp::option")]
+    pub starts_at: Option<DateTime<Utc>>,
+    pub timezone: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -90,6 +179,44 @@ pub struct ScanStats {
     pub scanned: i64,
     pub remaining: i64,
     pub scan_rate: f64,
+    pub inside: i64,                 // People admitted and not checked out
+}
+
+#[derive(Debug, Serialize)]
+pub struct DuplicateScanReport {
+    pub event_id: Uuid,
+    pub window_secs: i64,
+    pub tickets: Vec<DuplicateScan>,
+}
+
+// One ticket presented at more than one gate within the window.
+#[derive(Debug, Serialize)]
+pub struct DuplicateScan {
+    pub ticket_id: String,
+    pub holder_name: String,
+    pub scanners: Vec<Uuid>,         // Every scanner involved in a clash
+    pub clashes: i64,                // Back-to-back scans at different gates
+    #[serde(with = "crate::timestamp")]
+    pub first_scan_at: DateTime<Utc>,
+    #[serde(with = "crate::timestamp")]
+    pub last_scan_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncScansResponse {
+    pub received: usize,
+    pub collapsed: usize,            // Device retries folded into an earlier scan
+    pub results: Vec<SyncScanResult>,
+}
+
+// Outcome of one applied scan, in scanned_at order.
+#[derive(Debug, Serialize)]
+pub struct SyncScanResult {
+    pub ticket_id: Option<String>,
+    #[serde(with = "crate::timestamp")]
+    pub scanned_at: DateTime<Utc>,
+    pub result: Option<ScanResult>,
+    pub error: Option<String>,       // Set instead of result when the scan was rejected outright
 }
 
 #[derive(Debug, Serialize)]
@@ -102,47 +229,337 @@ pub struct RenewResult {
     pub payment_currency: Option<String>,
 }
 
+// Default and ceiling for the duplicate report's window.
+const DUPLICATE_WINDOW_SECS: i64 = 300;
+const MAX_DUPLICATE_WINDOW_SECS: i64 = 86_400;
+// Most tickets one duplicate report returns — newest clashes first.
+const MAX_DUPLICATE_TICKETS: i64 = 500;
+// Offline upload: most scans per request, and how close two scans of one
+// ticket must be to count as the device retrying rather than a re-entry.
+// Scans are applied one by one, so the cap keeps an upload well inside the
+// gateway's 10 s proxy timeout; a bigger backlog goes up in several calls.
+const MAX_SYNC_SCANS: usize = 100;
+pub(crate) const SYNC_DEDUP_WINDOW_SECS: i64 = 30;
+// Oldest offline scan a sync accepts. Synced scans are judged at the device's
+// time, so this bounds how far back a device can date one — past it an
+// expired rotating QR or a closed entry window would pass.
+pub(crate) const MAX_OFFLINE_SCAN_AGE_SECS: i64 = 4 * 3600;
+
+// ─── Scan input detection ─────────────────────────────────────────────────────
+
+// Auto-detect what the scanner sent. A JSON object is the signed QR payload —
+// the ticket id is read from "ticketId" (or "ticket_id") and the object is kept
+// for signature checks. Anything else is taken as the raw ticket id itself,
+// which is what a Code128 read off a printed ticket produces.
+pub(crate) fn parse_scan_input(raw: &str) -> (Option<String>, Option<serde_json::Value>) {
+    let raw = strip_barcode_framing(raw);
+    if raw.is_empty() {
+        return (None, None);
+    }
+    match serde_json::from_str::<serde_json::Value>(raw) {
+        Ok(v) if v.is_object() => {
+            let id = v["ticketId"].as_str()
+                .or_else(|| v["ticket_id"].as_str())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty());
+            (id, Some(v))
+        }
+        _ => (Some(raw.to_string()), None),
+    }
+}
+
+// Keyboard-wedge barcode readers can prefix an AIM symbology identifier
+// ("]C0" for Code128) and terminate with CR/LF or a GS byte. None of that is
+// part of the ticket id.
+fn strip_barcode_framing(raw: &str) -> &str {
+    let raw = raw.trim_matches(|c: char| c.is_whitespace() || c.is_control());
+    match raw.strip_prefix("]C") {
+        Some(rest) if rest.starts_with(|c: char| c.is_ascii_digit()) => rest[1..].trim_start(),
+        _ => raw,
+    }
+}
+
+// Ticket a queued scan is for, read the same way validate_ticket reads it.
+fn sync_scan_ticket_id(scan: &SyncScan) -> Option<String> {
+    scan.ticket_id.as_deref()
+        .map(strip_barcode_framing)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .or_else(|| parse_scan_input(scan.qr_data.as_deref().unwrap_or("")).0)
+}
+
+/// Put an offline batch in scan order and fold each ticket's retries into
+/// one: a scan within `window_secs` of the last kept scan of the same ticket
+/// is dropped. Scans with no readable ticket id are kept for validation to
+/// reject. Returns the scans to apply and how many were dropped.
+pub(crate) fn dedup_scans(mut scans: Vec<SyncScan>, window_secs: i64) -> (Vec<SyncScan>, usize) {
+    scans.sort_by_key(|s| s.scanned_at);
+    let window = chrono::Duration::seconds(window_secs);
+    let mut last_kept: std::collections::HashMap<String, DateTime<Utc>> = std::collections::HashMap::new();
+    let received = scans.len();
+    let kept: Vec<SyncScan> = scans
+        .into_iter()
+        .filter(|scan| {
+            let Some(id) = sync_scan_ticket_id(scan) else { return true };
+            match last_kept.get(&id) {
+                Some(&at) if scan.scanned_at - at <= window => false,
+                _ => {
+                    last_kept.insert(id, scan.scanned_at);
+                    true
+                }
+            }
+        })
+        .collect();
+    let collapsed = received - kept.len();
+    (kept, collapsed)
+}
+
+#[derive(Debug, Serialize)]
+pub struct UndoScanResult {
+    pub ticket_id: String,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckOutResult {
+    pub ticket_id: String,
+    pub event_id: Uuid,
+    pub inside: i64,                 // Headcount after this check-out
+}
+
+/// Whether `scanner_id` may undo the latest scan on this ticket at `now`.
+/// There has to be an admission to take back — a group part-way through
+/// the door is still valid — and only the scanner that made it, only
+/// inside the window.
+pub(crate) fn check_undo(
+    status: TicketStatus,
+    last_scan_quantity: i32,
+    scanned_by: Option<Uuid>,
+    scanned_at: Option<DateTime<Utc>>,
+    scanner_id: Uuid,
+    now: DateTime<Utc>,
+    window_secs: i64,
+) -> Result<()> {
+    if !matches!(status, TicketStatus::Valid | TicketStatus::Used) || last_scan_quantity <= 0 {
+        return Err(AppError::BadRequest(format!("Ticket status is '{}', nothing to undo", status)));
+    }
+    if scanned_by != Some(scanner_id) {
+        return Err(AppError::Forbidden);
+    }
+    match scanned_at {
+        Some(at) if now - at <= chrono::Duration::seconds(window_secs) => Ok(()),
+        _ => Err(AppError::BadRequest(format!(
+            "Undo window of {} seconds has passed",
+            window_secs
+        ))),
+    }
+}
+
+/// Seats let in by the scan an undo would take back. Single-use tickets
+/// record each admission's size; the usage engine lets the whole ticket in
+/// on every use, and an undo clears scanned_at so one use can't be given
+/// back twice.
+pub(crate) fn seats_to_undo(usage_model: &str, quantity: i32, last_scan_quantity: i32, scanned: bool) -> i32 {
+    match usage_model {
+        "single" => last_scan_quantity,
+        _ if scanned => quantity,
+        _ => 0,
+    }
+}
+
+/// Whether undoing a usage-engine scan gives a use back. A use that only
+/// decremented usage_left leaves the ticket valid; the last use marks it
+/// used without touching usage_left, so the status flip alone restores it.
+pub(crate) fn undo_restores_use(usage_model: &str, status: TicketStatus) -> bool {
+    usage_model != "single" && status == TicketStatus::Valid
+}
+
+/// Status once the latest admission is taken back: valid while any seat
+/// is left to come through.
+pub(crate) fn status_after_undo(quantity: i32, scanned_quantity: i32, last_scan_quantity: i32) -> TicketStatus {
+    if scanned_quantity - last_scan_quantity < quantity {
+        TicketStatus::Valid
+    } else {
+        TicketStatus::Used
+    }
+}
+
+/// Whether a ticket may enter `session` given the sessions it has already
+/// entered. Group passes scan at every session in the group; a single-use or
+/// consumable pass is spent on the first one. None = let it in.
+pub(crate) fn check_session_entry(usage_model: &str, entered: &[Uuid], session: Uuid) -> Option<&'static str> {
+    let one_session = matches!(usage_model, "single" | "consumable");
+    if one_session && entered.iter().any(|&s| s != session) {
+        return Some("Pass was already used for another session");
+    }
+    None
+}
+
+/// Whether admitting `people` keeps the venue within `max_inside`. `inside` is
+/// everyone already in except this ticket, so re-scanning someone who never
+/// checked out doesn't count them twice. None = let them in.
+pub(crate) fn check_capacity(max_inside: Option<i32>, inside: i64, people: i32) -> Option<String> {
+    let max = max_inside?;
+    if inside + people as i64 > max as i64 {
+        return Some(format!("Venue at capacity ({} of {} inside)", inside, max));
+    }
+    None
+}
+
+/// Renewing restores a ticket's uses and rotates its QR, so a blacklisted
+/// ticket can't be renewed back into circulation.
+pub(crate) fn check_renewable(is_renewable: bool, blacklisted: bool) -> Result<()> {
+    if !is_renewable {
+        return Err(AppError::BadRequest("Ticket is not renewable".into()));
+    }
+    crate::tickets::transfer::check_not_blacklisted(blacklisted, "renew")
+}
+
+/// Seats a single-use ticket lets in on this scan, and how many are left
+/// after it. `requested` None admits everyone still outside.
+pub(crate) fn admit_seats(quantity: i32, scanned: i32, requested: Option<i32>) -> Result<(i32, i32)> {
+    let remaining = quantity - scanned;
+    let admit = requested.unwrap_or(remaining);
+    if admit < 1 || admit > remaining {
+        return Err(AppError::Validation(format!(
+            "admit must be between 1 and {} ({} of {} seats already in)",
+            remaining, scanned, quantity
+        )));
+    }
+    Ok((admit, remaining - admit))
+}
+
+/// Outcome of claiming a single-use ticket while holding its row lock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Claim {
+    Won,
+    /// Someone got there first — who and when, as stored on the row.
+    Lost { scanned_by: Option<Uuid>, scanned_at: Option<DateTime<Utc>> },
+    /// Not scannable at all (refunded, cancelled, ...).
+    Refused(TicketStatus),
+    /// Already in once and checked out since — coming back in, nothing spent.
+    Reentry,
+}
+
+/// Decide a single-use claim from the locked row. Callers must hold the lock
+/// (FOR UPDATE) so two scanners can't both read `valid`. `checked_out` is
+/// whether the ticket left this session after being admitted.
+pub(crate) fn claim_single_use(
+    status: TicketStatus,
+    scanned_by: Option<Uuid>,
+    scanned_at: Option<DateTime<Utc>>,
+    checked_out: bool,
+) -> Claim {
+    match status {
+        TicketStatus::Valid => Claim::Won,
+        TicketStatus::Used if checked_out => Claim::Reentry,
+        TicketStatus::Used => Claim::Lost { scanned_by, scanned_at },
+        other => Claim::Refused(other),
+    }
+}
+
+/// The time an offline scan is judged and recorded at: the device's own,
+/// brought back to `now` if its clock runs ahead. A scan older than
+/// `max_age_secs` is refused rather than judged in the past.
+pub(crate) fn sync_judged_at(scanned_at: DateTime<Utc>, now: DateTime<Utc>, max_age_secs: i64) -> Result<DateTime<Utc>> {
+    if now - scanned_at > chrono::Duration::seconds(max_age_secs) {
+        return Err(AppError::Validation(format!(
+            "Scan is more than {} hours old — offline scans must be synced sooner",
+            max_age_secs / 3600
+        )));
+    }
+    Ok(scanned_at.min(now))
+}
+
+/// Record (or renew, after a check-out) a ticket's admission to a session.
+/// In the admitting transaction so the capacity count sees it at once.
+async fn record_admission_with_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ticket_db_id: Uuid,
+    event_id: Uuid,
+    scanned_by: Option<Uuid>,
+) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO ticket_session_admissions (ticket_id, event_id, scanned_by)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (ticket_id, event_id) DO UPDATE SET checked_out_at = NULL",
+    )
+    .bind(ticket_db_id)
+    .bind(event_id)
+    .bind(scanned_by)
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::Database)?;
+    Ok(())
+}
+
+/// Grouped events: whether a one-session pass already went into another
+/// session. Run with the ticket row locked, in the transaction that records
+/// the admission, so the read and the insert can't interleave with another
+/// gate's. Some(message) = turn it away.
+async fn session_refusal_with_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ticket_db_id: Uuid,
+    usage_model: &str,
+    event_id: Uuid,
+) -> Result<Option<&'static str>> {
+    let entered: Vec<Uuid> = sqlx::query_scalar("SELECT event_id FROM ticket_session_admissions WHERE ticket_id = $1")
+        .bind(ticket_db_id)
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::Database)?;
+    Ok(check_session_entry(usage_model, &entered, event_id))
+}
+
+fn session_refused(message: &str) -> ScanResult {
+    ScanResult {
+        result: "already_used".into(),
+        ticket: None,
+        message: Some(message.into()),
+        new_qr_data: None,
+        usage_left: None,
+    }
+}
+
+fn at_capacity(message: String) -> ScanResult {
+    ScanResult {
+        result: "at_capacity".into(),
+        ticket: None,
+        message: Some(message),
+        new_qr_data: None,
+        usage_left: None,
+    }
+}
+
 // ─── Service ──────────────────────────────────────────────────────────────────
 
 pub struct ScannerService {
     pool: PgPool,
-    redis: Option<redis::aio::ConnectionManager>,
+    // Shared with the rest of the app; every op degrades to a no-op when Redis is down.
+    cache: Arc<Cache>,
     qr_secret: String,
+    // SSRF-guarded client for organizer scan webhooks — never on the scan response path.
+    webhooks: Arc<WebhookClient>,
+    // How long the scanning device has to undo an accidental scan.
+    undo_window_secs: i64,
 }
 
 impl ScannerService {
     pub fn new(pool: PgPool, qr_secret: String) -> Self {
-        Self { pool, redis: None, qr_secret }
+        let webhooks = WebhookClient::new(HostPolicy::default(), std::time::Duration::from_secs(5));
+        Self { pool, cache: Cache::disabled(), qr_secret, webhooks, undo_window_secs: 0 }
     }
 
-    // qr_secret is injected from Config — never read from env directly here.
+    // qr_secret and the cache are injected from main — never read from env directly here.
     // This ensures the startup validation in config.rs is the single enforcement point.
-    pub async fn new_with_redis(pool: PgPool, qr_secret: String) -> Self {
-        let redis_url = std::env::var("REDIS_URL").unwrap_or_default();
-
-        let redis = if !redis_url.is_empty() {
-            match redis::Client::open(redis_url.as_str()) {
-                Ok(client) => match redis::aio::ConnectionManager::new(client).await {
-                    Ok(mgr) => {
-                        tracing::info!("Redis scan lock active");
-                        Some(mgr)
-                    }
-                    Err(e) => {
-                        tracing::warn!("Redis manager init failed: {} — scan lock disabled", e);
-                        None
-                    }
-                },
-                Err(e) => {
-                    tracing::warn!("Redis client open failed: {} — scan lock disabled", e);
-                    None
-                }
-            }
-        } else {
-            tracing::warn!("REDIS_URL not set — scan lock disabled");
-            None
-        };
-
-        Self { pool, redis, qr_secret }
+    pub fn new_with_cache(
+        pool: PgPool,
+        qr_secret: String,
+        cache: Arc<Cache>,
+        undo_window_secs: i64,
+        webhooks: Arc<WebhookClient>,
+    ) -> Self {
+        Self { pool, cache, qr_secret, webhooks, undo_window_secs }
     }
 
     // ─── event_key → UUID resolution ─────────────────────────────────────────
@@ -158,6 +575,22 @@ impl ScannerService {
             .ok_or_else(|| AppError::NotFound(format!("Event '{}' not found", event_key)))
     }
 
+    // Same as resolve_event_id, plus the QR bucket TTL when rotating_qr is on.
+    async fn resolve_event_for_scan(&self, event_key: &str) -> Result<(Uuid, Option<i64>)> {
+        let row = sqlx::query(
+            "SELECT id, rotating_qr, qr_ttl_seconds FROM events WHERE event_key = $1 AND status = 'active'",
+        )
+        .bind(event_key)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::Database)?
+        .ok_or_else(|| AppError::NotFound(format!("Event '{}' not found", event_key)))?;
+
+        let rotating_ttl = row.get::<bool, _>("rotating_qr")
+            .then(|| row.get::<i32, _>("qr_ttl_seconds") as i64);
+        Ok((row.get("id"), rotating_ttl))
+    }
+
     // ─── HMAC QR signing ─────────────────────────────────────────────────────
 
     fn sign_qr(&self, ticket_id: &str, nonce: &str) -> String {
@@ -191,51 +624,34 @@ impl ScannerService {
     // ─── Redis scan lock ──────────────────────────────────────────────────────
 
     async fn acquire_scan_lock(&self, ticket_id: &str) -> bool {
-        let Some(ref mut redis) = self.redis.clone() else {
-            return true; // degraded mode — DB atomic update is still the safety net
-        };
         let key = format!("scan:lock:{}", ticket_id);
-        let result: redis::RedisResult<Option<String>> = redis
-            .set_options(
-                &key,
-                "1",
-                redis::SetOptions::default()
-                    .conditional_set(redis::ExistenceCheck::NX)
-                    .get(false)
-                    .with_expiration(redis::SetExpiry::EX(10)),
-            )
-            .await;
-        matches!(result, Ok(Some(_)) | Ok(None))
+        // None = Redis unavailable: proceed, the DB atomic update is still the safety net.
+        self.cache.set_nx_ex(&key, 10).await.unwrap_or(true)
     }
 
     // ─── Redis usage cache ────────────────────────────────────────────────────
 
     // Cache usage state for 60s to avoid a DB read on every scan of multi-use tickets.
     async fn get_cached_usage(&self, ticket_id: &str) -> Option<(i32, i32, String)> {
-        let Some(ref mut redis) = self.redis.clone() else { return None; };
         let key = format!("ticket:usage:{}", ticket_id);
-        let val: redis::RedisResult<String> = redis.get(&key).await;
-        val.ok().and_then(|s| {
-            let v: serde_json::Value = serde_json::from_str(&s).ok()?;
-            Some((
-                v["left"].as_i64()? as i32,
-                v["total"].as_i64()? as i32,
-                v["model"].as_str()?.to_string(),
-            ))
-        })
+        let s = self.cache.get(&key).await?;
+        let v: serde_json::Value = serde_json::from_str(&s).ok()?;
+        Some((
+            v["left"].as_i64()? as i32,
+            v["total"].as_i64()? as i32,
+            v["model"].as_str()?.to_string(),
+        ))
     }
 
     async fn set_cached_usage(&self, ticket_id: &str, left: i32, total: i32, model: &str) {
-        let Some(ref mut redis) = self.redis.clone() else { return; };
         let key = format!("ticket:usage:{}", ticket_id);
         let val = serde_json::json!({ "left": left, "total": total, "model": model }).to_string();
-        let _: redis::RedisResult<()> = redis.set_ex(&key, val, 60).await;
+        self.cache.set_ex(&key, &val, 60).await;
     }
 
     async fn invalidate_usage_cache(&self, ticket_id: &str) {
-        let Some(ref mut redis) = self.redis.clone() else { return; };
         let key = format!("ticket:usage:{}", ticket_id);
-        let _: redis::RedisResult<()> = redis.del(&key).await;
+        self.cache.del(&key).await;
     }
 
     // ─── Fraud signal writer ──────────────────────────────────────────────────
@@ -253,6 +669,24 @@ impl ScannerService {
         .await;
     }
 
+    // ─── Scan webhook ─────────────────────────────────────────────────────────
+
+    // Fire-and-forget POST to the event's scan_webhook_url. Spawned so a slow or
+    // dead venue display can never delay the gate. Failures are logged only.
+    fn dispatch_scan_webhook(&self, url: Option<String>, payload: serde_json::Value) {
+        let Some(url) = url.filter(|u| !u.is_empty()) else { return; };
+        let webhooks = self.webhooks.clone();
+        tokio::spawn(async move {
+            match webhooks.post_json(&url, &payload).await {
+                Ok(status) if !status.is_success() => {
+                    tracing::warn!("Scan webhook {} returned {}", url, status);
+                }
+                Err(e) => tracing::warn!("Scan webhook {} failed: {}", url, e),
+                Ok(_) => {}
+            }
+        });
+    }
+
     // ─── Public API ───────────────────────────────────────────────────────────
 
     pub async fn verify_access(&self, req: VerifyAccessRequest) -> Result<AccessVerifyResponse> {
@@ -265,7 +699,7 @@ impl ScannerService {
         };
 
         let row = sqlx::query(
-            "SELECT sac.label, e.id as event_id, e.title, e.date::text as date
+            "SELECT sac.label, e.id as event_id, e.title, e.date::text as date, e.time::text as time, e.timezone
              FROM scanner_access_codes sac
              JOIN events e ON sac.event_id = e.id
              WHERE sac.code = $1 AND sac.event_id = $2 AND sac.is_active = true
@@ -278,49 +712,143 @@ impl ScannerService {
         .map_err(AppError::Database)?;
 
         match row {
-            Some(r) => Ok(AccessVerifyResponse {
-                verified: true,
-                event: Some(EventSummary {
-                    id: r.get("event_id"),
-                    title: r.get("title"),
-                    date: r.get("date"),
-                }),
-                gate_label: r.get("label"),
-            }),
+            Some(r) => {
+                let timezone: String = r.get("timezone");
+                let start = crate::event_time::localize(r.get("date"), r.get("time"), &timezone);
+                Ok(AccessVerifyResponse {
+                    verified: true,
+                    event: Some(EventSummary {
+                        id: r.get("event_id"),
+                        title: r.get("title"),
+                        date: start.date,
+                        starts_at: start.starts_at,
+                        timezone,
+                    }),
+                    gate_label: r.get("label"),
+                })
+            }
             None => Ok(AccessVerifyResponse { verified: false, event: None, gate_label: None }),
         }
     }
 
-    pub async fn validate_ticket(&self, req: ValidateTicketRequest) -> Result<ScanResult> {
-        let event_id = self.resolve_event_id(&req.event_key).await?;
+    pub async fn validate_ticket(&self, req: ValidateTicketRequest, scanned_by: Option<Uuid>, access_code: Option<String>) -> Result<ScanResult> {
+        self.validate_scan(req, scanned_by, access_code, None).await
+    }
+
+    /// validate_ticket at a given scan time. Offline scans are judged at the
+    /// device's clock — a rotating QR was fresh when it was read, not when
+    /// the upload arrived.
+    async fn validate_scan(
+        &self,
+        req: ValidateTicketRequest,
+        scanned_by: Option<Uuid>,
+        access_code: Option<String>,
+        at: Option<DateTime<Utc>>,
+    ) -> Result<ScanResult> {
+        let site = ScanSite {
+            location: GeoPoint::from_parts(req.lat, req.lon).map_err(AppError::Validation)?,
+            access_code,
+            at,
+        };
+        let (event_id, rotating_ttl) = self.resolve_event_for_scan(&req.event_key).await?;
+
+        let raw = req.qr_data.as_deref().or(req.ticket_id.as_deref()).unwrap_or("");
+        let (detected_id, qr) = parse_scan_input(raw);
+        let ticket_id = req.ticket_id.as_deref()
+            .map(strip_barcode_framing)
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .or(detected_id)
+            .ok_or_else(|| AppError::Validation("ticket_id or qr_data is required".into()))?;
+
+        // Rotating-QR events: a signed, time-bucketed payload is mandatory.
+        // No payload (raw id typed or read off a printout) is rejected too.
+        if let Some(ttl) = rotating_ttl {
+            let sig = qr.as_ref().and_then(|q| q["sig"].as_str()).unwrap_or("");
+            let now = site.time().timestamp();
+            if !crate::qr_bucket::verify(&self.qr_secret, &ticket_id, ttl, sig, now) {
+                tracing::warn!("Expired or invalid rotating QR for ticket {}", ticket_id);
+                self.record_fraud_signal(
+                    &ticket_id,
+                    event_id,
+                    "qr_expired",
+                    serde_json::json!({ "ticket_id": ticket_id, "ttl_seconds": ttl }),
+                ).await;
+                return Ok(ScanResult {
+                    result: "invalid".into(),
+                    ticket: None,
+                    message: Some("QR code has expired — refresh the ticket and scan again".into()),
+                    new_qr_data: None,
+                    usage_left: None,
+                });
+            }
+            return self.validate_and_mark(&ticket_id, event_id, scanned_by, &site, req.admit).await;
+        }
 
         // HMAC verification if full QR JSON provided
-        if let Some(ref qr_json) = req.qr_data {
-            if let Ok(qr) = serde_json::from_str::<serde_json::Value>(qr_json) {
-                let nonce = qr["nonce"].as_str().unwrap_or("");
-                let sig = qr["sig"].as_str().unwrap_or("");
-                if !nonce.is_empty() && !sig.is_empty() {
-                    if !self.verify_qr_sig(&req.ticket_id, nonce, sig) {
-                        tracing::warn!("QR signature mismatch for ticket {} — possible screenshot fraud", req.ticket_id);
-                        self.record_fraud_signal(
-                            &req.ticket_id,
-                            event_id,
-                            "hmac_mismatch",
-                            serde_json::json!({ "ticket_id": req.ticket_id }),
-                        ).await;
-                        return Ok(ScanResult {
-                            result: "invalid".into(),
-                            ticket: None,
-                            message: Some("QR code is invalid or has already been used".into()),
-                            new_qr_data: None,
-                            usage_left: None,
-                        });
-                    }
-                }
+        if let Some(qr) = qr {
+            let nonce = qr["nonce"].as_str().unwrap_or("");
+            let sig = qr["sig"].as_str().unwrap_or("");
+            if !nonce.is_empty() && !sig.is_empty() && !self.verify_qr_sig(&ticket_id, nonce, sig) {
+                tracing::warn!("QR signature mismatch for ticket {} — possible screenshot fraud", ticket_id);
+                self.record_fraud_signal(
+                    &ticket_id,
+                    event_id,
+                    "hmac_mismatch",
+                    serde_json::json!({ "ticket_id": ticket_id }),
+                ).await;
+                return Ok(ScanResult {
+                    result: "invalid".into(),
+                    ticket: None,
+                    message: Some("QR code is invalid or has already been used".into()),
+                    new_qr_data: None,
+                    usage_left: None,
+                });
             }
         }
 
-        self.validate_and_mark(&req.ticket_id, event_id, None).await
+        self.validate_and_mark(&ticket_id, event_id, scanned_by, &site, req.admit).await
+    }
+
+    /// Apply scans a device queued offline. Its own retries are folded away
+    /// first (see dedup_scans); each remaining scan then goes through
+    /// validate_ticket like a live one, judged and recorded at the device's
+    /// scan time (see sync_judged_at), and a scan that fails outright — or
+    /// is too old to judge — is reported on its own entry instead of
+    /// failing the upload.
+    pub async fn sync_scans(&self, req: SyncScansRequest, scanned_by: Option<Uuid>, access_code: Option<String>) -> Result<SyncScansResponse> {
+        if req.scans.len() > MAX_SYNC_SCANS {
+            return Err(AppError::Validation(format!("At most {} scans can be synced per request", MAX_SYNC_SCANS)));
+        }
+        let received = req.scans.len();
+        let (scans, collapsed) = dedup_scans(req.scans, SYNC_DEDUP_WINDOW_SECS);
+
+        let mut results = Vec::with_capacity(scans.len());
+        for scan in scans {
+            let ticket_id = sync_scan_ticket_id(&scan);
+            let scanned_at = scan.scanned_at;
+            let outcome = match sync_judged_at(scanned_at, Utc::now(), MAX_OFFLINE_SCAN_AGE_SECS) {
+                Ok(judged_at) => self.validate_scan(ValidateTicketRequest {
+                    ticket_id: scan.ticket_id,
+                    event_key: req.event_key.clone(),
+                    qr_data: scan.qr_data,
+                    lat: scan.lat,
+                    lon: scan.lon,
+                    admit: scan.admit,
+                }, scanned_by, access_code.clone(), Some(judged_at)).await,
+                Err(e) => Err(e),
+            };
+            let (result, error) = match outcome {
+                Ok(r) => (Some(r), None),
+                Err(e) => (None, Some(e.to_string())),
+            };
+            results.push(SyncScanResult { ticket_id, scanned_at, result, error });
+        }
+
+        if collapsed > 0 {
+            tracing::info!("Offline sync for {}: {} scans received, {} retries collapsed", req.event_key, received, collapsed);
+        }
+        Ok(SyncScansResponse { received, collapsed, results })
     }
 
     async fn authorize_scanner_for_event(&self, scanned_by: Uuid, event_id: Uuid) -> Result<()> {
@@ -339,7 +867,12 @@ impl ScannerService {
         Ok(())
     }
 
-    pub async fn manual_validate(&self, req: ManualValidateRequest, scanned_by: Uuid) -> Result<ScanResult> {
+    pub async fn manual_validate(&self, req: ManualValidateRequest, scanned_by: Uuid, access_code: Option<String>) -> Result<ScanResult> {
+        let site = ScanSite {
+            location: GeoPoint::from_parts(req.lat, req.lon).map_err(AppError::Validation)?,
+            access_code,
+            at: None,
+        };
         let event_id = match req.event_id {
             Some(id) => id,
             None => match &req.event_key {
@@ -349,12 +882,14 @@ impl ScannerService {
         };
         
         self.authorize_scanner_for_event(scanned_by, event_id).await?;
-        self.validate_and_mark(&req.ticket_id, event_id, Some(scanned_by)).await
+        self.validate_and_mark(&req.ticket_id, event_id, Some(scanned_by), &site, req.admit).await
     }
 
-    /// Core validation + usage engine dispatch.
+    /// Core validation + usage engine dispatch. `admit` lets part of a
+    /// single-use group ticket in; None admits every seat still outside.
     ///
     /// Flow:
+    /// 0. Geofence — only when the scan carries a location
     /// 1. Redis SET NX lock — fast distributed lock
     /// 2. DB SELECT — read ticket state (with Redis usage cache for multi-use)
     /// 3. UsageEngine.evaluate() — determine what to do
@@ -366,27 +901,43 @@ impl ScannerService {
         ticket_id: &str,
         event_id: Uuid,
         scanned_by: Option<Uuid>,
+        site: &ScanSite,
+        admit: Option<i32>,
     ) -> Result<ScanResult> {
-        // STEP 1: Redis lock — prevents simultaneous double-scan
-        if !self.acquire_scan_lock(ticket_id).await {
+        // Blacklisted tickets fail before anything else, whatever their status
+        if let Some(rejected) = self.check_blacklist(ticket_id, event_id, scanned_by, site).await? {
+            return Ok(rejected);
+        }
+
+        // STEP 0: Geofence — before the lock, so a rejected scan doesn't hold it
+        if let Some(rejected) = self.check_geofence(ticket_id, event_id, scanned_by, site).await? {
+            return Ok(rejected);
+        }
+
+        // STEP 1: Redis lock — prevents simultaneous double-scan. Single-use
+        // tickets don't stop here: the row lock in STEP 3 picks the winner and
+        // the loser is told who it was, instead of a race-dependent "busy".
+        let lock_contended = !self.acquire_scan_lock(ticket_id).await;
+        if lock_contended {
             self.record_fraud_signal(ticket_id, event_id, "rapid_rescan", serde_json::json!({})).await;
-            return Ok(ScanResult {
-                result: "already_used".into(),
-                ticket: None,
-                message: Some("Ticket is currently being processed".into()),
-                new_qr_data: None,
-                usage_left: None,
-            });
         }
 
         // STEP 2: Fetch ticket with user details
         let row = sqlx::query(
-            "SELECT t.id, t.ticket_id, t.status, t.ticket_type, t.quantity,
-                    t.scanned_at, t.event_id, t.usage_model, t.usage_left, t.usage_total,
-                    t.user_id, u.name as user_name
+            "SELECT t.id, t.ticket_id, t.status, t.ticket_type, t.quantity, t.scanned_quantity,
+                    t.scanned_at, t.scanned_by, su.name as scanned_by_name,
+                    t.event_id, t.usage_model, t.usage_left, t.usage_total,
+                    t.user_id, u.name as user_name, e.scan_webhook_url,
+                    EXISTS(SELECT 1 FROM ticket_session_admissions a
+                            WHERE a.ticket_id = t.id AND a.event_id = $2
+                              AND a.checked_out_at IS NOT NULL) AS checked_out
              FROM tickets t
              JOIN users u ON t.user_id = u.id
-             WHERE t.ticket_id = $1 AND t.event_id = $2",
+             JOIN events e ON t.event_id = e.id
+             LEFT JOIN users su ON t.scanned_by = su.id
+             WHERE t.ticket_id = $1
+               AND (t.event_id = $2
+                    OR e.event_group_id = (SELECT event_group_id FROM events WHERE id = $2))",
         )
         .bind(ticket_id)
         .bind(event_id)
@@ -407,19 +958,27 @@ impl ScannerService {
             Some(r) => r,
         };
 
-        let status: String = row.get("status");
+        let status = TicketStatus::from_db(row.get("status"))?;
         let ticket_db_id: Uuid = row.get("id");
         let tid: String = row.get("ticket_id");
         let user_name: String = row.get("user_name");
         let user_id: Uuid = row.get("user_id");
         let ticket_type: String = row.get("ticket_type");
         let quantity: i32 = row.get("quantity");
+        let scanned_quantity: i32 = row.get("scanned_quantity");
         let scanned_at: Option<DateTime<Utc>> = row.get("scanned_at");
+        let winner: Option<Uuid> = row.get("scanned_by");
+        let winner_name: Option<String> = row.get("scanned_by_name");
         let usage_model: String = row.get("usage_model");
         let usage_left: Option<i32> = row.get("usage_left");
         let usage_total: Option<i32> = row.get("usage_total");
+        let scan_webhook_url: Option<String> = row.get("scan_webhook_url");
+        // Differs from event_id when a group pass is scanned at a sibling session
+        let ticket_event_id: Uuid = row.get("event_id");
+        // A single-use ticket that checked out comes back in on the same scan
+        let reentry = status == TicketStatus::Used && usage_model == "single" && row.get::<bool, _>("checked_out");
 
-        if status == "used" {
+        if status == TicketStatus::Used && !reentry {
             return Ok(ScanResult {
                 result: "already_used".into(),
                 ticket: Some(ScanTicketInfo {
@@ -427,9 +986,14 @@ impl ScannerService {
                     user_name,
                     ticket_type,
                     quantity,
-                    scanned_at: scanned_at.map(|t| t.to_rfc3339()),
+                    scanned_at,
+                    scanned_by: winner,
+                    scanned_by_name: winner_name,
                     usage_left: None,
                     usage_total: None,
+                    admitted: None,
+                    remaining: None,
+                    add_ons: Vec::new(),
                 }),
                 message: None,
                 new_qr_data: None,
@@ -437,7 +1001,7 @@ impl ScannerService {
             });
         }
 
-        if status == "expired" {
+        if status == TicketStatus::Expired {
             return Ok(ScanResult {
                 result: "expired".into(),
                 ticket: None,
@@ -447,7 +1011,7 @@ impl ScannerService {
             });
         }
 
-        if status != "valid" {
+        if !reentry && !status.can_transition_to(TicketStatus::Used) {
             return Ok(ScanResult {
                 result: "invalid".into(),
                 ticket: None,
@@ -457,34 +1021,166 @@ impl ScannerService {
             });
         }
 
+        if lock_contended && usage_model != "single" {
+            return Ok(ScanResult {
+                result: "already_used".into(),
+                ticket: None,
+                message: Some("Ticket is currently being processed".into()),
+                new_qr_data: None,
+                usage_left: None,
+            });
+        }
+
+        if admit.is_some() && usage_model != "single" {
+            return Err(AppError::Validation("admit only applies to single-use tickets".into()));
+        }
+
         // STEP 3: Single-use fast path — skip engine overhead
         if usage_model == "single" {
-            let new_nonce = hex::encode(rand::random::<[u8; 32]>());
-            let updated = sqlx::query(
-                "UPDATE tickets SET status='used', scanned_at=NOW(), scanned_by=$3, qr_nonce=$4
-                 WHERE ticket_id=$1 AND event_id=$2 AND status='valid'
-                 RETURNING id",
+            // Sized from the unlocked read; the claim below re-checks it. The
+            // ticket's earlier seats count too, in case they never checked out
+            // — and are all that come back on a re-entry.
+            let (seats, _) = if reentry { (0, 0) } else { admit_seats(quantity, scanned_quantity, admit)? };
+            let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+            // Event row before ticket row, the same order purchases lock in
+            if let Some(message) = self.check_capacity_with_tx(&mut tx, ticket_db_id, event_id, scanned_quantity + seats).await? {
+                tx.rollback().await.map_err(AppError::Database)?;
+                self.log_scan(ticket_id, event_id, scanned_by, "at_capacity", site).await;
+                return Ok(at_capacity(message));
+            }
+            let locked = sqlx::query(
+                "SELECT t.status, t.scanned_quantity, t.scanned_by, t.scanned_at, su.name as scanned_by_name,
+                        EXISTS(SELECT 1 FROM ticket_session_admissions a
+                                WHERE a.ticket_id = t.id AND a.event_id = $3
+                                  AND a.checked_out_at IS NOT NULL) AS checked_out
+                 FROM tickets t
+                 LEFT JOIN users su ON t.scanned_by = su.id
+                 WHERE t.ticket_id = $1 AND t.event_id = $2
+                 FOR UPDATE OF t",
             )
             .bind(ticket_id)
+            .bind(ticket_event_id)
             .bind(event_id)
-            .bind(scanned_by)
-            .bind(&new_nonce)
-            .fetch_optional(&self.pool)
+            .fetch_one(&mut *tx)
             .await
             .map_err(AppError::Database)?;
 
-            if updated.is_none() {
-                return Ok(ScanResult {
-                    result: "already_used".into(),
-                    ticket: None,
-                    message: Some("Ticket was just scanned by another device".into()),
-                    new_qr_data: None,
-                    usage_left: None,
-                });
+            let locked_status = TicketStatus::from_db(locked.get("status"))?;
+            let claim = claim_single_use(
+                locked_status,
+                locked.get("scanned_by"),
+                locked.get("scanned_at"),
+                locked.get("checked_out"),
+            );
+            match claim {
+                Claim::Won | Claim::Reentry => {}
+                Claim::Lost { scanned_by: winner, scanned_at: won_at } => {
+                    tx.rollback().await.map_err(AppError::Database)?;
+                    self.log_scan(ticket_id, event_id, scanned_by, "already_used", site).await;
+                    return Ok(ScanResult {
+                        result: "already_used".into(),
+                        ticket: Some(ScanTicketInfo {
+                            ticket_id: tid,
+                            user_name,
+                            ticket_type,
+                            quantity,
+                            scanned_at: won_at,
+                            scanned_by: winner,
+                            scanned_by_name: locked.get("scanned_by_name"),
+                            usage_left: None,
+                            usage_total: None,
+                            admitted: None,
+                            remaining: None,
+                            add_ons: Vec::new(),
+                        }),
+                        message: Some("Ticket was just scanned by another device".into()),
+                        new_qr_data: None,
+                        usage_left: None,
+                    });
+                }
+                Claim::Refused(st) => {
+                    tx.rollback().await.map_err(AppError::Database)?;
+                    return Ok(ScanResult {
+                        result: "invalid".into(),
+                        ticket: None,
+                        message: Some(format!("Ticket status is '{}'", st)),
+                        new_qr_data: None,
+                        usage_left: None,
+                    });
+                }
             }
 
-            self.log_scan(ticket_id, event_id, scanned_by, "valid").await;
-            tracing::info!("Ticket {} scanned (single-use)", ticket_id);
+            // Grouped events: checked under the row lock, so two gates in
+            // different sessions can't both let a one-session pass in
+            if let Some(message) = session_refusal_with_tx(&mut tx, ticket_db_id, &usage_model, event_id).await? {
+                tx.rollback().await.map_err(AppError::Database)?;
+                self.log_scan(ticket_id, event_id, scanned_by, "already_used", site).await;
+                return Ok(session_refused(message));
+            }
+
+            let (admitted, remaining) = if claim == Claim::Reentry {
+                (locked.get("scanned_quantity"), 0)
+            } else {
+                match admit_seats(quantity, locked.get("scanned_quantity"), admit) {
+                    Ok(seats) => seats,
+                    Err(e) => {
+                        tx.rollback().await.map_err(AppError::Database)?;
+                        return Err(e);
+                    }
+                }
+            };
+
+            if claim == Claim::Reentry {
+                // Nothing is spent, and the earlier scan is no longer the
+                // latest thing an undo could take back — a check-out is
+                sqlx::query("UPDATE tickets SET last_scan_quantity=0 WHERE ticket_id=$1 AND event_id=$2")
+                    .bind(ticket_id)
+                    .bind(ticket_event_id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(AppError::Database)?;
+            } else {
+                // The QR stays the same until the last seat is in — the rest of
+                // the group comes through on it
+                let (new_status, new_nonce) = if remaining == 0 {
+                    (TicketStatus::Used, Some(hex::encode(rand::random::<[u8; 32]>())))
+                } else {
+                    (TicketStatus::Valid, None)
+                };
+                sqlx::query(
+                    "UPDATE tickets SET status=$3, scanned_at=$7, scanned_by=$2, qr_nonce=COALESCE($4, qr_nonce),
+                            scanned_quantity=scanned_quantity + $6, last_scan_quantity=$6
+                     WHERE ticket_id=$1 AND event_id=$5",
+                )
+                .bind(ticket_id)
+                .bind(scanned_by)
+                .bind(new_status.as_str())
+                .bind(&new_nonce)
+                .bind(ticket_event_id)
+                .bind(admitted)
+                .bind(site.time())
+                .execute(&mut *tx)
+                .await
+                .map_err(AppError::Database)?;
+            }
+            record_admission_with_tx(&mut tx, ticket_db_id, event_id, scanned_by).await?;
+            tx.commit().await.map_err(AppError::Database)?;
+
+            self.log_scan(ticket_id, event_id, scanned_by, "valid", site).await;
+            tracing::info!("Ticket {} scanned (single-use) — {} admitted, {} to come", ticket_id, admitted, remaining);
+
+            self.dispatch_scan_webhook(scan_webhook_url, serde_json::json!({
+                "event": "ticket.scanned",
+                "event_id": event_id,
+                "ticket_id": tid,
+                "holder_name": user_name,
+                "ticket_type": ticket_type,
+                "quantity": quantity,
+                "admitted": admitted,
+                "remaining": remaining,
+                "usage_left": null,
+                "scanned_at": crate::timestamp::format(&site.time()),
+            }));
 
             return Ok(ScanResult {
                 result: "valid".into(),
@@ -494,8 +1190,13 @@ impl ScannerService {
                     ticket_type,
                     quantity,
                     scanned_at: None,
+                    scanned_by: None,
+                    scanned_by_name: None,
                     usage_left: None,
                     usage_total: None,
+                    admitted: Some(admitted),
+                    remaining: Some(remaining),
+                    add_ons: self.ticket_add_ons(ticket_db_id).await,
                 }),
                 message: None,
                 new_qr_data: None,
@@ -505,7 +1206,7 @@ impl ScannerService {
 
         // STEP 4: Multi-use path — delegate to usage engine
         let engine = UsageEngine::new(&self.pool);
-        let decision = engine.evaluate(ticket_db_id).await?;
+        let decision = engine.evaluate(ticket_db_id, site.time()).await?;
 
         match &decision {
             UsageDecision::NotYetValid => {
@@ -547,7 +1248,34 @@ impl ScannerService {
             _ => {}
         }
 
-        let usage_left_after = engine.apply(ticket_db_id, ticket_id, event_id, scanned_by, &decision).await?;
+        // Hold the place inside before the use is spent; give it back if that fails
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+        if let Some(message) = self.check_capacity_with_tx(&mut tx, ticket_db_id, event_id, quantity).await? {
+            tx.rollback().await.map_err(AppError::Database)?;
+            self.log_scan(ticket_id, event_id, scanned_by, "at_capacity", site).await;
+            return Ok(at_capacity(message));
+        }
+        // Ticket row after the event row, as above; held until the admission is in
+        sqlx::query("SELECT 1 FROM tickets WHERE id = $1 FOR UPDATE")
+            .bind(ticket_db_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::Database)?;
+        if let Some(message) = session_refusal_with_tx(&mut tx, ticket_db_id, &usage_model, event_id).await? {
+            tx.rollback().await.map_err(AppError::Database)?;
+            self.log_scan(ticket_id, event_id, scanned_by, "already_used", site).await;
+            return Ok(session_refused(message));
+        }
+        record_admission_with_tx(&mut tx, ticket_db_id, event_id, scanned_by).await?;
+        tx.commit().await.map_err(AppError::Database)?;
+
+        let usage_left_after = match engine.apply(ticket_db_id, ticket_id, event_id, scanned_by, &decision, site).await {
+            Ok(left) => left,
+            Err(e) => {
+                self.release_admission(ticket_db_id, event_id).await;
+                return Err(e);
+            }
+        };
 
         // STEP 5: Queue notification if usage depleted or last use
         if usage_left_after == 0 {
@@ -568,6 +1296,17 @@ impl ScannerService {
 
         tracing::info!("Ticket {} scanned ({}) — {} uses left", ticket_id, usage_model, usage_left_after);
 
+        self.dispatch_scan_webhook(scan_webhook_url, serde_json::json!({
+            "event": "ticket.scanned",
+            "event_id": event_id,
+            "ticket_id": tid,
+            "holder_name": user_name,
+            "ticket_type": ticket_type,
+            "quantity": quantity,
+            "usage_left": usage_left_after,
+            "scanned_at": crate::timestamp::format(&site.time()),
+        }));
+
         Ok(ScanResult {
             result: "valid".into(),
             ticket: Some(ScanTicketInfo {
@@ -576,8 +1315,13 @@ impl ScannerService {
                 ticket_type,
                 quantity,
                 scanned_at: None,
+                scanned_by: None,
+                scanned_by_name: None,
                 usage_left: Some(usage_left_after),
                 usage_total,
+                admitted: None,
+                remaining: None,
+                add_ons: self.ticket_add_ons(ticket_db_id).await,
             }),
             message: None,
             new_qr_data: None,
@@ -585,7 +1329,161 @@ impl ScannerService {
         })
     }
 
-    pub async fn mark_used(&self, ticket_id: &str, scanned_by: Option<Uuid>) -> Result<bool> {
+    /// Reject a ticket on the admin blacklist (chargeback, leaked QR) no
+    /// matter its status. None = carry on.
+    async fn check_blacklist(
+        &self,
+        ticket_id: &str,
+        event_id: Uuid,
+        scanned_by: Option<Uuid>,
+        site: &ScanSite,
+    ) -> Result<Option<ScanResult>> {
+        let reason: Option<String> = sqlx::query_scalar("SELECT reason FROM ticket_blacklist WHERE ticket_id = $1")
+            .bind(ticket_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::Database)?;
+        let Some(reason) = reason else { return Ok(None) };
+
+        tracing::warn!("Scan of blacklisted ticket {} at event {}: {}", ticket_id, event_id, reason);
+        self.log_scan(ticket_id, event_id, scanned_by, "blacklisted", site).await;
+        self.record_fraud_signal(ticket_id, event_id, "blacklisted_scan", serde_json::json!({ "reason": reason })).await;
+        Ok(Some(ScanResult {
+            result: "blacklisted".into(),
+            ticket: None,
+            message: Some(format!("Ticket is blacklisted ({}) — do not admit", reason)),
+            new_qr_data: None,
+            usage_left: None,
+        }))
+    }
+
+    /// Reject a located scan made outside the event's geofence. None = carry on.
+    /// Events without venue coordinates and scans without a location always pass.
+    async fn check_geofence(
+        &self,
+        ticket_id: &str,
+        event_id: Uuid,
+        scanned_by: Option<Uuid>,
+        site: &ScanSite,
+    ) -> Result<Option<ScanResult>> {
+        let Some(scan) = site.location else { return Ok(None) };
+
+        let row = sqlx::query("SELECT venue_lat, venue_lon, geofence_radius_m FROM events WHERE id = $1")
+            .bind(event_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::Database)?;
+        let Some(row) = row else { return Ok(None) };
+        let (Some(lat), Some(lon)) = (row.get::<Option<f64>, _>("venue_lat"), row.get::<Option<f64>, _>("venue_lon")) else {
+            return Ok(None);
+        };
+        let radius_m: i32 = row.get("geofence_radius_m");
+
+        let Some(distance) = geofence::outside(GeoPoint { lat, lon }, radius_m as f64, scan) else {
+            return Ok(None);
+        };
+
+        tracing::warn!("Scan of {} rejected: {:.0} m from venue (radius {} m)", ticket_id, distance, radius_m);
+        self.log_scan(ticket_id, event_id, scanned_by, "wrong_location", site).await;
+        Ok(Some(ScanResult {
+            result: "wrong_location".into(),
+            ticket: None,
+            message: Some(format!(
+                "Scanner is {:.0} m from the venue (limit {} m) — check you are scanning for the right event",
+                distance, radius_m
+            )),
+            new_qr_data: None,
+            usage_left: None,
+        }))
+    }
+
+    /// Venue capacity guard, run in the admitting transaction. Events with a
+    /// cap have their row locked until commit so two gates can't both let in
+    /// the last person. Some(message) = at capacity.
+    async fn check_capacity_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        ticket_db_id: Uuid,
+        event_id: Uuid,
+        people: i32,
+    ) -> Result<Option<String>> {
+        let capped: Option<i32> = sqlx::query_scalar("SELECT max_concurrent_inside FROM events WHERE id = $1")
+            .bind(event_id)
+            .fetch_optional(&mut **tx)
+            .await
+            .map_err(AppError::Database)?
+            .flatten();
+        if capped.is_none() {
+            return Ok(None);
+        }
+
+        // Re-read under the lock — the cap may have just changed
+        let max_inside: Option<i32> = sqlx::query_scalar("SELECT max_concurrent_inside FROM events WHERE id = $1 FOR UPDATE")
+            .bind(event_id)
+            .fetch_one(&mut **tx)
+            .await
+            .map_err(AppError::Database)?;
+        let inside: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(SUM(CASE WHEN t.usage_model = 'single' THEN t.scanned_quantity ELSE t.quantity END), 0)::BIGINT
+             FROM ticket_session_admissions a
+             JOIN tickets t ON t.id = a.ticket_id
+             WHERE a.event_id = $1 AND a.checked_out_at IS NULL AND a.ticket_id <> $2",
+        )
+        .bind(event_id)
+        .bind(ticket_db_id)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(AppError::Database)?;
+
+        Ok(check_capacity(max_inside, inside, people))
+    }
+
+    /// A spent use that never went through — the holder isn't inside.
+    async fn release_admission(&self, ticket_db_id: Uuid, event_id: Uuid) {
+        let _ = sqlx::query(
+            "UPDATE ticket_session_admissions SET checked_out_at = NOW()
+             WHERE ticket_id = $1 AND event_id = $2 AND checked_out_at IS NULL",
+        )
+        .bind(ticket_db_id)
+        .bind(event_id)
+        .execute(&self.pool)
+        .await;
+    }
+
+    /// People admitted to an event and not checked out.
+    async fn count_inside(&self, event_id: Uuid) -> Result<i64> {
+        sqlx::query_scalar(
+            "SELECT COALESCE(SUM(CASE WHEN t.usage_model = 'single' THEN t.scanned_quantity ELSE t.quantity END), 0)::BIGINT
+             FROM ticket_session_admissions a
+             JOIN tickets t ON t.id = a.ticket_id
+             WHERE a.event_id = $1 AND a.checked_out_at IS NULL",
+        )
+        .bind(event_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::Database)
+    }
+
+    /// Add-ons bought with a ticket, for the gate display. Best-effort: a
+    /// failed lookup shows none rather than failing an admitted scan.
+    async fn ticket_add_ons(&self, ticket_db_id: Uuid) -> Vec<ScanAddOn> {
+        let rows = sqlx::query("SELECT name, quantity FROM ticket_add_ons WHERE ticket_id = $1 ORDER BY name")
+            .bind(ticket_db_id)
+            .fetch_all(&self.pool)
+            .await;
+        match rows {
+            Ok(rows) => rows.iter().map(|r| ScanAddOn { name: r.get("name"), quantity: r.get("quantity") }).collect(),
+            Err(e) => {
+                tracing::warn!("Add-on lookup failed for ticket {}: {}", ticket_db_id, e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Admit a ticket at its own event — `admit` seats of a group ticket, or
+    /// all that are left. Single-use tickets go through the row lock, so
+    /// concurrent calls can't let in more seats than the ticket holds.
+    pub async fn mark_used(&self, ticket_id: &str, scanned_by: Option<Uuid>, admit: Option<i32>) -> Result<ScanResult> {
         let row = sqlx::query("SELECT event_id FROM tickets WHERE ticket_id = $1")
             .bind(ticket_id)
             .fetch_optional(&self.pool)
@@ -594,8 +1492,131 @@ impl ScannerService {
             .ok_or_else(|| AppError::NotFound("Ticket not found".into()))?;
 
         let event_id: Uuid = row.get("event_id");
-        let result = self.validate_and_mark(ticket_id, event_id, scanned_by).await?;
-        Ok(result.result == "valid")
+        self.validate_and_mark(ticket_id, event_id, scanned_by, &ScanSite::default(), admit).await
+    }
+
+    /// Undo an accidental scan, only for the scanner that made it and only
+    /// within undo_window_secs. A group ticket gets back the seats of that
+    /// last scan only and is valid again; a multi-use ticket gets back the
+    /// use. The ticket row is locked so an undo can't interleave with a
+    /// re-scan.
+    pub async fn undo_scan(&self, ticket_id: &str, scanner_id: Uuid, access_code: Option<&str>) -> Result<UndoScanResult> {
+        let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+
+        let row = sqlx::query(
+            "SELECT id, event_id, status, usage_model, quantity, scanned_quantity, last_scan_quantity,
+                    scanned_by, scanned_at
+             FROM tickets WHERE ticket_id = $1 FOR UPDATE",
+        )
+        .bind(ticket_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::Database)?
+        .ok_or_else(|| AppError::NotFound("Ticket not found".into()))?;
+
+        let status = TicketStatus::from_db(row.get("status"))?;
+        let usage_model: String = row.get("usage_model");
+        let scanned_by: Option<Uuid> = row.get("scanned_by");
+        let scanned_at: Option<DateTime<Utc>> = row.get("scanned_at");
+        let single = usage_model == "single";
+        let seats = seats_to_undo(&usage_model, row.get("quantity"), row.get("last_scan_quantity"), scanned_at.is_some());
+        check_undo(status, seats, scanned_by, scanned_at, scanner_id, Utc::now(), self.undo_window_secs)?;
+
+        let ticket_db_id: Uuid = row.get("id");
+        let event_id: Uuid = row.get("event_id");
+        let restored = status_after_undo(row.get("quantity"), row.get("scanned_quantity"), seats);
+
+        sqlx::query(
+            "UPDATE tickets SET status = $2, scanned_at = NULL, scanned_by = NULL,
+                    scanned_quantity = scanned_quantity - $3, last_scan_quantity = 0,
+                    usage_left = CASE WHEN $4 THEN usage_left + 1 ELSE usage_left END,
+                    updated_at = NOW()
+             WHERE id = $1",
+        )
+        .bind(ticket_db_id)
+        .bind(restored.as_str())
+        .bind(if single { seats } else { 0 })
+        .bind(undo_restores_use(&usage_model, status))
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::Database)?;
+
+        // The undone scan never admitted anyone — free the session again. A
+        // multi-use ticket may have been inside before; it leaves instead.
+        // Matched on time alone: check_undo already tied the scan to this
+        // scanner, and the admission's scanned_by is NULL for unattributed scans.
+        if let Some(at) = scanned_at {
+            sqlx::query(
+                "DELETE FROM ticket_session_admissions
+                 WHERE ticket_id = $1 AND admitted_at >= $2",
+            )
+            .bind(ticket_db_id)
+            .bind(at)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::Database)?;
+        }
+        if !single {
+            sqlx::query(
+                "UPDATE ticket_session_admissions SET checked_out_at = NOW()
+                 WHERE ticket_id = $1 AND event_id = $2 AND checked_out_at IS NULL",
+            )
+            .bind(ticket_db_id)
+            .bind(event_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::Database)?;
+        }
+
+        sqlx::query(
+            "INSERT INTO scan_log (ticket_id, event_id, scanned_by, result, access_code)
+             VALUES ($1, $2, $3, 'undo', (SELECT code FROM scanner_access_codes WHERE code = $4 AND event_id = $2))",
+        )
+        .bind(ticket_db_id)
+        .bind(event_id)
+        .bind(scanner_id)
+        .bind(access_code)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::Database)?;
+
+        tx.commit().await.map_err(AppError::Database)?;
+        self.invalidate_usage_cache(ticket_id).await;
+
+        tracing::info!("Scan of ticket {} undone by scanner {}", ticket_id, scanner_id);
+        Ok(UndoScanResult { ticket_id: ticket_id.to_string(), status: restored.to_string() })
+    }
+
+    /// Check a ticket out of the venue so it stops counting against the
+    /// capacity cap. Scanning it again checks it back in.
+    pub async fn check_out(&self, ticket_id: &str, event_id: Option<Uuid>, scanner_id: Uuid, access_code: Option<String>) -> Result<CheckOutResult> {
+        let row = sqlx::query("SELECT id, event_id FROM tickets WHERE ticket_id = $1")
+            .bind(ticket_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Ticket not found".into()))?;
+        let ticket_db_id: Uuid = row.get("id");
+        let event_id = event_id.unwrap_or_else(|| row.get("event_id"));
+
+        self.authorize_scanner_for_event(scanner_id, event_id).await?;
+
+        let result = sqlx::query(
+            "UPDATE ticket_session_admissions SET checked_out_at = NOW()
+             WHERE ticket_id = $1 AND event_id = $2 AND checked_out_at IS NULL",
+        )
+        .bind(ticket_db_id)
+        .bind(event_id)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::BadRequest("Ticket is not checked in".into()));
+        }
+
+        let site = ScanSite { location: None, access_code, at: None };
+        self.log_scan(ticket_id, event_id, Some(scanner_id), "check_out", &site).await;
+        Ok(CheckOutResult { ticket_id: ticket_id.to_string(), event_id, inside: self.count_inside(event_id).await? })
     }
 
     /// Renew a ticket — reset usage_left to usage_total.
@@ -605,7 +1626,8 @@ impl ScannerService {
         let row = sqlx::query(
             "SELECT t.id, t.usage_total, t.is_renewable, t.unit_price, t.currency,
                     t.payment_provider, t.user_id, t.event_id,
-                    COALESCE(t.unit_price, 0) as price
+                    COALESCE(t.unit_price, 0) as price,
+                    EXISTS(SELECT 1 FROM ticket_blacklist b WHERE b.ticket_id = t.ticket_id) AS blacklisted
              FROM tickets t
              WHERE t.ticket_id = $1 AND t.user_id = $2",
         )
@@ -616,10 +1638,7 @@ impl ScannerService {
         .map_err(AppError::Database)?
         .ok_or_else(|| AppError::NotFound("Ticket not found".into()))?;
 
-        let is_renewable: bool = row.get("is_renewable");
-        if !is_renewable {
-            return Err(AppError::BadRequest("Ticket is not renewable".into()));
-        }
+        check_renewable(row.get("is_renewable"), row.get("blacklisted"))?;
 
         let ticket_db_id: Uuid = row.get("id");
         let usage_total: Option<i32> = row.get("usage_total");
@@ -648,13 +1667,14 @@ impl ScannerService {
 
         sqlx::query(
             "UPDATE tickets
-             SET usage_left=$2, status='valid', renewed_at=NOW(),
+             SET usage_left=$2, status=$4, renewed_at=NOW(),
                  renewal_count = renewal_count + 1, qr_nonce=$3
              WHERE id=$1",
         )
         .bind(ticket_db_id)
         .bind(new_left)
         .bind(&new_nonce)
+        .bind(TicketStatus::Valid.as_str())
         .execute(&self.pool)
         .await
         .map_err(AppError::Database)?;
@@ -672,6 +1692,68 @@ impl ScannerService {
         })
     }
 
+    /// Tickets scanned at two different gates within `window_secs` of each
+    /// other — the same QR shared or copied. A gate is a scanner plus the
+    /// access code it signed in with; undo and check-out rows aren't
+    /// presentations and are skipped. Organizer only.
+    pub async fn duplicate_scans(&self, organizer_id: Uuid, event_id: Uuid, window_secs: Option<i64>) -> Result<DuplicateScanReport> {
+        let owner: Option<Uuid> = sqlx::query_scalar("SELECT organizer_id FROM events WHERE id = $1")
+            .bind(event_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::Database)?;
+        if owner != Some(organizer_id) {
+            return Err(AppError::NotFound("Event not found or not owned by you".into()));
+        }
+
+        let window_secs = window_secs.unwrap_or(DUPLICATE_WINDOW_SECS).clamp(1, MAX_DUPLICATE_WINDOW_SECS);
+        let rows = sqlx::query(
+            r#"WITH scans AS (
+                   SELECT ticket_id, scanned_by, access_code, scanned_at,
+                          LAG(scanned_by)  OVER w AS prev_by,
+                          LAG(access_code) OVER w AS prev_code,
+                          LAG(scanned_at)  OVER w AS prev_at
+                   FROM scan_log
+                   WHERE event_id = $1 AND result NOT IN ('undo', 'check_out')
+                   WINDOW w AS (PARTITION BY ticket_id ORDER BY scanned_at)
+               )
+               SELECT t.ticket_id, u.name AS holder_name, COUNT(*) AS clashes,
+                      ARRAY_REMOVE(ARRAY_AGG(s.scanned_by) || ARRAY_AGG(s.prev_by), NULL) AS scanners,
+                      MIN(s.prev_at) AS first_scan_at, MAX(s.scanned_at) AS last_scan_at
+               FROM scans s
+               JOIN tickets t ON t.id = s.ticket_id
+               JOIN users u ON u.id = t.user_id
+               WHERE s.prev_at IS NOT NULL
+                 AND s.scanned_at - s.prev_at <= make_interval(secs => $2)
+                 AND (s.scanned_by IS DISTINCT FROM s.prev_by OR s.access_code IS DISTINCT FROM s.prev_code)
+               GROUP BY t.ticket_id, u.name
+               ORDER BY last_scan_at DESC
+               LIMIT $3"#,
+        )
+        .bind(event_id)
+        .bind(window_secs as f64)
+        .bind(MAX_DUPLICATE_TICKETS)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+
+        let tickets = rows.iter().map(|r| {
+            let mut scanners: Vec<Uuid> = r.get("scanners");
+            scanners.sort();
+            scanners.dedup();
+            DuplicateScan {
+                ticket_id: r.get("ticket_id"),
+                holder_name: r.get("holder_name"),
+                scanners,
+                clashes: r.get("clashes"),
+                first_scan_at: r.get("first_scan_at"),
+                last_scan_at: r.get("last_scan_at"),
+            }
+        }).collect();
+
+        Ok(DuplicateScanReport { event_id, window_secs, tickets })
+    }
+
     pub async fn get_stats(&self, event_id: Uuid) -> Result<ScanStats> {
         let row = sqlx::query(
             "SELECT e.total_tickets,
@@ -697,18 +1779,27 @@ impl ScannerService {
             0.0
         };
 
-        Ok(ScanStats { total_tickets, scanned, remaining, scan_rate })
+        let inside = self.count_inside(event_id).await?;
+
+        Ok(ScanStats { total_tickets, scanned, remaining, scan_rate, inside })
     }
 
-    async fn log_scan(&self, ticket_id: &str, event_id: Uuid, scanned_by: Option<Uuid>, result: &str) {
+    async fn log_scan(&self, ticket_id: &str, event_id: Uuid, scanned_by: Option<Uuid>, result: &str, site: &ScanSite) {
         let _ = sqlx::query(
-            "INSERT INTO scan_log (ticket_id, event_id, scanned_by, result)
-             SELECT t.id, $2, $3, $4 FROM tickets t WHERE t.ticket_id = $1",
+            "INSERT INTO scan_log (ticket_id, event_id, scanned_by, result, lat, lon, access_code, scanned_at)
+             SELECT t.id, $2, $3, $4, $5, $6,
+                    (SELECT code FROM scanner_access_codes WHERE code = $7 AND event_id = $2),
+                    COALESCE($8, NOW())
+             FROM tickets t WHERE t.ticket_id = $1",
         )
         .bind(ticket_id)
         .bind(event_id)
         .bind(scanned_by)
         .bind(result)
+        .bind(site.location.map(|p| p.lat))
+        .bind(site.location.map(|p| p.lon))
+        .bind(site.access_code.as_deref())
+        .bind(site.at)
         .execute(&self.pool)
         .await;
     }
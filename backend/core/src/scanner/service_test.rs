@@ -0,0 +1,366 @@
+// Unit tests for pure pieces of the scan path.
+// The concurrent claim test at the bottom needs a database and is #[ignore]d.
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::tickets::status::TicketStatus;
+use super::service::{
+    admit_seats, check_capacity, check_renewable, check_session_entry, check_undo, claim_single_use, dedup_scans, parse_scan_input,
+    seats_to_undo, status_after_undo, sync_judged_at, undo_restores_use, Claim, ScanTicketInfo, SyncScan,
+    MAX_OFFLINE_SCAN_AGE_SECS, SYNC_DEDUP_WINDOW_SECS,
+};
+
+const WINDOW: i64 = 120;
+
+#[test]
+fn same_scanner_can_undo_inside_window() {
+    let scanner = Uuid::new_v4();
+    let now = Utc::now();
+    let scanned_at = Some(now - Duration::seconds(30));
+    assert!(check_undo(TicketStatus::Used, 1, Some(scanner), scanned_at, scanner, now, WINDOW).is_ok());
+}
+
+#[test]
+fn undo_outside_window_is_rejected() {
+    let scanner = Uuid::new_v4();
+    let now = Utc::now();
+    let scanned_at = Some(now - Duration::seconds(WINDOW + 1));
+    assert!(matches!(
+        check_undo(TicketStatus::Used, 1, Some(scanner), scanned_at, scanner, now, WINDOW),
+        Err(AppError::BadRequest(_))
+    ));
+}
+
+#[test]
+fn other_scanner_cannot_undo() {
+    let now = Utc::now();
+    let scanned_at = Some(now - Duration::seconds(5));
+    assert!(matches!(
+        check_undo(TicketStatus::Used, 1, Some(Uuid::new_v4()), scanned_at, Uuid::new_v4(), now, WINDOW),
+        Err(AppError::Forbidden)
+    ));
+}
+
+#[test]
+fn ticket_without_an_admission_cannot_be_undone() {
+    let scanner = Uuid::new_v4();
+    let now = Utc::now();
+    assert!(matches!(
+        check_undo(TicketStatus::Valid, 0, None, None, scanner, now, WINDOW),
+        Err(AppError::BadRequest(_))
+    ));
+}
+
+#[test]
+fn partial_group_admission_can_be_undone() {
+    let scanner = Uuid::new_v4();
+    let now = Utc::now();
+    let scanned_at = Some(now - Duration::seconds(10));
+    assert!(check_undo(TicketStatus::Valid, 2, Some(scanner), scanned_at, scanner, now, WINDOW).is_ok());
+    assert_eq!(status_after_undo(5, 2, 2), TicketStatus::Valid);
+}
+
+#[test]
+fn multi_use_undo_gives_back_the_use() {
+    // Every use lets the whole ticket in; once undone there's nothing left to take back
+    assert_eq!(seats_to_undo("multi", 2, 0, true), 2);
+    assert_eq!(seats_to_undo("multi", 2, 0, false), 0);
+    assert_eq!(seats_to_undo("single", 4, 1, true), 1);
+    assert!(undo_restores_use("multi", TicketStatus::Valid));
+    // The last use marked it used and left usage_left alone
+    assert!(!undo_restores_use("multi", TicketStatus::Used));
+    assert!(!undo_restores_use("single", TicketStatus::Valid));
+}
+
+#[test]
+fn cancelled_ticket_cannot_be_undone() {
+    let scanner = Uuid::new_v4();
+    let now = Utc::now();
+    assert!(matches!(
+        check_undo(TicketStatus::Cancelled, 1, Some(scanner), Some(now), scanner, now, WINDOW),
+        Err(AppError::BadRequest(_))
+    ));
+}
+
+#[test]
+fn barcode_read_is_taken_as_raw_ticket_id() {
+    let (id, qr) = parse_scan_input("BUKR-0001-abcd\r\n");
+    assert_eq!(id.as_deref(), Some("BUKR-0001-abcd"));
+    assert!(qr.is_none());
+}
+
+#[test]
+fn aim_symbology_prefix_is_stripped() {
+    let (id, _) = parse_scan_input("]C0BUKR-0001-abcd\u{1d}");
+    assert_eq!(id.as_deref(), Some("BUKR-0001-abcd"));
+}
+
+#[test]
+fn signed_qr_json_still_detected() {
+    let (id, qr) = parse_scan_input(r#"{"ticketId":"BUKR-0001-abcd","nonce":"n","sig":"s"}"#);
+    assert_eq!(id.as_deref(), Some("BUKR-0001-abcd"));
+    assert!(qr.is_some());
+}
+
+#[test]
+fn single_use_pass_enters_only_one_session() {
+    let (room_a, room_b) = (Uuid::new_v4(), Uuid::new_v4());
+    assert!(check_session_entry("single", &[], room_a).is_none());
+    assert!(check_session_entry("single", &[room_a], room_b).is_some());
+}
+
+#[test]
+fn multi_use_pass_moves_between_sessions() {
+    let (room_a, room_b) = (Uuid::new_v4(), Uuid::new_v4());
+    assert!(check_session_entry("multi", &[room_a], room_b).is_none());
+    assert!(check_session_entry("multi", &[room_a, room_b], room_a).is_none());
+}
+
+#[test]
+fn capacity_counts_the_whole_group_ticket() {
+    assert!(check_capacity(Some(100), 97, 3).is_none());
+    assert!(check_capacity(Some(100), 98, 3).is_some());
+}
+
+#[test]
+fn no_cap_admits_everyone() {
+    assert!(check_capacity(None, 10_000, 1).is_none());
+}
+
+#[test]
+fn group_ticket_admits_part_then_the_rest() {
+    assert_eq!(admit_seats(5, 0, Some(2)).unwrap(), (2, 3));
+    assert_eq!(admit_seats(5, 2, None).unwrap(), (3, 0));
+    assert_eq!(admit_seats(1, 0, None).unwrap(), (1, 0));
+}
+
+#[test]
+fn cannot_admit_more_seats_than_are_left() {
+    assert!(matches!(admit_seats(5, 4, Some(2)), Err(AppError::Validation(_))));
+    assert!(matches!(admit_seats(5, 0, Some(0)), Err(AppError::Validation(_))));
+}
+
+#[test]
+fn scan_info_timestamps_use_the_api_format() {
+    use chrono::TimeZone;
+    let info = ScanTicketInfo {
+        ticket_id: "BUKR-0001-abcd".into(),
+        user_name: "Ada".into(),
+        ticket_type: "General".into(),
+        quantity: 1,
+        scanned_at: Some(Utc.with_ymd_and_hms(2026, 10, 16, 21, 5, 0).unwrap()),
+        scanned_by: None,
+        scanned_by_name: None,
+        usage_left: None,
+        usage_total: None,
+        admitted: None,
+        remaining: None,
+        add_ons: Vec::new(),
+    };
+    let json = serde_json::to_value(&info).unwrap();
+    assert_eq!(json["scanned_at"], "2026-10-16T21:05:00.000Z");
+}
+
+#[test]
+fn used_ticket_claim_reports_the_winner() {
+    let winner = Uuid::new_v4();
+    let at = Utc::now();
+    assert_eq!(
+        claim_single_use(TicketStatus::Used, Some(winner), Some(at), false),
+        Claim::Lost { scanned_by: Some(winner), scanned_at: Some(at) }
+    );
+    assert_eq!(claim_single_use(TicketStatus::Refunded, None, None, true), Claim::Refused(TicketStatus::Refunded));
+}
+
+#[test]
+fn checked_out_ticket_comes_back_in() {
+    let at = Some(Utc::now());
+    assert_eq!(claim_single_use(TicketStatus::Used, Some(Uuid::new_v4()), at, true), Claim::Reentry);
+    assert_eq!(claim_single_use(TicketStatus::Valid, None, None, true), Claim::Won);
+}
+
+// What each scanner sees once the row lock has ordered them; the lock itself
+// is exercised against a database by concurrent_claims_admit_one_scanner.
+// The second claim reads the row the first one wrote and must name it.
+#[test]
+fn second_claim_sees_the_first_winner() {
+    let first = Uuid::new_v4();
+    assert_eq!(claim_single_use(TicketStatus::Valid, None, None, false), Claim::Won);
+
+    let at = Utc::now();
+    assert_eq!(
+        claim_single_use(TicketStatus::Used, Some(first), Some(at), false),
+        Claim::Lost { scanned_by: Some(first), scanned_at: Some(at) }
+    );
+}
+
+fn queued(ticket_id: &str, secs: i64) -> SyncScan {
+    let t0 = chrono::DateTime::parse_from_rfc3339("2026-10-16T19:00:00Z").unwrap().with_timezone(&Utc);
+    SyncScan {
+        ticket_id: Some(ticket_id.to_string()),
+        qr_data: None,
+        scanned_at: t0 + Duration::seconds(secs),
+        lat: None,
+        lon: None,
+        admit: None,
+    }
+}
+
+#[test]
+fn retries_in_one_batch_collapse_to_the_first_scan() {
+    let batch = vec![queued("BUKR-0001-abcd", 4), queued("BUKR-0001-abcd", 0), queued("BUKR-0001-abcd", 9)];
+    let (kept, collapsed) = dedup_scans(batch, SYNC_DEDUP_WINDOW_SECS);
+    assert_eq!(collapsed, 2);
+    assert_eq!(kept.len(), 1);
+    assert_eq!(kept[0].scanned_at, queued("BUKR-0001-abcd", 0).scanned_at);
+}
+
+#[test]
+fn same_ticket_minutes_apart_is_kept_twice() {
+    let batch = vec![queued("BUKR-0001-abcd", 0), queued("BUKR-0001-abcd", 600)];
+    let (kept, collapsed) = dedup_scans(batch, SYNC_DEDUP_WINDOW_SECS);
+    assert_eq!(collapsed, 0);
+    assert_eq!(kept.len(), 2);
+}
+
+#[test]
+fn different_tickets_at_the_same_moment_are_all_kept() {
+    let batch = vec![queued("BUKR-0001-abcd", 0), queued("BUKR-0002-efgh", 0), queued("BUKR-0001-abcd", 1)];
+    let (kept, collapsed) = dedup_scans(batch, SYNC_DEDUP_WINDOW_SECS);
+    assert_eq!(collapsed, 1);
+    let ids: Vec<_> = kept.iter().map(|s| s.ticket_id.as_deref().unwrap()).collect();
+    assert_eq!(ids, ["BUKR-0001-abcd", "BUKR-0002-efgh"]);
+}
+
+#[test]
+fn qr_and_typed_id_for_one_ticket_dedup_together() {
+    let mut from_qr = queued("", 2);
+    from_qr.ticket_id = None;
+    from_qr.qr_data = Some(r#"{"ticketId":"BUKR-0001-abcd","nonce":"n","sig":"s"}"#.into());
+    let batch = vec![queued("]C0BUKR-0001-abcd", 0), from_qr];
+    let (kept, collapsed) = dedup_scans(batch, SYNC_DEDUP_WINDOW_SECS);
+    assert_eq!((kept.len(), collapsed), (1, 1));
+}
+
+#[test]
+fn unreadable_scans_are_never_collapsed() {
+    let mut blank = queued("", 0);
+    blank.ticket_id = None;
+    let (kept, collapsed) = dedup_scans(vec![blank.clone(), blank], SYNC_DEDUP_WINDOW_SECS);
+    assert_eq!((kept.len(), collapsed), (2, 0));
+}
+
+#[test]
+fn blacklisted_ticket_cannot_be_renewed() {
+    assert!(check_renewable(true, false).is_ok());
+    assert!(matches!(check_renewable(false, false), Err(AppError::BadRequest(_))));
+    assert!(matches!(check_renewable(true, true), Err(AppError::BadRequest(_))));
+}
+
+#[test]
+fn synced_scan_is_judged_at_device_time_within_the_offline_window() {
+    let now = Utc::now();
+    let earlier = now - Duration::minutes(20);
+    assert_eq!(sync_judged_at(earlier, now, MAX_OFFLINE_SCAN_AGE_SECS).unwrap(), earlier);
+    // A clock running ahead is brought back to now
+    assert_eq!(sync_judged_at(now + Duration::minutes(5), now, MAX_OFFLINE_SCAN_AGE_SECS).unwrap(), now);
+}
+
+#[test]
+fn synced_scan_older_than_the_offline_window_is_refused() {
+    let now = Utc::now();
+    let stale = now - Duration::seconds(MAX_OFFLINE_SCAN_AGE_SECS + 1);
+    assert!(matches!(sync_judged_at(stale, now, MAX_OFFLINE_SCAN_AGE_SECS), Err(AppError::Validation(_))));
+}
+
+// Two gates claim each single-use ticket at the same moment; the row lock
+// must let exactly one in. Several tickets per run so a lost lock shows up.
+// Needs a database with the migrations applied:
+//   TEST_DATABASE_URL=postgres://... cargo test -- --ignored
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[ignore = "needs TEST_DATABASE_URL with migrations applied"]
+async fn concurrent_claims_admit_one_scanner() {
+    use std::sync::Arc;
+    use super::service::{ScannerService, ValidateTicketRequest};
+
+    const TICKETS: usize = 20;
+
+    let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL");
+    let pool = sqlx::PgPool::connect(&url).await.unwrap();
+    let tag = Uuid::new_v4().simple().to_string();
+
+    let organizer: Uuid = sqlx::query_scalar(
+        "INSERT INTO users (email, name, user_type) VALUES ($1, 'Organizer', 'organizer') RETURNING id",
+    )
+    .bind(format!("org-{}@test.bukr", tag))
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let event_key = format!("race-{}", &tag[..12]);
+    let event_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO events (organizer_id, title, date, time, location, category, event_key, status)
+         VALUES ($1, 'Race', CURRENT_DATE, '20:00', 'Hall', 'music', $2, 'active') RETURNING id",
+    )
+    .bind(organizer)
+    .bind(&event_key)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    let service = Arc::new(ScannerService::new(pool.clone(), "test-secret".into()));
+    for n in 0..TICKETS {
+        let ticket_id = format!("BUKR-{}-{:02}", &tag[..12], n);
+        sqlx::query(
+            "INSERT INTO tickets (ticket_id, event_id, user_id, unit_price, total_price, qr_code_data)
+             VALUES ($1, $2, $3, 0, 0, $1)",
+        )
+        .bind(&ticket_id)
+        .bind(event_id)
+        .bind(organizer)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let barrier = Arc::new(tokio::sync::Barrier::new(2));
+        let gates: Vec<_> = (0..2).map(|_| {
+            let (service, barrier) = (service.clone(), barrier.clone());
+            let req = ValidateTicketRequest {
+                ticket_id: Some(ticket_id.clone()),
+                event_key: event_key.clone(),
+                qr_data: None,
+                lat: None,
+                lon: None,
+                admit: None,
+            };
+            tokio::spawn(async move {
+                barrier.wait().await;
+                service.validate_ticket(req, None, None).await
+            })
+        }).collect();
+        let mut results = Vec::new();
+        for gate in gates {
+            results.push(gate.await.unwrap().unwrap().result);
+        }
+        results.sort();
+        assert_eq!(results, ["already_used", "valid"], "ticket {}", ticket_id);
+
+        let scanned: i32 = sqlx::query_scalar("SELECT scanned_quantity FROM tickets WHERE ticket_id = $1")
+            .bind(&ticket_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(scanned, 1, "ticket {}", ticket_id);
+    }
+
+    for table in ["scan_log", "tickets", "events"] {
+        let column = if table == "events" { "id" } else { "event_id" };
+        sqlx::query(&format!("DELETE FROM {} WHERE {} = $1", table, column))
+            .bind(event_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+    sqlx::query("DELETE FROM users WHERE id = $1").bind(organizer).execute(&pool).await.unwrap();
+}
@@ -7,6 +7,8 @@ use sqlx::{PgPool, Row};
 use uuid::Uuid;
 
 use crate::error::{AppError, Result};
+use crate::tickets::status::TicketStatus;
+use super::service::ScanSite;
 
 #[derive(Debug)]
 pub enum UsageDecision {
@@ -32,8 +34,9 @@ impl<'a> UsageEngine<'a> {
     }
 
     /// Evaluate what action to take on this scan.
-    /// ticket_db_id is the UUID primary key (not the human-readable ticket_id string).
-    pub async fn evaluate(&self, ticket_db_id: Uuid) -> Result<UsageDecision> {
+    /// ticket_db_id is the UUID primary key (not the human-readable ticket_id string);
+    /// `now` is the scan time the validity window is checked against.
+    pub async fn evaluate(&self, ticket_db_id: Uuid, now: chrono::DateTime<Utc>) -> Result<UsageDecision> {
         let row = sqlx::query(
             "SELECT usage_model, usage_left, is_renewable, valid_from, valid_until
              FROM tickets WHERE id = $1",
@@ -48,7 +51,6 @@ impl<'a> UsageEngine<'a> {
         let is_renewable: bool = row.get("is_renewable");
         let valid_from: Option<chrono::DateTime<Utc>> = row.get("valid_from");
         let valid_until: Option<chrono::DateTime<Utc>> = row.get("valid_until");
-        let now = Utc::now();
 
         // Time-bound check applies to all models
         if let Some(from) = valid_from {
@@ -91,21 +93,25 @@ impl<'a> UsageEngine<'a> {
         event_id: Uuid,
         scanned_by: Option<Uuid>,
         decision: &UsageDecision,
+        site: &ScanSite,
     ) -> Result<i32> {
         match decision {
             UsageDecision::MarkUsed { new_nonce } => {
                 sqlx::query(
-                    "UPDATE tickets SET status='used', scanned_at=NOW(), scanned_by=$2, qr_nonce=$3
-                     WHERE id=$1 AND status='valid'",
+                    "UPDATE tickets SET status=$4, scanned_at=$6, scanned_by=$2, qr_nonce=$3
+                     WHERE id=$1 AND status=$5",
                 )
                 .bind(ticket_db_id)
                 .bind(scanned_by)
                 .bind(new_nonce)
+                .bind(TicketStatus::Used.as_str())
+                .bind(TicketStatus::Valid.as_str())
+                .bind(site.time())
                 .execute(self.pool)
                 .await
                 .map_err(AppError::Database)?;
 
-                self.write_scan_log(ticket_id_str, event_id, scanned_by, "valid", Some(0)).await;
+                self.write_scan_log(ticket_id_str, event_id, scanned_by, "valid", Some(0), site).await;
                 Ok(0)
             }
 
@@ -113,13 +119,17 @@ impl<'a> UsageEngine<'a> {
                 // Fetch usage_left before decrement for usage_events log
                 let before: i32 = usage_left + 1;
 
+                // scanned_at/scanned_by mark the latest use, so it can be undone
                 sqlx::query(
-                    "UPDATE tickets SET usage_left=$2, qr_nonce=$3, updated_at=NOW()
-                     WHERE id=$1 AND status='valid'",
+                    "UPDATE tickets SET usage_left=$2, qr_nonce=$3, scanned_at=$6, scanned_by=$5, updated_at=NOW()
+                     WHERE id=$1 AND status=$4",
                 )
                 .bind(ticket_db_id)
                 .bind(usage_left)
                 .bind(new_nonce)
+                .bind(TicketStatus::Valid.as_str())
+                .bind(scanned_by)
+                .bind(site.time())
                 .execute(self.pool)
                 .await
                 .map_err(AppError::Database)?;
@@ -137,27 +147,29 @@ impl<'a> UsageEngine<'a> {
                 .execute(self.pool)
                 .await;
 
-                self.write_scan_log(ticket_id_str, event_id, scanned_by, "valid", Some(*usage_left)).await;
+                self.write_scan_log(ticket_id_str, event_id, scanned_by, "valid", Some(*usage_left), site).await;
                 Ok(*usage_left)
             }
 
             UsageDecision::NotYetValid => {
-                self.write_scan_log(ticket_id_str, event_id, scanned_by, "invalid", None).await;
+                self.write_scan_log(ticket_id_str, event_id, scanned_by, "invalid", None, site).await;
                 Ok(-1)
             }
 
             UsageDecision::Expired => {
                 // Mark expired in DB so future scans skip the engine entirely
-                let _ = sqlx::query("UPDATE tickets SET status='expired' WHERE id=$1")
+                let _ = sqlx::query("UPDATE tickets SET status=$2 WHERE id=$1 AND status=ANY($3)")
                     .bind(ticket_db_id)
+                    .bind(TicketStatus::Expired.as_str())
+                    .bind(TicketStatus::Expired.sources())
                     .execute(self.pool)
                     .await;
-                self.write_scan_log(ticket_id_str, event_id, scanned_by, "invalid", None).await;
+                self.write_scan_log(ticket_id_str, event_id, scanned_by, "invalid", None, site).await;
                 Ok(-2)
             }
 
             UsageDecision::DepletedRenewable => {
-                self.write_scan_log(ticket_id_str, event_id, scanned_by, "already_used", None).await;
+                self.write_scan_log(ticket_id_str, event_id, scanned_by, "already_used", None, site).await;
                 Ok(-3)
             }
         }
@@ -170,16 +182,24 @@ impl<'a> UsageEngine<'a> {
         scanned_by: Option<Uuid>,
         result: &str,
         usage_left: Option<i32>,
+        site: &ScanSite,
     ) {
         let _ = sqlx::query(
-            "INSERT INTO scan_log (ticket_id, event_id, scanned_by, result, usage_left_after)
-             SELECT t.id, $2, $3, $4, $5 FROM tickets t WHERE t.ticket_id = $1",
+            "INSERT INTO scan_log (ticket_id, event_id, scanned_by, result, usage_left_after, lat, lon, access_code, scanned_at)
+             SELECT t.id, $2, $3, $4, $5, $6, $7,
+                    (SELECT code FROM scanner_access_codes WHERE code = $8 AND event_id = $2),
+                    COALESCE($9, NOW())
+             FROM tickets t WHERE t.ticket_id = $1",
         )
         .bind(ticket_id)
         .bind(event_id)
         .bind(scanned_by)
         .bind(result)
         .bind(usage_left)
+        .bind(site.location.map(|p| p.lat))
+        .bind(site.location.map(|p| p.lon))
+        .bind(site.access_code.as_deref())
+        .bind(site.at)
         .execute(self.pool)
         .await;
     }
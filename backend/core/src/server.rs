@@ -0,0 +1,82 @@
+// HTTP server loop with explicit connection tuning.
+//
+// axum::serve hides the hyper connection builder, so keep-alive, header
+// timeouts and buffer limits can't be set through it. This is the same
+// accept loop with those knobs exposed (HTTP_* env vars, see config.rs).
+//
+// The gateway talks to us over a small pool of long-lived HTTP/1.1
+// connections; keep-alive stays on so a burst of proxied requests doesn't
+// pay a TCP handshake each. HTTP/2 would need hyper's h2 support, which
+// this build doesn't carry — the gateway's Fiber client speaks HTTP/1.1
+// anyway.
+
+use std::time::Duration;
+
+use axum::Router;
+use hyper::server::conn::http1;
+use hyper_util::rt::{TokioIo, TokioTimer};
+use hyper_util::service::TowerToHyperService;
+use tokio::net::TcpListener;
+
+use crate::config::Config;
+
+// hyper rejects a read buffer smaller than this.
+const MIN_BUF_SIZE: usize = 8192;
+
+#[derive(Debug, Clone, Copy)]
+pub struct HttpSettings {
+    pub keep_alive: bool,
+    pub header_read_timeout: Duration,
+    pub max_buf_size: usize,
+}
+
+impl HttpSettings {
+    pub fn from_config(cfg: &Config) -> Self {
+        Self {
+            keep_alive: cfg.http_keep_alive,
+            header_read_timeout: Duration::from_secs(cfg.http_header_read_timeout_secs),
+            max_buf_size: cfg.http_max_buf_size.max(MIN_BUF_SIZE),
+        }
+    }
+
+    fn builder(&self) -> http1::Builder {
+        let mut builder = http1::Builder::new();
+        builder
+            .timer(TokioTimer::new())
+            .keep_alive(self.keep_alive)
+            .header_read_timeout(self.header_read_timeout)
+            .max_buf_size(self.max_buf_size);
+        builder
+    }
+}
+
+/// Accept connections forever, serving each on its own task.
+pub async fn serve(listener: TcpListener, app: Router, settings: HttpSettings) {
+    let builder = settings.builder();
+    tracing::info!(
+        "HTTP/1.1 keep_alive={} header_read_timeout={:?} max_buf_size={}",
+        settings.keep_alive, settings.header_read_timeout, settings.max_buf_size
+    );
+
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                // Usually fd exhaustion — back off instead of spinning.
+                tracing::error!("accept failed: {}", e);
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+        };
+        // Small JSON responses; don't let Nagle hold them back.
+        let _ = stream.set_nodelay(true);
+
+        let service = TowerToHyperService::new(app.clone());
+        let conn = builder.serve_connection(TokioIo::new(stream), service);
+        tokio::spawn(async move {
+            if let Err(e) = conn.await {
+                tracing::debug!("connection from {} closed with error: {}", peer, e);
+            }
+        });
+    }
+}
@@ -0,0 +1,105 @@
+// Code128 barcode rendering — pure, no I/O. The handler owns auth and headers.
+//
+// For venues whose hardware reads linear barcodes only. The bars encode the
+// bare ticket id (Code Set B), which the scanner's raw-id path accepts. A
+// printed barcode can't rotate, so the service refuses it for rotating-QR
+// events rather than hand out something the gate will reject.
+//
+// The encoder lives here instead of pulling in a barcode crate: Code128-B is
+// a lookup table and a checksum.
+
+use super::qr_image::{draw_watermark, set_pixel};
+
+const DEFAULT_MODULE_PX: u32 = 2;
+pub const MAX_MODULE_PX: u32 = 8;
+const QUIET_ZONE: u32 = 10; // modules of white either side, per the spec
+const BAR_HEIGHT_MODULES: u32 = 40;
+
+const START_B: usize = 104;
+const STOP: usize = 106;
+
+// Bar/space widths for every Code128 symbol value, bar first.
+// Each symbol is 11 modules wide; STOP is 13 (it carries the final bar).
+const PATTERNS: [&str; 107] = [
+    "212222", "222122", "222221", "121223", "121322", "131222", "122213", "122312", "132212", "221213",
+    "221312", "231212", "112232", "122132", "122231", "113222", "123122", "123221", "223211", "221132",
+    "221231", "213212", "223112", "312131", "311222", "321122", "321221", "312212", "322112", "322211",
+    "212123", "212321", "232121", "111323", "131123", "131321", "112313", "132113", "132311", "211313",
+    "231113", "231311", "112133", "112331", "132131", "113123", "113321", "133121", "313121", "211331",
+    "231131", "213113", "213311", "213131", "311123", "311321", "331121", "312113", "312311", "332111",
+    "314111", "221411", "431111", "111224", "111422", "121124", "121421", "141122", "141221", "112214",
+    "112412", "122114", "122411", "142112", "142211", "241211", "221114", "413111", "241112", "134111",
+    "111242", "121142", "121241", "114212", "124112", "124211", "411212", "421112", "421211", "212141",
+    "214121", "412121", "111143", "111341", "131141", "114113", "114311", "411113", "411311", "113141",
+    "114131", "311141", "411131", "211412", "211214", "211232", "2331112",
+];
+
+/// Clamp a requested module (narrowest bar) width in px. Missing → default;
+/// out-of-range values are pulled in, same as the QR size.
+pub fn clamp_module_px(requested: Option<u32>) -> u32 {
+    requested.unwrap_or(DEFAULT_MODULE_PX).clamp(1, MAX_MODULE_PX)
+}
+
+/// Encode `text` as Code128 Set B: start, data, mod-103 checksum, stop.
+/// Returns one bool per module, `true` for a bar. Only printable ASCII fits
+/// Set B, which covers every ticket id we issue.
+pub fn encode(text: &str) -> Result<Vec<bool>, String> {
+    if text.is_empty() {
+        return Err("nothing to encode".into());
+    }
+
+    let mut symbols = Vec::with_capacity(text.len() + 3);
+    symbols.push(START_B);
+    for ch in text.chars() {
+        if !(' '..='~').contains(&ch) {
+            return Err(format!("character {:?} is not encodable in Code128-B", ch));
+        }
+        symbols.push(ch as usize - 32);
+    }
+    let checksum = symbols.iter().enumerate()
+        .map(|(i, &v)| if i == 0 { v } else { v * i })
+        .sum::<usize>() % 103;
+    symbols.push(checksum);
+    symbols.push(STOP);
+
+    let mut modules = Vec::with_capacity(symbols.len() * 11 + 2);
+    for sym in symbols {
+        for (i, w) in PATTERNS[sym].bytes().enumerate() {
+            let bar = i % 2 == 0;
+            modules.extend(std::iter::repeat_n(bar, (w - b'0') as usize));
+        }
+    }
+    Ok(modules)
+}
+
+/// Render `text` as an RGB PNG strip, `module_px` pixels per module.
+pub fn render_png(text: &str, module_px: u32, cancelled: bool) -> Result<Vec<u8>, String> {
+    let modules = encode(text)?;
+    let total = modules.len() as u32 + QUIET_ZONE * 2;
+    let width = total * module_px;
+    let height = BAR_HEIGHT_MODULES * module_px;
+
+    let mut pixels = vec![255u8; (width * height * 3) as usize];
+    for (m, _) in modules.iter().enumerate().filter(|(_, &bar)| bar) {
+        let x0 = (QUIET_ZONE + m as u32) * module_px;
+        for y in 0..height {
+            for x in x0..x0 + module_px {
+                set_pixel(&mut pixels, width, x, y, [0, 0, 0]);
+            }
+        }
+    }
+
+    if cancelled {
+        draw_watermark(&mut pixels, width, height);
+    }
+
+    let mut out = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut out, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+        writer.write_image_data(&pixels).map_err(|e| e.to_string())?;
+    }
+    Ok(out)
+}
@@ -0,0 +1,25 @@
+// Checkout expiry — a purchase reserves its seats before the buyer pays, so
+// a checkout the buyer walks away from would hold them for good. The sweep
+// cancels pending tickets past their TTL and puts everything they held back.
+//
+// TicketService::release_stale_pending does the work; this module drives the
+// schedule. PENDING_SWEEP_INTERVAL_SECS=0 turns it off.
+
+use std::sync::Arc;
+
+use super::service::TicketService;
+
+/// Periodically release pending tickets older than `ttl_minutes`.
+pub fn spawn_pending_sweep(service: Arc<TicketService>, interval_secs: u64, ttl_minutes: i64) {
+    tokio::spawn(async move {
+        let mut tick = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+        loop {
+            tick.tick().await;
+            match service.release_stale_pending(ttl_minutes).await {
+                Ok(0) => {}
+                Ok(n) => tracing::info!("Released {} abandoned pending tickets", n),
+                Err(e) => tracing::error!("Pending ticket sweep failed: {}", e),
+            }
+        }
+    });
+}
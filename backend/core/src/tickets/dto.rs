@@ -11,6 +11,8 @@
  * Think of DTOs as the diplomatic translators between layers
  */
 
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -33,13 +35,186 @@ pub struct PurchaseTicketRequest {
     pub excitement_rating: Option<i32>,
     pub payment_provider: String,
     pub referral_code: Option<String>,
-    pub idempotency_key: Option<String>,  // NEW: Prevent duplicate purchases
+    #[serde(alias = "intent_id")]
+    pub idempotency_key: Option<String>,  // Client purchase intent — repeats return the original purchase
     // Advanced ticket model fields — all optional, default to single-use
     pub usage_model: Option<String>,   // "single"|"multi"|"consumable"|"time_bound"|"renewable"
     pub usage_total: Option<i32>,      // e.g. 4 for "4 PS5 sessions"
     pub valid_from: Option<String>,    // ISO8601 datetime string
     pub valid_until: Option<String>,   // ISO8601 datetime string
     pub is_renewable: Option<bool>,
+    #[serde(default)]
+    pub add_ons: Vec<AddOnSelection>,  // Extras bought with the ticket (parking, merch)
+    pub chosen_price: Option<Decimal>, // Pay-what-you-want events only: per-ticket price, >= event min_price
+    // Guest checkout only (POST /tickets/guest-purchase) — ignored on /purchase
+    pub guest_email: Option<String>,   // Buyer's email; the ticket lands on that account
+    pub callback_url: Option<String>,  // Where the provider sends the guest after paying
+}
+
+/**
+ * AddOnSelection: One add-on line in a purchase
+ */
+#[derive(Debug, Deserialize)]
+pub struct AddOnSelection {
+    pub add_on_id: Uuid,
+    pub quantity: i32,
+}
+
+/**
+ * CreateAddOnRequest: Organizer adds an extra to an event
+ */
+#[derive(Debug, Deserialize)]
+pub struct CreateAddOnRequest {
+    pub name: String,
+    pub price: Decimal,
+    pub inventory: Option<i32>,              // None = unlimited
+}
+
+/**
+ * BulkCancelRequest: Support cancels a buyer's whole order for an event
+ *
+ * Admin-only. Every non-used ticket the user holds for the event is cancelled.
+ */
+#[derive(Debug, Deserialize)]
+pub struct BulkCancelRequest {
+    pub user_id: Uuid,
+    pub event_id: Uuid,
+}
+
+/**
+ * ReassignTicketRequest: Support corrects a mistyped buyer email
+ *
+ * Admin-only. Not a transfer - the original buyer never had a usable account.
+ */
+#[derive(Debug, Deserialize)]
+pub struct ReassignTicketRequest {
+    pub email: String,
+}
+
+/**
+ * BlacklistTicketRequest: Staff kill a ticket known to be fraudulent
+ *
+ * Admin-only. The reason is shown to door staff when the ticket is scanned.
+ */
+#[derive(Debug, Deserialize)]
+pub struct BlacklistTicketRequest {
+    pub reason: String,                      // e.g. "chargeback", "QR posted online"
+}
+
+/**
+ * SetHoldRequest: Organizer reserves a block of inventory off public sale
+ *
+ * Absolute value, not a delta - 0 releases the whole hold back to the public.
+ */
+#[derive(Debug, Deserialize)]
+pub struct SetHoldRequest {
+    pub held_tickets: i32,
+}
+
+/**
+ * SetRefundDeadlineRequest: Organizer sets the last moment refunds are allowed
+ *
+ * null clears it - refunds are then allowed until the event starts.
+ */
+#[derive(Debug, Deserialize)]
+pub struct SetRefundDeadlineRequest {
+    pub refund_deadline: Option<DateTime<Utc>>,
+}
+
+/**
+ * SetLowStockThresholdRequest: Alert the organizer when stock runs low
+ *
+ * null turns the alert off. Saving re-arms an alert that already fired.
+ */
+#[derive(Debug, Deserialize)]
+pub struct SetLowStockThresholdRequest {
+    pub low_stock_threshold: Option<i32>,
+}
+
+/**
+ * SetDefaultTicketTypeRequest: Tier name used when a purchase names none
+ */
+#[derive(Debug, Deserialize)]
+pub struct SetDefaultTicketTypeRequest {
+    pub default_ticket_type: String,
+}
+
+/**
+ * SetOverbookRequest: Organizer allows selling N seats past total_tickets
+ *
+ * 0 turns overbooking off.
+ */
+#[derive(Debug, Deserialize)]
+pub struct SetOverbookRequest {
+    pub overbook_allowance: i32,
+}
+
+/**
+ * SetMaxInsideRequest: Organizer caps how many people may be inside at once
+ *
+ * null removes the cap.
+ */
+#[derive(Debug, Deserialize)]
+pub struct SetMaxInsideRequest {
+    pub max_concurrent_inside: Option<i32>,
+}
+
+/**
+ * SetSelfCancelDeadlineRequest: Organizer sets how late buyers may cancel themselves
+ *
+ * null clears it - self-cancel is then open until the event starts.
+ */
+#[derive(Debug, Deserialize)]
+pub struct SetSelfCancelDeadlineRequest {
+    pub self_cancel_deadline: Option<DateTime<Utc>>,
+}
+
+/**
+ * IssueCompRequest: Organizer issues a comp ticket from the held block
+ */
+#[derive(Debug, Deserialize)]
+pub struct IssueCompRequest {
+    pub email: String,
+}
+
+/**
+ * QrImageQuery: Options for the QR PNG endpoint
+ *
+ * Out-of-range sizes are clamped by the service, not rejected.
+ */
+#[derive(Debug, Deserialize)]
+pub struct QrImageQuery {
+    pub size: Option<u32>,                   // Edge length in px
+}
+
+/**
+ * BarcodeImageQuery: Options for the Code128 PNG endpoint
+ *
+ * Clamped by the service like QrImageQuery.size.
+ */
+#[derive(Debug, Deserialize)]
+pub struct BarcodeImageQuery {
+    pub module: Option<u32>,                 // Narrowest bar width in px
+}
+
+/**
+ * QrBatchQuery: ?page=&size= for GET /tickets/event/{id}/qr-batch.zip
+ *
+ * Pages are QR_BATCH_PAGE_SIZE tickets; size is clamped like QrImageQuery.
+ */
+#[derive(Debug, Deserialize)]
+pub struct QrBatchQuery {
+    pub page: Option<u32>,                   // 1-based
+    pub size: Option<u32>,                   // Edge length in px
+}
+
+/**
+ * TicketTypesQuery: Filters for the tier selector
+ */
+#[derive(Debug, Deserialize)]
+pub struct TicketTypesQuery {
+    #[serde(default)]
+    pub available_only: bool,                // Hide sold-out and inactive tiers
 }
 
 // RESPONSE DTOs - What goes OUT to the client
@@ -56,21 +231,35 @@ pub struct TicketResponse {
     pub ticket_id: String,                   // Human-readable ID (BUKR-1234-abc)
     pub event_id: Uuid,                      // Which event is this for?
     pub event_title: String,                 // Event name (denormalized for convenience)
-    pub event_date: String,                  // When's the party?
-    pub event_time: String,                  // What time?
+    pub event_date: String,                  // When's the party? (event's timezone)
+    pub event_time: String,                  // What time? (event's timezone)
+    #[serde(with = "crate::timestamp::option")]
+    pub event_starts_at: Option<DateTime<Utc>>, // Same moment in UTC
+    pub event_timezone: String,              // IANA name, e.g. Africa/Lagos
     pub event_location: String,              // Where's the party?
+    pub event_emoji: Option<String>,         // Branding for the post-purchase screen
+    pub event_image_url: Option<String>,     // events.thumbnail_url
     pub ticket_type: String,                 // What kind of ticket?
     pub quantity: i32,                       // How many tickets?
     pub usage_limit: i32,                    // NEW: Max number of uses (e.g. 4 for PS5)
     pub usage_count: i32,                    // NEW: Current number of uses
+    #[serde(with = "rust_decimal::serde::str")]
     pub unit_price: Decimal,                 // Price per ticket
     pub discount_applied: Decimal,           // Discount percentage (0-100)
+    #[serde(with = "rust_decimal::serde::str")]
     pub total_price: Decimal,                // Final price after discount
+    pub total_price_formatted: String,       // Display string, e.g. "₦15,000.00"
     pub currency: String,                    // NGN, USD, etc
     pub status: String,                      // valid, used, expired, cancelled
     pub qr_code_data: String,                // JSON payload for QR code
+    #[serde(with = "crate::timestamp::option")]
     pub valid_from: Option<DateTime<Utc>>,   // NEW: When the ticket starts being valid
+    #[serde(with = "crate::timestamp::option")]
     pub valid_until: Option<DateTime<Utc>>,  // NEW: When the ticket expires
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt_number: Option<String>,      // Finance-facing receipt, e.g. INV-0042
+    pub order_id: Uuid,                      // GET /tickets/order/{order_id} lists the whole order
+    #[serde(with = "crate::timestamp")]
     pub purchase_date: DateTime<Utc>,        // When did you buy this?
 }
 
@@ -89,12 +278,16 @@ pub struct TicketWithEventResponse {
     pub quantity: i32,
     pub usage_limit: i32,
     pub usage_count: i32,
+    #[serde(with = "rust_decimal::serde::str")]
     pub total_price: Decimal,
     pub currency: String,
     pub status: String,
     pub qr_code_data: String,
+    #[serde(with = "crate::timestamp::option")]
     pub valid_from: Option<DateTime<Utc>>,
+    #[serde(with = "crate::timestamp::option")]
     pub valid_until: Option<DateTime<Utc>>,
+    #[serde(with = "crate::timestamp")]
     pub purchase_date: DateTime<Utc>,
 }
 
@@ -125,6 +318,51 @@ pub struct TicketEventInfo {
 pub struct PurchaseResponse {
     pub ticket: TicketResponse,              // The ticket you're buying
     pub payment: PaymentInitResponse,        // Where to pay
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub add_ons: Vec<TicketAddOn>,           // Extras included in payment.amount
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub promo_split: Option<crate::promos::dto::PromoSplit>, // Promo covered only part of the order
+}
+
+/**
+ * AddOnResponse: An extra on sale for an event
+ */
+#[derive(Debug, Serialize)]
+pub struct AddOnResponse {
+    pub id: Uuid,
+    pub event_id: Uuid,
+    pub name: String,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub price: Decimal,
+    pub inventory: Option<i32>,              // Left to sell; None = unlimited
+    pub is_active: bool,
+}
+
+/**
+ * TicketTypeResponse: One purchasable tier of an event
+ */
+#[derive(Debug, Serialize)]
+pub struct TicketTypeResponse {
+    pub name: String,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub price: Decimal,
+    pub currency: String,
+    pub capacity: i32,                       // Seats in this tier
+    pub sold: i32,
+    pub remaining: i32,                      // Purchasable right now: hold excluded, overbook allowance included
+    pub is_active: bool,                     // Event on sale
+}
+
+/**
+ * TicketAddOn: An add-on line attached to a ticket, at the price charged
+ */
+#[derive(Debug, Clone, Serialize)]
+pub struct TicketAddOn {
+    pub add_on_id: Uuid,
+    pub name: String,
+    pub quantity: i32,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub unit_price: Decimal,
 }
 
 /**
@@ -140,13 +378,247 @@ pub struct PaymentInitResponse {
     pub authorization_url: Option<String>,   // Paystack uses this
     pub checkout_url: Option<String>,        // Stripe uses this
     pub reference: String,                   // Unique payment reference
-    pub amount: Decimal,                     // Attendee pays this (full ticket price)
+    #[serde(with = "rust_decimal::serde::str")]
+    pub amount: Decimal,                     // Attendee pays this (full ticket price + booking fee)
+    pub amount_formatted: String,            // Display string, e.g. "₦15,000.00"
+    #[serde(with = "rust_decimal::serde::str")]
+    pub booking_fee: Decimal,                // Per-order "pct% + flat" booking fee (already in amount)
+    #[serde(with = "rust_decimal::serde::str")]
+    pub tax_amount: Decimal,                 // Event tax (already in amount, whether inclusive or exclusive)
     pub currency: String,                    // In what currency
+    #[serde(with = "rust_decimal::serde::str")]
     pub platform_fee: Decimal,               // Bukr's 2% cut (deducted from organizer)
+    #[serde(with = "rust_decimal::serde::str")]
     pub bukrshield_fee: Decimal,             // ₦100/ticket fraud protection (deducted from organizer)
+    #[serde(with = "rust_decimal::serde::str")]
     pub organizer_payout: Decimal,           // What organizer actually receives
 }
 
+/**
+ * BulkCancelResponse: What a bulk cancellation actually did
+ *
+ * Counts only - support needs totals for the dispute record, not ticket bodies
+ */
+#[derive(Debug, Serialize)]
+pub struct BulkCancelResponse {
+    pub cancelled_tickets: i64,              // Ticket rows flipped to cancelled
+    pub restored_inventory: i64,             // Seats returned to the event (sum of quantity)
+    pub refunds_enqueued: i64,               // Paid tickets with a refund request in the outbox
+}
+
+/**
+ * CancelPreviewResponse: What cancelling the event would do, before doing it
+ *
+ * Live tickets (valid, pending) would be cancelled and their seats released;
+ * the paid ones among them would each get a refund.
+ */
+#[derive(Debug, Serialize)]
+pub struct CancelPreviewResponse {
+    pub event_id: Uuid,
+    pub tickets_by_status: BTreeMap<String, i64>, // Every ticket row, by current status
+    pub tickets_cancelled: i64,              // Live tickets the cancel would flip
+    pub seats_released: i64,                 // Sum of their quantity
+    pub refunds: i64,                        // Paid tickets among them
+    #[serde(with = "rust_decimal::serde::str")]
+    pub refund_total: Decimal,               // What those refunds add up to, in `currency`
+    pub currency: String,
+}
+
+/**
+ * ReassignTicketResponse: Where the ticket landed after a support correction
+ */
+#[derive(Debug, Serialize)]
+pub struct ReassignTicketResponse {
+    pub ticket_id: String,
+    pub user_id: Uuid,                       // New owner
+    pub email: String,                       // Normalized corrected email
+    pub account_created: bool,               // true = placeholder account, claimable via password reset
+    pub qr_code_data: String,                // Old QR is dead - frontend should show this one
+}
+
+/**
+ * BlacklistEntry: A ticket that fails every scan until removed
+ */
+#[derive(Debug, Serialize)]
+pub struct BlacklistEntry {
+    pub ticket_id: String,
+    pub reason: String,
+    pub blacklisted_by: Option<Uuid>,
+    #[serde(with = "crate::timestamp")]
+    pub blacklisted_at: DateTime<Utc>,
+}
+
+/**
+ * HoldResponse: Event inventory after a hold change
+ */
+#[derive(Debug, Serialize)]
+pub struct HoldResponse {
+    pub event_id: Uuid,
+    pub held_tickets: i32,
+    pub available_tickets: i32,              // Includes the held block
+    pub public_available: i32,               // What public buyers can still take
+}
+
+/**
+ * RefundDeadlineResponse: The refund window now in effect
+ */
+#[derive(Debug, Serialize)]
+pub struct RefundDeadlineResponse {
+    pub event_id: Uuid,
+    #[serde(with = "crate::timestamp")]
+    pub refund_deadline: DateTime<Utc>,      // Effective deadline (event start if unset)
+    #[serde(with = "crate::timestamp")]
+    pub event_starts_at: DateTime<Utc>,
+}
+
+/**
+ * LowStockThresholdResponse: The low-stock alert now in effect
+ */
+#[derive(Debug, Serialize)]
+pub struct LowStockThresholdResponse {
+    pub event_id: Uuid,
+    pub low_stock_threshold: Option<i32>,    // None = alert off
+    pub public_available: i32,               // Where stock stands right now
+}
+
+/**
+ * DefaultTicketTypeResponse: The fallback tier name now in effect
+ */
+#[derive(Debug, Serialize)]
+pub struct DefaultTicketTypeResponse {
+    pub event_id: Uuid,
+    pub default_ticket_type: String,
+}
+
+/**
+ * SetEventStatusRequest: Organizer moves the event along its lifecycle
+ *
+ * draft, active, completed or cancelled; see tickets/event_status.rs.
+ */
+#[derive(Debug, Deserialize)]
+pub struct SetEventStatusRequest {
+    pub status: String,
+}
+
+/**
+ * EventStatusResponse: The lifecycle move that was applied
+ */
+#[derive(Debug, Serialize)]
+pub struct EventStatusResponse {
+    pub event_id: Uuid,
+    pub previous_status: String,
+    pub status: String,
+    pub tickets_cancelled: i64,              // Live tickets cancelled with the event (0 otherwise)
+    pub refunds_enqueued: i64,               // Paid ones among them, refund queued via the outbox
+}
+
+/**
+ * PiiScrubResponse: What a retention scrub removed
+ */
+#[derive(Debug, Serialize)]
+pub struct PiiScrubResponse {
+    pub event_id: Uuid,
+    pub tickets_scrubbed: u64,
+    pub scans_scrubbed: u64,
+    #[serde(with = "crate::timestamp")]
+    pub scrubbed_at: chrono::DateTime<chrono::Utc>,
+}
+
+/**
+ * OverbookResponse: Nominal vs effective capacity after an overbook change
+ */
+#[derive(Debug, Serialize)]
+pub struct OverbookResponse {
+    pub event_id: Uuid,
+    pub total_tickets: i32,                  // Nominal capacity
+    pub overbook_allowance: i32,
+    pub effective_capacity: i32,             // total_tickets + overbook_allowance
+}
+
+/**
+ * MaxInsideResponse: The door headcount cap now in effect
+ */
+#[derive(Debug, Serialize)]
+pub struct MaxInsideResponse {
+    pub event_id: Uuid,
+    pub max_concurrent_inside: Option<i32>,  // None = no cap
+}
+
+/**
+ * SelfCancelDeadlineResponse: The self-cancel window now in effect
+ */
+#[derive(Debug, Serialize)]
+pub struct SelfCancelDeadlineResponse {
+    pub event_id: Uuid,
+    #[serde(with = "crate::timestamp")]
+    pub self_cancel_deadline: DateTime<Utc>, // Effective deadline (event start if unset)
+    #[serde(with = "crate::timestamp")]
+    pub event_starts_at: DateTime<Utc>,
+}
+
+/**
+ * ResendConfirmationResponse: A fresh confirmation was queued for delivery
+ */
+#[derive(Debug, Serialize)]
+pub struct ResendConfirmationResponse {
+    pub ticket_id: String,
+    #[serde(with = "crate::timestamp")]
+    pub queued_at: DateTime<Utc>,            // Next resend allowed a minute after this
+}
+
+/**
+ * CancelTicketResponse: Result of a buyer cancelling their own ticket
+ */
+#[derive(Debug, Serialize)]
+pub struct CancelTicketResponse {
+    pub ticket_id: String,
+    pub restored_inventory: i64,
+    pub refund_requested: bool,              // Paid ticket — refund queued via the outbox
+}
+
+/**
+ * CompTicketResponse: A comp ticket issued from the organizer hold
+ */
+#[derive(Debug, Serialize)]
+pub struct CompTicketResponse {
+    pub ticket_id: String,
+    pub user_id: Uuid,                       // Recipient
+    pub email: String,                       // Normalized recipient email
+    pub account_created: bool,               // true = placeholder account, claimable via password reset
+    pub held_tickets_remaining: i32,
+}
+
+/**
+ * OrderTicketsResponse: Every ticket in one checkout, for handing out to a group
+ */
+#[derive(Debug, Serialize)]
+pub struct OrderTicketsResponse {
+    pub order_id: Uuid,
+    pub tickets: Vec<OrderTicket>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrderTicket {
+    pub ticket_id: String,
+    pub ticket_type: String,
+    pub quantity: i32,
+    pub status: String,
+    pub qr_data: Option<String>,             // Signed dynamic payload; None until the ticket is valid
+}
+
+/**
+ * UserTicket: A ticket in the my-tickets list, with its event's branding
+ *
+ * Flattened so the list keeps its existing shape and only gains fields.
+ */
+#[derive(Debug, Serialize)]
+pub struct UserTicket {
+    #[serde(flatten)]
+    pub ticket: Ticket,
+    pub event_emoji: Option<String>,
+    pub event_image_url: Option<String>,     // events.thumbnail_url
+}
+
 // INTERNAL MODEL - What lives in the database
 
 /**
@@ -167,20 +639,33 @@ pub struct Ticket {
     pub quantity: i32,                       // Number of tickets
     pub usage_limit: i32,                    // NEW
     pub usage_count: i32,                    // NEW
+    #[serde(with = "rust_decimal::serde::str")]
     pub unit_price: Decimal,                 // Price per ticket
+    #[serde(with = "rust_decimal::serde::str")]
     pub total_price: Decimal,                // Total paid
     pub discount_applied: Decimal,           // Discount percentage
     pub promo_code_id: Option<Uuid>,         // Which promo was used?
     pub currency: String,                    // Currency code
     pub status: String,                      // Ticket status
     pub qr_code_data: String,                // QR code payload
+    #[serde(with = "crate::timestamp::option")]
     pub valid_from: Option<DateTime<Utc>>,   // NEW
+    #[serde(with = "crate::timestamp::option")]
     pub valid_until: Option<DateTime<Utc>>,  // NEW
     pub payment_ref: Option<String>,         // Payment reference
     pub payment_provider: Option<String>,    // Which provider
     pub idempotency_key: Option<String>,     // NEW: Prevent duplicates
+    #[serde(with = "rust_decimal::serde::str")]
+    pub booking_fee: Decimal,                // Resolved "pct% + flat" fee, included in total_price
+    #[serde(with = "rust_decimal::serde::str")]
+    pub tax_amount: Decimal,                 // Event tax (VAT) portion of total_price
+    pub receipt_number: Option<String>,      // INV-0001 per event; None for free/comp tickets
+    pub order_id: Uuid,                      // Checkout this ticket was created by
     pub excitement_rating: Option<i32>,      // User's hype level
+    #[serde(with = "crate::timestamp::option")]
     pub scanned_at: Option<DateTime<Utc>>,   // When was it scanned?
+    #[serde(with = "crate::timestamp")]
     pub purchase_date: DateTime<Utc>,        // When was it bought?
+    #[serde(with = "crate::timestamp")]
     pub created_at: DateTime<Utc>,           // Database timestamp
 }
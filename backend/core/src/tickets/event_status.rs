@@ -0,0 +1,75 @@
+// EventStatus — the event lifecycle the ticket flows depend on.
+//
+// Same shape as TicketStatus: the column stays VARCHAR (the CHECK in
+// 002_create_events.sql lists the same four values) and code parses it.
+//
+// Lifecycle:
+//   draft     → active (published) | cancelled
+//   active    → completed (closed) | cancelled
+//   cancelled → active (reinstated) — only while no ticket has been refunded
+//   completed   terminal
+//
+// Reinstating is for an organizer who cancelled by mistake. Once money has
+// gone back to buyers, bringing the event back would leave refunded tickets
+// pointing at a live event, so the service refuses it.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::AppError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventStatus {
+    Draft,
+    Active,
+    Completed,
+    Cancelled,
+}
+
+impl EventStatus {
+    pub const ALL: [EventStatus; 4] = [Self::Draft, Self::Active, Self::Completed, Self::Cancelled];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Draft => "draft",
+            Self::Active => "active",
+            Self::Completed => "completed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    /// Legal lifecycle moves, ignoring the refund rule on reinstating.
+    /// Same-status "transitions" are not legal.
+    pub fn can_transition_to(self, next: EventStatus) -> bool {
+        use EventStatus::*;
+        matches!(
+            (self, next),
+            (Draft, Active | Cancelled)
+                | (Active, Completed | Cancelled)
+                | (Cancelled, Active)
+        )
+    }
+
+    /// Parse a value read from events.status. Unknown means the schema and
+    /// this enum disagree — a server fault, not bad input.
+    pub fn from_db(s: &str) -> Result<Self, AppError> {
+        s.parse().map_err(AppError::Internal)
+    }
+}
+
+impl fmt::Display for EventStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EventStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|st| st.as_str() == s)
+            .ok_or_else(|| format!("unknown event status '{}'", s))
+    }
+}
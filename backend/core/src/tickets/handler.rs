@@ -11,16 +11,18 @@
  */
 
 use axum::{
-    extract::{Path, State},
-    http::HeaderMap,
-    Json,
+    extract::State,
+    http::{header, HeaderMap},
+    response::{IntoResponse, Response},
 };
 use serde_json::{json, Value};
 use uuid::Uuid;
 
 use crate::error::{AppError, Result};
-use super::dto::PurchaseTicketRequest;
+use crate::extract::{Json, Path, Query};
+use super::dto::{BlacklistTicketRequest, BulkCancelRequest, CreateAddOnRequest, IssueCompRequest, SetDefaultTicketTypeRequest, SetEventStatusRequest, SetLowStockThresholdRequest, SetMaxInsideRequest, SetOverbookRequest, SetRefundDeadlineRequest, SetSelfCancelDeadlineRequest, PurchaseTicketRequest, BarcodeImageQuery, QrBatchQuery, QrImageQuery, ReassignTicketRequest, SetHoldRequest, TicketTypesQuery};
 use super::service::TicketService;
+use crate::payments::service::{InitializePaymentRequest, PaymentService};
 use std::sync::Arc;
 
 /**
@@ -40,6 +42,27 @@ fn extract_user_id(headers: &HeaderMap) -> Result<Uuid> {
         .ok_or(AppError::Unauthorized)       // If any step fails, user is sus
 }
 
+/**
+ * Require an admin caller
+ * 
+ * The gateway only forwards admin-token requests with X-User-Type: admin,
+ * so a regular user JWT can never reach these handlers with that value.
+ * 
+ * @param headers - HTTP headers from the request
+ * @returns Uuid of the admin, Unauthorized if missing, Forbidden if not admin
+ */
+fn require_admin(headers: &HeaderMap) -> Result<Uuid> {
+    let admin_id = extract_user_id(headers)?;
+    let user_type = headers
+        .get("x-user-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    if user_type != "admin" {
+        return Err(AppError::Forbidden);
+    }
+    Ok(admin_id)
+}
+
 /**
  * POST /api/v1/tickets/purchase
  * 
@@ -73,6 +96,54 @@ pub async fn purchase_ticket(
     })))
 }
 
+/**
+ * POST /api/v1/tickets/guest-purchase
+ * 
+ * Buy without an account - no x-user-id required
+ * 
+ * The body is a normal purchase plus guest_email and callback_url. A guest
+ * can't call /payments/initialize afterwards, so the payment is initialized
+ * here and the response carries the provider's real checkout URL. If that
+ * fails the ticket is cancelled and its seats restocked.
+ * 
+ * @param service - Ticket service instance
+ * @param payments - Payment service instance
+ * @param req - Purchase request with guest_email and callback_url
+ * @returns JSON response with ticket and initialized payment
+ */
+pub async fn guest_purchase(
+    State(service): State<Arc<TicketService>>,
+    State(payments): State<Arc<PaymentService>>,
+    Json(req): Json<PurchaseTicketRequest>,
+) -> Result<Json<Value>> {
+    let callback_url = req.callback_url.clone()
+        .ok_or_else(|| AppError::Validation("callback_url is required for guest checkout".into()))?;
+    let provider = req.payment_provider.clone();
+
+    let (user_id, result) = service.guest_purchase(req).await?;
+    let payment = match payments.initialize(user_id, InitializePaymentRequest {
+        ticket_id: result.ticket.id,
+        provider,
+        callback_url,
+        amount: None,
+    }).await {
+        Ok(payment) => payment,
+        Err(e) => {
+            // Nobody can pay for this ticket — a guest can't retry the
+            // initialize — so give its seats back before reporting
+            if let Err(release) = service.release_unpaid(result.ticket.event_id, result.ticket.id).await {
+                tracing::error!("Guest ticket {} not released: {}", result.ticket.ticket_id, release);
+            }
+            return Err(e);
+        }
+    };
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": { "ticket": result.ticket, "payment": payment, "add_ons": result.add_ons }
+    })))
+}
+
 /**
  * GET /api/v1/tickets/me
  * 
@@ -99,6 +170,32 @@ pub async fn get_my_tickets(
     })))
 }
 
+/**
+ * GET /api/v1/tickets/order/:order_id
+ * 
+ * Get every ticket in one order, each with its own QR payload
+ * 
+ * Owner-only; someone else's order is 404.
+ * 
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with user auth
+ * @param order_id - Order UUID from path
+ * @returns JSON with the order's tickets
+ */
+pub async fn get_order_tickets(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Path(order_id): Path<Uuid>,
+) -> Result<Json<Value>> {
+    let user_id = extract_user_id(&headers)?;
+    let order = service.get_order_tickets(order_id, user_id).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": order
+    })))
+}
+
 /**
  * GET /api/v1/tickets/event/:event_id
  * 
@@ -128,6 +225,382 @@ pub async fn get_event_tickets(
     })))
 }
 
+/**
+ * PUT /api/v1/tickets/event/:event_id/hold
+ * 
+ * Reserve a block of tickets off public sale (sponsors, press)
+ * 
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with user auth (event owner)
+ * @param event_id - UUID of the event from URL path
+ * @param req - New absolute hold
+ * @returns JSON with held / available / public_available
+ */
+pub async fn set_event_hold(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Path(event_id): Path<Uuid>,
+    Json(req): Json<SetHoldRequest>,
+) -> Result<Json<Value>> {
+    let user_id = extract_user_id(&headers)?;
+    let result = service.set_hold(user_id, event_id, req.held_tickets).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": result
+    })))
+}
+
+/**
+ * PUT /api/v1/tickets/event/:event_id/refund-deadline
+ * 
+ * Set the last moment refunds are allowed for the event (null = event start)
+ * 
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with user auth (event owner)
+ * @param event_id - UUID of the event from URL path
+ * @param req - New deadline or null
+ * @returns JSON with the effective refund deadline
+ */
+pub async fn set_refund_deadline(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Path(event_id): Path<Uuid>,
+    Json(req): Json<SetRefundDeadlineRequest>,
+) -> Result<Json<Value>> {
+    let user_id = extract_user_id(&headers)?;
+    let result = service.set_refund_deadline(user_id, event_id, req.refund_deadline).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": result
+    })))
+}
+
+/**
+ * PUT /api/v1/tickets/event/:event_id/max-inside
+ * 
+ * Cap how many people the door lets in at once (null = no cap)
+ * 
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with user auth (event owner)
+ * @param event_id - UUID of the event from URL path
+ * @param req - New cap
+ * @returns JSON with the cap in effect
+ */
+pub async fn set_max_concurrent_inside(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Path(event_id): Path<Uuid>,
+    Json(req): Json<SetMaxInsideRequest>,
+) -> Result<Json<Value>> {
+    let user_id = extract_user_id(&headers)?;
+    let result = service.set_max_concurrent_inside(user_id, event_id, req.max_concurrent_inside).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": result
+    })))
+}
+
+/**
+ * PUT /api/v1/tickets/event/:event_id/overbook-allowance
+ * 
+ * Allow selling N seats past the event's capacity (0 = no overbooking)
+ * 
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with user auth (event owner)
+ * @param event_id - UUID of the event from URL path
+ * @param req - New allowance
+ * @returns JSON with nominal and effective capacity
+ */
+pub async fn set_overbook_allowance(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Path(event_id): Path<Uuid>,
+    Json(req): Json<SetOverbookRequest>,
+) -> Result<Json<Value>> {
+    let user_id = extract_user_id(&headers)?;
+    let result = service.set_overbook_allowance(user_id, event_id, req.overbook_allowance).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": result
+    })))
+}
+
+/**
+ * PUT /api/v1/tickets/event/:event_id/low-stock-threshold
+ * 
+ * Get an "inventory.low" event once stock drops to the threshold (null = off)
+ * 
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with user auth (event owner)
+ * @param event_id - UUID of the event from URL path
+ * @param req - New threshold
+ * @returns JSON with the threshold and current public stock
+ */
+pub async fn set_low_stock_threshold(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Path(event_id): Path<Uuid>,
+    Json(req): Json<SetLowStockThresholdRequest>,
+) -> Result<Json<Value>> {
+    let user_id = extract_user_id(&headers)?;
+    let result = service.set_low_stock_threshold(user_id, event_id, req.low_stock_threshold).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": result
+    })))
+}
+
+/**
+ * PUT /api/v1/tickets/event/:event_id/default-ticket-type
+ * 
+ * Name the tier purchases get when they don't specify one
+ * 
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with user auth (event owner)
+ * @param event_id - UUID of the event from URL path
+ * @param req - New default tier name
+ * @returns JSON with the default now in effect
+ */
+pub async fn set_default_ticket_type(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Path(event_id): Path<Uuid>,
+    Json(req): Json<SetDefaultTicketTypeRequest>,
+) -> Result<Json<Value>> {
+    let user_id = extract_user_id(&headers)?;
+    let result = service.set_default_ticket_type(user_id, event_id, &req.default_ticket_type).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": result
+    })))
+}
+
+/**
+ * PUT /api/v1/tickets/event/:event_id/self-cancel-deadline
+ * 
+ * Set how late buyers may cancel their own tickets (null = event start)
+ * 
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with user auth (event owner)
+ * @param event_id - UUID of the event from URL path
+ * @param req - New deadline or null
+ * @returns JSON with the effective self-cancel deadline
+ */
+pub async fn set_self_cancel_deadline(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Path(event_id): Path<Uuid>,
+    Json(req): Json<SetSelfCancelDeadlineRequest>,
+) -> Result<Json<Value>> {
+    let user_id = extract_user_id(&headers)?;
+    let result = service.set_self_cancel_deadline(user_id, event_id, req.self_cancel_deadline).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": result
+    })))
+}
+
+/**
+ * POST /api/v1/tickets/:ticket_id/cancel
+ * 
+ * Buyer cancels their own ticket, up to the event's self-cancel deadline
+ * 
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with user auth (ticket owner)
+ * @param ticket_id - Human-readable ticket ID from URL path
+ * @returns JSON with restored seats and whether a refund was queued
+ */
+pub async fn cancel_ticket(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Path(ticket_id): Path<String>,
+) -> Result<Json<Value>> {
+    let user_id = extract_user_id(&headers)?;
+    let result = service.cancel(user_id, &ticket_id).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": result
+    })))
+}
+
+/**
+ * POST /api/v1/tickets/:ticket_id/resend-confirmation
+ * 
+ * Buyer asks for their confirmation again (once per minute per ticket)
+ * 
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with user auth (ticket owner)
+ * @param ticket_id - Human-readable ticket ID from URL path
+ * @returns JSON with when the confirmation was queued
+ */
+pub async fn resend_confirmation(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Path(ticket_id): Path<String>,
+) -> Result<Json<Value>> {
+    let user_id = extract_user_id(&headers)?;
+    let result = service.resend_confirmation(user_id, &ticket_id).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": result
+    })))
+}
+
+/**
+ * POST /api/v1/tickets/event/:event_id/comp
+ * 
+ * Issue a comp ticket from the event's held block
+ * 
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with user auth (event owner)
+ * @param event_id - UUID of the event from URL path
+ * @param req - Recipient email
+ * @returns JSON with the comp ticket and remaining hold
+ */
+pub async fn issue_comp_ticket(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Path(event_id): Path<Uuid>,
+    Json(req): Json<IssueCompRequest>,
+) -> Result<Json<Value>> {
+    let user_id = extract_user_id(&headers)?;
+    let result = service.issue_comp(user_id, event_id, &req.email).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": result
+    })))
+}
+
+/**
+ * GET /api/v1/events/:event_id/cancel-preview
+ *
+ * What cancelling the event would affect and refund — read-only
+ *
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with user auth (event owner)
+ * @param event_id - UUID of the event from URL path
+ * @returns JSON with tickets by status, seats released and refund total
+ */
+pub async fn cancel_preview(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Path(event_id): Path<Uuid>,
+) -> Result<Json<Value>> {
+    let user_id = extract_user_id(&headers)?;
+    let result = service.cancel_preview(user_id, event_id).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": result
+    })))
+}
+
+/**
+ * PATCH /api/v1/events/:event_id/status
+ *
+ * Move the event along its lifecycle (draft → active → completed/cancelled)
+ *
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with user auth (event owner)
+ * @param event_id - UUID of the event from URL path
+ * @param req - Requested status
+ * @returns JSON with previous and new status
+ */
+pub async fn set_event_status(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Path(event_id): Path<Uuid>,
+    Json(req): Json<SetEventStatusRequest>,
+) -> Result<Json<Value>> {
+    let user_id = extract_user_id(&headers)?;
+    let result = service.set_event_status(user_id, event_id, &req.status).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": result
+    })))
+}
+
+/**
+ * GET /api/v1/events/:event_id/ticket-types
+ *
+ * Tiers with price and remaining capacity, for the buy page's selector
+ *
+ * @param service - Ticket service instance
+ * @param event_id - UUID of the event from URL path
+ * @param query - available_only=true hides sold-out and inactive tiers
+ * @returns JSON with the event's ticket types
+ */
+pub async fn list_ticket_types(
+    State(service): State<Arc<TicketService>>,
+    Path(event_id): Path<Uuid>,
+    Query(query): Query<TicketTypesQuery>,
+) -> Result<Json<Value>> {
+    let ticket_types = service.list_ticket_types(event_id, query.available_only).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": { "ticket_types": ticket_types }
+    })))
+}
+
+/**
+ * GET /api/v1/tickets/event/:event_id/add-ons
+ *
+ * Extras buyers can add to a purchase (parking, merch)
+ *
+ * @param service - Ticket service instance
+ * @param event_id - UUID of the event from URL path
+ * @returns JSON with the event's active add-ons
+ */
+pub async fn list_add_ons(
+    State(service): State<Arc<TicketService>>,
+    Path(event_id): Path<Uuid>,
+) -> Result<Json<Value>> {
+    let add_ons = service.list_add_ons(event_id).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": { "add_ons": add_ons }
+    })))
+}
+
+/**
+ * POST /api/v1/tickets/event/:event_id/add-ons
+ *
+ * Organizer adds an extra to their event
+ *
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with user auth (event owner)
+ * @param event_id - UUID of the event from URL path
+ * @param req - Name, price and optional inventory
+ * @returns JSON with the created add-on
+ */
+pub async fn create_add_on(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Path(event_id): Path<Uuid>,
+    Json(req): Json<CreateAddOnRequest>,
+) -> Result<Json<Value>> {
+    let user_id = extract_user_id(&headers)?;
+    let add_on = service.create_add_on(user_id, event_id, req).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": add_on
+    })))
+}
+
 /**
  * POST /api/v1/tickets/claim-free
  * 
@@ -179,3 +652,276 @@ pub async fn get_dynamic_qr(
         "data": { "qr_data": qr_data }
     })))
 }
+
+/**
+ * GET /api/v1/tickets/{ticket_id}/qr.png?size=320
+ * 
+ * Render the dynamic QR as a PNG image
+ * 
+ * Another user's ticket is 404 (never 403), cancelled tickets come back
+ * watermarked, and size is clamped to a sane range.
+ * 
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with user authentication
+ * @param ticket_id - Human-readable ticket ID from path
+ * @param query - Optional edge length in px
+ * @returns image/png body
+ */
+pub async fn get_qr_png(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Path(ticket_id): Path<String>,
+    Query(query): Query<QrImageQuery>,
+) -> Result<Response> {
+    let user_id = extract_user_id(&headers)?;
+    let png = service.get_qr_png(&ticket_id, user_id, query.size).await?;
+
+    // Payload rotates every few seconds — caches must never hold a copy.
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/png"),
+            (header::CACHE_CONTROL, "no-store"),
+        ],
+        png,
+    ).into_response())
+}
+
+/**
+ * GET /api/v1/tickets/event/{event_id}/qr-batch.zip?page=1&size=320
+ * 
+ * Download every valid ticket's QR as a ZIP of {ticket_id}.png
+ * 
+ * Organizer-only, for will-call printing. Streamed as the images render;
+ * 500 tickets per page, with X-Next-Page set while more remain. Not
+ * available on rotating-QR events.
+ * 
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with organizer authentication
+ * @param event_id - Event UUID from path
+ * @param query - Optional page and edge length in px
+ * @returns application/zip body
+ */
+pub async fn get_qr_batch_zip(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Path(event_id): Path<Uuid>,
+    Query(query): Query<QrBatchQuery>,
+) -> Result<Response> {
+    let user_id = extract_user_id(&headers)?;
+    let batch = service.qr_batch_page(user_id, event_id, query.page).await?;
+
+    let disposition = format!("attachment; filename=\"qr-{}-page-{}.zip\"", event_id, batch.page);
+    let next_page = batch.has_more.then_some(batch.page + 1);
+    let body = axum::body::Body::from_stream(service.stream_qr_batch(batch, query.size));
+
+    let mut response = (
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+            (header::CACHE_CONTROL, "no-store".to_string()),
+        ],
+        body,
+    ).into_response();
+    if let Some(next) = next_page {
+        response.headers_mut().insert("x-next-page", next.into());
+    }
+    Ok(response)
+}
+
+/**
+ * GET /api/v1/tickets/{ticket_id}/barcode.png?module=2
+ * 
+ * Render the ticket id as a Code128 barcode PNG
+ * 
+ * For scanners that only read linear barcodes. Not available on
+ * rotating-QR events; another user's ticket is 404.
+ * 
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with user authentication
+ * @param ticket_id - Human-readable ticket ID from path
+ * @param query - Optional narrowest-bar width in px
+ * @returns image/png body
+ */
+pub async fn get_barcode_png(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Path(ticket_id): Path<String>,
+    Query(query): Query<BarcodeImageQuery>,
+) -> Result<Response> {
+    let user_id = extract_user_id(&headers)?;
+    let png = service.get_barcode_png(&ticket_id, user_id, query.module).await?;
+
+    // Static for the ticket's life, but the watermark changes on cancel.
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/png"),
+            (header::CACHE_CONTROL, "private, no-cache"),
+        ],
+        png,
+    ).into_response())
+}
+
+/**
+ * POST /api/v1/admin/tickets/bulk-cancel
+ * 
+ * Cancel all of a buyer's live tickets for an event - disputed order cleanup
+ * 
+ * Admin-only. Inventory is restored and refunds are queued via the outbox
+ * in a single transaction.
+ * 
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with admin auth
+ * @param req - Buyer user_id and event_id
+ * @returns JSON with cancelled / restored / refund counts
+ */
+pub async fn bulk_cancel_tickets(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Json(req): Json<BulkCancelRequest>,
+) -> Result<Json<Value>> {
+    let admin_id = require_admin(&headers)?;
+    let result = service.bulk_cancel(admin_id, req.user_id, req.event_id).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": result
+    })))
+}
+
+/**
+ * POST /api/v1/admin/tickets/{ticket_id}/reassign
+ * 
+ * Re-point a ticket at a corrected email - support fix for buyer typos
+ * 
+ * Admin-only. Not the transfer flow: no time lock, and an account is
+ * created for the email if none exists yet.
+ * 
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with admin auth
+ * @param ticket_id - Human-readable ticket ID from path
+ * @param req - Corrected email
+ * @returns JSON with the new owner and fresh QR payload
+ */
+pub async fn reassign_ticket(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Path(ticket_id): Path<String>,
+    Json(req): Json<ReassignTicketRequest>,
+) -> Result<Json<Value>> {
+    let admin_id = require_admin(&headers)?;
+    let result = service.reassign(admin_id, &ticket_id, &req.email).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": result
+    })))
+}
+
+/**
+ * POST /api/v1/admin/tickets/{ticket_id}/blacklist
+ * 
+ * Make a known-fraudulent ticket fail every scan
+ * 
+ * Admin-only kill-switch for chargebacks and leaked QRs. Posting again
+ * replaces the reason.
+ * 
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with admin auth
+ * @param ticket_id - Human-readable ticket ID from path
+ * @param req - Reason shown to door staff
+ * @returns JSON with the blacklist entry
+ */
+pub async fn blacklist_ticket(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Path(ticket_id): Path<String>,
+    Json(req): Json<BlacklistTicketRequest>,
+) -> Result<Json<Value>> {
+    let admin_id = require_admin(&headers)?;
+    let entry = service.blacklist_ticket(admin_id, &ticket_id, &req.reason).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": entry
+    })))
+}
+
+/**
+ * DELETE /api/v1/admin/tickets/{ticket_id}/blacklist
+ * 
+ * Lift a blacklist entry so the ticket scans normally again
+ * 
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with admin auth
+ * @param ticket_id - Human-readable ticket ID from path
+ * @returns JSON with the ticket id
+ */
+pub async fn unblacklist_ticket(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Path(ticket_id): Path<String>,
+) -> Result<Json<Value>> {
+    let admin_id = require_admin(&headers)?;
+    service.unblacklist_ticket(admin_id, &ticket_id).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": { "ticket_id": ticket_id, "blacklisted": false }
+    })))
+}
+
+/**
+ * POST /api/v1/admin/events/{event_id}/scrub-pii
+ * 
+ * Remove attendee PII from an event's tickets
+ * 
+ * Admin-only. Refused while the event is inside its retention period
+ * (PII_RETENTION_DAYS after its last day).
+ * 
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with admin auth
+ * @param event_id - Event UUID from path
+ * @returns JSON with the number of tickets and scans scrubbed
+ */
+pub async fn scrub_event_pii(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Path(event_id): Path<Uuid>,
+) -> Result<Json<Value>> {
+    let admin_id = require_admin(&headers)?;
+    let result = service.scrub_event_pii(event_id, Some(admin_id)).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": result
+    })))
+}
+
+/**
+ * POST /api/v1/admin/events/{event_id}/cache/invalidate
+ * 
+ * Drop an event from the event cache after it was edited
+ * 
+ * Event edits go through the gateway, which calls this after an update or
+ * delete so new titles/dates show immediately instead of after the cache
+ * TTL. An admin, or the organizer who owns (or just deleted) the event.
+ * 
+ * @param service - Ticket service instance
+ * @param headers - HTTP headers with user authentication
+ * @param event_id - Event UUID from path
+ * @returns JSON with the invalidated event id
+ */
+pub async fn invalidate_event_cache(
+    State(service): State<Arc<TicketService>>,
+    headers: HeaderMap,
+    Path(event_id): Path<Uuid>,
+) -> Result<Json<Value>> {
+    let user_id = extract_user_id(&headers)?;
+    let is_admin = require_admin(&headers).is_ok();
+    service.invalidate_event_cache(user_id, is_admin, event_id).await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "data": { "event_id": event_id }
+    })))
+}
@@ -3,6 +3,14 @@ pub mod service;
 pub mod repository;
 pub mod dto;
 pub mod transfer;
+pub mod resale;
+pub mod qr_image;
+pub mod barcode_image;
+pub mod qr_zip;
+pub mod status;
+pub mod event_status;
+pub mod retention;
+pub mod checkout_expiry;
 
 #[cfg(test)]
 mod service_test;
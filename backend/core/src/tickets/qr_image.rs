@@ -0,0 +1,133 @@
+// QR PNG rendering — pure, no I/O. The handler owns auth and headers.
+//
+// The PNG encodes the same signed, rotating payload as GET /tickets/{id}/qr, so
+// a downloaded image stops scanning within seconds, same as a screenshot.
+// Cancelled tickets still render (support needs to see them) but carry a
+// "CANCELLED" band so nobody mistakes them for a live ticket.
+
+use crate::error::{AppError, Result};
+use super::status::TicketStatus;
+
+const DEFAULT_SIZE: u32 = 320;
+pub const MIN_SIZE: u32 = 128;
+pub const MAX_SIZE: u32 = 1024;
+const QUIET_ZONE: u32 = 4; // modules of white border, per the QR spec
+
+const WATERMARK: &str = "CANCELLED";
+const WATERMARK_RED: [u8; 3] = [200, 30, 30];
+
+/// Decide what the image endpoint does for a ticket lookup scoped to the
+/// caller. `None` covers both "no such ticket" and "someone else's ticket" —
+/// both are 404 so the endpoint can't be used to probe which ids exist.
+/// Returns whether the image needs the CANCELLED watermark.
+pub fn access_for(status: Option<TicketStatus>) -> Result<bool> {
+    match status {
+        None => Err(AppError::NotFound("Ticket not found".into())),
+        Some(s) => Ok(matches!(s, TicketStatus::Cancelled | TicketStatus::Refunded)),
+    }
+}
+
+/// Clamp a requested edge length. Missing → default; anything outside the
+/// range is pulled in rather than rejected, so `?size=99999` can't be used to
+/// make the server allocate a huge bitmap.
+pub fn clamp_size(requested: Option<u32>) -> u32 {
+    requested.unwrap_or(DEFAULT_SIZE).clamp(MIN_SIZE, MAX_SIZE)
+}
+
+/// Render `payload` as a `size`×`size` RGB PNG.
+pub fn render_png(payload: &str, size: u32, cancelled: bool) -> std::result::Result<Vec<u8>, String> {
+    let code = qrcode::QrCode::new(payload.as_bytes()).map_err(|e| e.to_string())?;
+    let modules = code.width() as u32;
+    let colors = code.to_colors();
+    let total = modules + QUIET_ZONE * 2;
+
+    let mut pixels = vec![255u8; (size * size * 3) as usize];
+    for y in 0..size {
+        for x in 0..size {
+            // Nearest-neighbour map from pixel to module, quiet zone included
+            let mx = x * total / size;
+            let my = y * total / size;
+            let dark = mx >= QUIET_ZONE
+                && my >= QUIET_ZONE
+                && mx < QUIET_ZONE + modules
+                && my < QUIET_ZONE + modules
+                && colors[((my - QUIET_ZONE) * modules + (mx - QUIET_ZONE)) as usize] == qrcode::Color::Dark;
+            if dark {
+                set_pixel(&mut pixels, size, x, y, [0, 0, 0]);
+            }
+        }
+    }
+
+    if cancelled {
+        draw_watermark(&mut pixels, size, size);
+    }
+
+    let mut out = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut out, size, size);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+        writer.write_image_data(&pixels).map_err(|e| e.to_string())?;
+    }
+    Ok(out)
+}
+
+pub(super) fn set_pixel(pixels: &mut [u8], width: u32, x: u32, y: u32, rgb: [u8; 3]) {
+    let i = ((y * width + x) * 3) as usize;
+    pixels[i..i + 3].copy_from_slice(&rgb);
+}
+
+// White band across the middle with the word in red, scaled to ~80% width
+// (and never taller than half the image, for wide barcode strips).
+pub(super) fn draw_watermark(pixels: &mut [u8], width: u32, height: u32) {
+    let chars = WATERMARK.len() as u32;
+    let text_cols = chars * (GLYPH_W + 1) - 1;
+    let scale = (width * 8 / 10 / text_cols).min(height / 2 / GLYPH_H).max(1);
+    let text_w = text_cols * scale;
+    let text_h = GLYPH_H * scale;
+    let left = width.saturating_sub(text_w) / 2;
+    let top = height.saturating_sub(text_h) / 2;
+
+    let pad = scale * 2;
+    for y in top.saturating_sub(pad)..(top + text_h + pad).min(height) {
+        for x in 0..width {
+            set_pixel(pixels, width, x, y, [255, 255, 255]);
+        }
+    }
+
+    for (ci, ch) in WATERMARK.chars().enumerate() {
+        let glyph = glyph(ch);
+        let gx0 = left + ci as u32 * (GLYPH_W + 1) * scale;
+        for (row, bits) in glyph.iter().enumerate() {
+            for col in 0..GLYPH_W {
+                if bits & (1 << (GLYPH_W - 1 - col)) == 0 {
+                    continue;
+                }
+                for dy in 0..scale {
+                    for dx in 0..scale {
+                        let x = gx0 + col * scale + dx;
+                        let y = top + row as u32 * scale + dy;
+                        set_pixel(pixels, width, x, y, WATERMARK_RED);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// 5×7 bitmap glyphs — only the letters the watermark needs.
+const GLYPH_W: u32 = 5;
+const GLYPH_H: u32 = 7;
+
+fn glyph(ch: char) -> [u8; 7] {
+    match ch {
+        'A' => [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'C' => [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110],
+        'D' => [0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11110],
+        'E' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111],
+        'L' => [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111],
+        'N' => [0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001, 0b10001],
+        _   => [0; 7],
+    }
+}
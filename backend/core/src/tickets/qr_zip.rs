@@ -0,0 +1,150 @@
+// Streaming ZIP writer for the will-call QR batch — pure, no I/O.
+//
+// Entries are written "stored" (no compression): PNGs are already deflated,
+// so squeezing them again costs CPU for nothing. Each entry is complete before
+// it is written, so the local header carries the real CRC and sizes and no
+// data descriptors are needed. `entry` and `finish` hand back the bytes to
+// send; only the central directory records (~100 bytes per file) are kept
+// until the end, never the images.
+//
+// No zip crate in this build; the stored format is headers and a CRC.
+
+const LOCAL_HEADER_SIG: u32 = 0x0403_4b50;
+const CENTRAL_HEADER_SIG: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIR_SIG: u32 = 0x0605_4b50;
+const VERSION: u16 = 20; // 2.0 — plain stored entries
+const UTF8_NAMES: u16 = 1 << 11;
+
+struct CentralRecord {
+    name: String,
+    crc: u32,
+    size: u32,
+    offset: u32,
+}
+
+#[derive(Default)]
+pub struct ZipStream {
+    records: Vec<CentralRecord>,
+    offset: u32,
+}
+
+impl ZipStream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Local header + data for one file.
+    pub fn entry(&mut self, name: &str, data: &[u8]) -> Vec<u8> {
+        let crc = crc32fast::hash(data);
+        let size = data.len() as u32;
+
+        let mut out = Vec::with_capacity(30 + name.len() + data.len());
+        put_u32(&mut out, LOCAL_HEADER_SIG);
+        put_u16(&mut out, VERSION);
+        put_u16(&mut out, UTF8_NAMES);
+        put_u16(&mut out, 0); // stored
+        put_u16(&mut out, 0); // mod time
+        put_u16(&mut out, 0x21); // mod date: 1980-01-01
+        put_u32(&mut out, crc);
+        put_u32(&mut out, size);
+        put_u32(&mut out, size);
+        put_u16(&mut out, name.len() as u16);
+        put_u16(&mut out, 0); // extra field length
+        out.extend_from_slice(name.as_bytes());
+        out.extend_from_slice(data);
+
+        self.records.push(CentralRecord { name: name.to_string(), crc, size, offset: self.offset });
+        self.offset += out.len() as u32;
+        out
+    }
+
+    /// Central directory and end record. Nothing may be added after this.
+    pub fn finish(self) -> Vec<u8> {
+        let mut out = Vec::new();
+        for r in &self.records {
+            put_u32(&mut out, CENTRAL_HEADER_SIG);
+            put_u16(&mut out, VERSION);
+            put_u16(&mut out, VERSION);
+            put_u16(&mut out, UTF8_NAMES);
+            put_u16(&mut out, 0);
+            put_u16(&mut out, 0);
+            put_u16(&mut out, 0x21);
+            put_u32(&mut out, r.crc);
+            put_u32(&mut out, r.size);
+            put_u32(&mut out, r.size);
+            put_u16(&mut out, r.name.len() as u16);
+            put_u16(&mut out, 0); // extra
+            put_u16(&mut out, 0); // comment
+            put_u16(&mut out, 0); // disk number
+            put_u16(&mut out, 0); // internal attrs
+            put_u32(&mut out, 0); // external attrs
+            put_u32(&mut out, r.offset);
+            out.extend_from_slice(r.name.as_bytes());
+        }
+
+        let count = self.records.len() as u16;
+        let dir_size = out.len() as u32;
+        put_u32(&mut out, END_OF_CENTRAL_DIR_SIG);
+        put_u16(&mut out, 0);
+        put_u16(&mut out, 0);
+        put_u16(&mut out, count);
+        put_u16(&mut out, count);
+        put_u32(&mut out, dir_size);
+        put_u32(&mut out, self.offset);
+        put_u16(&mut out, 0); // comment length
+        out
+    }
+}
+
+fn put_u16(out: &mut Vec<u8>, v: u16) {
+    out.extend_from_slice(&v.to_le_bytes());
+}
+
+fn put_u32(out: &mut Vec<u8>, v: u32) {
+    out.extend_from_slice(&v.to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u16_at(b: &[u8], i: usize) -> u16 {
+        u16::from_le_bytes([b[i], b[i + 1]])
+    }
+
+    fn u32_at(b: &[u8], i: usize) -> u32 {
+        u32::from_le_bytes([b[i], b[i + 1], b[i + 2], b[i + 3]])
+    }
+
+    #[test]
+    fn archive_indexes_every_entry() {
+        let mut zip = ZipStream::new();
+        let mut bytes = zip.entry("BUKR-0001.png", b"first");
+        bytes.extend(zip.entry("BUKR-0002.png", b"second!"));
+        let entries_len = bytes.len();
+        bytes.extend(zip.finish());
+
+        // End record: 2 entries, directory right after the data
+        let eocd = bytes.len() - 22;
+        assert_eq!(u32_at(&bytes, eocd), END_OF_CENTRAL_DIR_SIG);
+        assert_eq!(u16_at(&bytes, eocd + 10), 2);
+        assert_eq!(u32_at(&bytes, eocd + 16) as usize, entries_len);
+        assert_eq!(u32_at(&bytes, eocd + 12) as usize, eocd - entries_len);
+
+        // Second central record points at the second local header
+        let second = entries_len + 46 + "BUKR-0001.png".len();
+        assert_eq!(u32_at(&bytes, second), CENTRAL_HEADER_SIG);
+        let offset = u32_at(&bytes, second + 42) as usize;
+        assert_eq!(u32_at(&bytes, offset), LOCAL_HEADER_SIG);
+        assert_eq!(u32_at(&bytes, offset + 14), crc32fast::hash(b"second!"));
+        let data = offset + 30 + "BUKR-0002.png".len();
+        assert_eq!(&bytes[data..data + 7], b"second!");
+    }
+
+    #[test]
+    fn empty_archive_is_just_the_end_record() {
+        let bytes = ZipStream::new().finish();
+        assert_eq!(bytes.len(), 22);
+        assert_eq!(u32_at(&bytes, 0), END_OF_CENTRAL_DIR_SIG);
+    }
+}
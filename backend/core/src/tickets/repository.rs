@@ -20,7 +20,13 @@ use sqlx::{PgPool, Row};
 use uuid::Uuid;
 // Decimal kept: used in EventData and create_free_with_tx price binds
 
-use super::dto::Ticket;
+use super::dto::{BlacklistEntry, Ticket, TicketAddOn, UserTicket};
+use super::event_status::EventStatus;
+use super::resale::RESALE_REF_LIKE;
+use super::status::TicketStatus;
+use crate::error::AppError;
+use crate::fees::normalize_amount;
+use crate::promos::service::PromoPricing;
 
 /**
  * TicketRepository: Your friendly neighborhood database accessor
@@ -48,96 +54,68 @@ impl TicketRepository {
     }
 
     /**
-     * Create a new ticket in the database
-     * 
-     * This is where the INSERT happens - the moment of truth
-     * Returns the created ticket with all database-generated fields
-     * 
-     * Note: The database trigger will auto-decrement available_tickets
-     * So we don't need to do it manually - database does the heavy lifting
-     * 
-     * @returns Created ticket with ID and timestamps
-     * @throws sqlx::Error if database says no (constraint violation, etc)
+     * Take seats off sale inside the caller's transaction
+     *
+     * Every ticket insert goes through here first. The conditional UPDATE is
+     * the inventory guard: it never drives available_tickets below
+     * -overbook, and no row updated means the event can't cover `qty`.
+     *
+     * @param qty - Seats to take (> 0)
+     * @param overbook - Seats the organizer allows past nominal capacity (0 = none)
+     * @returns Seats left after the decrement (negative once overbooked)
+     * @throws AppError::TicketsExhausted when fewer than `qty` remain
      */
-    pub async fn create(
+    pub async fn reserve_inventory(
         &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
         event_id: Uuid,
-        user_id: Uuid,
-        ticket_id: &str,
-        ticket_type: &str,
-        quantity: i32,
-        usage_limit: i32,
-        unit_price: Decimal,
-        total_price: Decimal,
-        discount_applied: Decimal,
-        promo_code_id: Option<Uuid>,
-        currency: &str,
-        qr_code_data: &str,
-        payment_ref: &str,
-        payment_provider: &str,
-        excitement_rating: Option<i32>,
-        valid_from: Option<chrono::DateTime<chrono::Utc>>,
-        valid_until: Option<chrono::DateTime<chrono::Utc>>,
-    ) -> Result<Ticket, sqlx::Error> {
-        let row = sqlx::query(
-            r#"INSERT INTO tickets
-                (event_id, user_id, ticket_id, ticket_type, quantity, usage_limit, usage_count,
-                 unit_price, total_price, discount_applied, promo_code_id, currency,
-                 qr_code_data, payment_ref, payment_provider, excitement_rating, status,
-                 valid_from, valid_until)
-            VALUES ($1, $2, $3, $4, $5, $6, 0, $7, $8, $9, $10, $11, $12, $13, $14, $15, 'valid', $16, $17)
-            RETURNING id, ticket_id, event_id, user_id, ticket_type, quantity, usage_limit, usage_count,
-                      unit_price, total_price, discount_applied, promo_code_id,
-                      currency, status, qr_code_data, valid_from, valid_until,
-                      payment_ref, payment_provider, excitement_rating, scanned_at,
-                      purchase_date, created_at, idempotency_key"#,
+        qty: i32,
+        overbook: i32,
+    ) -> crate::error::Result<i32> {
+        if qty <= 0 {
+            return Err(AppError::Validation("quantity must be positive".into()));
+        }
+        sqlx::query_scalar(
+            r#"UPDATE events SET available_tickets = available_tickets - $2, updated_at = NOW()
+               WHERE id = $1 AND available_tickets + $3 >= $2
+               RETURNING available_tickets"#,
         )
         .bind(event_id)
-        .bind(user_id)
-        .bind(ticket_id)
-        .bind(ticket_type)
-        .bind(quantity)
-        .bind(usage_limit)
-        .bind(unit_price)
-        .bind(total_price)
-        .bind(discount_applied)
-        .bind(promo_code_id)
-        .bind(currency)
-        .bind(qr_code_data)
-        .bind(payment_ref)
-        .bind(payment_provider)
-        .bind(excitement_rating)
-        .bind(valid_from)
-        .bind(valid_until)
-        .fetch_one(&self.pool)
-        .await?;
+        .bind(qty)
+        .bind(overbook.max(0))
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(AppError::Database)?
+        .ok_or(AppError::TicketsExhausted)
+    }
 
-        Ok(row_to_ticket(&row))
+    /**
+     * Next receipt number for an event (1, 2, 3, ...)
+     *
+     * Must run in the purchase transaction after the event row is locked
+     * (SELECT ... FOR UPDATE): concurrent buyers queue on that lock, so each
+     * sees the previous buyer's committed value — no gaps from races, no repeats.
+     * A rolled-back purchase rolls its bump back too.
+     */
+    pub async fn next_receipt_seq(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(
+            "UPDATE events SET receipt_seq = receipt_seq + 1 WHERE id = $1 RETURNING receipt_seq",
+        )
+        .bind(event_id)
+        .fetch_one(&mut **tx)
+        .await
     }
 
+    /// Insert a ticket inside an open transaction. Call reserve_inventory first.
+    /// Paid tickets go in as pending; the payment webhook makes them valid.
     pub async fn create_with_tx(
         &self,
         tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
-        event_id: Uuid,
-        user_id: Uuid,
-        ticket_id: &str,
-        ticket_type: &str,
-        quantity: i32,
-        usage_limit: i32,
-        usage_model: &str,
-        is_renewable: bool,
-        unit_price: Decimal,
-        total_price: Decimal,
-        discount_applied: Decimal,
-        promo_code_id: Option<Uuid>,
-        currency: &str,
-        qr_code_data: &str,
-        payment_ref: &str,
-        payment_provider: &str,
-        excitement_rating: Option<i32>,
-        valid_from: Option<chrono::DateTime<chrono::Utc>>,
-        valid_until: Option<chrono::DateTime<chrono::Utc>>,
-        idempotency_key: Option<&str>,
+        t: &NewTicket<'_>,
     ) -> Result<Ticket, sqlx::Error> {
         let row = sqlx::query(
             r#"INSERT INTO tickets
@@ -145,42 +123,66 @@ impl TicketRepository {
                  usage_limit, usage_count, usage_model, usage_total, usage_left, is_renewable,
                  unit_price, total_price, discount_applied, promo_code_id, currency,
                  qr_code_data, payment_ref, payment_provider, excitement_rating, status,
-                 valid_from, valid_until, idempotency_key)
+                 valid_from, valid_until, idempotency_key, booking_fee, tax_amount, receipt_number,
+                 promo_units, promo_rate)
             VALUES ($1, $2, $3, $4, $5, $6, 0, $7, $6, $6, $8,
-                    $9, $10, $11, $12, $13, $14, $15, $16, $17, 'valid', $18, $19, $20)
+                    $9, $10, $11, $12, $13, $14, $15, $16, $17, $24, $18, $19, $20, $21, $22, $23,
+                    $25, $26)
             RETURNING id, ticket_id, event_id, user_id, ticket_type, quantity,
                       usage_limit, usage_count, unit_price, total_price,
                       discount_applied, promo_code_id, currency, status,
                       qr_code_data, valid_from, valid_until, payment_ref,
                       payment_provider, excitement_rating, scanned_at,
-                      purchase_date, created_at, idempotency_key"#,
+                      purchase_date, created_at, idempotency_key, booking_fee, tax_amount, receipt_number, order_id"#,
         )
-        .bind(event_id)
-        .bind(user_id)
-        .bind(ticket_id)
-        .bind(ticket_type)
-        .bind(quantity)
-        .bind(usage_limit)
-        .bind(usage_model)
-        .bind(is_renewable)
-        .bind(unit_price)
-        .bind(total_price)
-        .bind(discount_applied)
-        .bind(promo_code_id)
-        .bind(currency)
-        .bind(qr_code_data)
-        .bind(payment_ref)
-        .bind(payment_provider)
-        .bind(excitement_rating)
-        .bind(valid_from)
-        .bind(valid_until)
-        .bind(idempotency_key)
+        .bind(t.event_id)
+        .bind(t.user_id)
+        .bind(t.ticket_id)
+        .bind(t.ticket_type)
+        .bind(t.quantity)
+        .bind(t.usage_limit)
+        .bind(t.usage_model)
+        .bind(t.is_renewable)
+        .bind(t.unit_price)
+        .bind(t.total_price)
+        .bind(t.discount_applied)
+        .bind(t.promo_code_id)
+        .bind(t.currency)
+        .bind(t.qr_code_data)
+        .bind(t.payment_ref)
+        .bind(t.payment_provider)
+        .bind(t.excitement_rating)
+        .bind(t.valid_from)
+        .bind(t.valid_until)
+        .bind(t.idempotency_key)
+        .bind(t.booking_fee)
+        .bind(t.tax_amount)
+        .bind(t.receipt_number)
+        .bind(t.status.as_str())
+        .bind(t.promo_pricing.map(|p| p.units))
+        .bind(t.promo_pricing.map(|p| p.rate))
         .fetch_one(&mut **tx)
         .await?;
 
         Ok(row_to_ticket(&row))
     }
 
+    /// How a ticket's promo priced it — see PromoPricing::stored.
+    pub async fn get_promo_pricing(&self, ticket_id: Uuid) -> Result<PromoPricing, sqlx::Error> {
+        let row = sqlx::query(
+            "SELECT quantity, discount_applied, promo_units, promo_rate FROM tickets WHERE id = $1",
+        )
+        .bind(ticket_id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(PromoPricing::stored(
+            row.get("quantity"),
+            row.get("discount_applied"),
+            row.get("promo_units"),
+            row.get("promo_rate"),
+        ))
+    }
+
     pub async fn get_by_idempotency_key(
         &self,
         tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
@@ -193,13 +195,15 @@ impl TicketRepository {
                       unit_price, total_price, discount_applied, promo_code_id,
                       currency, status, qr_code_data, valid_from, valid_until,
                       payment_ref, payment_provider, excitement_rating, scanned_at,
-                      purchase_date, created_at, idempotency_key
+                      purchase_date, created_at, idempotency_key, booking_fee, tax_amount, receipt_number, order_id
             FROM tickets 
-            WHERE user_id = $1 AND event_id = $2 AND idempotency_key = $3 AND status IN ('valid', 'used')"#,
+            WHERE user_id = $1 AND event_id = $2 AND idempotency_key = $3 AND status = ANY($4)"#,
         )
         .bind(user_id)
         .bind(event_id)
         .bind(idempotency_key)
+        // Intent still in play — a cancelled/failed attempt may be retried
+        .bind([TicketStatus::Pending, TicketStatus::Valid, TicketStatus::Used].map(TicketStatus::as_str))
         .fetch_optional(&mut **tx)
         .await?;
 
@@ -212,7 +216,7 @@ impl TicketRepository {
                       unit_price, total_price, discount_applied, promo_code_id,
                       currency, status, qr_code_data, valid_from, valid_until,
                       payment_ref, payment_provider, excitement_rating, scanned_at,
-                      purchase_date, created_at, idempotency_key
+                      purchase_date, created_at, idempotency_key, booking_fee, tax_amount, receipt_number, order_id
             FROM tickets WHERE ticket_id = $1"#,
         )
         .bind(ticket_id)
@@ -222,20 +226,53 @@ impl TicketRepository {
         Ok(row.as_ref().map(row_to_ticket))
     }
 
-    pub async fn get_user_tickets(&self, user_id: Uuid) -> Result<Vec<Ticket>, sqlx::Error> {
+    /// The user's tickets with their event's branding joined in.
+    pub async fn get_user_tickets(&self, user_id: Uuid) -> Result<Vec<UserTicket>, sqlx::Error> {
         let rows = sqlx::query(
-            r#"SELECT id, ticket_id, event_id, user_id, ticket_type, quantity, usage_limit, usage_count,
-                      unit_price, total_price, discount_applied, promo_code_id,
-                      currency, status, qr_code_data, valid_from, valid_until,
-                      payment_ref, payment_provider, excitement_rating, scanned_at,
-                      purchase_date, created_at, idempotency_key
-            FROM tickets WHERE user_id = $1 ORDER BY purchase_date DESC"#,
+            r#"SELECT t.id, t.ticket_id, t.event_id, t.user_id, t.ticket_type, t.quantity, t.usage_limit, t.usage_count,
+                      t.unit_price, t.total_price, t.discount_applied, t.promo_code_id,
+                      t.currency, t.status, t.qr_code_data, t.valid_from, t.valid_until,
+                      t.payment_ref, t.payment_provider, t.excitement_rating, t.scanned_at,
+                      t.purchase_date, t.created_at, t.idempotency_key, t.booking_fee, t.tax_amount, t.receipt_number, t.order_id,
+                      e.emoji AS event_emoji, e.thumbnail_url AS event_image_url
+            FROM tickets t
+            JOIN events e ON t.event_id = e.id
+            WHERE t.user_id = $1 ORDER BY t.purchase_date DESC"#,
         )
         .bind(user_id)
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(rows.iter().map(row_to_ticket).collect())
+        Ok(rows.iter().map(|r| UserTicket {
+            ticket: row_to_ticket(r),
+            event_emoji: r.get("event_emoji"),
+            event_image_url: r.get("event_image_url"),
+        }).collect())
+    }
+
+    /// The caller's tickets in one order, with the event fields QR signing needs.
+    pub async fn get_order_tickets(&self, order_id: Uuid, user_id: Uuid) -> Result<Vec<OrderTicketRow>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"SELECT t.ticket_id, t.ticket_type, t.quantity, t.status,
+                      e.event_key, e.rotating_qr, e.qr_ttl_seconds
+               FROM tickets t
+               JOIN events e ON t.event_id = e.id
+               WHERE t.order_id = $1 AND t.user_id = $2
+               ORDER BY t.created_at, t.ticket_id"#,
+        )
+        .bind(order_id)
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(|r| OrderTicketRow {
+            ticket_id: r.get("ticket_id"),
+            ticket_type: r.get("ticket_type"),
+            quantity: r.get("quantity"),
+            status: r.get("status"),
+            event_key: r.get("event_key"),
+            rotating_ttl: r.get::<bool, _>("rotating_qr").then(|| r.get::<i32, _>("qr_ttl_seconds") as i64),
+        }).collect())
     }
 
     pub async fn get_event_tickets(&self, event_id: Uuid) -> Result<Vec<Ticket>, sqlx::Error> {
@@ -244,7 +281,7 @@ impl TicketRepository {
                       unit_price, total_price, discount_applied, promo_code_id,
                       currency, status, qr_code_data, valid_from, valid_until,
                       payment_ref, payment_provider, excitement_rating, scanned_at,
-                      purchase_date, created_at, idempotency_key
+                      purchase_date, created_at, idempotency_key, booking_fee, tax_amount, receipt_number, order_id
             FROM tickets WHERE event_id = $1 ORDER BY purchase_date DESC"#,
         )
         .bind(event_id)
@@ -254,6 +291,20 @@ impl TicketRepository {
         Ok(rows.iter().map(row_to_ticket).collect())
     }
 
+    /// One page of an event's admissible ticket ids, oldest purchase first.
+    pub async fn list_valid_ticket_ids(&self, event_id: Uuid, limit: i64, offset: i64) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT ticket_id FROM tickets WHERE event_id = $1 AND status = $2
+             ORDER BY purchase_date, id LIMIT $3 OFFSET $4",
+        )
+        .bind(event_id)
+        .bind(TicketStatus::Valid.as_str())
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+    }
+
     /**
      * Mark a ticket as used (scanned at the door)
      * 
@@ -266,11 +317,15 @@ impl TicketRepository {
      */
     pub async fn mark_used(&self, ticket_id: &str, scanned_by: Option<Uuid>) -> Result<bool, sqlx::Error> {
         let result = sqlx::query(
-            "UPDATE tickets SET status = 'used', scanned_at = $2, scanned_by = $3 WHERE ticket_id = $1 AND status = 'valid'",
+            "UPDATE tickets SET status = $4, scanned_at = $2, scanned_by = $3,
+                    last_scan_quantity = quantity - scanned_quantity, scanned_quantity = quantity
+             WHERE ticket_id = $1 AND status = ANY($5)",
         )
         .bind(ticket_id)
         .bind(Utc::now())
         .bind(scanned_by)
+        .bind(TicketStatus::Used.as_str())
+        .bind(TicketStatus::Used.sources())
         .execute(&self.pool)
         .await?;
 
@@ -285,15 +340,16 @@ impl TicketRepository {
      * Changes status from 'pending' to 'valid' (or 'failed')
      * 
      * @param payment_ref - Payment reference from gateway
-     * @param status - New status ('valid' or 'failed')
-     * @returns true if updated, false if payment_ref not found
+     * @param status - New status (Valid or Failed); only legal transitions apply
+     * @returns true if updated, false if payment_ref not found or already past it
      */
-    pub async fn update_payment_status(&self, payment_ref: &str, status: &str) -> Result<bool, sqlx::Error> {
+    pub async fn update_payment_status(&self, payment_ref: &str, status: TicketStatus) -> Result<bool, sqlx::Error> {
         let result = sqlx::query(
-            "UPDATE tickets SET status = $2 WHERE payment_ref = $1",
+            "UPDATE tickets SET status = $2 WHERE payment_ref = $1 AND status = ANY($3)",
         )
         .bind(payment_ref)
-        .bind(status)
+        .bind(status.as_str())
+        .bind(status.sources())
         .execute(&self.pool)
         .await?;
 
@@ -332,6 +388,10 @@ fn row_to_ticket(row: &sqlx::postgres::PgRow) -> Ticket {
         payment_ref: row.get("payment_ref"),
         payment_provider: row.get("payment_provider"),
         idempotency_key: row.get("idempotency_key"),
+        booking_fee: row.get("booking_fee"),
+        tax_amount: row.get("tax_amount"),
+        receipt_number: row.get("receipt_number"),
+        order_id: row.get("order_id"),
         excitement_rating: row.get("excitement_rating"),
         scanned_at: row.get("scanned_at"),
         purchase_date: row.get("purchase_date"),
@@ -341,17 +401,28 @@ fn row_to_ticket(row: &sqlx::postgres::PgRow) -> Ticket {
 
 impl TicketRepository {
     pub async fn get_event(&self, event_id: Uuid) -> Result<Option<EventData>, sqlx::Error> {
-        let row = sqlx::query("SELECT id, price, available_tickets, status, currency FROM events WHERE id = $1")
+        let row = sqlx::query(&format!("SELECT {} FROM events WHERE id = $1", EVENT_DATA_COLUMNS))
             .bind(event_id)
             .fetch_optional(&self.pool)
             .await?;
-        Ok(row.map(|r| EventData {
-            id: r.get("id"),
-            price: r.get("price"),
-            available_tickets: r.get("available_tickets"),
-            status: r.get("status"),
-            currency: r.get("currency"),
-        }))
+        Ok(row.as_ref().map(row_to_event_data))
+    }
+
+    /// "public" or "invite_only"; None when the event doesn't exist.
+    pub async fn get_event_access_mode(&self, event_id: Uuid) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar("SELECT access_mode FROM events WHERE id = $1")
+            .bind(event_id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Owner straight from the table, bypassing the event cache. None when
+    /// the event doesn't exist.
+    pub async fn get_event_organizer(&self, event_id: Uuid) -> Result<Option<Uuid>, sqlx::Error> {
+        sqlx::query_scalar("SELECT organizer_id FROM events WHERE id = $1")
+            .bind(event_id)
+            .fetch_optional(&self.pool)
+            .await
     }
 
     /// Same as get_event but acquires a row-level lock inside an open transaction.
@@ -362,27 +433,361 @@ impl TicketRepository {
         tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
         event_id: Uuid,
     ) -> Result<Option<EventData>, sqlx::Error> {
+        let row = sqlx::query(&format!("SELECT {} FROM events WHERE id = $1 FOR UPDATE", EVENT_DATA_COLUMNS))
+            .bind(event_id)
+            .fetch_optional(&mut **tx)
+            .await?;
+        Ok(row.as_ref().map(row_to_event_data))
+    }
+
+    /// Set (or clear, with None) an event's refund deadline.
+    pub async fn set_refund_deadline(
+        &self,
+        event_id: Uuid,
+        deadline: Option<chrono::DateTime<Utc>>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE events SET refund_deadline = $2 WHERE id = $1")
+            .bind(event_id)
+            .bind(deadline)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Set (or clear) the low-stock alert threshold and re-arm the alert.
+    /// Returns (available_tickets, held_tickets), None if no such event.
+    pub async fn set_low_stock_threshold(
+        &self,
+        event_id: Uuid,
+        threshold: Option<i32>,
+    ) -> Result<Option<(i32, i32)>, sqlx::Error> {
         let row = sqlx::query(
-            "SELECT id, price, available_tickets, status, currency FROM events WHERE id = $1 FOR UPDATE"
+            r#"UPDATE events SET low_stock_threshold = $2, low_stock_alerted = FALSE
+               WHERE id = $1 RETURNING available_tickets, held_tickets"#,
         )
         .bind(event_id)
-        .fetch_optional(&mut **tx)
+        .bind(threshold)
+        .fetch_optional(&self.pool)
         .await?;
-        Ok(row.map(|r| EventData {
-            id: r.get("id"),
-            price: r.get("price"),
-            available_tickets: r.get("available_tickets"),
-            status: r.get("status"),
-            currency: r.get("currency"),
-        }))
+        Ok(row.map(|r| (r.get("available_tickets"), r.get("held_tickets"))))
+    }
+
+    /// Set the tier name purchases fall back to. False if no such event.
+    pub async fn set_default_ticket_type(&self, event_id: Uuid, name: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("UPDATE events SET default_ticket_type = $2 WHERE id = $1")
+            .bind(event_id)
+            .bind(name)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Mark the event's low-stock alert as sent. False if it already was —
+    /// only the caller that flips it enqueues the alert.
+    pub async fn claim_low_stock_alert(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE events SET low_stock_alerted = TRUE WHERE id = $1 AND NOT low_stock_alerted",
+        )
+        .bind(event_id)
+        .execute(&mut **tx)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Set how many seats may be sold past total_tickets. Returns total_tickets, None if no such event.
+    pub async fn set_overbook_allowance(
+        &self,
+        event_id: Uuid,
+        allowance: i32,
+    ) -> Result<Option<i32>, sqlx::Error> {
+        sqlx::query_scalar(
+            "UPDATE events SET overbook_allowance = $2 WHERE id = $1 RETURNING total_tickets",
+        )
+        .bind(event_id)
+        .bind(allowance)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Set (or clear, with None) the door headcount cap. False if no such event.
+    pub async fn set_max_concurrent_inside(&self, event_id: Uuid, max_inside: Option<i32>) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("UPDATE events SET max_concurrent_inside = $2 WHERE id = $1")
+            .bind(event_id)
+            .bind(max_inside)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Set (or clear, with None) an event's self-service cancellation deadline.
+    pub async fn set_self_cancel_deadline(
+        &self,
+        event_id: Uuid,
+        deadline: Option<chrono::DateTime<Utc>>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE events SET self_cancel_deadline = $2 WHERE id = $1")
+            .bind(event_id)
+            .bind(deadline)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Set the organizer hold on an event, inside an open transaction.
+    pub async fn set_held_tickets_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: Uuid,
+        held: i32,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE events SET held_tickets = $2 WHERE id = $1")
+            .bind(event_id)
+            .bind(held)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+
+    /// Lock the event row and read its status. None when the event doesn't exist.
+    pub async fn lock_event_status_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: Uuid,
+    ) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar("SELECT status FROM events WHERE id = $1 FOR UPDATE")
+            .bind(event_id)
+            .fetch_optional(&mut **tx)
+            .await
+    }
+
+    /// Tickets of the event whose money has gone, or is queued to go, back:
+    /// refunded ones, and cancelled ones a charge was collected for.
+    pub async fn count_refunded_tickets_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"SELECT COUNT(*) FROM tickets t
+               WHERE t.event_id = $1
+                 AND (t.status = $2
+                      OR (t.status = $3 AND EXISTS (
+                          SELECT 1 FROM payment_transactions p
+                          WHERE p.ticket_id = t.id AND p.status = 'success'
+                            AND p.provider_ref NOT LIKE $4)))"#,
+        )
+        .bind(event_id)
+        .bind(TicketStatus::Refunded.as_str())
+        .bind(TicketStatus::Cancelled.as_str())
+        .bind(RESALE_REF_LIKE)
+        .fetch_one(&mut **tx)
+        .await
+    }
+
+    pub async fn set_event_status_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: Uuid,
+        status: EventStatus,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE events SET status = $2, updated_at = NOW() WHERE id = $1")
+            .bind(event_id)
+            .bind(status.as_str())
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+
+    /// The event's last day (end_date, else date). None when the event doesn't exist.
+    pub async fn get_event_last_day(&self, event_id: Uuid) -> Result<Option<chrono::NaiveDate>, sqlx::Error> {
+        sqlx::query_scalar("SELECT COALESCE(end_date, date) FROM events WHERE id = $1")
+            .bind(event_id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Events past retention that still have unscrubbed tickets.
+    pub async fn list_events_due_for_pii_scrub(&self, retention_days: i64) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT e.id FROM events e
+             WHERE COALESCE(e.end_date, e.date) + make_interval(days => $1) <= CURRENT_DATE
+               AND EXISTS (SELECT 1 FROM tickets t WHERE t.event_id = e.id AND t.holder_hash IS NULL)
+             LIMIT 100",
+        )
+        .bind(retention_days as i32)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Detach tickets from their holders: keep a salted hash of the holder,
+    /// re-point the row at the placeholder account, drop the other links.
+    /// Already-scrubbed rows are skipped. Returns the rows scrubbed.
+    pub async fn scrub_ticket_holders_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: Uuid,
+        placeholder_user: Uuid,
+        salt: &str,
+    ) -> Result<u64, sqlx::Error> {
+        // SET expressions all read the pre-update row, so the hash is of the real holder.
+        let result = sqlx::query(
+            "UPDATE tickets
+             SET holder_hash = encode(sha256(convert_to($3 || user_id::text, 'UTF8')), 'hex'),
+                 user_id = $2, original_user_id = NULL, idempotency_key = NULL, updated_at = NOW()
+             WHERE event_id = $1 AND holder_hash IS NULL",
+        )
+        .bind(event_id)
+        .bind(placeholder_user)
+        .bind(salt)
+        .execute(&mut **tx)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Unlink payments and resale listings of the event's tickets from the
+    /// people involved. Amounts, references and statuses stay for reconciliation.
+    pub async fn scrub_event_payers_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: Uuid,
+        placeholder_user: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE payment_transactions p
+             SET user_id = $2, provider_response = p.provider_response - 'customer', updated_at = NOW()
+             FROM tickets t
+             WHERE t.id = p.ticket_id AND t.event_id = $1 AND p.user_id <> $2",
+        )
+        .bind(event_id)
+        .bind(placeholder_user)
+        .execute(&mut **tx)
+        .await?;
+        sqlx::query(
+            "UPDATE ticket_resale_listings
+             SET seller_id = $2, buyer_id = CASE WHEN buyer_id IS NULL THEN NULL ELSE $2 END
+             WHERE event_id = $1 AND (seller_id <> $2 OR buyer_id <> $2)",
+        )
+        .bind(event_id)
+        .bind(placeholder_user)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    /// Unlink the event's transfer records from the people involved: both
+    /// parties become the placeholder account and the recipient's email a
+    /// salted hash. Ticket, event and time stay as the forensic record.
+    pub async fn scrub_transfers_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: Uuid,
+        placeholder_user: Uuid,
+        salt: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE ticket_transfers
+             SET to_email = encode(sha256(convert_to($3 || to_email, 'UTF8')), 'hex'),
+                 from_user_id = $2, to_user_id = $2
+             WHERE event_id = $1 AND (from_user_id <> $2 OR to_user_id <> $2)",
+        )
+        .bind(event_id)
+        .bind(placeholder_user)
+        .bind(salt)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    /// Drop attendee emails from the audit entries of the event's tickets
+    /// (comps and reassignments record who the ticket went to).
+    pub async fn scrub_audit_emails_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE audit_log a SET metadata = a.metadata - 'email'
+             FROM tickets t
+             WHERE a.entity = 'ticket' AND a.entity_id = t.ticket_id AND t.event_id = $1
+               AND a.action IN ('ticket.comp_issued', 'ticket.reassign')
+               AND a.metadata ? 'email'",
+        )
+        .bind(event_id)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    /// Forget which staff member admitted each ticket to each session.
+    pub async fn scrub_session_scanners_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE ticket_session_admissions a SET scanned_by = NULL
+             FROM tickets t
+             WHERE a.ticket_id = t.id AND (t.event_id = $1 OR a.event_id = $1)
+               AND a.scanned_by IS NOT NULL",
+        )
+        .bind(event_id)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    /// Delete waitlist signups older than the retention period. The waitlist
+    /// isn't tied to an event, so it ages out on its own clock.
+    pub async fn purge_waitlist_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        retention_days: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM waitlist WHERE created_at < NOW() - make_interval(days => $1)")
+            .bind(retention_days as i32)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+
+    /// Drop device locations from the event's scan log. Results and times stay.
+    pub async fn scrub_scan_locations_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE scan_log SET lat = NULL, lon = NULL
+             WHERE event_id = $1 AND (lat IS NOT NULL OR lon IS NOT NULL)",
+        )
+        .bind(event_id)
+        .execute(&mut **tx)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn mark_event_pii_scrubbed_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: Uuid,
+    ) -> Result<chrono::DateTime<chrono::Utc>, sqlx::Error> {
+        sqlx::query_scalar("UPDATE events SET pii_scrubbed_at = NOW() WHERE id = $1 RETURNING pii_scrubbed_at")
+            .bind(event_id)
+            .fetch_one(&mut **tx)
+            .await
     }
 
     pub async fn check_user_ticket(&self, user_id: Uuid, event_id: Uuid) -> Result<bool, sqlx::Error> {
         let count: i64 = sqlx::query_scalar(
-            "SELECT COUNT(*) FROM tickets WHERE user_id = $1 AND event_id = $2 AND status != 'cancelled'"
+            "SELECT COUNT(*) FROM tickets WHERE user_id = $1 AND event_id = $2 AND status != $3"
         )
         .bind(user_id)
         .bind(event_id)
+        .bind(TicketStatus::Cancelled.as_str())
         .fetch_one(&self.pool)
         .await?;
         Ok(count > 0)
@@ -396,23 +801,47 @@ impl TicketRepository {
         event_id: Uuid,
     ) -> Result<bool, sqlx::Error> {
         let count: i64 = sqlx::query_scalar(
-            "SELECT COUNT(*) FROM tickets WHERE user_id = $1 AND event_id = $2 AND status != 'cancelled'"
+            "SELECT COUNT(*) FROM tickets WHERE user_id = $1 AND event_id = $2 AND status != $3"
         )
         .bind(user_id)
         .bind(event_id)
+        .bind(TicketStatus::Cancelled.as_str())
         .fetch_one(&mut **tx)
         .await?;
         Ok(count > 0)
     }
 
-    /// Insert a free ticket inside an open transaction.
-    /// The DB trigger decrements available_tickets atomically on INSERT.
+    /// Insert a free ticket inside an open transaction. Call reserve_inventory first.
     pub async fn create_free_with_tx(
         &self,
         tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
         user_id: Uuid,
         event_id: Uuid,
         currency: &str,
+    ) -> Result<Ticket, sqlx::Error> {
+        self.create_zero_price_with_tx(tx, user_id, event_id, currency, "free").await
+    }
+
+    /// Insert a comp ticket (organizer hold) inside an open transaction.
+    /// Same shape as a free ticket; payment_provider 'comp' keeps comps out of
+    /// sales reporting and distinguishable from public free claims.
+    pub async fn create_comp_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: Uuid,
+        event_id: Uuid,
+        currency: &str,
+    ) -> Result<Ticket, sqlx::Error> {
+        self.create_zero_price_with_tx(tx, user_id, event_id, currency, "comp").await
+    }
+
+    async fn create_zero_price_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: Uuid,
+        event_id: Uuid,
+        currency: &str,
+        provider: &str,
     ) -> Result<Ticket, sqlx::Error> {
         let ticket_id = format!(
             "BUKR-{}",
@@ -422,7 +851,7 @@ impl TicketRepository {
             "{{\"ticket_id\":\"{}\",\"event_id\":\"{}\"}}",
             ticket_id, event_id
         );
-        let payment_ref = format!("FREE-{}", Uuid::new_v4());
+        let payment_ref = format!("{}-{}", provider.to_uppercase(), Uuid::new_v4());
 
         let row = sqlx::query(
             r#"INSERT INTO tickets
@@ -431,12 +860,12 @@ impl TicketRepository {
                  qr_code_data, payment_ref, payment_provider, status)
             VALUES ($1, $2, $3, 'general', 1, 1, 0,
                     0, 0, 0, $6,
-                    $4, $5, 'free', 'valid')
+                    $4, $5, $7, 'valid')
             RETURNING id, ticket_id, event_id, user_id, ticket_type, quantity, usage_limit, usage_count,
                       unit_price, total_price, discount_applied, promo_code_id,
                       currency, status, qr_code_data, valid_from, valid_until,
                       payment_ref, payment_provider, excitement_rating, scanned_at,
-                      purchase_date, created_at, idempotency_key"#,
+                      purchase_date, created_at, idempotency_key, booking_fee, tax_amount, receipt_number, order_id"#,
         )
         .bind(event_id)
         .bind(user_id)
@@ -444,17 +873,586 @@ impl TicketRepository {
         .bind(&qr_data)
         .bind(&payment_ref)
         .bind(currency)
+        .bind(provider)
         .fetch_one(&mut **tx)
         .await?;
 
         Ok(row_to_ticket(&row))
     }
+
+    /// Cancel every live ticket for an event — only `user_id`'s when given —
+    /// inside an open transaction. Used and already-terminal tickets are left
+    /// alone — only 'valid' and 'pending' rows still hold inventory that can
+    /// go back on sale. A group ticket part-way through the gate is treated
+    /// like a used one: some of its seats were already admitted.
+    pub async fn cancel_event_tickets_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> Result<Vec<CancelledTicket>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"UPDATE tickets SET status = $3
+               WHERE ($1::uuid IS NULL OR user_id = $1) AND event_id = $2 AND status = ANY($4)
+                 AND scanned_quantity = 0
+               RETURNING id, ticket_id, quantity, total_price, currency,
+                         payment_ref, payment_provider,
+                         (SELECT COALESCE(SUM(p.amount), 0) FROM payment_transactions p
+                           WHERE p.ticket_id = tickets.id AND p.status = 'success'
+                             AND p.provider_ref NOT LIKE $5) AS amount_paid,
+                         (SELECT l.payment_ref FROM ticket_resale_listings l
+                           WHERE l.ticket_id = tickets.id AND l.status = 'sold'
+                           ORDER BY l.sold_at DESC LIMIT 1) AS resale_ref,
+                         (SELECT l.price FROM ticket_resale_listings l
+                           WHERE l.ticket_id = tickets.id AND l.status = 'sold'
+                           ORDER BY l.sold_at DESC LIMIT 1) AS resale_price"#,
+        )
+        .bind(user_id)
+        .bind(event_id)
+        .bind(TicketStatus::Cancelled.as_str())
+        .bind(live_statuses())
+        .bind(RESALE_REF_LIKE)
+        .fetch_all(&mut **tx)
+        .await?;
+
+        Ok(rows.iter().map(|r| CancelledTicket {
+            id: r.get("id"),
+            ticket_id: r.get("ticket_id"),
+            quantity: r.get("quantity"),
+            total_price: r.get("total_price"),
+            currency: r.get("currency"),
+            payment_ref: r.get("payment_ref"),
+            payment_provider: r.get("payment_provider"),
+            amount_paid: normalize_amount(r.get("amount_paid")),
+            resale: resale_charge(r),
+        }).collect())
+    }
+
+    /// Per-status ticket totals for an event. Read-only; feeds the cancel preview.
+    pub async fn summarize_event_tickets(&self, event_id: Uuid) -> Result<Vec<StatusSummary>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"SELECT status,
+                      COUNT(*) AS tickets,
+                      COALESCE(SUM(quantity), 0)::BIGINT AS seats,
+                      COUNT(*) FILTER (WHERE amount_paid > 0) AS paid_tickets,
+                      COALESCE(SUM(amount_paid), 0) AS paid_amount
+               FROM (
+                   SELECT t.status, t.quantity,
+                          COALESCE(
+                              (SELECT l.price FROM ticket_resale_listings l
+                                WHERE l.ticket_id = t.id AND l.status = 'sold'
+                                ORDER BY l.sold_at DESC LIMIT 1),
+                              (SELECT COALESCE(SUM(p.amount), 0) FROM payment_transactions p
+                                WHERE p.ticket_id = t.id AND p.status = 'success'
+                                  AND p.provider_ref NOT LIKE $2)
+                          ) AS amount_paid
+                   FROM tickets t WHERE t.event_id = $1
+               ) t
+               GROUP BY status"#,
+        )
+        .bind(event_id)
+        .bind(RESALE_REF_LIKE)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(|r| StatusSummary {
+            status: r.get("status"),
+            tickets: r.get("tickets"),
+            seats: r.get("seats"),
+            paid_tickets: r.get("paid_tickets"),
+            paid_amount: normalize_amount(r.get("paid_amount")),
+        }).collect())
+    }
+
+    /// Event of one of the user's tickets, read without a lock so the event
+    /// row can be locked before the ticket. None if it isn't theirs.
+    pub async fn get_owned_ticket_event_id(&self, user_id: Uuid, ticket_id: &str) -> Result<Option<Uuid>, sqlx::Error> {
+        sqlx::query_scalar("SELECT event_id FROM tickets WHERE ticket_id = $1 AND user_id = $2")
+            .bind(ticket_id)
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Lock one of the user's tickets for self-cancellation. None if it doesn't
+    /// exist or belongs to someone else. The last field counts scans of any
+    /// kind: uses spent plus group seats admitted.
+    pub async fn get_owned_ticket_for_update(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: Uuid,
+        ticket_id: &str,
+    ) -> Result<Option<(CancelledTicket, Uuid, String, i32)>, sqlx::Error> {
+        let row = sqlx::query(
+            r#"SELECT id, ticket_id, event_id, status, usage_count + scanned_quantity AS scanned, quantity, total_price,
+                      currency, payment_ref, payment_provider,
+                      (SELECT COALESCE(SUM(p.amount), 0) FROM payment_transactions p
+                        WHERE p.ticket_id = tickets.id AND p.status = 'success'
+                          AND p.provider_ref NOT LIKE $3) AS amount_paid,
+                      (SELECT l.payment_ref FROM ticket_resale_listings l
+                        WHERE l.ticket_id = tickets.id AND l.status = 'sold'
+                        ORDER BY l.sold_at DESC LIMIT 1) AS resale_ref,
+                      (SELECT l.price FROM ticket_resale_listings l
+                        WHERE l.ticket_id = tickets.id AND l.status = 'sold'
+                        ORDER BY l.sold_at DESC LIMIT 1) AS resale_price
+               FROM tickets WHERE ticket_id = $1 AND user_id = $2
+               FOR UPDATE"#,
+        )
+        .bind(ticket_id)
+        .bind(user_id)
+        .bind(RESALE_REF_LIKE)
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        let Some(r) = row else { return Ok(None) };
+        Ok(Some((
+            CancelledTicket {
+                id: r.get("id"),
+                ticket_id: r.get("ticket_id"),
+                quantity: r.get("quantity"),
+                total_price: r.get("total_price"),
+                currency: r.get("currency"),
+                payment_ref: r.get("payment_ref"),
+                payment_provider: r.get("payment_provider"),
+                amount_paid: normalize_amount(r.get("amount_paid")),
+                resale: resale_charge(&r),
+            },
+            r.get("event_id"),
+            r.get("status"),
+            r.get("scanned"),
+        )))
+    }
+
+    /// Flip one live ticket to cancelled. False if it stopped being live.
+    pub async fn cancel_ticket_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("UPDATE tickets SET status = $2 WHERE id = $1 AND status = ANY($3)")
+            .bind(id)
+            .bind(TicketStatus::Cancelled.as_str())
+            .bind(live_statuses())
+            .execute(&mut **tx)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Cancel a ticket that is still waiting for its first payment. The
+    /// seats it held come back; None if it was paid or cancelled meanwhile.
+    pub async fn cancel_pending_ticket_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        id: Uuid,
+    ) -> Result<Option<i32>, sqlx::Error> {
+        sqlx::query_scalar("UPDATE tickets SET status = $2 WHERE id = $1 AND status = $3 RETURNING quantity")
+            .bind(id)
+            .bind(TicketStatus::Cancelled.as_str())
+            .bind(TicketStatus::Pending.as_str())
+            .fetch_optional(&mut **tx)
+            .await
+    }
+
+    /// Pending tickets older than `ttl_minutes` that nothing was collected
+    /// for and that have no checkout opened within the TTL. Returns
+    /// (ticket id, event id), oldest first.
+    pub async fn list_stale_pending(&self, ttl_minutes: i64) -> Result<Vec<(Uuid, Uuid)>, sqlx::Error> {
+        sqlx::query_as(
+            "SELECT t.id, t.event_id FROM tickets t
+             WHERE t.status = $1
+               AND t.created_at < NOW() - make_interval(mins => $2)
+               AND NOT EXISTS (SELECT 1 FROM payment_transactions p
+                               WHERE p.ticket_id = t.id
+                                 AND (p.status = 'success'
+                                      OR (p.status = 'pending' AND p.created_at >= NOW() - make_interval(mins => $2))))
+             ORDER BY t.created_at
+             LIMIT 200",
+        )
+        .bind(TicketStatus::Pending.as_str())
+        .bind(ttl_minutes as i32)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Put seats back on sale — the inverse of reserve_inventory. Every
+    /// cancellation path must call this explicitly.
+    pub async fn restore_inventory_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: Uuid,
+        quantity: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE events SET available_tickets = available_tickets + $2 WHERE id = $1",
+        )
+        .bind(event_id)
+        .bind(quantity as i32)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+}
+
+impl TicketRepository {
+    /// Resolve an email to a user id, creating a passwordless placeholder account if
+    /// none exists. The placeholder is claimed later through the normal password-reset flow.
+    /// Returns (user_id, created).
+    pub async fn find_or_create_user_by_email_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        email: &str,
+    ) -> Result<(Uuid, bool), sqlx::Error> {
+        if let Some(id) = sqlx::query_scalar::<_, Uuid>("SELECT id FROM users WHERE email = $1")
+            .bind(email)
+            .fetch_optional(&mut **tx)
+            .await?
+        {
+            return Ok((id, false));
+        }
+
+        let name = email.split('@').next().unwrap_or(email);
+        let id: Uuid = sqlx::query_scalar(
+            r#"INSERT INTO users (email, name, user_type)
+               VALUES ($1, $2, 'user')
+               ON CONFLICT (email) DO UPDATE SET email = EXCLUDED.email
+               RETURNING id"#,
+        )
+        .bind(email)
+        .bind(name)
+        .fetch_one(&mut **tx)
+        .await?;
+        Ok((id, true))
+    }
+
+    /// Re-point a ticket at a new owner and rotate its QR nonce so any QR
+    /// rendered for the previous owner stops scanning.
+    pub async fn reassign_owner_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        ticket_id: &str,
+        new_user_id: Uuid,
+    ) -> Result<Option<Ticket>, sqlx::Error> {
+        let new_nonce = hex::encode(rand::random::<[u8; 32]>());
+        let row = sqlx::query(
+            r#"UPDATE tickets
+               SET original_user_id = COALESCE(original_user_id, user_id),
+                   user_id  = $2,
+                   qr_nonce = $3
+               WHERE ticket_id = $1 AND status = ANY($4)
+               RETURNING id, ticket_id, event_id, user_id, ticket_type, quantity, usage_limit, usage_count,
+                         unit_price, total_price, discount_applied, promo_code_id,
+                         currency, status, qr_code_data, valid_from, valid_until,
+                         payment_ref, payment_provider, excitement_rating, scanned_at,
+                         purchase_date, created_at, idempotency_key, booking_fee, tax_amount, receipt_number, order_id"#,
+        )
+        .bind(ticket_id)
+        .bind(new_user_id)
+        .bind(&new_nonce)
+        .bind(live_statuses())
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        Ok(row.as_ref().map(row_to_ticket))
+    }
+
+    /// Put a ticket on the scan blacklist, or update the reason if it's
+    /// already there. None when no ticket has this id.
+    pub async fn blacklist_ticket(&self, ticket_id: &str, reason: &str, admin_id: Uuid) -> Result<Option<BlacklistEntry>, sqlx::Error> {
+        let row = sqlx::query(
+            r#"INSERT INTO ticket_blacklist (ticket_id, reason, blacklisted_by)
+               SELECT ticket_id, $2, $3 FROM tickets WHERE ticket_id = $1
+               ON CONFLICT (ticket_id) DO UPDATE
+                   SET reason = EXCLUDED.reason,
+                       blacklisted_by = EXCLUDED.blacklisted_by,
+                       blacklisted_at = NOW()
+               RETURNING ticket_id, reason, blacklisted_by, blacklisted_at"#,
+        )
+        .bind(ticket_id)
+        .bind(reason)
+        .bind(admin_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|r| BlacklistEntry {
+            ticket_id: r.get("ticket_id"),
+            reason: r.get("reason"),
+            blacklisted_by: r.get("blacklisted_by"),
+            blacklisted_at: r.get("blacklisted_at"),
+        }))
+    }
+
+    /// Take a ticket off the blacklist. false when it wasn't on it.
+    pub async fn unblacklist_ticket(&self, ticket_id: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM ticket_blacklist WHERE ticket_id = $1")
+            .bind(ticket_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Add an add-on to an event.
+    pub async fn create_add_on(
+        &self,
+        event_id: Uuid,
+        name: &str,
+        price: Decimal,
+        inventory: Option<i32>,
+    ) -> Result<EventAddOn, sqlx::Error> {
+        let row = sqlx::query(
+            r#"INSERT INTO event_add_ons (event_id, name, price, inventory)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id, event_id, name, price, inventory, is_active"#,
+        )
+        .bind(event_id)
+        .bind(name)
+        .bind(price)
+        .bind(inventory)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row_to_add_on(&row))
+    }
+
+    /// Active add-ons for an event, cheapest first.
+    pub async fn list_add_ons(&self, event_id: Uuid) -> Result<Vec<EventAddOn>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"SELECT id, event_id, name, price, inventory, is_active
+               FROM event_add_ons
+               WHERE event_id = $1 AND is_active = true
+               ORDER BY price, name"#,
+        )
+        .bind(event_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.iter().map(row_to_add_on).collect())
+    }
+
+    /// Lock the requested active add-ons of an event so inventory can be
+    /// checked and decremented inside the purchase transaction.
+    pub async fn lock_add_ons_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event_id: Uuid,
+        add_on_ids: &[Uuid],
+    ) -> Result<Vec<EventAddOn>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"SELECT id, event_id, name, price, inventory, is_active
+               FROM event_add_ons
+               WHERE event_id = $1 AND id = ANY($2) AND is_active = true
+               ORDER BY id
+               FOR UPDATE"#,
+        )
+        .bind(event_id)
+        .bind(add_on_ids)
+        .fetch_all(&mut **tx)
+        .await?;
+        Ok(rows.iter().map(row_to_add_on).collect())
+    }
+
+    /// Record the add-on lines bought with a ticket and take them out of
+    /// inventory. Rows must already be locked by lock_add_ons_with_tx.
+    pub async fn attach_add_ons_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        ticket_db_id: Uuid,
+        lines: &[TicketAddOn],
+    ) -> Result<(), sqlx::Error> {
+        for line in lines {
+            sqlx::query(
+                r#"INSERT INTO ticket_add_ons (ticket_id, add_on_id, name, quantity, unit_price)
+                   VALUES ($1, $2, $3, $4, $5)"#,
+            )
+            .bind(ticket_db_id)
+            .bind(line.add_on_id)
+            .bind(&line.name)
+            .bind(line.quantity)
+            .bind(line.unit_price)
+            .execute(&mut **tx)
+            .await?;
+
+            sqlx::query(
+                "UPDATE event_add_ons SET inventory = inventory - $2 WHERE id = $1 AND inventory IS NOT NULL",
+            )
+            .bind(line.add_on_id)
+            .bind(line.quantity)
+            .execute(&mut **tx)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Put the add-ons bought with these tickets back in stock — the inverse
+    /// of attach_add_ons_with_tx, called next to restore_inventory_with_tx.
+    pub async fn restore_add_ons_with_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        ticket_db_ids: &[Uuid],
+    ) -> Result<(), sqlx::Error> {
+        if ticket_db_ids.is_empty() {
+            return Ok(());
+        }
+        sqlx::query(
+            r#"UPDATE event_add_ons a SET inventory = a.inventory + s.quantity
+               FROM (SELECT add_on_id, SUM(quantity)::INT AS quantity
+                     FROM ticket_add_ons WHERE ticket_id = ANY($1)
+                     GROUP BY add_on_id) s
+               WHERE a.id = s.add_on_id AND a.inventory IS NOT NULL"#,
+        )
+        .bind(ticket_db_ids)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    /// Add-on lines attached to a ticket.
+    pub async fn get_ticket_add_ons(&self, ticket_db_id: Uuid) -> Result<Vec<TicketAddOn>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"SELECT add_on_id, name, quantity, unit_price
+               FROM ticket_add_ons WHERE ticket_id = $1 ORDER BY created_at, name"#,
+        )
+        .bind(ticket_db_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.iter().map(|r| TicketAddOn {
+            add_on_id: r.get("add_on_id"),
+            name: r.get("name"),
+            quantity: r.get("quantity"),
+            unit_price: normalize_amount(r.get("unit_price")),
+        }).collect())
+    }
+}
+
+/// A ticket row for create_with_tx, priced and numbered by the purchase.
+pub struct NewTicket<'a> {
+    pub event_id: Uuid,
+    pub user_id: Uuid,
+    pub ticket_id: &'a str,
+    pub ticket_type: &'a str,
+    pub quantity: i32,
+    pub usage_limit: i32,                    // Also seeds usage_total / usage_left
+    pub usage_model: &'a str,
+    pub is_renewable: bool,
+    pub unit_price: Decimal,
+    pub total_price: Decimal,
+    pub discount_applied: Decimal,
+    pub promo_code_id: Option<Uuid>,
+    pub promo_pricing: Option<PromoPricing>, // Set with promo_code_id
+    pub currency: &'a str,
+    pub qr_code_data: &'a str,
+    pub payment_ref: &'a str,
+    pub payment_provider: &'a str,
+    pub excitement_rating: Option<i32>,
+    pub valid_from: Option<chrono::DateTime<Utc>>,
+    pub valid_until: Option<chrono::DateTime<Utc>>,
+    pub idempotency_key: Option<&'a str>,
+    pub booking_fee: Decimal,
+    pub tax_amount: Decimal,
+    pub receipt_number: &'a str,
+    pub status: TicketStatus,                // Paid tickets go in pending
 }
 
 pub struct EventData {
     pub id: Uuid,
     pub price: Decimal,
+    pub total_tickets: i32,
     pub available_tickets: i32,
+    pub held_tickets: i32,                   // Organizer hold, not sold publicly
+    pub overbook_allowance: i32,             // Seats a purchase may take past capacity
+    pub status: String,
+    pub currency: String,
+    pub starts_at: chrono::DateTime<Utc>,
+    pub refund_deadline: chrono::DateTime<Utc>, // Effective: the set deadline, else event start
+    pub self_cancel_deadline: chrono::DateTime<Utc>, // Effective, same default
+    pub default_ticket_type: String,         // Tier a purchase gets when it names none
+}
+
+// Event date/time are the local start; events.timezone places them in time.
+const EVENT_DATA_COLUMNS: &str = "id, price, total_tickets, available_tickets, held_tickets, overbook_allowance, status, currency, default_ticket_type,
+     (date + time) AT TIME ZONE timezone AS starts_at,
+     COALESCE(refund_deadline, (date + time) AT TIME ZONE timezone) AS refund_deadline,
+     COALESCE(self_cancel_deadline, (date + time) AT TIME ZONE timezone) AS self_cancel_deadline";
+
+fn row_to_event_data(r: &sqlx::postgres::PgRow) -> EventData {
+    EventData {
+        id: r.get("id"),
+        price: normalize_amount(r.get("price")),
+        total_tickets: r.get("total_tickets"),
+        available_tickets: r.get("available_tickets"),
+        held_tickets: r.get("held_tickets"),
+        overbook_allowance: r.get("overbook_allowance"),
+        status: r.get("status"),
+        currency: r.get("currency"),
+        starts_at: r.get("starts_at"),
+        refund_deadline: r.get("refund_deadline"),
+        self_cancel_deadline: r.get("self_cancel_deadline"),
+        default_ticket_type: r.get("default_ticket_type"),
+    }
+}
+
+pub struct OrderTicketRow {
+    pub ticket_id: String,
+    pub ticket_type: String,
+    pub quantity: i32,
+    pub status: String,
+    pub event_key: String,
+    pub rotating_ttl: Option<i64>,           // Set when the event rotates its QR
+}
+
+pub struct EventAddOn {
+    pub id: Uuid,
+    pub event_id: Uuid,
+    pub name: String,
+    pub price: Decimal,
+    pub inventory: Option<i32>,              // None = unlimited
+    pub is_active: bool,
+}
+
+// Tickets that still hold a seat and can be cancelled or reassigned.
+fn live_statuses() -> Vec<&'static str> {
+    TicketStatus::ALL.into_iter().filter(|s| s.is_live()).map(TicketStatus::as_str).collect()
+}
+
+fn row_to_add_on(r: &sqlx::postgres::PgRow) -> EventAddOn {
+    EventAddOn {
+        id: r.get("id"),
+        event_id: r.get("event_id"),
+        name: r.get("name"),
+        price: normalize_amount(r.get("price")),
+        inventory: r.get("inventory"),
+        is_active: r.get("is_active"),
+    }
+}
+
+/// Ticket counts for one status of an event — one row of the cancel preview.
+pub struct StatusSummary {
     pub status: String,
+    pub tickets: i64,
+    pub seats: i64,
+    pub paid_tickets: i64,                   // Same rule as a refund: a successful charge collected
+    pub paid_amount: Decimal,                // What their refunds would pay back
+}
+
+pub struct CancelledTicket {
+    pub id: Uuid,
+    pub ticket_id: String,
+    pub quantity: i32,
+    pub total_price: Decimal,
     pub currency: String,
+    pub payment_ref: Option<String>,
+    pub payment_provider: Option<String>,
+    pub amount_paid: Decimal,                // Successful primary-sale charges, resale excluded
+    pub resale: Option<ResaleCharge>,        // Set once resold: the current holder paid this, not amount_paid
+}
+
+/// What the current holder of a resold ticket paid: the latest sold listing.
+pub struct ResaleCharge {
+    pub payment_ref: String,
+    pub price: Decimal,
+}
+
+fn resale_charge(r: &sqlx::postgres::PgRow) -> Option<ResaleCharge> {
+    let payment_ref: Option<String> = r.get("resale_ref");
+    payment_ref.map(|payment_ref| ResaleCharge {
+        payment_ref,
+        price: normalize_amount(r.get("resale_price")),
+    })
 }
@@ -0,0 +1,429 @@
+// Ticket Resale — face-value marketplace
+//
+// POST   /api/v1/tickets/:ticket_id/resale          list (owner)
+// DELETE /api/v1/tickets/:ticket_id/resale          withdraw (owner)
+// GET    /api/v1/tickets/event/:event_id/resale     open listings
+// POST   /api/v1/tickets/resale/:listing_id/buy     start checkout (buyer)
+//
+// Anti-scalping: a ticket can only be listed at or below what the seller
+// paid (its total_price), and only while it is valid and has never been
+// scanned. Same time lock as a plain transfer.
+//
+// Settlement:
+// 1. buy_resale holds the listing for the buyer (pending, RESALE_HOLD_MINUTES)
+//    and opens a provider checkout under a BUKR-RSL- reference
+// 2. charge.success for that reference calls settle()
+// 3. settle() re-checks the ticket under lock, then moves ownership exactly
+//    like a transfer (new QR nonce, ticket_transfers audit row)
+// 4. If the ticket stopped being sellable in between (seller scanned in,
+//    listing taken over after the hold lapsed), the listing is voided and a
+//    resale.refund_requested outbox event refunds the buyer
+
+use axum::{
+    extract::State,
+    http::HeaderMap,
+};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::error::{is_unique_violation, AppError, Result};
+use crate::extract::{Json, Path};
+use crate::payments::service::PaymentService;
+use super::status::TicketStatus;
+use super::transfer::{check_not_blacklisted, TRANSFER_LOCK_MINUTES};
+
+/// Payment references for resale checkouts carry this prefix so the webhook
+/// can route them to settle() instead of primary-sale activation.
+pub const RESALE_REF_PREFIX: &str = "BUKR-RSL-";
+
+/// RESALE_REF_PREFIX as a LIKE pattern. A resale charge pays the seller, not
+/// the event, so sums of what a ticket collected at primary sale leave it out.
+pub const RESALE_REF_LIKE: &str = "BUKR-RSL-%";
+
+/// How long a buyer's checkout holds a listing before another buyer may take it.
+pub const RESALE_HOLD_MINUTES: i64 = 15;
+
+#[derive(Debug, Deserialize)]
+pub struct ListForResaleRequest {
+    pub price: Decimal,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BuyResaleRequest {
+    pub provider: String,
+    pub callback_url: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResaleListing {
+    pub listing_id: Uuid,
+    pub ticket_id: String,
+    pub event_id: Uuid,
+    pub ticket_type: String,
+    pub quantity: i32,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub price: Decimal,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub face_value: Decimal,                 // What the seller paid — the cap
+    pub currency: String,
+    #[serde(with = "crate::timestamp")]
+    pub listed_at: chrono::DateTime<Utc>,
+}
+
+fn extract_user_id(headers: &HeaderMap) -> Result<Uuid> {
+    headers
+        .get("x-user-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| Uuid::parse_str(s).ok())
+        .ok_or(AppError::Unauthorized)
+}
+
+/// Price must be positive and not above the original total_price.
+pub(crate) fn check_resale_price(price: Decimal, face_value: Decimal) -> Result<()> {
+    if price <= Decimal::ZERO {
+        return Err(AppError::Validation("Resale price must be greater than zero".into()));
+    }
+    if price > face_value {
+        return Err(AppError::Validation(format!(
+            "Resale price cannot exceed the original price of {}",
+            face_value.round_dp(2)
+        )));
+    }
+    Ok(())
+}
+
+/// Valid and never scanned — the only state a ticket may be resold in.
+pub(crate) fn check_resellable(status: TicketStatus, usage_count: i32, scanned: bool) -> Result<()> {
+    if status != TicketStatus::Valid {
+        return Err(AppError::BadRequest(format!("Cannot resell a ticket with status '{}'", status)));
+    }
+    if usage_count > 0 || scanned {
+        return Err(AppError::BadRequest("Cannot resell a ticket that has been scanned".into()));
+    }
+    Ok(())
+}
+
+pub async fn list_for_resale(
+    State(pool): State<Arc<PgPool>>,
+    headers: HeaderMap,
+    Path(ticket_id_str): Path<String>,
+    Json(req): Json<ListForResaleRequest>,
+) -> Result<Json<Value>> {
+    let caller_id = extract_user_id(&headers)?;
+    let mut tx = pool.begin().await.map_err(AppError::Database)?;
+
+    let row = sqlx::query(
+        r#"SELECT t.id, t.event_id, t.user_id, t.status, t.usage_count, t.scanned_at,
+                  t.ticket_type, t.quantity, t.total_price, t.currency,
+                  (e.date + e.time) AT TIME ZONE e.timezone AS event_starts_at,
+                  EXISTS(SELECT 1 FROM ticket_blacklist b WHERE b.ticket_id = t.ticket_id) AS blacklisted
+           FROM tickets t
+           JOIN events e ON e.id = t.event_id
+           WHERE t.ticket_id = $1
+           FOR UPDATE OF t"#,
+    )
+    .bind(&ticket_id_str)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(AppError::Database)?
+    .ok_or_else(|| AppError::NotFound("Ticket not found".into()))?;
+
+    let ticket_uuid: Uuid = row.get("id");
+    let event_id: Uuid = row.get("event_id");
+    let face_value: Decimal = row.get("total_price");
+    let currency: String = row.get("currency");
+    let event_starts_at: chrono::DateTime<Utc> = row.get("event_starts_at");
+
+    if row.get::<Uuid, _>("user_id") != caller_id {
+        return Err(AppError::Forbidden);
+    }
+    check_resellable(
+        TicketStatus::from_db(row.get("status"))?,
+        row.get("usage_count"),
+        row.get::<Option<chrono::DateTime<Utc>>, _>("scanned_at").is_some(),
+    )?;
+    check_not_blacklisted(row.get("blacklisted"), "resell")?;
+    check_resale_price(req.price, face_value)?;
+    if (event_starts_at - Utc::now()).num_minutes() < TRANSFER_LOCK_MINUTES {
+        return Err(AppError::Validation(format!(
+            "Resale closes {} minutes before the event", TRANSFER_LOCK_MINUTES
+        )));
+    }
+
+    // The partial unique index is the real guard; this gives the clearer error.
+    let open: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM ticket_resale_listings WHERE ticket_id = $1 AND status IN ('listed', 'pending'))",
+    )
+    .bind(ticket_uuid)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(AppError::Database)?;
+    if open {
+        return Err(AppError::Conflict("Ticket is already listed for resale".into()));
+    }
+
+    let listing = sqlx::query(
+        r#"INSERT INTO ticket_resale_listings (ticket_id, event_id, seller_id, price, currency)
+           VALUES ($1, $2, $3, $4, $5)
+           RETURNING id, created_at"#,
+    )
+    .bind(ticket_uuid)
+    .bind(event_id)
+    .bind(caller_id)
+    .bind(req.price)
+    .bind(&currency)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| if is_unique_violation(&e) {
+        // Lost a race with a concurrent listing of the same ticket
+        AppError::Conflict("Ticket is already listed for resale".into())
+    } else {
+        AppError::Database(e)
+    })?;
+
+    tx.commit().await.map_err(AppError::Database)?;
+
+    let listing = ResaleListing {
+        listing_id: listing.get("id"),
+        ticket_id: ticket_id_str,
+        event_id,
+        ticket_type: row.get("ticket_type"),
+        quantity: row.get("quantity"),
+        price: req.price,
+        face_value,
+        currency,
+        listed_at: listing.get("created_at"),
+    };
+    tracing::info!("Ticket {} listed for resale at {} by {}", listing.ticket_id, listing.price, caller_id);
+
+    Ok(Json(json!({ "status": "success", "data": listing })))
+}
+
+/// Withdraw a listing. A listing with a buyer mid-checkout can't be pulled
+/// until their hold lapses.
+pub async fn cancel_resale(
+    State(pool): State<Arc<PgPool>>,
+    headers: HeaderMap,
+    Path(ticket_id_str): Path<String>,
+) -> Result<Json<Value>> {
+    let caller_id = extract_user_id(&headers)?;
+
+    let result = sqlx::query(
+        r#"UPDATE ticket_resale_listings l
+           SET status = 'cancelled', buyer_id = NULL, reserved_until = NULL
+           FROM tickets t
+           WHERE t.id = l.ticket_id AND t.ticket_id = $1 AND l.seller_id = $2
+             AND (l.status = 'listed' OR (l.status = 'pending' AND l.reserved_until < NOW()))"#,
+    )
+    .bind(&ticket_id_str)
+    .bind(caller_id)
+    .execute(pool.as_ref())
+    .await
+    .map_err(AppError::Database)?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("No open resale listing for this ticket".into()));
+    }
+    Ok(Json(json!({ "status": "success", "data": { "ticket_id": ticket_id_str, "cancelled": true } })))
+}
+
+pub async fn list_event_resales(
+    State(pool): State<Arc<PgPool>>,
+    Path(event_id): Path<Uuid>,
+) -> Result<Json<Value>> {
+    let rows = sqlx::query(
+        r#"SELECT l.id, t.ticket_id, l.event_id, t.ticket_type, t.quantity,
+                  l.price, t.total_price, l.currency, l.created_at
+           FROM ticket_resale_listings l
+           JOIN tickets t ON t.id = l.ticket_id
+           WHERE l.event_id = $1
+             AND (l.status = 'listed' OR (l.status = 'pending' AND l.reserved_until < NOW()))
+           ORDER BY l.price, l.created_at"#,
+    )
+    .bind(event_id)
+    .fetch_all(pool.as_ref())
+    .await
+    .map_err(AppError::Database)?;
+
+    let listings: Vec<ResaleListing> = rows.iter().map(|r| ResaleListing {
+        listing_id: r.get("id"),
+        ticket_id: r.get("ticket_id"),
+        event_id: r.get("event_id"),
+        ticket_type: r.get("ticket_type"),
+        quantity: r.get("quantity"),
+        price: r.get("price"),
+        face_value: r.get("total_price"),
+        currency: r.get("currency"),
+        listed_at: r.get("created_at"),
+    }).collect();
+
+    Ok(Json(json!({ "status": "success", "data": listings })))
+}
+
+pub async fn buy_resale(
+    State(service): State<Arc<PaymentService>>,
+    headers: HeaderMap,
+    Path(listing_id): Path<Uuid>,
+    Json(req): Json<BuyResaleRequest>,
+) -> Result<Json<Value>> {
+    let buyer_id = extract_user_id(&headers)?;
+    let result = service.initialize_resale(buyer_id, listing_id, req).await?;
+    Ok(Json(json!({ "status": "success", "data": result })))
+}
+
+/**
+ * Settle a paid resale — called from the charge.success webhook
+ *
+ * Idempotent: only a pending listing with this reference is acted on, so a
+ * redelivered webhook finds nothing to do.
+ */
+pub(crate) async fn settle(pool: &PgPool, reference: &str) -> Result<()> {
+    let mut tx = pool.begin().await.map_err(AppError::Database)?;
+
+    let listing = sqlx::query(
+        r#"SELECT l.id, l.ticket_id, l.seller_id, l.buyer_id, l.price, l.currency,
+                  t.user_id, t.status, t.usage_count, t.scanned_at, t.ticket_id AS ticket_code,
+                  t.event_id, u.email AS buyer_email,
+                  EXISTS(SELECT 1 FROM ticket_blacklist b WHERE b.ticket_id = t.ticket_id) AS blacklisted
+           FROM ticket_resale_listings l
+           JOIN tickets t ON t.id = l.ticket_id
+           JOIN users u ON u.id = l.buyer_id
+           WHERE l.payment_ref = $1 AND l.status = 'pending'
+           FOR UPDATE OF l, t"#,
+    )
+    .bind(reference)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(AppError::Database)?;
+
+    let Some(listing) = listing else {
+        // Already handled (sold, or voided with a refund queued) — a redelivery.
+        // Otherwise the hold lapsed first: the seller withdrew the listing or
+        // another buyer took it over (payment_ref overwritten). That money goes back.
+        let settled: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM ticket_resale_listings WHERE payment_ref = $1 AND status IN ('sold', 'void'))",
+        )
+        .bind(reference)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::Database)?;
+        if !settled {
+            tracing::warn!("Resale payment {} has no listing — requesting refund", reference);
+            request_refund(&mut tx, None, reference, "listing_taken").await?;
+        }
+        return tx.commit().await.map_err(AppError::Database);
+    };
+
+    let listing_id: Uuid = listing.get("id");
+    let ticket_uuid: Uuid = listing.get("ticket_id");
+    let seller_id: Uuid = listing.get("seller_id");
+    let buyer_id: Uuid = listing.get("buyer_id");
+
+    let status = TicketStatus::from_db(listing.get("status"))?;
+    let sellable = listing.get::<Uuid, _>("user_id") == seller_id
+        && check_resellable(
+            status,
+            listing.get("usage_count"),
+            listing.get::<Option<chrono::DateTime<Utc>>, _>("scanned_at").is_some(),
+        ).is_ok()
+        && check_not_blacklisted(listing.get("blacklisted"), "resell").is_ok();
+
+    if !sellable {
+        sqlx::query("UPDATE ticket_resale_listings SET status = 'void' WHERE id = $1")
+            .bind(listing_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::Database)?;
+        request_refund(&mut tx, Some(listing_id), reference, "ticket_unavailable").await?;
+        tx.commit().await.map_err(AppError::Database)?;
+        tracing::warn!("Resale {} voided: ticket no longer sellable ({})", listing_id, status);
+        return Ok(());
+    }
+
+    // Same ownership move as a transfer — the seller's QR dies here.
+    let new_nonce = hex::encode(rand::random::<[u8; 32]>());
+    sqlx::query(
+        r#"UPDATE tickets
+           SET user_id          = $2,
+               qr_nonce         = $3,
+               transferred_from = COALESCE(transferred_from, id),
+               transferred_at   = NOW(),
+               original_user_id = COALESCE(original_user_id, $4)
+           WHERE id = $1"#,
+    )
+    .bind(ticket_uuid)
+    .bind(buyer_id)
+    .bind(&new_nonce)
+    .bind(seller_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::Database)?;
+
+    sqlx::query(
+        r#"INSERT INTO ticket_transfers
+           (ticket_id, from_user_id, to_user_id, to_email, ticket_id_str, event_id)
+           VALUES ($1, $2, $3, $4, $5, $6)"#,
+    )
+    .bind(ticket_uuid)
+    .bind(seller_id)
+    .bind(buyer_id)
+    .bind(listing.get::<String, _>("buyer_email"))
+    .bind(listing.get::<String, _>("ticket_code"))
+    .bind(listing.get::<Uuid, _>("event_id"))
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::Database)?;
+
+    sqlx::query("UPDATE ticket_resale_listings SET status = 'sold', sold_at = NOW() WHERE id = $1")
+        .bind(listing_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::Database)?;
+
+    // Seller payout goes through the same outbox consumer as refunds.
+    crate::outbox::enqueue(
+        &mut tx,
+        "resale.settled",
+        listing_id,
+        json!({
+            "listing_id": listing_id,
+            "seller_id": seller_id,
+            "buyer_id": buyer_id,
+            "payment_ref": reference,
+            "amount": listing.get::<Decimal, _>("price"),
+            "currency": listing.get::<String, _>("currency"),
+        }),
+    )
+    .await
+    .map_err(AppError::Database)?;
+
+    tx.commit().await.map_err(AppError::Database)?;
+    tracing::info!("Resale {} settled: ticket {} moved {} -> {}", listing_id, ticket_uuid, seller_id, buyer_id);
+    Ok(())
+}
+
+async fn request_refund(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    listing_id: Option<Uuid>,
+    reference: &str,
+    reason: &str,
+) -> Result<()> {
+    crate::outbox::enqueue(
+        tx,
+        "resale.refund_requested",
+        listing_id.unwrap_or_else(Uuid::nil),
+        json!({
+            "listing_id": listing_id,
+            "payment_ref": reference,
+            "payment_provider": "paystack",
+            "reason": reason,
+        }),
+    )
+    .await
+    .map_err(AppError::Database)
+}
@@ -0,0 +1,66 @@
+// Attendee data retention — when an event's PII may be scrubbed, and the
+// background job that does it.
+//
+// The retention clock starts on the event's last day (end_date, else date).
+// TicketService::scrub_event_pii does the work; this module only decides
+// when and drives the schedule. PII_SCRUB_INTERVAL_SECS=0 leaves scrubbing
+// to the admin endpoint.
+
+use std::sync::Arc;
+
+use chrono::{Duration, NaiveDate};
+use uuid::Uuid;
+
+use super::service::TicketService;
+
+/// Placeholder account scrubbed tickets are re-pointed at (seeded by 054_pii_retention.sql).
+pub const REMOVED_ATTENDEE_ID: Uuid = Uuid::nil();
+
+/// Whether the retention period after `last_day` is over on `today`.
+pub fn scrub_due(last_day: NaiveDate, retention_days: i64, today: NaiveDate) -> bool {
+    today >= last_day + Duration::days(retention_days.max(0))
+}
+
+/// Periodically scrub every event whose retention period has passed.
+pub fn spawn_scrub_job(service: Arc<TicketService>, interval_secs: u64) {
+    tokio::spawn(async move {
+        let mut tick = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+        loop {
+            tick.tick().await;
+            let due = match service.events_due_for_pii_scrub().await {
+                Ok(ids) => ids,
+                Err(e) => {
+                    tracing::error!("PII scrub poll failed: {}", e);
+                    continue;
+                }
+            };
+            for event_id in due {
+                match service.scrub_event_pii(event_id, None).await {
+                    Ok(r) => tracing::info!("Scrubbed PII for event {} ({} tickets)", event_id, r.tickets_scrubbed),
+                    Err(e) => tracing::error!("PII scrub of event {} failed: {}", event_id, e),
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn due_once_retention_has_passed() {
+        assert!(!scrub_due(day("2026-01-10"), 30, day("2026-02-08")));
+        assert!(scrub_due(day("2026-01-10"), 30, day("2026-02-09")));
+    }
+
+    #[test]
+    fn zero_retention_is_due_the_day_of() {
+        assert!(scrub_due(day("2026-01-10"), 0, day("2026-01-10")));
+        assert!(!scrub_due(day("2026-01-10"), 0, day("2026-01-09")));
+    }
+}
@@ -15,11 +15,48 @@ use rust_decimal::Decimal;
 use sqlx::Row;
 use uuid::Uuid;
 
+use std::sync::Arc;
+
 use crate::error::{AppError, Result};
-use crate::fees::{compute_fees, validate_min_price, FeeMode};
+use crate::event_cache::{CachedEvent, EventCache};
+use crate::fees::{compute_booking_fee, compute_tax, split_order, format_amount, normalize_amount, validate_min_price, FeeMode};
+use crate::currency::Currency;
+use crate::money::Money;
+use crate::payments::service::{check_allowed_provider, check_provider_currency};
 use crate::promos::repository::PromoRepository;
-use super::dto::{PurchaseTicketRequest, TicketResponse, PaymentInitResponse, PurchaseResponse};
-use super::repository::TicketRepository;
+use crate::promos::service::PromoPricing;
+use super::dto::{
+    AddOnResponse, AddOnSelection, BlacklistEntry, BulkCancelResponse, CancelPreviewResponse, CancelTicketResponse, CompTicketResponse, CreateAddOnRequest, EventStatusResponse, OrderTicket, OrderTicketsResponse, HoldResponse, DefaultTicketTypeResponse, LowStockThresholdResponse, MaxInsideResponse, OverbookResponse, PiiScrubResponse, PurchaseTicketRequest, ReassignTicketResponse, RefundDeadlineResponse, ResendConfirmationResponse, SelfCancelDeadlineResponse, TicketAddOn, TicketResponse, TicketTypeResponse, PaymentInitResponse, PurchaseResponse,
+};
+use super::qr_image;
+use super::barcode_image;
+use super::qr_zip;
+use super::repository::{EventAddOn, NewTicket, StatusSummary, TicketRepository};
+use super::event_status::EventStatus;
+use super::retention::{scrub_due, REMOVED_ATTENDEE_ID};
+use super::resale::RESALE_REF_LIKE;
+use super::status::TicketStatus;
+
+// Per-line cap on add-on quantity — same spirit as the 1-10 ticket cap.
+const MAX_ADD_ON_QUANTITY: i32 = 10;
+
+// Highest per-ticket price a pay-what-you-want buyer can choose. Ten of
+// them plus fees still fit tickets.total_price (NUMERIC(12,2)).
+const MAX_CHOSEN_PRICE: Decimal = Decimal::from_parts(100_000_000, 0, 0, false, 0);
+
+// Longest tier name tickets.ticket_type can hold.
+const MAX_TICKET_TYPE_LEN: usize = 50;
+
+/// Tickets per will-call QR ZIP; larger events page with ?page=.
+pub const QR_BATCH_PAGE_SIZE: i64 = 500;
+
+/// One page of the will-call QR download.
+pub struct QrBatch {
+    pub event_key: String,
+    pub ticket_ids: Vec<String>,
+    pub page: u32,
+    pub has_more: bool,
+}
 
 /**
  * TicketService: The conductor of the ticket purchase orchestra
@@ -32,11 +69,25 @@ pub struct TicketService {
     // qr_secret injected from Config — never read from env directly.
     // Keeps the startup validation in config.rs as the single enforcement point.
     qr_secret: String,
+    // Display fields only (title, date, venue) — inventory and price always
+    // come from the locked event row.
+    events: Arc<EventCache>,
+    // Days after an event's last day before attendee PII may be scrubbed.
+    pii_retention_days: i64,
+    // Salt for scrubbed holder hashes — deliberately not qr_secret.
+    pii_hash_secret: String,
 }
 
 impl TicketService {
-    pub fn new(repo: TicketRepository, promo_repo: PromoRepository, qr_secret: String) -> Self {
-        Self { repo, promo_repo, qr_secret }
+    pub fn new(
+        repo: TicketRepository,
+        promo_repo: PromoRepository,
+        qr_secret: String,
+        events: Arc<EventCache>,
+        pii_retention_days: i64,
+        pii_hash_secret: String,
+    ) -> Self {
+        Self { repo, promo_repo, qr_secret, events, pii_retention_days, pii_hash_secret }
     }
 
     /**
@@ -46,7 +97,8 @@ impl TicketService {
      * 1. Quantity must be 1-10 (no bulk buying, no zero buying)
      * 2. Event must exist and be active (can't buy tickets to imaginary events)
      * 3. Tickets must be available (first come, first served)
-     * 4. Promo codes must be valid if provided (no fake discounts)
+     * 4. Promo codes must be valid if provided (no fake discounts), and
+     *    within the event's promo budget when it has one
      * 5. Price calculation must be accurate (math matters)
      * 
      * Flow:
@@ -56,7 +108,8 @@ impl TicketService {
      * 4. Validate promo code (if provided)
      * 5. Calculate final price
      * 6. Generate ticket ID and QR code
-     * 7. Create ticket in database
+     * 7. Create ticket in database - pending until the payment webhook
+     *    confirms, unless nothing is owed
      * 8. Generate payment reference
      * 9. Return ticket + payment info
      * 
@@ -74,47 +127,31 @@ impl TicketService {
             return Err(AppError::Validation("Quantity must be between 1 and 10".into()));
         }
         if let Some(rating) = req.excitement_rating {
-            if rating < 1 || rating > 5 {
-                return Err(AppError::Validation("Excitement rating must be between 1 and 5".into()));
+            if rating < 1 || rating > 10 {
+                return Err(AppError::Validation("Excitement rating must be between 1 and 10".into()));
             }
         }
-        if req.payment_provider != "paystack" {
-            return Err(AppError::Validation("Only 'paystack' is supported".into()));
-        }
 
         // ── STEP 1: Validate promo code BEFORE acquiring the row lock ────────────────────
         // Promo validation is a read-only query with no side effects.
         // Doing it outside the transaction means the row lock on `events` is held
         // for the minimum possible time — critical during high-concurrency sales.
-        let (promo_code_id, discount) = if let Some(ref code) = req.promo_code {
-            let promo = self.promo_repo.validate(req.event_id, code).await
-                .map_err(AppError::Database)?;
-            match promo {
-                Some(p) => (Some(p.id), p.discount_percentage),
+        let promo = match req.promo_code {
+            Some(ref code) => match self.promo_repo.validate(req.event_id, code).await.map_err(AppError::Database)? {
+                Some(p) => Some(p),
                 None => return Err(AppError::PromoInvalid("Invalid or expired promo code".into())),
-            }
-        } else {
-            (None, Decimal::ZERO)
+            },
+            None => None,
         };
 
         // ── STEP 2: Open transaction and acquire row lock ─────────────────────────────
         let mut tx = self.repo.pool().begin().await.map_err(AppError::Database)?;
 
-        // If idempotency_key is provided, check if we already processed this request
-        if let Some(ref key) = req.idempotency_key {
-            if let Some(existing) = self.repo.get_by_idempotency_key(&mut tx, user_id, req.event_id, key).await.map_err(AppError::Database)? {
-                // Return existing ticket data immediately to avoid double charge
-                let title: String = sqlx::query_scalar("SELECT title FROM events WHERE id = $1").bind(req.event_id).fetch_one(&mut *tx).await.unwrap_or_default();
-                // ... (reconstruct response DTOs)
-                // Note: simplified for brevity, in production we ensure consistent response format
-                return Ok(self.build_purchase_response(existing, title, req.payment_provider, req.quantity).await?);
-            }
-        }
-
         let row = sqlx::query(
-            r#"SELECT title, date::text as date, time::text as time, location, price, currency,
-                      available_tickets, organizer_id, is_multi_use, max_usage,
-                      is_time_bound, duration_minutes
+            r#"SELECT price, currency, available_tickets, held_tickets, organizer_id, is_multi_use, max_usage,
+                      is_time_bound, duration_minutes, booking_fee_pct, booking_fee_flat,
+                      tax_percentage, tax_inclusive, allowed_providers, require_rating, min_price,
+                      overbook_allowance, low_stock_threshold, default_ticket_type, promo_budget
             FROM events WHERE id = $1 AND status = 'active' FOR UPDATE"#,
         )
         .bind(req.event_id)
@@ -123,34 +160,120 @@ impl TicketService {
         .map_err(AppError::Database)?
         .ok_or_else(|| AppError::NotFound("Event not found".into()))?;
 
-        let title: String = row.get("title");
-        let date: String = row.get("date");
-        let time: String = row.get("time");
-        let location: String = row.get("location");
-        let unit_price: Decimal = row.get("price");
-        let currency: String = row.get("currency");
+        // Range is checked up front; presence only once we know the event wants it.
+        check_rating_required(row.get("require_rating"), req.excitement_rating)?;
+
+        // Organizer's allowed providers first (clear error listing the set),
+        // then what this service can actually initialize today.
+        let allowed_providers: Option<Vec<String>> = row.get("allowed_providers");
+        check_allowed_provider(&req.payment_provider, allowed_providers.as_deref())?;
+        if req.payment_provider != "paystack" {
+            return Err(AppError::Validation("Only 'paystack' is supported".into()));
+        }
+
+        // Purchase intent (idempotency_key / intent_id): a repeat of the same intent
+        // returns the original purchase instead of a second ticket + payment session.
+        // Checked AFTER the event lock — a double-click's second request blocks on
+        // FOR UPDATE until the first commits, then sees its ticket here.
+        if let Some(ref key) = req.idempotency_key {
+            if let Some(existing) = self.repo.get_by_idempotency_key(&mut tx, user_id, req.event_id, key).await.map_err(AppError::Database)? {
+                tracing::info!("Purchase intent {} replayed for user {} — returning ticket {}", key, user_id, existing.ticket_id);
+                let event = self.event_details(req.event_id).await?;
+                let add_ons = self.repo.get_ticket_add_ons(existing.id).await.map_err(AppError::Database)?;
+                return self.replay_purchase_response(&event, existing, add_ons, req.payment_provider).await;
+            }
+        }
+
+        // Donation events: the buyer's chosen price replaces the list price.
+        let unit_price = resolve_unit_price(
+            normalize_amount(row.get("price")),
+            row.get::<Option<Decimal>, _>("min_price"),
+            req.chosen_price,
+        )?;
+        // Stored as free text — never forward a typo to the provider
+        let currency = Currency::parse(row.get("currency"))?;
+        check_provider_currency(&req.payment_provider, currency.as_str(), allowed_providers.as_deref())?;
         let available: i32 = row.get("available_tickets");
+        let held: i32 = row.get("held_tickets");
+        let overbook: i32 = row.get("overbook_allowance");
+        let low_stock_threshold: Option<i32> = row.get("low_stock_threshold");
+        let default_ticket_type: String = row.get("default_ticket_type");
         let organizer_id: Uuid = row.get("organizer_id");
 
         let is_multi_use: bool = row.get("is_multi_use");
         let max_usage: i32 = row.get("max_usage");
         let is_time_bound: bool = row.get("is_time_bound");
         let duration_minutes: Option<i32> = row.get("duration_minutes");
+        let booking_fee_pct: Decimal = row.get("booking_fee_pct");
+        let booking_fee_flat: Decimal = normalize_amount(row.get("booking_fee_flat"));
+        let tax_percentage: Decimal = row.get("tax_percentage");
+        let tax_inclusive: bool = row.get("tax_inclusive");
 
-        if available < req.quantity {
+        // Organizer hold is off public sale; an overbook allowance sells past capacity
+        if sellable(available, held, overbook) < req.quantity {
             return Err(AppError::TicketsExhausted);
         }
 
+        // Uses left are re-read under lock — the check in STEP 1 may be stale.
+        // discount is the headline percentage; pricing is what each ticket is charged.
+        let (promo_code_id, discount, pricing, promo_split) = match promo {
+            Some(mut p) => {
+                let (limit, used) = self.promo_repo.lock_usage_with_tx(&mut tx, p.id).await
+                    .map_err(AppError::Database)?
+                    .ok_or_else(|| AppError::PromoInvalid("Invalid or expired promo code".into()))?;
+                p.ticket_limit = limit;
+                p.used_count = used;
+                let (discount, pricing, split) = crate::promos::service::apply_usage_limit(&p, req.quantity)?;
+                (Some(p.id), discount, pricing, split)
+            }
+            None => (None, Decimal::ZERO, PromoPricing::NONE, None),
+        };
+
+        // Event-wide promo budget — the running total is summed under the event
+        // lock, so two discounted sales can't both squeeze under it.
+        let promo_budget: Option<Decimal> = row.get("promo_budget");
+        if promo_code_id.is_some() && promo_budget.is_some() {
+            let given = self.promo_repo.discount_given_with_tx(&mut tx, req.event_id).await
+                .map_err(AppError::Database)?;
+            crate::promos::service::check_promo_budget(
+                promo_budget,
+                given,
+                pricing.discount_amount(unit_price),
+            )?;
+        }
+
+        // Add-on rows are locked after the event row (same order everywhere),
+        // so their inventory check and decrement are atomic with the ticket.
+        let (add_on_lines, add_ons_total) = if req.add_ons.is_empty() {
+            (Vec::new(), Decimal::ZERO)
+        } else {
+            let ids: Vec<Uuid> = req.add_ons.iter().map(|a| a.add_on_id).collect();
+            let locked = self.repo.lock_add_ons_with_tx(&mut tx, req.event_id, &ids).await
+                .map_err(AppError::Database)?;
+            price_add_ons(&req.add_ons, &locked).map_err(AppError::Validation)?
+        };
+
         // ── STEP 3: Fee calculation (pure math, no I/O, lock still held) ─────────────
         validate_min_price(unit_price).map_err(AppError::Validation)?;
-        let discount_multiplier = (Decimal::from(100) - discount) / Decimal::from(100);
-        let desired_payout = unit_price * discount_multiplier;
+        // Discounted and full-price tickets are priced separately — fees are per ticket.
         let fee_mode = FeeMode::default();
-        let fees = compute_fees(desired_payout, req.quantity, &fee_mode);
-        let total_price    = fees.buyer_total;
+        let split = split_order(&pricing.lines(unit_price, req.quantity), add_ons_total, &fee_mode);
+        let fees = &split.tickets;
+        // Tax applies to the post-discount ticket subtotal. Inclusive mode backs
+        // it out of the price (total unchanged); exclusive mode adds it on top.
+        let tax            = compute_tax(fees.buyer_total, tax_percentage, tax_inclusive);
+        let tax_amount     = tax.tax_amount;
+        let ticket_total   = Money::new(tax.gross, currency.clone());
+        // Booking fee is per order, on top of the ticket total — buyer pays it.
+        let booking_fee    = ticket_total.with_amount(
+            compute_booking_fee(fees.buyer_total, booking_fee_pct, booking_fee_flat).total,
+        );
+        // Add-ons are charged at list price — no promo discount, no ticket fees.
+        let add_ons_total  = ticket_total.with_amount(add_ons_total);
+        let total_price    = ticket_total.checked_add(&booking_fee)?.checked_add(&add_ons_total)?;
         let platform_fee   = fees.platform_fee;
         let bukrshield_fee = fees.bukrshield_fee;
-        let organizer_payout = fees.organizer_payout;
+        let organizer_payout = ticket_total.with_amount(split.organizer_payout);
 
         // ── STEP 4: Generate IDs (no I/O) ─────────────────────────────────────────
         let short_id = rand::random::<u16>();
@@ -191,26 +314,65 @@ impl TicketService {
             "ticketId": ticket_id_str,
             "eventId": req.event_id.to_string(),
         }).to_string();
-        let timestamp = chrono::Utc::now().timestamp();
-        let pay_rand: u32 = rand::random();
-        let payment_ref = format!("BUKR-PAY-{}-{:06x}", timestamp, pay_rand);
-        let ticket_type = req.ticket_type.as_deref().unwrap_or("General Admission");
+        let payment_ref = crate::payment_ref::reserve(&mut tx, crate::payment_ref::TICKET_PREFIX).await?;
+        let ticket_type = req.ticket_type.as_deref().unwrap_or(&default_ticket_type);
 
-        // ── STEP 5: Insert ticket within transaction, then COMMIT ──────────────────
-        let ticket = self.repo.create_with_tx(
-            &mut tx,
-            req.event_id, user_id, &ticket_id_str, ticket_type, req.quantity,
-            usage_limit, &usage_model, is_renewable, unit_price, total_price,
-            discount, promo_code_id, &currency,
-            &qr_data, &payment_ref, &req.payment_provider, req.excitement_rating,
-            valid_from, valid_until, req.idempotency_key.as_deref()
-        ).await.map_err(|e| {
-            if e.to_string().contains("Not enough tickets") {
-                AppError::TicketsExhausted
-            } else {
-                AppError::Database(e)
-            }
-        })?;
+        // ── STEP 5: Take inventory, insert ticket within transaction, then COMMIT ──
+        let remaining = self.repo.reserve_inventory(&mut tx, req.event_id, req.quantity, overbook).await?;
+        let receipt_seq = self.repo.next_receipt_seq(&mut tx, req.event_id).await
+            .map_err(AppError::Database)?;
+        let receipt_number = format_receipt_number(receipt_seq);
+        let ticket = self.repo.create_with_tx(&mut tx, &NewTicket {
+            event_id: req.event_id,
+            user_id,
+            ticket_id: &ticket_id_str,
+            ticket_type,
+            quantity: req.quantity,
+            usage_limit,
+            usage_model: &usage_model,
+            is_renewable,
+            unit_price,
+            total_price: total_price.amount(),
+            discount_applied: discount,
+            promo_code_id,
+            promo_pricing: promo_code_id.map(|_| pricing),
+            currency: currency.as_str(),
+            qr_code_data: &qr_data,
+            payment_ref: &payment_ref,
+            payment_provider: &req.payment_provider,
+            excitement_rating: req.excitement_rating,
+            valid_from,
+            valid_until,
+            idempotency_key: req.idempotency_key.as_deref(),
+            booking_fee: booking_fee.amount(),
+            tax_amount,
+            receipt_number: &receipt_number,
+            status: initial_status(&total_price),
+        }).await.map_err(AppError::Database)?;
+
+        if !add_on_lines.is_empty() {
+            self.repo.attach_add_ons_with_tx(&mut tx, ticket.id, &add_on_lines).await
+                .map_err(AppError::Database)?;
+        }
+
+        // Low-stock alert: written with the sale, so it fires exactly once —
+        // the flag flip and the outbox row commit or roll back together.
+        let public_remaining = public_available(remaining, held);
+        if low_stock_reached(public_remaining, low_stock_threshold)
+            && self.repo.claim_low_stock_alert(&mut tx, req.event_id).await.map_err(AppError::Database)?
+        {
+            crate::outbox::enqueue(
+                &mut tx,
+                "inventory.low",
+                req.event_id,
+                serde_json::json!({
+                    "event_id": req.event_id,
+                    "organizer_id": organizer_id,
+                    "remaining": public_remaining,
+                    "threshold": low_stock_threshold,
+                }),
+            ).await.map_err(AppError::Database)?;
+        }
 
         // COMMIT — row lock released here. All subsequent work is non-blocking.
         tx.commit().await.map_err(AppError::Database)?;
@@ -240,49 +402,25 @@ impl TicketService {
             .await;
         });
 
-        // ── STEP 7: Revenue ledger — fire-and-forget after lock release ───────────
-        let pool = self.repo.pool();
-        let ticket_db_id = ticket.id;
-
-        if platform_fee > Decimal::ZERO {
-            let _ = sqlx::query(
-                r#"INSERT INTO platform_revenue
-                   (source, reference_id, organizer_id, amount, currency, meta)
-                   VALUES ('ticket_fee', $1, $2, $3, $4, $5)"#,
-            )
-            .bind(ticket_db_id)
-            .bind(organizer_id)
-            .bind(platform_fee)
-            .bind(&currency)
-            .bind(serde_json::json!({ "quantity": req.quantity, "unit_price": unit_price }))
-            .execute(pool)
-            .await;
-        }
-
-        if bukrshield_fee > Decimal::ZERO {
-            let _ = sqlx::query(
-                r#"INSERT INTO platform_revenue
-                   (source, reference_id, organizer_id, amount, currency, meta)
-                   VALUES ('bukrshield_fee', $1, $2, $3, $4, $5)"#,
-            )
-            .bind(ticket_db_id)
-            .bind(organizer_id)
-            .bind(bukrshield_fee)
-            .bind(&currency)
-            .bind(serde_json::json!({ "quantity": req.quantity }))
-            .execute(pool)
-            .await;
-        }
-
+        // Display fields from the event cache — not needed under the lock.
+        let event = self.event_details(req.event_id).await?;
+        let start = event.local_start();
         let ticket_resp = TicketResponse {
             id: ticket.id, ticket_id: ticket.ticket_id, event_id: ticket.event_id,
-            event_title: title, event_date: date, event_time: time, event_location: location,
+            event_title: event.title.clone(), event_date: start.date,
+            event_time: start.time, event_starts_at: start.starts_at,
+            event_timezone: event.timezone.clone(), event_location: event.location.clone(),
+            event_emoji: event.emoji.clone(), event_image_url: event.image_url.clone(),
             ticket_type: ticket.ticket_type, quantity: ticket.quantity,
             usage_limit: ticket.usage_limit, usage_count: ticket.usage_count,
             unit_price: ticket.unit_price, discount_applied: ticket.discount_applied,
-            total_price: ticket.total_price, currency: ticket.currency.clone(),
+            total_price: ticket.total_price,
+            total_price_formatted: format_amount(ticket.total_price, &ticket.currency),
+            currency: ticket.currency.clone(),
             status: ticket.status, qr_code_data: ticket.qr_code_data,
             valid_from: ticket.valid_from, valid_until: ticket.valid_until,
+            receipt_number: ticket.receipt_number,
+            order_id: ticket.order_id,
             purchase_date: ticket.purchase_date,
         };
 
@@ -291,14 +429,84 @@ impl TicketService {
             authorization_url: Some(format!("https://checkout.paystack.com/{}", payment_ref)),
             checkout_url: None,
             reference: payment_ref,
-            amount: total_price,
-            currency: ticket.currency,
+            amount: total_price.amount(),
+            amount_formatted: total_price.formatted(),
+            booking_fee: booking_fee.amount(),
+            tax_amount,
+            currency: currency.to_string(),
             platform_fee,
             bukrshield_fee,
-            organizer_payout,
+            organizer_payout: organizer_payout.amount(),
+        };
+
+        Ok(PurchaseResponse { ticket: ticket_resp, payment: payment_resp, add_ons: add_on_lines, promo_split })
+    }
+
+    /**
+     * Purchase without an account - guest checkout
+     *
+     * Business Rules:
+     * 1. guest_email is required; it is the buyer's identity for this order
+     * 2. Invite-only events are closed to guests - the guest list is checked
+     *    against a signed-in email, and a typed one proves nothing
+     * 3. The email resolves to its existing account, or a passwordless
+     *    placeholder (same as comps and transfers) the buyer can claim later
+     *    through password reset
+     * 4. Everything else is the normal purchase, as that user
+     *
+     * @param req - Purchase request with guest_email set
+     * @returns The buyer's user id (for payment initialization) and the purchase
+     */
+    pub async fn guest_purchase(&self, req: PurchaseTicketRequest) -> Result<(Uuid, PurchaseResponse)> {
+        let email = normalize_guest_email(req.guest_email.as_deref())?;
+
+        let access_mode = self.repo.get_event_access_mode(req.event_id).await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Event not found".into()))?;
+        if access_mode != "public" {
+            return Err(AppError::Forbidden);
+        }
+
+        let mut tx = self.repo.pool().begin().await.map_err(AppError::Database)?;
+        let (user_id, account_created) = self.repo
+            .find_or_create_user_by_email_with_tx(&mut tx, &email).await
+            .map_err(AppError::Database)?;
+        tx.commit().await.map_err(AppError::Database)?;
+
+        let result = self.purchase(user_id, req).await?;
+        tracing::info!("Guest checkout {} for {} (account created: {})", result.ticket.ticket_id, email, account_created);
+        Ok((user_id, result))
+    }
+
+    /**
+     * Take back a guest checkout whose payment never started
+     *
+     * The buyer never reached a checkout page, so the pending ticket is
+     * cancelled and its seats go back on sale. Event row first, then the
+     * ticket, same order as bulk_cancel.
+     *
+     * @param event_id - Event the ticket is for
+     * @param ticket_db_id - Ticket primary key
+     * @returns Seats put back (0 if the ticket was no longer pending)
+     */
+    pub async fn release_unpaid(&self, event_id: Uuid, ticket_db_id: Uuid) -> Result<i64> {
+        let mut tx = self.repo.pool().begin().await.map_err(AppError::Database)?;
+        self.repo.get_event_for_update(&mut tx, event_id).await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Event not found".into()))?;
+
+        let Some(quantity) = self.repo.cancel_pending_ticket_with_tx(&mut tx, ticket_db_id).await
+            .map_err(AppError::Database)? else {
+            return Ok(0);
         };
+        let restored = quantity as i64;
+        self.repo.restore_inventory_with_tx(&mut tx, event_id, restored).await
+            .map_err(AppError::Database)?;
+        self.repo.restore_add_ons_with_tx(&mut tx, &[ticket_db_id]).await
+            .map_err(AppError::Database)?;
 
-        Ok(PurchaseResponse { ticket: ticket_resp, payment: payment_resp })
+        tx.commit().await.map_err(AppError::Database)?;
+        Ok(restored)
     }
 
     /**
@@ -307,9 +515,9 @@ impl TicketService {
      * Simple delegation to repository - no business logic needed here
      * 
      * @param user_id - User's UUID
-     * @returns List of tickets owned by this user
+     * @returns List of tickets owned by this user, with event emoji and image
      */
-    pub async fn get_user_tickets(&self, user_id: Uuid) -> Result<Vec<super::dto::Ticket>> {
+    pub async fn get_user_tickets(&self, user_id: Uuid) -> Result<Vec<super::dto::UserTicket>> {
         self.repo.get_user_tickets(user_id).await.map_err(AppError::Database)
     }
 
@@ -328,14 +536,8 @@ impl TicketService {
     // Verify the caller owns the event. Returns Forbidden if not.
     // Used by handlers that need ownership checks beyond what the gateway provides.
     pub async fn verify_event_owner(&self, user_id: Uuid, event_id: Uuid) -> Result<()> {
-        let row = sqlx::query("SELECT organizer_id FROM events WHERE id = $1")
-            .bind(event_id)
-            .fetch_optional(self.repo.pool())
-            .await
-            .map_err(AppError::Database)?
-            .ok_or_else(|| AppError::NotFound("Event not found".into()))?;
-        let organizer_id: Uuid = row.get("organizer_id");
-        if organizer_id != user_id {
+        let event = self.event_details(event_id).await?;
+        if event.organizer_id != user_id {
             return Err(AppError::Forbidden);
         }
         Ok(())
@@ -381,7 +583,8 @@ impl TicketService {
         if event.price > Decimal::ZERO {
             return Err(AppError::BadRequest("Event is not free".into()));
         }
-        if event.available_tickets <= 0 {
+        let currency = crate::currency::normalize(&event.currency)?;
+        if public_available(event.available_tickets, event.held_tickets) <= 0 {
             return Err(AppError::BadRequest("No tickets available".into()));
         }
 
@@ -392,7 +595,8 @@ impl TicketService {
             return Err(AppError::BadRequest("Already claimed ticket for this event".into()));
         }
 
-        let ticket = self.repo.create_free_with_tx(&mut tx, user_id, event_id, &event.currency).await
+        self.repo.reserve_inventory(&mut tx, event_id, 1, 0).await?;
+        let ticket = self.repo.create_free_with_tx(&mut tx, user_id, event_id, &currency).await
             .map_err(AppError::Database)?;
 
         tx.commit().await.map_err(AppError::Database)?;
@@ -400,86 +604,1524 @@ impl TicketService {
     }
 
     /**
-     * Get dynamic QR payload for a ticket
-     * 
-     * Generates a 3-second rotating TOTP-style QR payload
-     * Used by the frontend to refresh the QR code every 3s
-     * 
-     * @param ticket_id - Human-readable ticket ID
-     * @returns Signed QR JSON payload
+     * Set the organizer hold on an event
+     *
+     * Held tickets stay in available_tickets but are off public sale; only
+     * issue_comp can draw from them.
+     *
+     * @param organizer_id - Caller; must own the event
+     * @param event_id - Event to adjust
+     * @param held - New absolute hold (0 releases it)
+     * @returns Inventory after the change
      */
-    pub async fn get_dynamic_qr(&self, ticket_id: &str, user_id: Uuid) -> Result<String> {
-        // Ownership check: user_id must match the ticket owner.
-        // Without this, any authenticated user can fetch any ticket's QR by guessing the ID.
-        let row = sqlx::query(
-            r#"SELECT e.event_key 
-               FROM tickets t 
-               JOIN events e ON t.event_id = e.id 
-               WHERE t.ticket_id = $1 AND t.user_id = $2"#
-        )
-        .bind(ticket_id)
-        .bind(user_id)
-        .fetch_optional(self.repo.pool())
-        .await
-        .map_err(AppError::Database)?
-        .ok_or_else(|| AppError::NotFound("Ticket not found".into()))?;
+    pub async fn set_hold(&self, organizer_id: Uuid, event_id: Uuid, held: i32) -> Result<HoldResponse> {
+        self.verify_event_owner(organizer_id, event_id).await?;
 
-        let event_key: String = row.get("event_key");
+        let mut tx = self.repo.pool().begin().await.map_err(AppError::Database)?;
+        let event = self.repo.get_event_for_update(&mut tx, event_id).await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Event not found".into()))?;
 
-        // Use the injected secret — same key ScannerService uses to verify.
-        let qr_secret = &self.qr_secret;
+        validate_hold(held, event.available_tickets).map_err(AppError::Validation)?;
 
-        let now = chrono::Utc::now().timestamp();
-        let window = now / 3;
+        self.repo.set_held_tickets_with_tx(&mut tx, event_id, held).await
+            .map_err(AppError::Database)?;
+        crate::audit::record(&mut *tx, Some(organizer_id), "event.hold_set", "event", event_id,
+            serde_json::json!({ "held_tickets": held, "previous": event.held_tickets }))
+            .await.map_err(AppError::Database)?;
+        tx.commit().await.map_err(AppError::Database)?;
 
-        // Calculate time-based nonce (TOTP style)
-        use hmac::{Hmac, Mac};
-        use sha2::Sha256;
-        let mut mac = Hmac::<Sha256>::new_from_slice(qr_secret.as_bytes())
-            .expect("HMAC accepts any key size");
-        mac.update(format!("{}:{}", ticket_id, window).as_bytes());
-        let nonce = hex::encode(mac.finalize().into_bytes());
+        tracing::info!("Event {} hold set to {} by {}", event_id, held, organizer_id);
 
-        // Sign the nonce
-        let mut mac_sig = Hmac::<Sha256>::new_from_slice(qr_secret.as_bytes())
-            .expect("HMAC accepts any key size");
-        mac_sig.update(format!("{}:{}", ticket_id, nonce).as_bytes());
-        let sig = hex::encode(mac_sig.finalize().into_bytes());
+        Ok(HoldResponse {
+            event_id,
+            held_tickets: held,
+            available_tickets: event.available_tickets,
+            public_available: public_available(event.available_tickets, held),
+        })
+    }
 
-        Ok(serde_json::json!({
-            "ticketId": ticket_id,
-            "eventKey": event_key,
-            "nonce": nonce,
-            "sig": sig,
-            "ts": now
-        }).to_string())
+    /**
+     * Set the refund deadline for an event
+     *
+     * Must not be after the event starts — refunds never run past the start.
+     * None restores the default (event start).
+     *
+     * @param organizer_id - Caller; must own the event
+     * @param event_id - Event to configure
+     * @param deadline - New deadline, or None to clear
+     * @returns The effective refund window
+     */
+    pub async fn set_refund_deadline(
+        &self,
+        organizer_id: Uuid,
+        event_id: Uuid,
+        deadline: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<RefundDeadlineResponse> {
+        self.verify_event_owner(organizer_id, event_id).await?;
+
+        let event = self.repo.get_event(event_id).await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Event not found".into()))?;
+        if deadline.is_some_and(|d| d > event.starts_at) {
+            return Err(AppError::Validation("refund_deadline cannot be after the event starts".into()));
+        }
+
+        self.repo.set_refund_deadline(event_id, deadline).await.map_err(AppError::Database)?;
+        crate::audit::record_logged(self.repo.pool(), Some(organizer_id), "event.refund_deadline_set", "event", event_id,
+            serde_json::json!({ "refund_deadline": deadline })).await;
+
+        Ok(RefundDeadlineResponse {
+            event_id,
+            refund_deadline: deadline.unwrap_or(event.starts_at),
+            event_starts_at: event.starts_at,
+        })
     }
 
-    async fn build_purchase_response(&self, ticket: super::dto::Ticket, title: String, provider: String, _qty: i32) -> Result<PurchaseResponse> {
-        let ticket_resp = TicketResponse {
-            id: ticket.id, ticket_id: ticket.ticket_id, event_id: ticket.event_id,
-            event_title: title, event_date: "N/A".into(), event_time: "N/A".into(), event_location: "N/A".into(),
-            ticket_type: ticket.ticket_type, quantity: ticket.quantity,
-            usage_limit: ticket.usage_limit, usage_count: ticket.usage_count,
-            unit_price: ticket.unit_price, discount_applied: ticket.discount_applied,
-            total_price: ticket.total_price, currency: ticket.currency.clone(),
-            status: ticket.status, qr_code_data: ticket.qr_code_data,
-            valid_from: ticket.valid_from, valid_until: ticket.valid_until,
-            purchase_date: ticket.purchase_date,
-        };
+    /**
+     * Alert the organizer when an event's stock runs low
+     *
+     * The first purchase that leaves public inventory at or below the
+     * threshold writes an "inventory.low" outbox event; later sales don't.
+     * Saving the setting (even unchanged) re-arms the alert.
+     *
+     * @param organizer_id - Caller; must own the event
+     * @param event_id - Event to configure
+     * @param threshold - Seats left that trigger the alert, None to turn it off
+     * @returns The threshold and current public stock
+     */
+    pub async fn set_low_stock_threshold(
+        &self,
+        organizer_id: Uuid,
+        event_id: Uuid,
+        threshold: Option<i32>,
+    ) -> Result<LowStockThresholdResponse> {
+        if threshold.is_some_and(|t| t < 0) {
+            return Err(AppError::Validation("low_stock_threshold cannot be negative".into()));
+        }
+        self.verify_event_owner(organizer_id, event_id).await?;
 
-        let payment_resp = PaymentInitResponse {
-            provider,
-            authorization_url: None,
-            checkout_url: None,
-            reference: ticket.payment_ref.unwrap_or_default(),
-            amount: ticket.total_price,
-            currency: ticket.currency,
-            platform_fee: Decimal::ZERO,
-            bukrshield_fee: Decimal::ZERO,
-            organizer_payout: Decimal::ZERO,
+        let (available, held) = self.repo.set_low_stock_threshold(event_id, threshold).await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Event not found".into()))?;
+        crate::audit::record_logged(self.repo.pool(), Some(organizer_id), "event.low_stock_set", "event", event_id,
+            serde_json::json!({ "low_stock_threshold": threshold })).await;
+
+        Ok(LowStockThresholdResponse {
+            event_id,
+            low_stock_threshold: threshold,
+            public_available: public_available(available, held),
+        })
+    }
+
+    /**
+     * Preview cancelling an event — counts only, nothing changes
+     *
+     * Mirrors what a cancel does to tickets: live ones (valid, pending)
+     * are cancelled and their seats released, and each one with a
+     * successful charge gets back what was collected. Used and already-closed tickets are untouched
+     * but still listed by status so the organizer sees the whole picture.
+     *
+     * @param organizer_id - Caller; must own the event
+     * @param event_id - Event that would be cancelled
+     * @returns Tickets by status plus what the cancel would affect and refund
+     */
+    pub async fn cancel_preview(&self, organizer_id: Uuid, event_id: Uuid) -> Result<CancelPreviewResponse> {
+        self.verify_event_owner(organizer_id, event_id).await?;
+
+        let event = self.repo.get_event(event_id).await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Event not found".into()))?;
+        let summary = self.repo.summarize_event_tickets(event_id).await
+            .map_err(AppError::Database)?;
+
+        Ok(cancel_impact(event_id, Currency::parse(&event.currency)?, &summary))
+    }
+
+    /**
+     * Name the tier a purchase gets when it doesn't pick one
+     *
+     * Applies to tickets bought from now on; existing tickets keep the
+     * type they were issued with.
+     *
+     * @param organizer_id - Caller; must own the event
+     * @param event_id - Event to configure
+     * @param name - Tier name, e.g. "Regular" or "Standing"
+     * @returns The default now in effect
+     */
+    pub async fn set_default_ticket_type(
+        &self,
+        organizer_id: Uuid,
+        event_id: Uuid,
+        name: &str,
+    ) -> Result<DefaultTicketTypeResponse> {
+        let name = normalize_ticket_type(name)?;
+        self.verify_event_owner(organizer_id, event_id).await?;
+
+        if !self.repo.set_default_ticket_type(event_id, &name).await.map_err(AppError::Database)? {
+            return Err(AppError::NotFound("Event not found".into()));
+        }
+        crate::audit::record_logged(self.repo.pool(), Some(organizer_id), "event.default_ticket_type_set", "event", event_id,
+            serde_json::json!({ "default_ticket_type": name })).await;
+
+        Ok(DefaultTicketTypeResponse { event_id, default_ticket_type: name })
+    }
+
+    /**
+     * Let an event sell past its nominal capacity
+     *
+     * Purchases may take up to total_tickets + allowance seats, betting on
+     * no-shows. 0 turns overbooking off; lowering it never un-sells tickets.
+     *
+     * @param organizer_id - Caller; must own the event
+     * @param event_id - Event to configure
+     * @param allowance - Extra seats beyond total_tickets (>= 0)
+     * @returns Nominal and effective capacity
+     */
+    pub async fn set_overbook_allowance(
+        &self,
+        organizer_id: Uuid,
+        event_id: Uuid,
+        allowance: i32,
+    ) -> Result<OverbookResponse> {
+        if allowance < 0 {
+            return Err(AppError::Validation("overbook_allowance cannot be negative".into()));
+        }
+        self.verify_event_owner(organizer_id, event_id).await?;
+
+        let total_tickets = self.repo.set_overbook_allowance(event_id, allowance).await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Event not found".into()))?;
+        crate::audit::record_logged(self.repo.pool(), Some(organizer_id), "event.overbook_set", "event", event_id,
+            serde_json::json!({ "overbook_allowance": allowance })).await;
+
+        Ok(OverbookResponse {
+            event_id,
+            total_tickets,
+            overbook_allowance: allowance,
+            effective_capacity: total_tickets + allowance,
+        })
+    }
+
+    /**
+     * Cap how many people may be inside the venue at once
+     *
+     * Enforced at the door: once admissions not checked out reach the cap,
+     * scans are refused as at_capacity even for valid tickets. Lowering it
+     * below the current headcount turns nobody out, it only stops entries.
+     *
+     * @param organizer_id - Caller; must own the event
+     * @param event_id - Event to configure
+     * @param max_inside - Headcount cap (> 0), None to remove it
+     * @returns The cap now in effect
+     */
+    pub async fn set_max_concurrent_inside(
+        &self,
+        organizer_id: Uuid,
+        event_id: Uuid,
+        max_inside: Option<i32>,
+    ) -> Result<MaxInsideResponse> {
+        if max_inside.is_some_and(|max| max <= 0) {
+            return Err(AppError::Validation("max_concurrent_inside must be positive".into()));
+        }
+        self.verify_event_owner(organizer_id, event_id).await?;
+
+        if !self.repo.set_max_concurrent_inside(event_id, max_inside).await.map_err(AppError::Database)? {
+            return Err(AppError::NotFound("Event not found".into()));
+        }
+        crate::audit::record_logged(self.repo.pool(), Some(organizer_id), "event.max_inside_set", "event", event_id,
+            serde_json::json!({ "max_concurrent_inside": max_inside })).await;
+
+        Ok(MaxInsideResponse { event_id, max_concurrent_inside: max_inside })
+    }
+
+    /**
+     * Move an event along its lifecycle
+     *
+     * The event row is locked for the check and the write, so two organizer
+     * tabs can't both act on the same old status.
+     *
+     * Cancelling takes the tickets with it, in the same transaction: every
+     * live ticket is cancelled, its seats released, and each one a charge was
+     * collected for gets a refund request in the outbox — what cancel_preview
+     * shows. No refund window applies; the organizer called the event off.
+     * A group ticket some of whose seats were already admitted stays as it
+     * is, like a used one.
+     *
+     * Edge cases:
+     * - Only legal moves (see EventStatus); repeating the current status is rejected
+     * - A cancelled event can be reinstated only while none of its tickets
+     *   has been refunded or has a refund queued. Reinstating doesn't revive
+     *   tickets cancelled along with the event
+     *
+     * @param organizer_id - Caller; must own the event
+     * @param event_id - Event to move
+     * @param status - Requested status
+     * @returns Previous and new status, plus what a cancel did to tickets
+     */
+    pub async fn set_event_status(&self, organizer_id: Uuid, event_id: Uuid, status: &str) -> Result<EventStatusResponse> {
+        let next: EventStatus = status.trim().to_ascii_lowercase().parse().map_err(AppError::Validation)?;
+        self.verify_event_owner(organizer_id, event_id).await?;
+
+        let mut tx = self.repo.pool().begin().await.map_err(AppError::Database)?;
+        let current = self.repo.lock_event_status_with_tx(&mut tx, event_id).await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Event not found".into()))?;
+        let current = EventStatus::from_db(&current)?;
+
+        let refunded = if current == EventStatus::Cancelled {
+            self.repo.count_refunded_tickets_with_tx(&mut tx, event_id).await.map_err(AppError::Database)?
+        } else {
+            0
         };
+        check_event_transition(current, next, refunded)?;
+
+        self.repo.set_event_status_with_tx(&mut tx, event_id, next).await
+            .map_err(AppError::Database)?;
+
+        let (mut tickets_cancelled, mut refunds_enqueued) = (0, 0);
+        if next == EventStatus::Cancelled {
+            let cancelled = self.repo.cancel_event_tickets_with_tx(&mut tx, event_id, None).await
+                .map_err(AppError::Database)?;
+            let restored: i64 = cancelled.iter().map(|t| t.quantity as i64).sum();
+            if restored > 0 {
+                self.repo.restore_inventory_with_tx(&mut tx, event_id, restored).await
+                    .map_err(AppError::Database)?;
+            }
+            let ids: Vec<Uuid> = cancelled.iter().map(|t| t.id).collect();
+            self.repo.restore_add_ons_with_tx(&mut tx, &ids).await.map_err(AppError::Database)?;
+            refunds_enqueued = enqueue_refunds(&mut tx, &cancelled, "event_cancelled", organizer_id).await?;
+            tickets_cancelled = cancelled.len() as i64;
+        }
+        tx.commit().await.map_err(AppError::Database)?;
+
+        crate::audit::record_logged(self.repo.pool(), Some(organizer_id), "event.status_change", "event", event_id,
+            serde_json::json!({
+                "from": current.as_str(),
+                "to": next.as_str(),
+                "tickets_cancelled": tickets_cancelled,
+                "refunds_enqueued": refunds_enqueued,
+            })).await;
+
+        Ok(EventStatusResponse {
+            event_id,
+            previous_status: current.to_string(),
+            status: next.to_string(),
+            tickets_cancelled,
+            refunds_enqueued,
+        })
+    }
+
+    /**
+     * Set how late buyers may cancel their own tickets
+     *
+     * Same rules as the refund deadline: not after the event starts, None
+     * restores the default (event start).
+     *
+     * @param organizer_id - Caller; must own the event
+     * @param event_id - Event to configure
+     * @param deadline - New deadline, or None to clear
+     * @returns The effective self-cancel window
+     */
+    pub async fn set_self_cancel_deadline(
+        &self,
+        organizer_id: Uuid,
+        event_id: Uuid,
+        deadline: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<SelfCancelDeadlineResponse> {
+        self.verify_event_owner(organizer_id, event_id).await?;
 
-        Ok(PurchaseResponse { ticket: ticket_resp, payment: payment_resp })
+        let event = self.repo.get_event(event_id).await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Event not found".into()))?;
+        if deadline.is_some_and(|d| d > event.starts_at) {
+            return Err(AppError::Validation("self_cancel_deadline cannot be after the event starts".into()));
+        }
+
+        self.repo.set_self_cancel_deadline(event_id, deadline).await.map_err(AppError::Database)?;
+        crate::audit::record_logged(self.repo.pool(), Some(organizer_id), "event.self_cancel_deadline_set", "event", event_id,
+            serde_json::json!({ "self_cancel_deadline": deadline })).await;
+
+        Ok(SelfCancelDeadlineResponse {
+            event_id,
+            self_cancel_deadline: deadline.unwrap_or(event.starts_at),
+            event_starts_at: event.starts_at,
+        })
     }
+
+    /**
+     * Buyer cancels one of their own tickets
+     *
+     * Business Rules:
+     * 1. Only the owner, only live tickets that were never scanned
+     * 2. Closed at the event's self_cancel_deadline (default: event start) —
+     *    after that the buyer has to go through support
+     * 3. Paid tickets also need the refund window open; a refund request is
+     *    written to the outbox in the same transaction
+     * 4. A ticket listed for resale must be withdrawn first
+     *
+     * @param user_id - Caller; must own the ticket
+     * @param ticket_id - Human-readable ticket ID
+     * @returns Seats restored and whether a refund was queued
+     */
+    pub async fn cancel(&self, user_id: Uuid, ticket_id: &str) -> Result<CancelTicketResponse> {
+        let event_id = self.repo.get_owned_ticket_event_id(user_id, ticket_id).await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Ticket not found".into()))?;
+
+        // Event row first, then the ticket — the order bulk_cancel and
+        // purchase take, so the two can't deadlock
+        let mut tx = self.repo.pool().begin().await.map_err(AppError::Database)?;
+        let event = self.repo.get_event_for_update(&mut tx, event_id).await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Event not found".into()))?;
+
+        let (ticket, _, status, scanned) = self.repo
+            .get_owned_ticket_for_update(&mut tx, user_id, ticket_id).await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Ticket not found".into()))?;
+
+        let status = TicketStatus::from_db(&status)?;
+        if !status.is_live() {
+            return Err(AppError::BadRequest(format!("Cannot cancel a ticket with status '{}'", status)));
+        }
+        if scanned > 0 {
+            return Err(AppError::BadRequest("Cannot cancel a ticket that has been scanned".into()));
+        }
+
+        let now = chrono::Utc::now();
+        check_self_cancel_window(event.self_cancel_deadline, now)?;
+        let paid = is_paid_ticket(&ticket);
+        if paid {
+            check_refund_window(event.refund_deadline, now)?;
+        }
+
+        let listed: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM ticket_resale_listings WHERE ticket_id = $1 AND status IN ('listed', 'pending'))",
+        )
+        .bind(ticket.id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::Database)?;
+        if listed {
+            return Err(AppError::Conflict("Ticket is listed for resale — withdraw the listing first".into()));
+        }
+
+        if !self.repo.cancel_ticket_with_tx(&mut tx, ticket.id).await.map_err(AppError::Database)? {
+            return Err(AppError::Conflict("Ticket changed while cancelling".into()));
+        }
+        let restored = ticket.quantity as i64;
+        self.repo.restore_inventory_with_tx(&mut tx, event_id, restored).await
+            .map_err(AppError::Database)?;
+        self.repo.restore_add_ons_with_tx(&mut tx, &[ticket.id]).await
+            .map_err(AppError::Database)?;
+
+        enqueue_refunds(&mut tx, std::slice::from_ref(&ticket), "self_cancel", user_id).await?;
+
+        tx.commit().await.map_err(AppError::Database)?;
+        tracing::info!("Ticket {} cancelled by owner {} (refund queued: {})", ticket.ticket_id, user_id, paid);
+
+        Ok(CancelTicketResponse {
+            ticket_id: ticket.ticket_id,
+            restored_inventory: restored,
+            refund_requested: paid,
+        })
+    }
+
+    /**
+     * Queue a fresh confirmation for a ticket the buyer never received
+     *
+     * Business Rules:
+     * 1. Caller must own the ticket (someone else's is 404)
+     * 2. Ticket must be live and its payment confirmed by the provider
+     * 3. At most one resend per ticket per RESEND_COOLDOWN_SECS — the last
+     *    ticket.confirmation outbox row is the clock, checked under the
+     *    ticket row lock so two quick clicks can't both slip through
+     *
+     * @param user_id - Caller; must own the ticket
+     * @param ticket_id - Human-readable ticket ID
+     * @returns When the confirmation was queued
+     */
+    pub async fn resend_confirmation(&self, user_id: Uuid, ticket_id: &str) -> Result<ResendConfirmationResponse> {
+        let mut tx = self.repo.pool().begin().await.map_err(AppError::Database)?;
+
+        let (ticket, event_id, status, _) = self.repo
+            .get_owned_ticket_for_update(&mut tx, user_id, ticket_id).await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Ticket not found".into()))?;
+
+        let status = TicketStatus::from_db(&status)?;
+        if !status.is_live() {
+            return Err(AppError::BadRequest(format!("Cannot resend confirmation for a ticket with status '{}'", status)));
+        }
+
+        let paid: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM payment_transactions WHERE ticket_id = $1 AND status = 'success')",
+        )
+        .bind(ticket.id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::Database)?;
+        if !paid {
+            return Err(AppError::BadRequest("Payment for this ticket has not been confirmed".into()));
+        }
+
+        let last: Option<chrono::DateTime<chrono::Utc>> = sqlx::query_scalar(
+            r#"SELECT MAX(created_at) FROM outbox_events
+               WHERE event_type = 'ticket.confirmation' AND aggregate_id = $1"#,
+        )
+        .bind(ticket.id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::Database)?;
+        let now = chrono::Utc::now();
+        check_resend_cooldown(last, now)?;
+
+        crate::outbox::enqueue(
+            &mut tx,
+            "ticket.confirmation",
+            ticket.id,
+            serde_json::json!({
+                "ticket_id": ticket.ticket_id,
+                "event_id": event_id,
+                "user_id": user_id,
+                "payment_ref": ticket.payment_ref,
+                "amount": ticket.total_price,
+                "currency": ticket.currency,
+                "resend": true,
+            }),
+        ).await.map_err(AppError::Database)?;
+
+        tx.commit().await.map_err(AppError::Database)?;
+        tracing::info!("Confirmation resend queued for ticket {} by {}", ticket.ticket_id, user_id);
+
+        Ok(ResendConfirmationResponse { ticket_id: ticket.ticket_id, queued_at: now })
+    }
+
+    /**
+     * Issue a comp ticket from the organizer hold
+     *
+     * Business Rules:
+     * 1. Caller must own the event
+     * 2. The hold must have at least one ticket left
+     * 3. Recipient is found or created by email, same as reassign
+     *
+     * The hold shrinks by one and reserve_inventory takes one from
+     * available_tickets, so public availability is unchanged.
+     *
+     * @param organizer_id - Caller; must own the event
+     * @param event_id - Event to issue for
+     * @param email - Recipient email
+     * @returns The comp ticket and remaining hold
+     */
+    pub async fn issue_comp(&self, organizer_id: Uuid, event_id: Uuid, email: &str) -> Result<CompTicketResponse> {
+        let email = email.trim().to_lowercase();
+        if email.is_empty() || !email.contains('@') {
+            return Err(AppError::Validation("Valid email required".into()));
+        }
+        self.verify_event_owner(organizer_id, event_id).await?;
+
+        let mut tx = self.repo.pool().begin().await.map_err(AppError::Database)?;
+        let event = self.repo.get_event_for_update(&mut tx, event_id).await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Event not found".into()))?;
+
+        if event.held_tickets <= 0 || event.available_tickets <= 0 {
+            return Err(AppError::BadRequest("No held tickets left to comp".into()));
+        }
+
+        let (user_id, account_created) = self.repo
+            .find_or_create_user_by_email_with_tx(&mut tx, &email).await
+            .map_err(AppError::Database)?;
+
+        let remaining = event.held_tickets - 1;
+        self.repo.set_held_tickets_with_tx(&mut tx, event_id, remaining).await
+            .map_err(AppError::Database)?;
+        self.repo.reserve_inventory(&mut tx, event_id, 1, 0).await?;
+        let ticket = self.repo.create_comp_with_tx(&mut tx, user_id, event_id, &event.currency).await
+            .map_err(AppError::Database)?;
+        crate::audit::record(&mut *tx, Some(organizer_id), "ticket.comp_issued", "ticket", &ticket.ticket_id,
+            serde_json::json!({ "event_id": event_id, "email": email, "account_created": account_created }))
+            .await.map_err(AppError::Database)?;
+
+        tx.commit().await.map_err(AppError::Database)?;
+
+        tracing::info!("Comp ticket {} issued by {} to {}", ticket.ticket_id, organizer_id, email);
+
+        Ok(CompTicketResponse {
+            ticket_id: ticket.ticket_id,
+            user_id,
+            email,
+            account_created,
+            held_tickets_remaining: remaining,
+        })
+    }
+
+    /**
+     * Bulk-cancel a user's tickets for an event (support dispute flow)
+     *
+     * Business Rules:
+     * 1. Only 'valid' / 'pending' tickets are cancelled - scanned tickets stay used,
+     *    and so does a group ticket with some of its seats already admitted
+     * 2. Inventory is restored in the same transaction as the cancellation
+     * 3. Paid tickets get a refund request written to the outbox, also in-transaction
+     * 4. Refunds are only queued before the event's refund deadline; past it the
+     *    whole cancellation is rejected rather than cancelling without a refund
+     *
+     * The event row is locked first so a concurrent purchase can't interleave
+     * between the cancel and the inventory restore.
+     *
+     * @param admin_id - Support agent performing the cancellation
+     * @param user_id - Buyer whose tickets are cancelled
+     * @param event_id - Event the order belongs to
+     * @returns Counts of cancelled tickets, restored seats, and enqueued refunds
+     */
+    pub async fn bulk_cancel(&self, admin_id: Uuid, user_id: Uuid, event_id: Uuid) -> Result<BulkCancelResponse> {
+        let mut tx = self.repo.pool().begin().await.map_err(AppError::Database)?;
+
+        let event = self.repo.get_event_for_update(&mut tx, event_id).await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Event not found".into()))?;
+
+        let cancelled = self.repo.cancel_event_tickets_with_tx(&mut tx, event_id, Some(user_id)).await
+            .map_err(AppError::Database)?;
+
+        // Dropping tx on error rolls the cancellation back too.
+        if cancelled.iter().any(is_paid_ticket) {
+            check_refund_window(event.refund_deadline, chrono::Utc::now())?;
+        }
+
+        let restored: i64 = cancelled.iter().map(|t| t.quantity as i64).sum();
+        if restored > 0 {
+            self.repo.restore_inventory_with_tx(&mut tx, event_id, restored).await
+                .map_err(AppError::Database)?;
+        }
+        let ids: Vec<Uuid> = cancelled.iter().map(|t| t.id).collect();
+        self.repo.restore_add_ons_with_tx(&mut tx, &ids).await.map_err(AppError::Database)?;
+
+        let refunds_enqueued = enqueue_refunds(&mut tx, &cancelled, "bulk_cancel", admin_id).await?;
+
+        crate::audit::record(&mut *tx, Some(admin_id), "ticket.bulk_cancel", "event", event_id, serde_json::json!({
+            "user_id": user_id,
+            "ticket_ids": cancelled.iter().map(|t| t.ticket_id.as_str()).collect::<Vec<_>>(),
+            "restored_inventory": restored,
+            "refunds_enqueued": refunds_enqueued,
+        })).await.map_err(AppError::Database)?;
+
+        tx.commit().await.map_err(AppError::Database)?;
+
+        tracing::info!(
+            "Bulk cancel by {}: user {} event {} — {} tickets, {} seats restored, {} refunds queued",
+            admin_id, user_id, event_id, cancelled.len(), restored, refunds_enqueued
+        );
+
+        Ok(BulkCancelResponse {
+            cancelled_tickets: cancelled.len() as i64,
+            restored_inventory: restored,
+            refunds_enqueued,
+        })
+    }
+
+    /**
+     * Reassign a ticket to a corrected email (support-initiated)
+     *
+     * Distinct from the peer-to-peer transfer flow: no time lock and no
+     * recipient-account requirement - the buyer typo'd their email, so the
+     * "sender" never had meaningful access to begin with.
+     *
+     * Business Rules:
+     * 1. Only 'valid' / 'pending' tickets can be reassigned
+     * 2. Unknown emails get a placeholder account (claimable via password reset)
+     * 3. QR nonce rotates so anything rendered for the wrong account stops scanning
+     *
+     * @param admin_id - Support agent performing the correction
+     * @param ticket_id - Human-readable ticket ID
+     * @param email - Corrected buyer email
+     * @returns New owner and fresh QR payload
+     */
+    pub async fn reassign(&self, admin_id: Uuid, ticket_id: &str, email: &str) -> Result<ReassignTicketResponse> {
+        let email = email.trim().to_lowercase();
+        if email.is_empty() || !email.contains('@') {
+            return Err(AppError::Validation("Valid email required".into()));
+        }
+
+        let mut tx = self.repo.pool().begin().await.map_err(AppError::Database)?;
+
+        let current = sqlx::query("SELECT user_id, status FROM tickets WHERE ticket_id = $1 FOR UPDATE")
+            .bind(ticket_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Ticket not found".into()))?;
+
+        let current_owner: Uuid = current.get("user_id");
+        let status = TicketStatus::from_db(current.get("status"))?;
+        if !status.is_live() {
+            return Err(AppError::BadRequest(format!(
+                "Cannot reassign a ticket with status '{}'",
+                status
+            )));
+        }
+
+        let (new_owner, account_created) = self.repo
+            .find_or_create_user_by_email_with_tx(&mut tx, &email).await
+            .map_err(AppError::Database)?;
+        if new_owner == current_owner {
+            return Err(AppError::Validation("Ticket already belongs to this email".into()));
+        }
+
+        let ticket = self.repo.reassign_owner_with_tx(&mut tx, ticket_id, new_owner).await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::Conflict("Ticket changed while reassigning".into()))?;
+        crate::audit::record(&mut *tx, Some(admin_id), "ticket.reassign", "ticket", ticket_id, serde_json::json!({
+            "from_user_id": current_owner,
+            "to_user_id": new_owner,
+            "email": email,
+            "account_created": account_created,
+        })).await.map_err(AppError::Database)?;
+
+        tx.commit().await.map_err(AppError::Database)?;
+
+        tracing::info!(
+            "Ticket {} reassigned by {} from {} to {} (account_created: {})",
+            ticket_id, admin_id, current_owner, email, account_created
+        );
+
+        Ok(ReassignTicketResponse {
+            ticket_id: ticket.ticket_id,
+            user_id: new_owner,
+            email,
+            account_created,
+            qr_code_data: ticket.qr_code_data,
+        })
+    }
+
+    /**
+     * Get dynamic QR payload for a ticket
+     * 
+     * Generates a 3-second rotating TOTP-style QR payload
+     * Used by the frontend to refresh the QR code every 3s
+     * 
+     * @param ticket_id - Human-readable ticket ID
+     * @returns Signed QR JSON payload
+     */
+    pub async fn get_dynamic_qr(&self, ticket_id: &str, user_id: Uuid) -> Result<String> {
+        // Ownership check: user_id must match the ticket owner.
+        // Without this, any authenticated user can fetch any ticket's QR by guessing the ID.
+        let row = sqlx::query(
+            r#"SELECT e.event_key, e.rotating_qr, e.qr_ttl_seconds
+               FROM tickets t 
+               JOIN events e ON t.event_id = e.id 
+               WHERE t.ticket_id = $1 AND t.user_id = $2"#
+        )
+        .bind(ticket_id)
+        .bind(user_id)
+        .fetch_optional(self.repo.pool())
+        .await
+        .map_err(AppError::Database)?
+        .ok_or_else(|| AppError::NotFound("Ticket not found".into()))?;
+
+        let event_key: String = row.get("event_key");
+        Ok(self.sign_dynamic_qr(ticket_id, &event_key, rotating_ttl(&row)))
+    }
+
+    /**
+     * Get every ticket in an order with its own QR payload
+     *
+     * The screen a buyer uses to hand passes out to their group. Only tickets
+     * the caller still owns are listed - one already transferred away is the
+     * new holder's. An order with none left (or someone else's) is 404.
+     *
+     * Pending tickets are listed without a QR: nothing unpaid gets a
+     * scannable payload.
+     *
+     * @param order_id - Checkout the tickets were created by
+     * @param user_id - Caller; must own the tickets
+     * @returns The order's tickets, oldest first
+     */
+    pub async fn get_order_tickets(&self, order_id: Uuid, user_id: Uuid) -> Result<OrderTicketsResponse> {
+        let rows = self.repo.get_order_tickets(order_id, user_id).await
+            .map_err(AppError::Database)?;
+        if rows.is_empty() {
+            return Err(AppError::NotFound("Order not found".into()));
+        }
+
+        let tickets = rows.into_iter().map(|row| {
+            let qr_data = (row.status == TicketStatus::Valid.as_str())
+                .then(|| self.sign_dynamic_qr(&row.ticket_id, &row.event_key, row.rotating_ttl));
+            OrderTicket {
+                ticket_id: row.ticket_id,
+                ticket_type: row.ticket_type,
+                quantity: row.quantity,
+                status: row.status,
+                qr_data,
+            }
+        }).collect();
+
+        Ok(OrderTicketsResponse { order_id, tickets })
+    }
+
+    /**
+     * Get the dynamic QR as a PNG image
+     *
+     * Same signed payload as get_dynamic_qr, rendered server-side for clients
+     * that can't draw QR codes (email, kiosks).
+     *
+     * Edge cases:
+     * - Another user's ticket is 404, not 403 - existence is not leaked
+     * - Cancelled/refunded tickets still render, watermarked CANCELLED
+     * - size is clamped, never rejected
+     *
+     * @param ticket_id - Human-readable ticket ID
+     * @param user_id - Caller; must own the ticket
+     * @param size - Requested edge length in px
+     * @returns PNG bytes
+     */
+    pub async fn get_qr_png(&self, ticket_id: &str, user_id: Uuid, size: Option<u32>) -> Result<Vec<u8>> {
+        let row = sqlx::query(
+            r#"SELECT t.status, e.event_key, e.rotating_qr, e.qr_ttl_seconds
+               FROM tickets t
+               JOIN events e ON t.event_id = e.id
+               WHERE t.ticket_id = $1 AND t.user_id = $2"#
+        )
+        .bind(ticket_id)
+        .bind(user_id)
+        .fetch_optional(self.repo.pool())
+        .await
+        .map_err(AppError::Database)?;
+
+        let status = row.as_ref()
+            .map(|r| TicketStatus::from_db(r.get("status")))
+            .transpose()?;
+        let watermark = qr_image::access_for(status)?;
+        let event_key: String = row.as_ref().map(|r| r.get("event_key")).unwrap_or_default();
+        let ttl = row.as_ref().and_then(rotating_ttl);
+
+        let payload = self.sign_dynamic_qr(ticket_id, &event_key, ttl);
+        qr_image::render_png(&payload, qr_image::clamp_size(size), watermark)
+            .map_err(|e| AppError::Internal(format!("QR render failed: {}", e)))
+    }
+
+    /**
+     * Get a Code128 barcode of the ticket id as a PNG image
+     *
+     * For venues whose scanners only read linear barcodes. The bars carry the
+     * bare ticket id, which the scanner accepts through its raw-id path.
+     *
+     * Edge cases:
+     * - Another user's ticket is 404, not 403 - existence is not leaked
+     * - Rotating-QR events are refused: a static barcode would never scan there
+     * - Cancelled/refunded tickets still render, watermarked CANCELLED
+     *
+     * @param ticket_id - Human-readable ticket ID
+     * @param user_id - Caller; must own the ticket
+     * @param module_px - Requested narrowest-bar width in px
+     * @returns PNG bytes
+     */
+    pub async fn get_barcode_png(&self, ticket_id: &str, user_id: Uuid, module_px: Option<u32>) -> Result<Vec<u8>> {
+        let row = sqlx::query(
+            r#"SELECT t.status, e.rotating_qr
+               FROM tickets t
+               JOIN events e ON t.event_id = e.id
+               WHERE t.ticket_id = $1 AND t.user_id = $2"#
+        )
+        .bind(ticket_id)
+        .bind(user_id)
+        .fetch_optional(self.repo.pool())
+        .await
+        .map_err(AppError::Database)?;
+
+        let status = row.as_ref()
+            .map(|r| TicketStatus::from_db(r.get("status")))
+            .transpose()?;
+        let watermark = qr_image::access_for(status)?;
+        if row.as_ref().is_some_and(|r| r.get::<bool, _>("rotating_qr")) {
+            return Err(AppError::BadRequest(
+                "This event uses rotating QR codes; printed barcodes are not accepted".into(),
+            ));
+        }
+
+        barcode_image::render_png(ticket_id, barcode_image::clamp_module_px(module_px), watermark)
+            .map_err(|e| AppError::Internal(format!("Barcode render failed: {}", e)))
+    }
+
+    /**
+     * Pick one page of an event's tickets for the will-call QR download
+     *
+     * Valid tickets only, oldest purchase first, QR_BATCH_PAGE_SIZE per page.
+     * Rotating-QR events are refused like printed barcodes: the images would
+     * stop scanning within seconds.
+     *
+     * @param organizer_id - Caller; must own the event
+     * @param event_id - Event to export
+     * @param page - 1-based page, default 1
+     * @returns Ticket ids for the page and whether another page follows
+     */
+    pub async fn qr_batch_page(&self, organizer_id: Uuid, event_id: Uuid, page: Option<u32>) -> Result<QrBatch> {
+        self.verify_event_owner(organizer_id, event_id).await?;
+
+        let row = sqlx::query("SELECT event_key, rotating_qr, qr_ttl_seconds FROM events WHERE id = $1")
+            .bind(event_id)
+            .fetch_optional(self.repo.pool())
+            .await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Event not found".into()))?;
+        if rotating_ttl(&row).is_some() {
+            return Err(AppError::BadRequest(
+                "This event uses rotating QR codes; printed codes are not accepted".into(),
+            ));
+        }
+
+        let page = page.unwrap_or(1).max(1);
+        let offset = (page as i64 - 1) * QR_BATCH_PAGE_SIZE;
+        // One extra row says whether another page follows
+        let mut ticket_ids = self.repo.list_valid_ticket_ids(event_id, QR_BATCH_PAGE_SIZE + 1, offset).await
+            .map_err(AppError::Database)?;
+        let has_more = ticket_ids.len() as i64 > QR_BATCH_PAGE_SIZE;
+        ticket_ids.truncate(QR_BATCH_PAGE_SIZE as usize);
+
+        Ok(QrBatch { event_key: row.get("event_key"), ticket_ids, page, has_more })
+    }
+
+    /// Render a batch as a ZIP of `{ticket_id}.png`, one chunk per image.
+    /// Rendering runs on the blocking pool; a client that disconnects stops it.
+    pub fn stream_qr_batch(
+        self: Arc<Self>,
+        batch: QrBatch,
+        size: Option<u32>,
+    ) -> tokio_stream::wrappers::ReceiverStream<std::io::Result<Vec<u8>>> {
+        let (tx, rx) = tokio::sync::mpsc::channel(4);
+        tokio::task::spawn_blocking(move || {
+            let size = qr_image::clamp_size(size);
+            let mut zip = qr_zip::ZipStream::new();
+            for ticket_id in &batch.ticket_ids {
+                let payload = self.sign_dynamic_qr(ticket_id, &batch.event_key, None);
+                let chunk = qr_image::render_png(&payload, size, false)
+                    .map(|png| zip.entry(&format!("{}.png", ticket_id), &png))
+                    .map_err(std::io::Error::other);
+                let failed = chunk.is_err();
+                if tx.blocking_send(chunk).is_err() || failed {
+                    return;
+                }
+            }
+            let _ = tx.blocking_send(Ok(zip.finish()));
+        });
+        tokio_stream::wrappers::ReceiverStream::new(rx)
+    }
+
+    fn sign_dynamic_qr(&self, ticket_id: &str, event_key: &str, rotating_ttl: Option<i64>) -> String {
+        // Use the injected secret — same key ScannerService uses to verify.
+        let qr_secret = &self.qr_secret;
+
+        let now = chrono::Utc::now().timestamp();
+
+        // rotating_qr events: signature covers the time bucket, scanner checks the window
+        if let Some(ttl) = rotating_ttl {
+            return crate::qr_bucket::build_payload(qr_secret, ticket_id, event_key, ttl, now);
+        }
+        let window = now / 3;
+
+        // Calculate time-based nonce (TOTP style)
+        use hmac::{Hmac, Mac};
+        use sha2::Sha256;
+        let mut mac = Hmac::<Sha256>::new_from_slice(qr_secret.as_bytes())
+            .expect("HMAC accepts any key size");
+        mac.update(format!("{}:{}", ticket_id, window).as_bytes());
+        let nonce = hex::encode(mac.finalize().into_bytes());
+
+        // Sign the nonce
+        let mut mac_sig = Hmac::<Sha256>::new_from_slice(qr_secret.as_bytes())
+            .expect("HMAC accepts any key size");
+        mac_sig.update(format!("{}:{}", ticket_id, nonce).as_bytes());
+        let sig = hex::encode(mac_sig.finalize().into_bytes());
+
+        serde_json::json!({
+            "ticketId": ticket_id,
+            "eventKey": event_key,
+            "nonce": nonce,
+            "sig": sig,
+            "ts": now
+        }).to_string()
+    }
+
+    /**
+     * Scrub attendee PII from an event's tickets
+     *
+     * Only once the retention period after the event's last day is over.
+     * Ticket rows, prices and statuses stay so analytics keep adding up; the
+     * holder link becomes a salted hash and scan locations are dropped.
+     * Payment records are kept for financial reconciliation, but no longer
+     * point at the payer or carry the provider's customer details; resale
+     * listings lose their seller and buyer, transfer records their parties
+     * and recipient email, session admissions the staff who scanned them,
+     * and comp/reassign audit entries the attendee's email. Waitlist
+     * signups, which belong to no event, go once they're past the same
+     * retention period. Safe to re-run: already-scrubbed rows are skipped.
+     *
+     * @param event_id - Event to scrub
+     * @param actor - Admin who asked, or None for the scheduled job
+     * @returns How many tickets and scans were scrubbed
+     */
+    pub async fn scrub_event_pii(&self, event_id: Uuid, actor: Option<Uuid>) -> Result<PiiScrubResponse> {
+        let last_day = self.repo.get_event_last_day(event_id).await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Event not found".into()))?;
+        if !scrub_due(last_day, self.pii_retention_days, chrono::Utc::now().date_naive()) {
+            return Err(AppError::BadRequest(format!(
+                "Event is inside its {}-day retention period",
+                self.pii_retention_days
+            )));
+        }
+
+        let mut tx = self.repo.pool().begin().await.map_err(AppError::Database)?;
+        let tickets_scrubbed = self.repo
+            .scrub_ticket_holders_with_tx(&mut tx, event_id, REMOVED_ATTENDEE_ID, &self.pii_hash_secret).await
+            .map_err(AppError::Database)?;
+        self.repo.scrub_event_payers_with_tx(&mut tx, event_id, REMOVED_ATTENDEE_ID).await
+            .map_err(AppError::Database)?;
+        self.repo.scrub_transfers_with_tx(&mut tx, event_id, REMOVED_ATTENDEE_ID, &self.pii_hash_secret).await
+            .map_err(AppError::Database)?;
+        self.repo.scrub_audit_emails_with_tx(&mut tx, event_id).await
+            .map_err(AppError::Database)?;
+        self.repo.scrub_session_scanners_with_tx(&mut tx, event_id).await
+            .map_err(AppError::Database)?;
+        self.repo.purge_waitlist_with_tx(&mut tx, self.pii_retention_days).await
+            .map_err(AppError::Database)?;
+        let scans_scrubbed = self.repo.scrub_scan_locations_with_tx(&mut tx, event_id).await
+            .map_err(AppError::Database)?;
+        let scrubbed_at = self.repo.mark_event_pii_scrubbed_with_tx(&mut tx, event_id).await
+            .map_err(AppError::Database)?;
+        tx.commit().await.map_err(AppError::Database)?;
+
+        crate::audit::record_logged(self.repo.pool(), actor, "event.pii_scrub", "event", event_id,
+            serde_json::json!({ "tickets": tickets_scrubbed, "scans": scans_scrubbed })).await;
+
+        Ok(PiiScrubResponse { event_id, tickets_scrubbed, scans_scrubbed, scrubbed_at })
+    }
+
+    /**
+     * Blacklist a ticket so every scan of it fails
+     *
+     * Independent of the status machine: the ticket keeps its status, QR and
+     * owner, and removing the entry restores it as it was.
+     *
+     * @param admin_id - Admin making the change (audited)
+     * @param ticket_id - Human-readable ticket ID
+     * @param reason - Why, shown to door staff on a scan
+     * @returns The blacklist entry
+     */
+    pub async fn blacklist_ticket(&self, admin_id: Uuid, ticket_id: &str, reason: &str) -> Result<BlacklistEntry> {
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return Err(AppError::Validation("reason is required".into()));
+        }
+
+        let entry = self.repo.blacklist_ticket(ticket_id, reason, admin_id).await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Ticket not found".into()))?;
+        crate::audit::record_logged(self.repo.pool(), Some(admin_id), "ticket.blacklist", "ticket", ticket_id,
+            serde_json::json!({ "reason": reason })).await;
+
+        tracing::warn!("Ticket {} blacklisted by {}: {}", ticket_id, admin_id, reason);
+        Ok(entry)
+    }
+
+    /// Take a ticket off the blacklist. NotFound when it wasn't on it.
+    pub async fn unblacklist_ticket(&self, admin_id: Uuid, ticket_id: &str) -> Result<()> {
+        if !self.repo.unblacklist_ticket(ticket_id).await.map_err(AppError::Database)? {
+            return Err(AppError::NotFound("Ticket is not blacklisted".into()));
+        }
+        crate::audit::record_logged(self.repo.pool(), Some(admin_id), "ticket.unblacklist", "ticket", ticket_id,
+            serde_json::json!({})).await;
+        Ok(())
+    }
+
+    /**
+     * Cancel checkouts abandoned for longer than `ttl_minutes`
+     *
+     * A pending ticket holds seats, add-on stock, promo uses and promo
+     * budget. Once its TTL has passed with nothing paid and no checkout
+     * opened, it is released like a guest checkout that never started. A
+     * charge that still lands afterwards finds the ticket cancelled and is
+     * refunded in full by the webhook.
+     *
+     * @returns Tickets cancelled
+     */
+    pub async fn release_stale_pending(&self, ttl_minutes: i64) -> Result<i64> {
+        let stale = self.repo.list_stale_pending(ttl_minutes).await.map_err(AppError::Database)?;
+        let mut released = 0;
+        for (ticket_db_id, event_id) in stale {
+            match self.release_unpaid(event_id, ticket_db_id).await {
+                Ok(0) => {}
+                Ok(_) => released += 1,
+                Err(e) => tracing::error!("Releasing stale pending ticket {} failed: {}", ticket_db_id, e),
+            }
+        }
+        Ok(released)
+    }
+
+    /// Events the scheduled scrub should pick up next.
+    pub async fn events_due_for_pii_scrub(&self) -> Result<Vec<Uuid>> {
+        self.repo.list_events_due_for_pii_scrub(self.pii_retention_days).await.map_err(AppError::Database)
+    }
+
+    /// Drop a cached event after it was edited elsewhere. Ownership is read
+    /// from the table, not the cache being dropped; a deleted event has no
+    /// owner left to check.
+    pub async fn invalidate_event_cache(&self, actor_id: Uuid, is_admin: bool, event_id: Uuid) -> Result<()> {
+        if !is_admin {
+            let owner = self.repo.get_event_organizer(event_id).await.map_err(AppError::Database)?;
+            if owner.is_some_and(|owner| owner != actor_id) {
+                return Err(AppError::Forbidden);
+            }
+        }
+        self.events.invalidate(event_id).await;
+        crate::audit::record_logged(self.repo.pool(), Some(actor_id), "event.cache_invalidate", "event", event_id,
+            serde_json::json!({})).await;
+        Ok(())
+    }
+
+    // Read-mostly event fields via the cache. NotFound if the event doesn't exist.
+    async fn event_details(&self, event_id: Uuid) -> Result<Arc<CachedEvent>> {
+        self.events.get(event_id).await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Event not found".into()))
+    }
+
+    /**
+     * Ticket types on sale for an event (the buy page's tier selector)
+     *
+     * Events sell a single tier today: the event's price and capacity,
+     * under the event's default ticket type label. remaining counts the
+     * overbook allowance, the same as a purchase does. The list
+     * shape stays the same when events gain more tiers.
+     *
+     * @param event_id - Event to list
+     * @param available_only - Leave out sold-out and inactive tiers
+     * @returns Tiers with price and remaining capacity
+     */
+    pub async fn list_ticket_types(&self, event_id: Uuid, available_only: bool) -> Result<Vec<TicketTypeResponse>> {
+        let event = self.repo.get_event(event_id).await
+            .map_err(AppError::Database)?
+            .ok_or_else(|| AppError::NotFound("Event not found".into()))?;
+
+        let types = vec![TicketTypeResponse {
+            name: event.default_ticket_type,
+            price: event.price,
+            currency: event.currency,
+            capacity: event.total_tickets,
+            sold: (event.total_tickets - event.available_tickets).max(0),
+            remaining: sellable(event.available_tickets, event.held_tickets, event.overbook_allowance),
+            is_active: event.status == "active",
+        }];
+        Ok(visible_ticket_types(types, available_only))
+    }
+
+    /**
+     * Add an extra (parking, merch) to an event
+     *
+     * @param organizer_id - Caller; must own the event
+     * @param event_id - Event to sell it with
+     * @param req - Name, price and optional inventory
+     * @returns The created add-on
+     */
+    pub async fn create_add_on(&self, organizer_id: Uuid, event_id: Uuid, req: CreateAddOnRequest) -> Result<AddOnResponse> {
+        self.verify_event_owner(organizer_id, event_id).await?;
+
+        let name = req.name.trim();
+        if name.is_empty() || name.chars().count() > 100 {
+            return Err(AppError::Validation("Add-on name must be 1-100 characters".into()));
+        }
+        if req.price < Decimal::ZERO {
+            return Err(AppError::Validation("Add-on price cannot be negative".into()));
+        }
+        if req.inventory.is_some_and(|n| n < 0) {
+            return Err(AppError::Validation("Add-on inventory cannot be negative".into()));
+        }
+
+        let add_on = self.repo.create_add_on(event_id, name, normalize_amount(req.price), req.inventory).await
+            .map_err(AppError::Database)?;
+        crate::audit::record_logged(self.repo.pool(), Some(organizer_id), "event.add_on_create", "event", event_id,
+            serde_json::json!({ "add_on_id": add_on.id, "name": add_on.name, "price": add_on.price })).await;
+        Ok(add_on_response(add_on))
+    }
+
+    /// Active add-ons buyers can pick for an event.
+    pub async fn list_add_ons(&self, event_id: Uuid) -> Result<Vec<AddOnResponse>> {
+        let add_ons = self.repo.list_add_ons(event_id).await.map_err(AppError::Database)?;
+        Ok(add_ons.into_iter().map(add_on_response).collect())
+    }
+
+    /**
+     * Rebuild the PurchaseResponse for an already-processed purchase intent
+     *
+     * Same shape as a first-time purchase: event details from the event
+     * cache, the original payment reference/checkout URL, and the fee breakdown
+     * recomputed from the stored unit price, promo pricing and quantity.
+     *
+     * @param event - Cached event display fields
+     * @param ticket - Ticket created by the original request
+     * @param add_ons - Add-on lines stored with the ticket
+     * @param provider - Payment provider from the request
+     */
+    async fn replay_purchase_response(&self, event: &CachedEvent, ticket: super::dto::Ticket, add_ons: Vec<TicketAddOn>, provider: String) -> Result<PurchaseResponse> {
+        let pricing = self.repo.get_promo_pricing(ticket.id).await.map_err(AppError::Database)?;
+        let split = split_order(&pricing.lines(ticket.unit_price, ticket.quantity), add_ons_total(&add_ons), &FeeMode::default());
+        let reference = ticket.payment_ref.clone().unwrap_or_default();
+        let start = event.local_start();
+
+        let ticket_resp = TicketResponse {
+            id: ticket.id, ticket_id: ticket.ticket_id, event_id: ticket.event_id,
+            event_title: event.title.clone(), event_date: start.date,
+            event_time: start.time, event_starts_at: start.starts_at,
+            event_timezone: event.timezone.clone(), event_location: event.location.clone(),
+            event_emoji: event.emoji.clone(), event_image_url: event.image_url.clone(),
+            ticket_type: ticket.ticket_type, quantity: ticket.quantity,
+            usage_limit: ticket.usage_limit, usage_count: ticket.usage_count,
+            unit_price: ticket.unit_price, discount_applied: ticket.discount_applied,
+            total_price: ticket.total_price,
+            total_price_formatted: format_amount(ticket.total_price, &ticket.currency),
+            currency: ticket.currency.clone(),
+            status: ticket.status, qr_code_data: ticket.qr_code_data,
+            valid_from: ticket.valid_from, valid_until: ticket.valid_until,
+            receipt_number: ticket.receipt_number,
+            order_id: ticket.order_id,
+            purchase_date: ticket.purchase_date,
+        };
+
+        let payment_resp = PaymentInitResponse {
+            provider,
+            authorization_url: Some(format!("https://checkout.paystack.com/{}", reference)),
+            checkout_url: None,
+            reference,
+            amount: ticket.total_price,
+            amount_formatted: format_amount(ticket.total_price, &ticket.currency),
+            booking_fee: ticket.booking_fee,
+            tax_amount: ticket.tax_amount,
+            currency: ticket.currency,
+            platform_fee: split.tickets.platform_fee,
+            bukrshield_fee: split.tickets.bukrshield_fee,
+            organizer_payout: split.organizer_payout,
+        };
+
+        Ok(PurchaseResponse { ticket: ticket_resp, payment: payment_resp, add_ons, promo_split: None })
+    }
+}
+
+/// Events with require_rating set reject purchases without an excitement_rating.
+pub(crate) fn check_rating_required(require_rating: bool, rating: Option<i32>) -> Result<()> {
+    if require_rating && rating.is_none() {
+        return Err(AppError::Validation("This event requires an excitement rating (1-10)".into()));
+    }
+    Ok(())
+}
+
+/// Per-ticket price for a purchase. Fixed-price events (no min_price) take the
+/// list price and reject chosen_price; pay-what-you-want events take
+/// chosen_price — or the list price as the suggested amount — if >= min_price
+/// and no more than MAX_CHOSEN_PRICE.
+pub(crate) fn resolve_unit_price(
+    list_price: Decimal,
+    min_price: Option<Decimal>,
+    chosen_price: Option<Decimal>,
+) -> Result<Decimal> {
+    let Some(min) = min_price else {
+        if chosen_price.is_some() {
+            return Err(AppError::Validation("This event has a fixed price; chosen_price is not accepted".into()));
+        }
+        return Ok(list_price);
+    };
+    let price = normalize_amount(chosen_price.unwrap_or(list_price.max(min)));
+    if price < min {
+        return Err(AppError::Validation(format!(
+            "chosen_price must be at least {}",
+            normalize_amount(min)
+        )));
+    }
+    if chosen_price.is_some() && price > MAX_CHOSEN_PRICE {
+        return Err(AppError::Validation(format!(
+            "chosen_price must be at most {}",
+            MAX_CHOSEN_PRICE
+        )));
+    }
+    Ok(price)
+}
+
+/// Trimmed, lowercased guest email; rejects anything without a local part and domain.
+pub(crate) fn normalize_guest_email(raw: Option<&str>) -> Result<String> {
+    let email = raw.unwrap_or_default().trim().to_lowercase();
+    match email.split_once('@') {
+        Some((local, domain)) if !local.is_empty() && domain.contains('.') && !domain.contains('@') => Ok(email),
+        _ => Err(AppError::Validation("A valid guest_email is required for guest checkout".into())),
+    }
+}
+
+/// Receipt sequence → "INV-0001". Pads to four digits; wider sequences keep every digit.
+pub(crate) fn format_receipt_number(seq: i64) -> String {
+    format!("INV-{:04}", seq)
+}
+
+/// Minimum gap between confirmation resends for one ticket.
+pub(crate) const RESEND_COOLDOWN_SECS: i64 = 60;
+
+/// A resend is allowed once RESEND_COOLDOWN_SECS have passed since the last one (or if there was none).
+pub(crate) fn check_resend_cooldown(
+    last: Option<chrono::DateTime<chrono::Utc>>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<()> {
+    let Some(last) = last else { return Ok(()) };
+    let wait = RESEND_COOLDOWN_SECS - (now - last).num_seconds();
+    if wait > 0 {
+        return Err(AppError::RateLimited(format!(
+            "A confirmation was just sent — try again in {} seconds", wait
+        )));
+    }
+    Ok(())
+}
+
+/// Refunds are allowed strictly before the deadline; at or after it the window is closed.
+pub(crate) fn check_refund_window(
+    deadline: chrono::DateTime<chrono::Utc>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<()> {
+    if now >= deadline {
+        return Err(AppError::BadRequest("refund window closed".into()));
+    }
+    Ok(())
+}
+
+/// Buyers may cancel themselves strictly before the deadline; after it, support handles it.
+pub(crate) fn check_self_cancel_window(
+    deadline: chrono::DateTime<chrono::Utc>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<()> {
+    if now >= deadline {
+        return Err(AppError::BadRequest(
+            "Self-service cancellation has closed for this event — please contact support".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Queue refunds for each cancelled ticket a charge was collected for, in
+/// the cancelling transaction: one per successful charge, since a ticket paid
+/// in split parts was charged under several references. Returns how many
+/// tickets got refunds. A resold ticket refunds its current holder's resale
+/// charge: the seller's original purchase was already paid back to them out
+/// of that sale.
+async fn enqueue_refunds(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    cancelled: &[super::repository::CancelledTicket],
+    reason: &str,
+    requested_by: Uuid,
+) -> Result<i64> {
+    let mut enqueued = 0;
+    for t in cancelled.iter().filter(|t| is_paid_ticket(t)) {
+        let charges: Vec<(String, String, Decimal)> = match &t.resale {
+            Some(r) => vec![(r.payment_ref.clone(), "paystack".into(), r.price)],
+            None => sqlx::query_as(
+                r#"SELECT provider_ref, provider, amount FROM payment_transactions
+                   WHERE ticket_id = $1 AND status = 'success' AND provider_ref NOT LIKE $2
+                   ORDER BY created_at"#,
+            )
+            .bind(t.id)
+            .bind(RESALE_REF_LIKE)
+            .fetch_all(&mut **tx)
+            .await
+            .map_err(AppError::Database)?,
+        };
+        for (payment_ref, payment_provider, amount) in charges {
+            crate::outbox::enqueue(
+                tx,
+                "ticket.refund_requested",
+                t.id,
+                serde_json::json!({
+                    "ticket_id": t.ticket_id,
+                    "payment_ref": payment_ref,
+                    "payment_provider": payment_provider,
+                    "amount": amount,
+                    "currency": t.currency,
+                    "reason": reason,
+                    "requested_by": requested_by,
+                }),
+            ).await.map_err(AppError::Database)?;
+        }
+        enqueued += 1;
+    }
+    Ok(enqueued)
+}
+
+/// Whether cancelling the ticket owes the buyer money back: a charge has to
+/// have succeeded. A pending ticket was never paid for, whatever its price.
+fn is_paid_ticket(t: &super::repository::CancelledTicket) -> bool {
+    refund_amount(t) > Decimal::ZERO
+}
+
+/// What a cancel pays back: the holder's resale price once resold, else the
+/// primary-sale charges.
+fn refund_amount(t: &super::repository::CancelledTicket) -> Decimal {
+    t.resale.as_ref().map_or(t.amount_paid, |r| r.price)
+}
+
+/// Status a newly purchased ticket starts in. Anything with a charge waits
+/// for the payment webhook before it can be scanned; a zero total (full
+/// discount) has nothing to confirm and is valid straight away.
+pub(crate) fn initial_status(total: &Money) -> TicketStatus {
+    if total.is_positive() {
+        TicketStatus::Pending
+    } else {
+        TicketStatus::Valid
+    }
+}
+
+/// Whether an event may move from `from` to `next`. `refunded_tickets` only
+/// matters when reinstating a cancelled event: any refund blocks it.
+pub(crate) fn check_event_transition(from: EventStatus, next: EventStatus, refunded_tickets: i64) -> Result<()> {
+    if from == next {
+        return Err(AppError::BadRequest(format!("Event is already {}", next)));
+    }
+    if !from.can_transition_to(next) {
+        return Err(AppError::BadRequest(format!("An event can't go from {} to {}", from, next)));
+    }
+    if from == EventStatus::Cancelled && refunded_tickets > 0 {
+        return Err(AppError::Conflict(format!(
+            "Can't reinstate: {} ticket(s) have already been refunded", refunded_tickets
+        )));
+    }
+    Ok(())
+}
+
+/// Inventory public buyers may take — the organizer hold is carved out.
+pub(crate) fn public_available(available: i32, held: i32) -> i32 {
+    (available - held).max(0)
+}
+
+/// Fold per-status totals into the cancel preview: live rows are what a cancel touches.
+pub(crate) fn cancel_impact(event_id: Uuid, currency: Currency, summary: &[StatusSummary]) -> CancelPreviewResponse {
+    let live: Vec<&StatusSummary> = summary.iter()
+        .filter(|s| s.status.parse::<TicketStatus>().is_ok_and(TicketStatus::is_live))
+        .collect();
+
+    CancelPreviewResponse {
+        event_id,
+        tickets_by_status: summary.iter().map(|s| (s.status.clone(), s.tickets)).collect(),
+        tickets_cancelled: live.iter().map(|s| s.tickets).sum(),
+        seats_released: live.iter().map(|s| s.seats).sum(),
+        refunds: live.iter().map(|s| s.paid_tickets).sum(),
+        refund_total: Money::new(live.iter().map(|s| s.paid_amount).sum(), currency.clone()).amount(),
+        currency: currency.to_string(),
+    }
+}
+
+/// Trim a tier name and check it fits tickets.ticket_type.
+pub(crate) fn normalize_ticket_type(name: &str) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation("default_ticket_type cannot be empty".into()));
+    }
+    if name.chars().count() > MAX_TICKET_TYPE_LEN {
+        return Err(AppError::Validation(format!("default_ticket_type must be at most {} characters", MAX_TICKET_TYPE_LEN)));
+    }
+    Ok(name.to_string())
+}
+
+/// With available_only, drop tiers that are sold out or not on sale.
+pub(crate) fn visible_ticket_types(types: Vec<TicketTypeResponse>, available_only: bool) -> Vec<TicketTypeResponse> {
+    if !available_only {
+        return types;
+    }
+    types.into_iter().filter(|t| t.is_active && t.remaining > 0).collect()
+}
+
+/// True once public stock is at or below the organizer's threshold (None = alert off).
+pub(crate) fn low_stock_reached(public_remaining: i32, threshold: Option<i32>) -> bool {
+    threshold.is_some_and(|t| public_remaining <= t)
+}
+
+/// Seats a purchase may take: public inventory plus the overbook allowance.
+/// available_tickets goes negative once overbooked, bottoming out at -overbook.
+pub(crate) fn sellable(available: i32, held: i32, overbook: i32) -> i32 {
+    (available - held + overbook.max(0)).max(0)
+}
+
+/// A hold can't be negative or exceed what is left unsold.
+pub(crate) fn validate_hold(held: i32, available: i32) -> std::result::Result<(), String> {
+    if held < 0 {
+        return Err("held_tickets cannot be negative".into());
+    }
+    if held > available {
+        return Err(format!("held_tickets cannot exceed available tickets ({})", available));
+    }
+    Ok(())
+}
+
+/// Price the requested add-ons against the locked rows. Repeated ids are
+/// merged into one line; each selection and each merged line needs
+/// 1..=MAX_ADD_ON_QUANTITY units and enough inventory. The total is checked,
+/// so a price near Decimal's limit is refused instead of overflowing.
+pub(crate) fn price_add_ons(
+    selections: &[AddOnSelection],
+    available: &[EventAddOn],
+) -> std::result::Result<(Vec<TicketAddOn>, Decimal), String> {
+    let mut lines: Vec<TicketAddOn> = Vec::new();
+    for sel in selections {
+        let add_on = available.iter()
+            .find(|a| a.id == sel.add_on_id)
+            .ok_or_else(|| format!("Add-on {} is not available for this event", sel.add_on_id))?;
+        if sel.quantity < 1 {
+            return Err("Add-on quantity must be at least 1".into());
+        }
+        if sel.quantity > MAX_ADD_ON_QUANTITY {
+            return Err(format!("At most {} of '{}' per order", MAX_ADD_ON_QUANTITY, add_on.name));
+        }
+        match lines.iter_mut().find(|l| l.add_on_id == add_on.id) {
+            Some(line) => {
+                line.quantity = line.quantity.checked_add(sel.quantity)
+                    .filter(|&q| q <= MAX_ADD_ON_QUANTITY)
+                    .ok_or_else(|| format!("At most {} of '{}' per order", MAX_ADD_ON_QUANTITY, add_on.name))?;
+            }
+            None => lines.push(TicketAddOn {
+                add_on_id: add_on.id,
+                name: add_on.name.clone(),
+                quantity: sel.quantity,
+                unit_price: add_on.price,
+            }),
+        }
+    }
+
+    let mut total = Decimal::ZERO;
+    for line in &lines {
+        let inventory = available.iter().find(|a| a.id == line.add_on_id).and_then(|a| a.inventory);
+        if let Some(left) = inventory {
+            if left < line.quantity {
+                return Err(format!("Only {} of '{}' left", left, line.name));
+            }
+        }
+        total = line.unit_price.checked_mul(Decimal::from(line.quantity))
+            .and_then(|amount| total.checked_add(amount))
+            .ok_or_else(|| "Add-on total is too large".to_string())?;
+    }
+
+    Ok((lines, total))
+}
+
+fn add_ons_total(lines: &[TicketAddOn]) -> Decimal {
+    lines.iter().map(|l| l.unit_price * Decimal::from(l.quantity)).sum()
+}
+
+fn add_on_response(a: EventAddOn) -> AddOnResponse {
+    AddOnResponse {
+        id: a.id,
+        event_id: a.event_id,
+        name: a.name,
+        price: a.price,
+        inventory: a.inventory,
+        is_active: a.is_active,
+    }
+}
+
+// Bucket TTL for events with rotating_qr on; None keeps the nonce-based QR.
+fn rotating_ttl(row: &sqlx::postgres::PgRow) -> Option<i64> {
+    row.get::<bool, _>("rotating_qr")
+        .then(|| row.get::<i32, _>("qr_ttl_seconds") as i64)
 }
@@ -0,0 +1,520 @@
+// Unit tests for pure pieces of the ticket purchase path.
+
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use rust_decimal_macros::dec;
+
+use crate::currency::Currency;
+use crate::money::Money;
+use crate::fees::normalize_amount;
+use super::barcode_image;
+use super::qr_image::{access_for, clamp_size, render_png, MAX_SIZE, MIN_SIZE};
+use uuid::Uuid;
+
+use super::dto::{AddOnSelection, TicketTypeResponse};
+use super::repository::{EventAddOn, StatusSummary};
+use super::event_status::EventStatus;
+use super::status::TicketStatus;
+use super::resale::{check_resale_price, check_resellable};
+use super::transfer::{check_not_admitted, check_not_blacklisted};
+use super::service::{cancel_impact, check_event_transition, check_resend_cooldown, format_receipt_number, initial_status, low_stock_reached, normalize_guest_email, normalize_ticket_type, resolve_unit_price, check_refund_window, check_self_cancel_window, check_rating_required, price_add_ons, public_available, sellable, validate_hold, visible_ticket_types};
+
+#[test]
+fn event_price_with_db_scale_normalizes_to_two_places() {
+    let normalized = normalize_amount(dec!(1000.0000));
+    assert_eq!(normalized.to_string(), "1000.00");
+}
+
+#[test]
+fn integer_price_gains_two_decimal_places() {
+    assert_eq!(normalize_amount(dec!(1000)).to_string(), "1000.00");
+}
+
+#[test]
+fn sub_kobo_amount_rounds_to_two_places() {
+    assert_eq!(normalize_amount(dec!(5299.995)).to_string(), "5300.00");
+}
+
+// ─── QR PNG endpoint edge cases ───────────────────────────────────────────────
+
+const PAYLOAD: &str = r#"{"ticketId":"BUKR-0001-abcdef12","eventKey":"k","nonce":"n","sig":"s","ts":0}"#;
+
+fn decode_rgb(png_bytes: &[u8]) -> (u32, Vec<u8>) {
+    let decoder = png::Decoder::new(png_bytes);
+    let mut reader = decoder.read_info().unwrap();
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).unwrap();
+    assert_eq!(info.width, info.height);
+    buf.truncate(info.buffer_size());
+    (info.width, buf)
+}
+
+fn has_red(rgb: &[u8]) -> bool {
+    rgb.chunks(3).any(|p| p[0] > 150 && p[1] < 80 && p[2] < 80)
+}
+
+#[test]
+fn other_users_ticket_is_404_not_403() {
+    // The lookup is scoped to the caller, so someone else's ticket looks
+    // exactly like a missing one.
+    let err = access_for(None).unwrap_err();
+    assert_eq!(err.into_response().status(), StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn cancelled_ticket_renders_with_watermark() {
+    assert!(access_for(Some(TicketStatus::Cancelled)).unwrap());
+    let (_, rgb) = decode_rgb(&render_png(PAYLOAD, 320, true).unwrap());
+    assert!(has_red(&rgb));
+}
+
+#[test]
+fn valid_ticket_renders_without_watermark() {
+    assert!(!access_for(Some(TicketStatus::Valid)).unwrap());
+    let (_, rgb) = decode_rgb(&render_png(PAYLOAD, 320, false).unwrap());
+    assert!(!has_red(&rgb));
+}
+
+#[test]
+fn oversized_and_tiny_sizes_are_clamped() {
+    assert_eq!(clamp_size(Some(1_000_000)), MAX_SIZE);
+    assert_eq!(clamp_size(Some(1)), MIN_SIZE);
+    assert_eq!(clamp_size(Some(256)), 256);
+
+    let (width, _) = decode_rgb(&render_png(PAYLOAD, clamp_size(Some(u32::MAX)), false).unwrap());
+    assert_eq!(width, MAX_SIZE);
+}
+
+// ─── Organizer hold ───────────────────────────────────────────────────────────
+
+#[test]
+fn held_block_is_off_public_sale() {
+    assert_eq!(public_available(100, 20), 80);
+    assert_eq!(public_available(20, 20), 0);
+}
+
+#[test]
+fn public_availability_never_goes_negative() {
+    assert_eq!(public_available(5, 10), 0);
+}
+
+#[test]
+fn hold_must_fit_in_unsold_inventory() {
+    assert!(validate_hold(0, 0).is_ok());
+    assert!(validate_hold(50, 50).is_ok());
+    assert!(validate_hold(51, 50).is_err());
+    assert!(validate_hold(-1, 50).is_err());
+}
+
+// ─── Add-ons ──────────────────────────────────────────────────────────────────
+
+fn add_on(name: &str, price: rust_decimal::Decimal, inventory: Option<i32>) -> EventAddOn {
+    EventAddOn {
+        id: Uuid::new_v4(),
+        event_id: Uuid::nil(),
+        name: name.into(),
+        price,
+        inventory,
+        is_active: true,
+    }
+}
+
+fn pick(a: &EventAddOn, quantity: i32) -> AddOnSelection {
+    AddOnSelection { add_on_id: a.id, quantity }
+}
+
+#[test]
+fn add_ons_are_summed_and_repeats_merged() {
+    let parking = add_on("Parking", dec!(2000.00), None);
+    let shirt = add_on("T-shirt", dec!(7500.00), Some(10));
+    let selections = [pick(&parking, 1), pick(&shirt, 1), pick(&shirt, 1)];
+    let (lines, total) = price_add_ons(&selections, &[parking, shirt]).unwrap();
+
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[1].quantity, 2);
+    assert_eq!(total, dec!(17000.00));
+}
+
+#[test]
+fn add_on_inventory_and_quantity_are_enforced() {
+    let shirt = add_on("T-shirt", dec!(7500.00), Some(1));
+    let (two, zero, one) = (pick(&shirt, 2), pick(&shirt, 0), pick(&shirt, 1));
+    let available = [shirt];
+    assert!(price_add_ons(&[two], &available).is_err());
+    assert!(price_add_ons(&[zero], &available).is_err());
+    assert!(price_add_ons(&[one], &available).is_ok());
+}
+
+#[test]
+fn each_add_on_selection_is_capped_before_merging() {
+    let parking = add_on("Parking", dec!(2000.00), None);
+    assert!(price_add_ons(&[pick(&parking, i32::MAX), pick(&parking, 1)], std::slice::from_ref(&parking)).is_err());
+    assert!(price_add_ons(&[pick(&parking, 6), pick(&parking, 5)], &[parking]).is_err());
+}
+
+#[test]
+fn add_on_total_overflow_is_refused() {
+    let huge = add_on("Suite", rust_decimal::Decimal::MAX, None);
+    assert!(price_add_ons(&[pick(&huge, 2)], &[huge]).is_err());
+}
+
+#[test]
+fn add_on_from_another_event_is_rejected() {
+    let parking = add_on("Parking", dec!(2000.00), None);
+    let other = add_on("Parking", dec!(2000.00), None);
+    assert!(price_add_ons(&[pick(&other, 1)], &[parking]).is_err());
+}
+
+// ─── Required rating ──────────────────────────────────────────────────────────
+
+#[test]
+fn rating_is_optional_unless_the_event_requires_it() {
+    assert!(check_rating_required(false, None).is_ok());
+    assert!(check_rating_required(true, Some(3)).is_ok());
+    assert!(check_rating_required(true, None).is_err());
+}
+
+// ─── Status transitions ───────────────────────────────────────────────────────
+
+#[test]
+fn status_round_trips_through_strings() {
+    for status in TicketStatus::ALL {
+        assert_eq!(status.as_str().parse::<TicketStatus>().unwrap(), status);
+    }
+    assert!("VALID".parse::<TicketStatus>().is_err());
+}
+
+#[test]
+fn used_tickets_cannot_become_valid_again() {
+    assert!(TicketStatus::Valid.can_transition_to(TicketStatus::Used));
+    assert!(!TicketStatus::Used.can_transition_to(TicketStatus::Valid));
+    assert!(!TicketStatus::Refunded.can_transition_to(TicketStatus::Valid));
+    assert!(!TicketStatus::Valid.can_transition_to(TicketStatus::Valid));
+}
+
+#[test]
+fn payment_confirmation_only_activates_unsettled_tickets() {
+    let mut sources = TicketStatus::Valid.sources();
+    sources.sort();
+    assert_eq!(sources, vec!["failed", "pending"]);
+}
+
+// ─── Refund window ────────────────────────────────────────────────────────────
+
+#[test]
+fn refunds_close_exactly_at_the_deadline() {
+    let deadline = chrono::Utc::now();
+    let second = chrono::Duration::seconds(1);
+    assert!(check_refund_window(deadline, deadline - second).is_ok());
+
+    for now in [deadline, deadline + second] {
+        let err = check_refund_window(deadline, now).unwrap_err();
+        assert!(matches!(err, crate::error::AppError::BadRequest(ref m) if m == "refund window closed"));
+    }
+}
+
+// ─── Resale ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn resale_price_capped_at_face_value() {
+    let face = dec!(15000.00);
+    assert!(check_resale_price(face, face).is_ok());
+    assert!(check_resale_price(dec!(9000.00), face).is_ok());
+    assert!(check_resale_price(dec!(15000.01), face).is_err());
+    assert!(check_resale_price(dec!(0), face).is_err());
+}
+
+#[test]
+fn only_unscanned_valid_tickets_are_resellable() {
+    assert!(check_resellable(TicketStatus::Valid, 0, false).is_ok());
+    assert!(check_resellable(TicketStatus::Valid, 1, false).is_err());
+    assert!(check_resellable(TicketStatus::Valid, 0, true).is_err());
+    assert!(check_resellable(TicketStatus::Used, 0, false).is_err());
+    assert!(check_resellable(TicketStatus::Pending, 0, false).is_err());
+}
+
+#[test]
+fn blacklisted_ticket_cannot_change_hands() {
+    assert!(check_not_blacklisted(false, "transfer").is_ok());
+    for action in ["transfer", "resell", "renew"] {
+        let err = check_not_blacklisted(true, action).unwrap_err();
+        assert!(err.to_string().contains(action), "{}", err);
+    }
+}
+
+#[test]
+fn part_admitted_group_ticket_cannot_change_hands() {
+    assert!(check_not_admitted(0, "transfer").is_ok());
+    let err = check_not_admitted(2, "transfer").unwrap_err();
+    assert!(err.to_string().contains("started entering"), "{}", err);
+}
+
+// ─── Pay what you want ────────────────────────────────────────────────────────
+
+#[test]
+fn fixed_price_events_reject_chosen_price() {
+    assert_eq!(resolve_unit_price(dec!(5000.00), None, None).unwrap(), dec!(5000.00));
+    assert!(resolve_unit_price(dec!(5000.00), None, Some(dec!(6000))).is_err());
+}
+
+#[test]
+fn chosen_price_must_meet_the_minimum() {
+    let min = Some(dec!(1000.00));
+    assert_eq!(resolve_unit_price(dec!(2500.00), min, Some(dec!(1000))).unwrap(), dec!(1000.00));
+    assert_eq!(resolve_unit_price(dec!(2500.00), min, Some(dec!(7777.5))).unwrap(), dec!(7777.50));
+    assert!(matches!(
+        resolve_unit_price(dec!(2500.00), min, Some(dec!(999.99))),
+        Err(crate::error::AppError::Validation(_))
+    ));
+}
+
+#[test]
+fn chosen_price_has_a_ceiling() {
+    let min = Some(dec!(1000.00));
+    assert!(resolve_unit_price(dec!(2500.00), min, Some(dec!(100000000))).is_ok());
+    assert!(matches!(
+        resolve_unit_price(dec!(2500.00), min, Some(dec!(100000000.01))),
+        Err(crate::error::AppError::Validation(_))
+    ));
+    assert!(resolve_unit_price(dec!(2500.00), min, Some(rust_decimal::Decimal::MAX)).is_err());
+}
+
+#[test]
+fn missing_chosen_price_falls_back_to_suggested() {
+    assert_eq!(resolve_unit_price(dec!(2500.00), Some(dec!(1000)), None).unwrap(), dec!(2500.00));
+    // A list price below the floor never undercuts it.
+    assert_eq!(resolve_unit_price(dec!(0), Some(dec!(1000)), None).unwrap(), dec!(1000.00));
+}
+
+// ─── Self-cancel window ───────────────────────────────────────────────────────
+
+#[test]
+fn self_cancel_closes_at_the_deadline() {
+    let deadline = chrono::Utc::now();
+    let second = chrono::Duration::seconds(1);
+    assert!(check_self_cancel_window(deadline, deadline - second).is_ok());
+
+    for now in [deadline, deadline + second] {
+        let err = check_self_cancel_window(deadline, now).unwrap_err();
+        assert!(matches!(err, crate::error::AppError::BadRequest(ref m) if m.contains("contact support")));
+    }
+}
+
+#[test]
+fn receipt_numbers_pad_to_four_digits() {
+    assert_eq!(format_receipt_number(1), "INV-0001");
+    assert_eq!(format_receipt_number(42), "INV-0042");
+    assert_eq!(format_receipt_number(9999), "INV-9999");
+}
+
+#[test]
+fn receipt_numbers_past_9999_keep_every_digit() {
+    assert_eq!(format_receipt_number(10000), "INV-10000");
+    assert_eq!(format_receipt_number(123456), "INV-123456");
+}
+
+#[test]
+fn first_confirmation_resend_is_allowed() {
+    assert!(check_resend_cooldown(None, chrono::Utc::now()).is_ok());
+}
+
+#[test]
+fn confirmation_resend_within_a_minute_is_rate_limited() {
+    let now = chrono::Utc::now();
+    let err = check_resend_cooldown(Some(now - chrono::Duration::seconds(59)), now).unwrap_err();
+    assert_eq!(err.into_response().status(), StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[test]
+fn confirmation_resend_after_a_minute_is_allowed() {
+    let now = chrono::Utc::now();
+    assert!(check_resend_cooldown(Some(now - chrono::Duration::seconds(60)), now).is_ok());
+}
+
+#[test]
+fn without_overbooking_sales_stop_at_capacity() {
+    assert_eq!(sellable(0, 0, 0), 0);
+    assert_eq!(sellable(3, 1, 0), 2);
+}
+
+#[test]
+fn overbooking_sells_exactly_allowance_past_capacity() {
+    // Sold out at nominal capacity, 5 extra allowed.
+    assert_eq!(sellable(0, 0, 5), 5);
+    // Four overbooked so far: one left, then none.
+    assert_eq!(sellable(-4, 0, 5), 1);
+    assert_eq!(sellable(-5, 0, 5), 0);
+}
+
+#[test]
+fn overbook_allowance_lowered_below_oversold_never_goes_negative() {
+    assert_eq!(sellable(-5, 0, 2), 0);
+}
+
+#[test]
+fn guest_email_is_trimmed_and_lowercased() {
+    assert_eq!(normalize_guest_email(Some("  Ada@Example.COM ")).unwrap(), "ada@example.com");
+}
+
+#[test]
+fn guest_checkout_without_usable_email_is_rejected() {
+    for raw in [None, Some(""), Some("ada"), Some("@example.com"), Some("ada@localhost"), Some("a@b@c.com")] {
+        let err = normalize_guest_email(raw).unwrap_err();
+        assert_eq!(err.into_response().status(), StatusCode::BAD_REQUEST, "{:?}", raw);
+    }
+}
+
+#[test]
+fn low_stock_alert_trips_at_or_below_threshold() {
+    assert!(!low_stock_reached(11, Some(10)));
+    assert!(low_stock_reached(10, Some(10)));
+    assert!(low_stock_reached(0, Some(10)));
+}
+
+#[test]
+fn low_stock_alert_off_without_threshold() {
+    assert!(!low_stock_reached(0, None));
+}
+
+fn tier(name: &str, remaining: i32, is_active: bool) -> TicketTypeResponse {
+    TicketTypeResponse {
+        name: name.into(),
+        price: dec!(5000.00),
+        currency: "NGN".into(),
+        capacity: 100,
+        sold: 100 - remaining,
+        remaining,
+        is_active,
+    }
+}
+
+#[test]
+fn all_ticket_types_listed_by_default() {
+    let types = vec![tier("GA", 0, true), tier("VIP", 5, false)];
+    assert_eq!(visible_ticket_types(types, false).len(), 2);
+}
+
+#[test]
+fn available_only_hides_sold_out_and_inactive_tiers() {
+    let types = vec![tier("GA", 0, true), tier("VIP", 5, false), tier("Early", 3, true)];
+    let visible = visible_ticket_types(types, true);
+    assert_eq!(visible.len(), 1);
+    assert_eq!(visible[0].name, "Early");
+}
+
+#[test]
+fn default_ticket_type_is_trimmed() {
+    assert_eq!(normalize_ticket_type("  Regular ").unwrap(), "Regular");
+}
+
+#[test]
+fn default_ticket_type_rejects_blank_and_overlong_names() {
+    assert!(normalize_ticket_type("   ").is_err());
+    assert!(normalize_ticket_type(&"x".repeat(51)).is_err());
+    assert!(normalize_ticket_type(&"x".repeat(50)).is_ok());
+}
+
+fn status_row(status: &str, tickets: i64, seats: i64, paid_tickets: i64, paid_amount: rust_decimal::Decimal) -> StatusSummary {
+    StatusSummary { status: status.into(), tickets, seats, paid_tickets, paid_amount }
+}
+
+#[test]
+fn cancel_preview_counts_only_live_tickets_toward_the_cancel() {
+    let summary = vec![
+        status_row("valid", 10, 14, 8, dec!(80000)),
+        status_row("pending", 2, 2, 2, dec!(10000)),
+        status_row("used", 3, 3, 3, dec!(30000)),
+        status_row("refunded", 1, 1, 1, dec!(5000)),
+    ];
+    let preview = cancel_impact(Uuid::nil(), Currency::parse("NGN").unwrap(), &summary);
+
+    assert_eq!(preview.tickets_cancelled, 12);
+    assert_eq!(preview.seats_released, 16);
+    assert_eq!(preview.refunds, 10);
+    assert_eq!(preview.refund_total, dec!(90000));
+    assert_eq!(preview.currency, "NGN");
+    assert_eq!(preview.tickets_by_status.len(), 4);
+    assert_eq!(preview.tickets_by_status["used"], 3);
+}
+
+#[test]
+fn cancel_preview_of_an_unsold_event_is_all_zero() {
+    let preview = cancel_impact(Uuid::nil(), Currency::parse("NGN").unwrap(), &[]);
+    assert_eq!(preview.tickets_cancelled, 0);
+    assert_eq!(preview.refund_total, rust_decimal::Decimal::ZERO);
+    assert!(preview.tickets_by_status.is_empty());
+}
+
+#[test]
+fn code128_patterns_are_well_formed() {
+    // Every symbol is 11 modules wide, starts on a bar and ends on a space;
+    // stop adds the 2-module terminating bar.
+    let modules = barcode_image::encode("A").unwrap();
+    // start + 'A' + checksum + stop
+    assert_eq!(modules.len(), 11 * 3 + 13);
+    assert!(modules[0]);
+    assert!(*modules.last().unwrap());
+}
+
+#[test]
+fn code128_checksum_matches_reference() {
+    // 104 + 48·1 + 42·2 + 42·3 + 17·4 + 18·5 + 19·6 + 35·7 = 879 ≡ 55 (mod 103)
+    let modules = barcode_image::encode("PJJ123C").unwrap();
+    let checksum_at = 11 * 8;
+    let checksum: Vec<bool> = modules[checksum_at..checksum_at + 11].to_vec();
+    // Pattern 55 is "311321"
+    let expected: Vec<bool> = [3, 1, 1, 3, 2, 1].iter().enumerate()
+        .flat_map(|(i, &w)| std::iter::repeat_n(i % 2 == 0, w))
+        .collect();
+    assert_eq!(checksum, expected);
+}
+
+#[test]
+fn code128_rejects_non_ascii() {
+    assert!(barcode_image::encode("BUKR-é").is_err());
+    assert!(barcode_image::encode("").is_err());
+}
+
+#[test]
+fn barcode_png_is_a_wide_strip() {
+    let png_bytes = barcode_image::render_png("BUKR-0001-abcd", barcode_image::clamp_module_px(None), true).unwrap();
+    let decoder = png::Decoder::new(png_bytes.as_slice());
+    let mut reader = decoder.read_info().unwrap();
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).unwrap();
+    assert!(info.width > info.height);
+    buf.truncate(info.buffer_size());
+    assert!(has_red(&buf));
+    assert_eq!(barcode_image::clamp_module_px(Some(500)), barcode_image::MAX_MODULE_PX);
+}
+
+#[test]
+fn paid_purchase_starts_pending_until_webhook() {
+    let ngn = Currency::parse("NGN").unwrap();
+    assert_eq!(initial_status(&Money::new(dec!(5000.00), ngn.clone())), TicketStatus::Pending);
+    // Fully discounted: nothing to confirm, scannable straight away
+    assert_eq!(initial_status(&Money::new(dec!(0), ngn)), TicketStatus::Valid);
+}
+
+#[test]
+fn event_lifecycle_moves_forward_only() {
+    assert!(check_event_transition(EventStatus::Draft, EventStatus::Active, 0).is_ok());
+    assert!(check_event_transition(EventStatus::Active, EventStatus::Completed, 0).is_ok());
+    assert!(check_event_transition(EventStatus::Active, EventStatus::Cancelled, 0).is_ok());
+    assert!(check_event_transition(EventStatus::Completed, EventStatus::Active, 0).is_err());
+    assert!(check_event_transition(EventStatus::Active, EventStatus::Draft, 0).is_err());
+    assert!(check_event_transition(EventStatus::Active, EventStatus::Active, 0).is_err());
+}
+
+#[test]
+fn cancelled_event_with_refunds_cannot_be_reinstated() {
+    assert!(check_event_transition(EventStatus::Cancelled, EventStatus::Active, 0).is_ok());
+    let err = check_event_transition(EventStatus::Cancelled, EventStatus::Active, 3).unwrap_err();
+    assert_eq!(err.into_response().status(), StatusCode::CONFLICT);
+}
+
+#[test]
+fn event_status_parses_db_values_only() {
+    assert_eq!("completed".parse::<EventStatus>().unwrap(), EventStatus::Completed);
+    assert!("closed".parse::<EventStatus>().is_err());
+}
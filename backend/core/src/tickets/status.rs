@@ -0,0 +1,109 @@
+// TicketStatus — the one place ticket status strings are spelled out.
+//
+// The DB column stays VARCHAR; queries bind `as_str()` instead of embedding
+// literals, and code that reads a status parses it back with `parse()`.
+//
+// Lifecycle:
+//   pending  → valid (payment confirmed) | failed | cancelled | expired
+//   valid    → used | cancelled | expired | refunded
+//   used     → refunded
+//   failed   → pending (payment retried) | valid (late confirmation) | cancelled
+//   expired  → refunded
+//   cancelled→ refunded
+//   refunded   terminal
+//
+// used → valid is deliberately not a transition. The two places that reopen
+// a used ticket are explicit corrections that bypass this table: scan undo
+// locks the row and checks it with check_undo (the scanner that made the
+// scan, inside its short window), and renewal of a renewable ticket writes
+// valid after checking is_renewable.
+//
+// Chargebacks don't get a status of their own; the admin blacklist stops a
+// disputed ticket at the gate without touching its lifecycle.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::AppError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TicketStatus {
+    Pending,
+    Valid,
+    Used,
+    Expired,
+    Cancelled,
+    Refunded,
+    Failed,
+}
+
+impl TicketStatus {
+    pub const ALL: [TicketStatus; 7] = [
+        Self::Pending, Self::Valid, Self::Used, Self::Expired,
+        Self::Cancelled, Self::Refunded, Self::Failed,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Valid => "valid",
+            Self::Used => "used",
+            Self::Expired => "expired",
+            Self::Cancelled => "cancelled",
+            Self::Refunded => "refunded",
+            Self::Failed => "failed",
+        }
+    }
+
+    /// Legal lifecycle moves. Same-status "transitions" are not legal — an
+    /// UPDATE that changes nothing is a bug upstream.
+    pub fn can_transition_to(self, next: TicketStatus) -> bool {
+        use TicketStatus::*;
+        matches!(
+            (self, next),
+            (Pending, Valid | Failed | Cancelled | Expired)
+                | (Valid, Used | Cancelled | Expired | Refunded)
+                | (Used, Refunded)
+                | (Failed, Pending | Valid | Cancelled)
+                | (Expired, Refunded)
+                | (Cancelled, Refunded)
+        )
+    }
+
+    /// Statuses a ticket may be in for an UPDATE to `self` — bind as `status = ANY($n)`.
+    pub fn sources(self) -> Vec<&'static str> {
+        Self::ALL
+            .into_iter()
+            .filter(|s| s.can_transition_to(self))
+            .map(Self::as_str)
+            .collect()
+    }
+
+    /// Parse a value read from tickets.status. An unknown value means the
+    /// schema and this enum disagree — a server fault, not bad input.
+    pub fn from_db(s: &str) -> Result<Self, AppError> {
+        s.parse().map_err(AppError::Internal)
+    }
+
+    /// Can still be admitted, transferred or reassigned.
+    pub fn is_live(self) -> bool {
+        matches!(self, Self::Valid | Self::Pending)
+    }
+}
+
+impl fmt::Display for TicketStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TicketStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|st| st.as_str() == s)
+            .ok_or_else(|| format!("unknown ticket status '{}'", s))
+    }
+}
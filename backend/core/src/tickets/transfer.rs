@@ -25,9 +25,8 @@
  */
 
 use axum::{
-    extract::{Path, State},
+    extract::State,
     http::HeaderMap,
-    Json,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
@@ -35,13 +34,38 @@ use uuid::Uuid;
 use chrono::Utc;
 
 use crate::error::{AppError, Result};
+use crate::extract::{Json, Path};
+use super::status::TicketStatus;
 use sqlx::{PgPool, Row};
 use std::sync::Arc;
 
 /// Transfers are blocked this many minutes before event start.
 /// 60 minutes gives scanners time to process the crowd before
 /// any last-minute transfer could create a duplicate-entry window.
-const TRANSFER_LOCK_MINUTES: i64 = 60;
+pub(crate) const TRANSFER_LOCK_MINUTES: i64 = 60;
+
+/// A blacklisted ticket (chargeback, leaked QR) can't be handed on — moving
+/// it would give a new holder a ticket the gate will refuse. `action` names
+/// what was attempted, for the error.
+pub(crate) fn check_not_blacklisted(blacklisted: bool, action: &str) -> Result<()> {
+    if blacklisted {
+        return Err(AppError::BadRequest(format!("Cannot {} a blacklisted ticket", action)));
+    }
+    Ok(())
+}
+
+/// A group ticket that has let some of its seats in is part-way through the
+/// gate: the rest of the group still enters on the same QR. Handing it on,
+/// or cancelling it for a full refund, would pay back seats already used.
+pub(crate) fn check_not_admitted(scanned_quantity: i32, action: &str) -> Result<()> {
+    if scanned_quantity > 0 {
+        return Err(AppError::BadRequest(format!(
+            "Cannot {} a ticket whose group has started entering",
+            action
+        )));
+    }
+    Ok(())
+}
 
 #[derive(Debug, Deserialize)]
 pub struct TransferRequest {
@@ -83,8 +107,9 @@ pub async fn transfer_ticket(
 
     // STEP 1: Fetch ticket with row lock — verify ownership and status
     let ticket_row = sqlx::query(
-        r#"SELECT t.id, t.ticket_id, t.event_id, t.user_id, t.status,
-                  (e.date + e.time) AT TIME ZONE 'UTC' AS event_starts_at
+        r#"SELECT t.id, t.ticket_id, t.event_id, t.user_id, t.status, t.scanned_quantity,
+                  (e.date + e.time) AT TIME ZONE e.timezone AS event_starts_at,
+                  EXISTS(SELECT 1 FROM ticket_blacklist b WHERE b.ticket_id = t.ticket_id) AS blacklisted
            FROM tickets t
            JOIN events e ON e.id = t.event_id
            WHERE t.ticket_id = $1
@@ -99,7 +124,7 @@ pub async fn transfer_ticket(
     let ticket_uuid: Uuid        = ticket_row.get("id");
     let event_id: Uuid           = ticket_row.get("event_id");
     let owner_id: Uuid           = ticket_row.get("user_id");
-    let status: String           = ticket_row.get("status");
+    let status = TicketStatus::from_db(ticket_row.get("status"))?;
     let event_starts_at: chrono::DateTime<Utc> = ticket_row.get("event_starts_at");
 
     // STEP 2: Verify caller owns this ticket
@@ -108,12 +133,27 @@ pub async fn transfer_ticket(
     }
 
     // STEP 3: Verify ticket is transferable
-    if status != "valid" {
+    if status != TicketStatus::Valid {
         return Err(AppError::BadRequest(format!(
             "Cannot transfer a ticket with status '{}'",
             status
         )));
     }
+    check_not_blacklisted(ticket_row.get("blacklisted"), "transfer")?;
+    check_not_admitted(ticket_row.get("scanned_quantity"), "transfer")?;
+
+    // A listed ticket belongs to the marketplace until the listing is withdrawn —
+    // otherwise a buyer could pay for a ticket that was just given away.
+    let listed: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM ticket_resale_listings WHERE ticket_id = $1 AND status IN ('listed', 'pending'))",
+    )
+    .bind(ticket_uuid)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(AppError::Database)?;
+    if listed {
+        return Err(AppError::Conflict("Ticket is listed for resale — withdraw the listing first".into()));
+    }
 
     // STEP 4: Enforce time lock — block transfers too close to event start.
     // This is the primary anti-fraud guard. A transfer within the lock window
@@ -158,12 +198,13 @@ pub async fn transfer_ticket(
                transferred_from = COALESCE(transferred_from, id),
                transferred_at   = NOW(),
                original_user_id = COALESCE(original_user_id, $4)
-           WHERE id = $1 AND status = 'valid'"#,
+           WHERE id = $1 AND status = $5"#,
     )
     .bind(ticket_uuid)
     .bind(recipient_id)
     .bind(&new_nonce)
     .bind(caller_id)
+    .bind(TicketStatus::Valid.as_str())
     .execute(&mut *tx)
     .await
     .map_err(AppError::Database)?;
@@ -199,7 +240,7 @@ pub async fn transfer_ticket(
             "transfer_id": transfer_id,
             "ticket_id": ticket_id_str,
             "to_email": to_email,
-            "transferred_at": Utc::now().to_rfc3339()
+            "transferred_at": crate::timestamp::format(&Utc::now())
         }
     })))
 }
@@ -0,0 +1,66 @@
+// Timestamps on the wire — one shape for every instant a client sees.
+//
+// RFC 3339 in UTC with a "Z" suffix and millisecond precision:
+// "2026-10-16T09:30:00.000Z". chrono's default serde writes as many
+// fractional digits as the value happens to have, and to_rfc3339() writes
+// "+00:00", so the same instant used to leave the API in several spellings.
+//
+// DTO fields:    #[serde(with = "crate::timestamp")]
+//                #[serde(with = "crate::timestamp::option")]
+// json!() bodies: timestamp::format(&t)
+//
+// Output only; requests still parse any RFC 3339 offset via chrono's default.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serializer;
+
+pub fn format(t: &DateTime<Utc>) -> String {
+    t.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+pub fn serialize<S: Serializer>(t: &DateTime<Utc>, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(&format(t))
+}
+
+/// Same format for Option fields; None stays null.
+pub mod option {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(t: &Option<DateTime<Utc>>, s: S) -> Result<S::Ok, S::Error> {
+        match t {
+            Some(t) => s.serialize_str(&format(t)),
+            None => s.serialize_none(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use serde::Serialize;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Stamped {
+        #[serde(with = "crate::timestamp")]
+        at: DateTime<Utc>,
+        #[serde(with = "crate::timestamp::option")]
+        maybe: Option<DateTime<Utc>>,
+    }
+
+    #[test]
+    fn serializes_utc_with_millis_and_z() {
+        let at = Utc.with_ymd_and_hms(2026, 10, 16, 9, 30, 0).unwrap() + chrono::Duration::nanoseconds(123_456_789);
+        let json = serde_json::to_value(Stamped { at, maybe: None }).unwrap();
+        assert_eq!(json, serde_json::json!({ "at": "2026-10-16T09:30:00.123Z", "maybe": null }));
+    }
+
+    #[test]
+    fn whole_seconds_keep_the_same_width() {
+        let at = Utc.with_ymd_and_hms(2026, 1, 2, 3, 4, 5).unwrap();
+        let json = serde_json::to_value(Stamped { at, maybe: Some(at) }).unwrap();
+        assert_eq!(json["at"], "2026-01-02T03:04:05.000Z");
+        assert_eq!(json["maybe"], json["at"]);
+    }
+}
@@ -119,6 +119,7 @@ pub struct VendorResponse {
     pub is_verified:       bool,
     pub is_available:      bool,
     pub profile_views:     i32,
+    #[serde(with = "crate::timestamp")]
     pub created_at:        DateTime<Utc>,
 }
 
@@ -144,14 +145,20 @@ pub struct HireResponse {
     pub event_id:        Uuid,
     pub vendor_id:       Uuid,
     pub organizer_id:    Uuid,
+    #[serde(with = "rust_decimal::serde::str_option")]
     pub proposed_amount: Option<Decimal>,
+    #[serde(with = "rust_decimal::serde::str_option")]
     pub agreed_amount:   Option<Decimal>,
+    #[serde(with = "rust_decimal::serde::str_option")]
     pub bukr_commission: Option<Decimal>,
     pub commission_rate: Decimal,
     pub status:          String,
     pub message:         Option<String>,
+    #[serde(with = "rust_decimal::serde::str_option")]
     pub counter_amount:  Option<Decimal>,
+    #[serde(with = "crate::timestamp")]
     pub created_at:      DateTime<Utc>,
+    #[serde(with = "crate::timestamp")]
     pub updated_at:      DateTime<Utc>,
 }
 
@@ -164,6 +171,7 @@ pub struct ReviewResponse {
     pub hire_id:     Uuid,
     pub rating:      i32,
     pub review:      Option<String>,
+    #[serde(with = "crate::timestamp")]
     pub created_at:  DateTime<Utc>,
 }
 
@@ -173,6 +181,7 @@ pub struct InviteResponse {
     pub invitation_id: Uuid,
     pub email:         String,
     pub token:         String,  // The signed invite token (embed in email link)
+    #[serde(with = "crate::timestamp")]
     pub expires_at:    DateTime<Utc>,
 }
 
@@ -7,14 +7,14 @@
  */
 
 use axum::{
-    extract::{Path, Query, State},
+    extract::State,
     http::HeaderMap,
-    Json,
 };
 use serde_json::{json, Value};
 use uuid::Uuid;
 
 use crate::error::{AppError, Result};
+use crate::extract::{Json, Path, Query};
 use super::dto::{
     AvailabilitySetRequest, CompleteHireRequest, CreateVendorRequest, HireRequest,
     HireRespondRequest, InviteVendorRequest, ReviewRequest, VendorSearchParams,
@@ -16,7 +16,7 @@ use chrono::{NaiveDate, Utc};
 use rust_decimal::Decimal;
 use uuid::Uuid;
 
-use crate::error::{AppError, Result};
+use crate::error::{is_unique_violation, AppError, Result};
 
 use super::dto::{
     AvailabilitySetRequest, CompleteHireRequest, CreateVendorRequest, HireRequest,
@@ -42,7 +42,11 @@ impl VendorService {
         if let Some(_) = self.repo.get_by_user_id(user_id).await.map_err(AppError::Database)? {
             return Err(AppError::Validation("You already have a vendor profile".into()));
         }
-        let vendor = self.repo.create(user_id, &req).await.map_err(AppError::Database)?;
+        let vendor = self.repo.create(user_id, &req).await.map_err(|e| if is_unique_violation(&e) {
+            AppError::Validation("You already have a vendor profile".into())
+        } else {
+            AppError::Database(e)
+        })?;
         Ok(VendorResponse::from(vendor))
     }
 
@@ -95,14 +99,18 @@ impl VendorService {
             return Err(AppError::Validation("This vendor is currently unavailable".into()));
         }
 
-        self.repo.create_hire(
+        let hire = self.repo.create_hire(
             req.event_id,
             req.vendor_id,
             organizer_id,
             req.proposed_amount,
             vendor.commission_rate,
             req.message.as_deref(),
-        ).await.map_err(AppError::Database)
+        ).await.map_err(AppError::Database)?;
+
+        crate::audit::record_logged(self.repo.pool(), Some(organizer_id), "vendor_hire.request", "vendor_hire", hire.id,
+            serde_json::json!({ "event_id": req.event_id, "vendor_id": req.vendor_id, "proposed_amount": req.proposed_amount })).await;
+        Ok(hire)
     }
 
     pub async fn respond_hire(
@@ -184,6 +192,9 @@ impl VendorService {
         .execute(self.repo.pool())
         .await;
 
+        crate::audit::record_logged(self.repo.pool(), Some(organizer_id), "vendor_hire.complete", "vendor_hire", hire_id,
+            serde_json::json!({ "agreed_amount": req.agreed_amount, "commission": commission })).await;
+
         Ok(completed)
     }
 
@@ -219,7 +230,11 @@ impl VendorService {
             Some(hire.event_id),
             req.rating,
             req.review.as_deref(),
-        ).await.map_err(AppError::Database)
+        ).await.map_err(|e| if is_unique_violation(&e) {
+            AppError::Conflict("This hire has already been reviewed".into())
+        } else {
+            AppError::Database(e)
+        })
     }
 
     pub async fn get_vendor_reviews(&self, vendor_id: Uuid) -> Result<Vec<ReviewResponse>> {
@@ -233,8 +248,11 @@ impl VendorService {
         organizer_id: Uuid,
         req: InviteVendorRequest,
     ) -> Result<InviteResponse> {
-        self.repo.create_invitation(organizer_id, req.event_id, &req.email)
-            .await.map_err(AppError::Database)
+        let invite = self.repo.create_invitation(organizer_id, req.event_id, &req.email)
+            .await.map_err(AppError::Database)?;
+        crate::audit::record_logged(self.repo.pool(), Some(organizer_id), "vendor_invitation.send", "vendor_invitation", invite.invitation_id,
+            serde_json::json!({ "event_id": req.event_id, "email": invite.email })).await;
+        Ok(invite)
     }
 
     pub async fn claim_invitation(&self, token: &str) -> Result<serde_json::Value> {
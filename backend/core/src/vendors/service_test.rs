@@ -0,0 +1,9 @@
+// Unit tests for vendor DTO parsing.
+
+use super::dto::VendorSearchParams;
+
+#[test]
+fn empty_search_params_parse_to_defaults() {
+    let params: VendorSearchParams = serde_json::from_str("{}").unwrap();
+    assert!(params.limit.is_none());
+}
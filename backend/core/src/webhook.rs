@@ -0,0 +1,221 @@
+// Outbound webhook HTTP — one hardened client shape for every callback we POST.
+//
+// Event organizers configure scan_webhook_url themselves, so it is untrusted
+// input pointing our server at an arbitrary address (SSRF). Every request
+// through WebhookClient gets:
+//   - https only, certificate verification always on (rustls, system roots)
+//   - a strict total timeout and no redirect following (a 302 to
+//     http://169.254.169.254/ would otherwise walk straight past the checks)
+//   - host policy: WEBHOOK_DENY_HOSTS always wins; when WEBHOOK_ALLOW_HOSTS is
+//     set, only those hosts (and their subdomains) are reachable
+//   - address policy: loopback, private, link-local (cloud metadata), CGNAT
+//     and other non-public ranges are refused — checked on IP literals up
+//     front and again on every DNS answer at connect time, so a hostname that
+//     later re-resolves to 10.0.0.1 is still blocked
+//
+// The operator-configured outbox sink uses base_client() (timeout, TLS, no
+// redirects) without the address policy — it legitimately targets internal hosts.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::Url;
+
+/// Hosts webhooks may (allow) or may never (deny) reach. Entries match the
+/// host itself and any subdomain: "example.com" covers "hooks.example.com".
+#[derive(Debug, Clone, Default)]
+pub struct HostPolicy {
+    allow: Vec<String>,
+    deny: Vec<String>,
+}
+
+impl HostPolicy {
+    pub fn new(allow: Vec<String>, deny: Vec<String>) -> Self {
+        let norm = |v: Vec<String>| {
+            v.into_iter()
+                .map(|h| h.trim().trim_start_matches('.').to_ascii_lowercase())
+                .filter(|h| !h.is_empty())
+                .collect()
+        };
+        Self { allow: norm(allow), deny: norm(deny) }
+    }
+
+    /// Whether only listed hosts are reachable.
+    pub fn has_allowlist(&self) -> bool {
+        !self.allow.is_empty()
+    }
+
+    /// Parse and vet a destination URL. Err carries a reason safe to log.
+    pub fn check_url(&self, raw: &str) -> Result<Url, String> {
+        let url = Url::parse(raw.trim()).map_err(|e| format!("invalid URL: {}", e))?;
+        if url.scheme() != "https" {
+            return Err("only https URLs are allowed".into());
+        }
+        if !url.username().is_empty() || url.password().is_some() {
+            return Err("credentials in URL are not allowed".into());
+        }
+        let host = url.host_str().ok_or("URL has no host")?.to_ascii_lowercase();
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+
+        if self.deny.iter().any(|d| host_matches(host, d)) {
+            return Err(format!("host '{}' is denied", host));
+        }
+        if !self.allow.is_empty() && !self.allow.iter().any(|a| host_matches(host, a)) {
+            return Err(format!("host '{}' is not in the webhook allowlist", host));
+        }
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            if is_blocked_ip(ip) {
+                return Err(format!("address {} is not publicly routable", ip));
+            }
+        }
+        if host == "localhost" || host.ends_with(".localhost") || host.ends_with(".internal") {
+            return Err(format!("host '{}' is internal", host));
+        }
+        Ok(url)
+    }
+}
+
+fn host_matches(host: &str, entry: &str) -> bool {
+    host == entry || host.strip_suffix(entry).is_some_and(|rest| rest.ends_with('.'))
+}
+
+/// True for addresses a user-supplied webhook must never reach.
+pub fn is_blocked_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => is_blocked_v4(v4),
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => is_blocked_v4(v4),
+            None => is_blocked_v6(v6),
+        },
+    }
+}
+
+fn is_blocked_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()          // 169.254/16 — cloud metadata lives here
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        || a == 0
+        || (a == 100 && (64..128).contains(&b))   // CGNAT 100.64/10
+        || (a == 198 && (b == 18 || b == 19))     // benchmarking 198.18/15
+        || a >= 240                               // reserved
+}
+
+fn is_blocked_v6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        || (first & 0xfe00) == 0xfc00   // unique local fc00::/7
+        || (first & 0xffc0) == 0xfe80   // link-local fe80::/10
+}
+
+// Resolver that drops blocked addresses from every DNS answer.
+struct GuardedResolver;
+
+impl Resolve for GuardedResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|a| !is_blocked_ip(a.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{} resolves only to non-public addresses", host).into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Timeout + verified TLS + no redirects. For trusted, operator-set targets.
+pub fn base_client(timeout: Duration) -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(timeout)
+        .connect_timeout(timeout.min(Duration::from_secs(3)))
+        .redirect(reqwest::redirect::Policy::none())
+        .tls_built_in_root_certs(true)
+        .build()
+        .expect("reqwest client build failed")
+}
+
+/// Client for untrusted, user-configured webhook URLs.
+pub struct WebhookClient {
+    http: reqwest::Client,
+    policy: HostPolicy,
+}
+
+impl WebhookClient {
+    pub fn new(policy: HostPolicy, timeout: Duration) -> Arc<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(timeout)
+            .connect_timeout(timeout.min(Duration::from_secs(3)))
+            .redirect(reqwest::redirect::Policy::none())
+            .https_only(true)
+            .tls_built_in_root_certs(true)
+            .dns_resolver(Arc::new(GuardedResolver))
+            .build()
+            .expect("reqwest client build failed");
+        Arc::new(Self { http, policy })
+    }
+
+    /// POST JSON after the URL passes the host policy. Returns the response
+    /// status; Err covers policy rejections and transport failures alike.
+    pub async fn post_json(&self, url: &str, payload: &serde_json::Value) -> Result<reqwest::StatusCode, String> {
+        let url = self.policy.check_url(url)?;
+        self.http
+            .post(url)
+            .json(payload)
+            .send()
+            .await
+            .map(|resp| resp.status())
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open() -> HostPolicy {
+        HostPolicy::default()
+    }
+
+    #[test]
+    fn requires_https_and_public_hosts() {
+        assert!(open().check_url("https://hooks.example.com/scan").is_ok());
+        assert!(open().check_url("http://hooks.example.com/scan").is_err());
+        assert!(open().check_url("https://127.0.0.1/").is_err());
+        assert!(open().check_url("https://169.254.169.254/latest/meta-data").is_err());
+        assert!(open().check_url("https://10.1.2.3/").is_err());
+        assert!(open().check_url("https://[::1]/").is_err());
+        assert!(open().check_url("https://localhost/").is_err());
+        assert!(open().check_url("https://user:pw@example.com/").is_err());
+    }
+
+    #[test]
+    fn allow_and_deny_lists_match_subdomains() {
+        let policy = HostPolicy::new(vec!["example.com".into()], vec!["bad.example.com".into()]);
+        assert!(policy.check_url("https://example.com/").is_ok());
+        assert!(policy.check_url("https://hooks.example.com/").is_ok());
+        assert!(policy.check_url("https://bad.example.com/").is_err());
+        assert!(policy.check_url("https://notexample.com/").is_err());
+        assert!(policy.check_url("https://other.org/").is_err());
+    }
+
+    #[test]
+    fn blocks_mapped_and_reserved_ranges() {
+        assert!(is_blocked_ip("::ffff:192.168.1.1".parse().unwrap()));
+        assert!(is_blocked_ip("100.64.0.1".parse().unwrap()));
+        assert!(is_blocked_ip("fd00::1".parse().unwrap()));
+        assert!(!is_blocked_ip("8.8.8.8".parse().unwrap()));
+        assert!(!is_blocked_ip("2606:4700::1111".parse().unwrap()));
+    }
+}
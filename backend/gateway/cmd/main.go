@@ -7,6 +7,7 @@ import (
 	"os"
 	"os/signal"
 	"syscall"
+	"time"
 
 	"github.com/bukr/gateway/internal/admin"
 	"github.com/bukr/gateway/internal/auth"
@@ -70,12 +71,22 @@ func main() {
 	notifWorker.SetInviteExpirer(inviteService)
 	notifWorker.Start(workerCtx)
 
+	// Behind a load balancer every request arrives from its address, so
+	// c.IP() — the key for the per-IP limiters below — must come from
+	// X-Forwarded-For, and only when a TRUSTED_PROXIES hop set it. Fiber reads the
+	// left-most entry, which the client controls, so middleware.ClientIP
+	// first cuts the header down to the hop our own proxy appended.
 	app := fiber.New(fiber.Config{
-		AppName:      "Bukr Gateway",
-		ErrorHandler: globalErrorHandler,
+		AppName:                 "Bukr Gateway",
+		ErrorHandler:            globalErrorHandler,
+		ProxyHeader:             fiber.HeaderXForwardedFor,
+		EnableTrustedProxyCheck: true,
+		TrustedProxies:          cfg.TrustedProxies,
+		EnableIPValidation:      true,
 	})
 
 	app.Use(recover.New())
+	app.Use(middleware.ClientIP(cfg.TrustedProxies))
 	app.Use(middleware.RequestLogger())
 	app.Use(middleware.SetupCORS(cfg.AllowedOrigins))
 	app.Use(middleware.SecurityHeaders())
@@ -84,9 +95,12 @@ func main() {
 	if cfg.RedisURL != "" {
 		limiterStore = redisStorage.New(redisStorage.Config{URL: cfg.RedisURL})
 	}
+	// X-RateLimit-* tell clients how much room is left so they can back off
+	// before a 429; see middleware.TrackRateLimit for nested limiters.
+	app.Use(middleware.RateLimitHeaders())
 	app.Use(limiter.New(limiter.Config{
 		Max:        100,
-		Expiration: 60,
+		Expiration: 60 * time.Second,
 		Storage:    limiterStore,
 		KeyGenerator: func(c *fiber.Ctx) string {
 			return c.IP()
@@ -128,15 +142,42 @@ func main() {
 	creditsService := credits.NewService(creditsRepo, cfg.PaystackSecret, cfg.AllowedOrigins)
 	creditsHandler := credits.NewHandler(creditsService, cfg.PaystackSecret)
 
+	// Created ahead of the event groups: a few public /events paths are served
+	// by the Rust core and must be registered before eventsProtected's USE.
+	rustProxy := proxy.NewRustProxy(cfg.RustServiceURL, cfg.GatewaySecret)
+
+	// Machine-readable contract for the Rust-served DTOs — public, no auth.
+	v1.Get("/openapi.json", func(c *fiber.Ctx) error {
+		return rustProxy.Forward(c, "/api/v1/openapi.json")
+	})
+
+	// Server clock, build and feature flags — scanners sync against it offline.
+	v1.Get("/meta", func(c *fiber.Ctx) error {
+		return rustProxy.Forward(c, "/api/v1/meta")
+	})
+
 	// ── Public event routes ────────────────────────────────────────────────────
 	eventsPublic := v1.Group("/events")
 	eventRepo := events.NewRepository(db)
 	eventService := events.NewService(eventRepo, rdb)
 	eventService.WithCredits(creditsService)
+	eventService.WithCoreCache(rustProxy)
 	eventHandler := events.NewHandler(eventService)
 	// /me must be registered before /:id to prevent the wildcard swallowing it.
 	// It carries auth middleware even though it lives in the public group.
 	eventsPublic.Get("/me", userAuth, eventHandler.ListMyEvents)
+	// Tier selector on the buy page — public, guests buy too.
+	eventsPublic.Get("/:id/ticket-types", func(c *fiber.Ctx) error {
+		return rustProxy.Forward(c, fmt.Sprintf("/api/v1/events/%s/ticket-types", c.Params("id")))
+	})
+	// Read-only impact summary shown before the organizer confirms a cancel.
+	eventsPublic.Get("/:id/cancel-preview", userAuth, middleware.RequireOrganizer(), func(c *fiber.Ctx) error {
+		return rustProxy.Forward(c, fmt.Sprintf("/api/v1/events/%s/cancel-preview", c.Params("id")))
+	})
+	// Lifecycle moves are validated in Rust (draft → active → completed/cancelled).
+	eventsPublic.Patch("/:id/status", userAuth, middleware.RequireOrganizer(), func(c *fiber.Ctx) error {
+		return rustProxy.Forward(c, fmt.Sprintf("/api/v1/events/%s/status", c.Params("id")))
+	})
 	eventHandler.RegisterPublicRoutes(eventsPublic)
 
 	// ── Protected user routes ──────────────────────────────────────────────────
@@ -177,16 +218,36 @@ func main() {
 	infHandler.RegisterRoutes(infGroup)
 
 	// ── Proxy routes (Rust core) ───────────────────────────────────────────────
-	rustProxy := proxy.NewRustProxy(cfg.RustServiceURL, cfg.GatewaySecret)
 	proxyHandler := proxy.NewHandler(rustProxy)
 
 	var ticketLimiterStore fiber.Storage
 	if cfg.RedisURL != "" {
 		ticketLimiterStore = redisStorage.New(redisStorage.Config{URL: cfg.RedisURL})
 	}
-	ticketGroup := v1.Group("/tickets", userAuth, limiter.New(limiter.Config{
+	// Guest checkout is public — register it BEFORE ticketGroup installs its
+	// userAuth USE handler on /tickets (same ordering rule as the webhooks
+	// below). No user id to key on, so the per-IP limit applies. Rust refuses
+	// invite-only events for guests, so the invite gate isn't needed here.
+	v1.Post("/tickets/guest-purchase", middleware.TrackRateLimit(), limiter.New(limiter.Config{
 		Max:        10,
-		Expiration: 60,
+		Expiration: 60 * time.Second,
+		Storage:    ticketLimiterStore,
+		KeyGenerator: func(c *fiber.Ctx) string {
+			return "guest:" + c.IP()
+		},
+	}), func(c *fiber.Ctx) error {
+		return rustProxy.Forward(c, "/api/v1/tickets/guest-purchase")
+	})
+
+	// Add-ons are listed on the buy page, which guests see too — public, so
+	// it goes ahead of ticketGroup's userAuth USE like guest-purchase above.
+	v1.Get("/tickets/event/:event_id/add-ons", func(c *fiber.Ctx) error {
+		return rustProxy.Forward(c, fmt.Sprintf("/api/v1/tickets/event/%s/add-ons", c.Params("event_id")))
+	})
+
+	ticketGroup := v1.Group("/tickets", userAuth, middleware.TrackRateLimit(), limiter.New(limiter.Config{
+		Max:        10,
+		Expiration: 60 * time.Second,
 		Storage:    ticketLimiterStore,
 		KeyGenerator: func(c *fiber.Ctx) string {
 			return c.Locals("user_id").(string)
@@ -198,6 +259,41 @@ func main() {
 	v1.Get("/tickets/event/:event_id", userAuth, middleware.RequireOrganizer(), func(c *fiber.Ctx) error {
 		return rustProxy.Forward(c, fmt.Sprintf("/api/v1/tickets/event/%s", c.Params("event_id")))
 	})
+	// Seats that may be sold past capacity — the event's organizer, checked in Rust.
+	v1.Put("/tickets/event/:event_id/overbook-allowance", userAuth, middleware.RequireOrganizer(), func(c *fiber.Ctx) error {
+		return rustProxy.Forward(c, fmt.Sprintf("/api/v1/tickets/event/%s/overbook-allowance", c.Params("event_id")))
+	})
+	// Seats held back from public sale, and comp tickets issued from them.
+	v1.Put("/tickets/event/:event_id/hold", userAuth, middleware.RequireOrganizer(), func(c *fiber.Ctx) error {
+		return rustProxy.Forward(c, fmt.Sprintf("/api/v1/tickets/event/%s/hold", c.Params("event_id")))
+	})
+	v1.Post("/tickets/event/:event_id/comp", userAuth, middleware.RequireOrganizer(), func(c *fiber.Ctx) error {
+		return rustProxy.Forward(c, fmt.Sprintf("/api/v1/tickets/event/%s/comp", c.Params("event_id")))
+	})
+	// Extras (parking, merch) sold alongside tickets — created by the organizer.
+	v1.Post("/tickets/event/:event_id/add-ons", userAuth, middleware.RequireOrganizer(), func(c *fiber.Ctx) error {
+		return rustProxy.Forward(c, fmt.Sprintf("/api/v1/tickets/event/%s/add-ons", c.Params("event_id")))
+	})
+	// Last moment a buyer may still get money back — the event's organizer, checked in Rust.
+	v1.Put("/tickets/event/:event_id/refund-deadline", userAuth, middleware.RequireOrganizer(), func(c *fiber.Ctx) error {
+		return rustProxy.Forward(c, fmt.Sprintf("/api/v1/tickets/event/%s/refund-deadline", c.Params("event_id")))
+	})
+	// Last moment a holder may cancel their own ticket — the event's organizer, checked in Rust.
+	v1.Put("/tickets/event/:event_id/self-cancel-deadline", userAuth, middleware.RequireOrganizer(), func(c *fiber.Ctx) error {
+		return rustProxy.Forward(c, fmt.Sprintf("/api/v1/tickets/event/%s/self-cancel-deadline", c.Params("event_id")))
+	})
+	// Remaining-seat count that triggers the low-stock alert — organizer, checked in Rust.
+	v1.Put("/tickets/event/:event_id/low-stock-threshold", userAuth, middleware.RequireOrganizer(), func(c *fiber.Ctx) error {
+		return rustProxy.Forward(c, fmt.Sprintf("/api/v1/tickets/event/%s/low-stock-threshold", c.Params("event_id")))
+	})
+	// Tier a purchase gets when it names none — organizer, checked in Rust.
+	v1.Put("/tickets/event/:event_id/default-ticket-type", userAuth, middleware.RequireOrganizer(), func(c *fiber.Ctx) error {
+		return rustProxy.Forward(c, fmt.Sprintf("/api/v1/tickets/event/%s/default-ticket-type", c.Params("event_id")))
+	})
+	// Venue headcount cap the door scanners enforce — organizer, checked in Rust.
+	v1.Put("/tickets/event/:event_id/max-inside", userAuth, middleware.RequireOrganizer(), func(c *fiber.Ctx) error {
+		return rustProxy.Forward(c, fmt.Sprintf("/api/v1/tickets/event/%s/max-inside", c.Params("event_id")))
+	})
 
 	scannerGroup := v1.Group("/scanner", userAuth)
 	proxyHandler.RegisterScannerRoutes(scannerGroup)
@@ -279,6 +375,8 @@ func main() {
 	adminGroup := v1.Group("/admin", adminAuth)
 	adminHandler := admin.NewHandler(db)
 	adminHandler.RegisterRoutes(adminGroup)
+	// Admin operations served by the Rust core, on the same admin token.
+	proxyHandler.RegisterAdminRoutes(adminGroup)
 
 	// ── Graceful shutdown ──────────────────────────────────────────────────────
 	quit := make(chan os.Signal, 1)
@@ -48,10 +48,26 @@ type CreateEventRequest struct {
 	ThumbnailURL    *string  `json:"thumbnail_url"`
 	VideoURL        *string  `json:"video_url"`
 	FlierURL        *string  `json:"flier_url"`
+	Timezone        *string  `json:"timezone"`                          // IANA name date/time are in (default Africa/Lagos)
+	VenueLat        *float64 `json:"venue_lat"`                         // Scan geofence centre — set with venue_lon
+	VenueLon        *float64 `json:"venue_lon"`
+	GeofenceRadiusM *int     `json:"geofence_radius_m"`                 // Metres from the venue a scan may be (default 500)
+	ScanWebhookURL  *string  `json:"scan_webhook_url"`                  // https URL POSTed after every successful scan
+	BookingFeePct   *float64 `json:"booking_fee_pct"`                   // Per-order fee, % of the order total (default 0)
+	BookingFeeFlat  *float64 `json:"booking_fee_flat"`                  // Per-order fee added to the % part (default 0)
+	TaxPercentage   *float64 `json:"tax_percentage"`                    // VAT etc. on the discounted subtotal (default 0)
+	TaxInclusive    *bool    `json:"tax_inclusive"`                     // Price already includes the tax (default false)
+	RotatingQR      *bool    `json:"rotating_qr"`                       // QR payload rotates so screenshots go stale (default false)
+	QRTTLSeconds    *int     `json:"qr_ttl_seconds"`                    // Rotation period, 10–600 (default 30)
+	AllowedProviders []string `json:"allowed_providers"`                // Payment providers buyers may use (default: all)
+	RequireRating   *bool    `json:"require_rating"`                    // Buyers must give an excitement rating, 1–10 (default false)
+	MinPrice        *float64 `json:"min_price"`                         // Pay-what-you-want floor; price becomes the suggested amount
 }
 
 // UpdateEventRequest: Partial event update
 // All fields optional (nil = no change)
+// Status is not here: it moves through PATCH /events/:id/status, which
+// checks the transition and cancels tickets with the event.
 type UpdateEventRequest struct {
 	Title           *string  `json:"title"`
 	Description     *string  `json:"description"`
@@ -69,11 +85,26 @@ type UpdateEventRequest struct {
 	Category        *string  `json:"category"`
 	Emoji           *string  `json:"emoji"`
 	TotalTickets    *int     `json:"total_tickets"`
-	Status          *string  `json:"status"`          // active, cancelled, completed
 	RequiresPayment *bool    `json:"requires_payment"`
 	ThumbnailURL    *string  `json:"thumbnail_url"`
 	VideoURL        *string  `json:"video_url"`
 	FlierURL        *string  `json:"flier_url"`
+	EventGroupID    *string  `json:"event_group_id"`  // links sub-events for cross-session scanning; "" unlinks
+	Timezone        *string  `json:"timezone"`        // IANA name date/time are in, e.g. Africa/Lagos
+	VenueLat        *float64 `json:"venue_lat"`       // Scan geofence centre — set with venue_lon
+	VenueLon        *float64 `json:"venue_lon"`
+	GeofenceRadiusM *int     `json:"geofence_radius_m"`
+	ScanWebhookURL  *string  `json:"scan_webhook_url"` // "" removes it
+	BookingFeePct   *float64 `json:"booking_fee_pct"`
+	BookingFeeFlat  *float64 `json:"booking_fee_flat"`
+	TaxPercentage   *float64 `json:"tax_percentage"`
+	TaxInclusive    *bool    `json:"tax_inclusive"`
+	RotatingQR      *bool    `json:"rotating_qr"`
+	QRTTLSeconds    *int     `json:"qr_ttl_seconds"`
+	AllowedProviders *[]string `json:"allowed_providers"` // [] allows every provider again
+	RequireRating   *bool    `json:"require_rating"`
+	MinPrice        *float64 `json:"min_price"`
+	ClearMinPrice   bool     `json:"clear_min_price"` // back to a fixed price
 }
 
 // ListEventsQuery: Event filtering and pagination
@@ -189,6 +220,8 @@ type Event struct {
  * 
  * Calculates derived fields:
  * - sold_tickets = total_tickets - available_tickets
+ * - available_tickets never below 0 — an overbooked event has sold past
+ *   capacity, which shows in sold_tickets instead
  * - organizer info from joined fields
  * 
  * @returns EventResponse for API
@@ -199,6 +232,10 @@ func (e *Event) ToResponse() EventResponse {
 	if sold < 0 {
 		sold = 0
 	}
+	available := e.AvailableTickets
+	if available < 0 {
+		available = 0
+	}
 
 	// Build response
 	resp := EventResponse{
@@ -222,7 +259,7 @@ func (e *Event) ToResponse() EventResponse {
 		EventKey:         e.EventKey,
 		Status:           e.Status,
 		TotalTickets:     e.TotalTickets,
-		AvailableTickets: e.AvailableTickets,
+		AvailableTickets: available,
 		SoldTickets:      sold,
 		RequiresPayment:  e.RequiresPayment,
 		ThumbnailURL:     e.ThumbnailURL,
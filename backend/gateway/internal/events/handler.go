@@ -251,6 +251,9 @@ func (h *Handler) UpdateEvent(c *fiber.Ctx) error {
 		if errors.Is(err, shared.ErrNotFound) {
 			return shared.Error(c, fiber.StatusNotFound, shared.CodeNotFound, "Event not found or not owned by you")
 		}
+		if errors.Is(err, shared.ErrValidation) {
+			return shared.Error(c, fiber.StatusBadRequest, shared.CodeValidationError, err.Error())
+		}
 		return shared.Error(c, fiber.StatusInternalServerError, shared.CodeInternalError, "Failed to update event")
 	}
 
@@ -287,6 +287,19 @@ func (r *Repository) Create(ctx context.Context, organizerID string, req CreateE
 		}
 	}
 
+	// Default timezone — date and time are the local start in it
+	timezone := "Africa/Lagos"
+	if req.Timezone != nil {
+		timezone = *req.Timezone
+	}
+
+	// No list means every provider — stored as NULL, not an empty array,
+	// which the column's CHECK refuses
+	var allowedProviders any
+	if len(req.AllowedProviders) > 0 {
+		allowedProviders = req.AllowedProviders
+	}
+
 	// Generate URL-friendly slug
 	eventKey := generateEventKey(req.Title)
 
@@ -297,11 +310,27 @@ func (r *Repository) Create(ctx context.Context, organizerID string, req CreateE
 		  (organizer_id, title, description, date, time, end_date, location, city, event_type,
 		   latitude, longitude, online_link,
 		   price, currency, category, emoji, event_key, total_tickets, available_tickets,
-		   requires_payment, thumbnail_url, video_url, flier_url)
+		   requires_payment, thumbnail_url, video_url, flier_url, timezone,
+		   venue_lat, venue_lon, geofence_radius_m,
+		   scan_webhook_url,
+		   booking_fee_pct, booking_fee_flat,
+		   tax_percentage, tax_inclusive,
+		   rotating_qr, qr_ttl_seconds,
+		   allowed_providers,
+		   require_rating,
+		   min_price)
 		VALUES ($1, $2, $3, $4::date, $5::time, $6::date, $7, $8, $9,
 		        $10, $11, $12,
 		        $13, $14, $15, $16, $17, $18, $18,
-		        $19, $20, $21, $22)
+		        $19, $20, $21, $22, $23,
+		        $24, $25, COALESCE($26::int, 500),
+		        NULLIF($27, ''),
+		        COALESCE($28::numeric, 0), COALESCE($29::numeric, 0),
+		        COALESCE($30::numeric, 0), COALESCE($31::boolean, false),
+		        COALESCE($32::boolean, false), COALESCE($33::int, 30),
+		        $34::text[],
+		        COALESCE($35::boolean, false),
+		        $36::numeric)
 		RETURNING id::text, organizer_id::text, title, description, date::text, time::text,
 		          end_date::text, location, city, event_type, latitude, longitude, online_link,
 		          price, currency, category, emoji,
@@ -312,6 +341,14 @@ func (r *Repository) Create(ctx context.Context, organizerID string, req CreateE
 		req.Latitude, req.Longitude, req.OnlineLink,
 		req.Price, currency, req.Category, req.Emoji, eventKey,
 		req.TotalTickets, requiresPayment, req.ThumbnailURL, req.VideoURL, req.FlierURL,
+		timezone, req.VenueLat, req.VenueLon, req.GeofenceRadiusM,
+		req.ScanWebhookURL,
+		req.BookingFeePct, req.BookingFeeFlat,
+		req.TaxPercentage, req.TaxInclusive,
+		req.RotatingQR, req.QRTTLSeconds,
+		allowedProviders,
+		req.RequireRating,
+		req.MinPrice,
 	).Scan(
 		&ev.ID, &ev.OrganizerID, &ev.Title, &ev.Description,
 		&ev.Date, &ev.Time, &ev.EndDate, &ev.Location,
@@ -405,9 +442,6 @@ func (r *Repository) Update(ctx context.Context, id, organizerID string, req Upd
 	if req.TotalTickets != nil {
 		addField("total_tickets", *req.TotalTickets)
 	}
-	if req.Status != nil {
-		addField("status", *req.Status)
-	}
 	if req.ThumbnailURL != nil {
 		addField("thumbnail_url", *req.ThumbnailURL)
 	}
@@ -420,6 +454,75 @@ func (r *Repository) Update(ctx context.Context, id, organizerID string, req Upd
 	if req.RequiresPayment != nil {
 		addField("requires_payment", *req.RequiresPayment)
 	}
+	if req.Timezone != nil {
+		addField("timezone", *req.Timezone) // checked by the service
+	}
+	// Geofence values are checked by the service; the coordinates come as a pair
+	if req.VenueLat != nil {
+		addField("venue_lat", *req.VenueLat)
+	}
+	if req.VenueLon != nil {
+		addField("venue_lon", *req.VenueLon)
+	}
+	if req.GeofenceRadiusM != nil {
+		addField("geofence_radius_m", *req.GeofenceRadiusM)
+	}
+	if req.ScanWebhookURL != nil {
+		if *req.ScanWebhookURL == "" {
+			addField("scan_webhook_url", nil)
+		} else {
+			addField("scan_webhook_url", *req.ScanWebhookURL) // checked by the service
+		}
+	}
+	if req.BookingFeePct != nil {
+		addField("booking_fee_pct", *req.BookingFeePct) // checked by the service
+	}
+	if req.BookingFeeFlat != nil {
+		addField("booking_fee_flat", *req.BookingFeeFlat)
+	}
+	if req.TaxPercentage != nil {
+		addField("tax_percentage", *req.TaxPercentage) // checked by the service
+	}
+	if req.TaxInclusive != nil {
+		addField("tax_inclusive", *req.TaxInclusive)
+	}
+	if req.RotatingQR != nil {
+		addField("rotating_qr", *req.RotatingQR)
+	}
+	if req.QRTTLSeconds != nil {
+		addField("qr_ttl_seconds", *req.QRTTLSeconds) // checked by the service
+	}
+	if req.AllowedProviders != nil {
+		if len(*req.AllowedProviders) == 0 {
+			addField("allowed_providers", nil)
+		} else {
+			addField("allowed_providers", *req.AllowedProviders) // checked by the service
+		}
+	}
+	if req.RequireRating != nil {
+		addField("require_rating", *req.RequireRating)
+	}
+	if req.ClearMinPrice {
+		addField("min_price", nil)
+	} else if req.MinPrice != nil {
+		addField("min_price", *req.MinPrice) // checked by the service
+	}
+	// Only groups made of this organizer's own events can be joined — a group
+	// pass scans at every event in it.
+	groupGuard := ""
+	if req.EventGroupID != nil {
+		if *req.EventGroupID == "" {
+			addField("event_group_id", nil)
+		} else {
+			setClauses = append(setClauses, fmt.Sprintf("event_group_id = $%d::uuid", argIdx))
+			groupGuard = fmt.Sprintf(
+				" AND NOT EXISTS (SELECT 1 FROM events g WHERE g.event_group_id = $%d::uuid AND g.organizer_id <> events.organizer_id)",
+				argIdx,
+			)
+			args = append(args, *req.EventGroupID)
+			argIdx++
+		}
+	}
 
 	// No changes, return existing
 	if len(setClauses) == 0 {
@@ -430,13 +533,13 @@ func (r *Repository) Update(ctx context.Context, id, organizerID string, req Upd
 
 	query := fmt.Sprintf(`
 		UPDATE events SET %s
-		WHERE id = $%d AND organizer_id = $%d
+		WHERE id = $%d AND organizer_id = $%d%s
 		RETURNING id::text, organizer_id::text, title, description, date::text, time::text,
 		          end_date::text, location, city, event_type, latitude, longitude, online_link,
 		          price, currency, category, emoji,
 		          event_key, status, total_tickets, available_tickets, requires_payment,
 		          thumbnail_url, video_url, flier_url, is_featured, created_at, updated_at`,
-		strings.Join(setClauses, ", "), argIdx, argIdx+1,
+		strings.Join(setClauses, ", "), argIdx, argIdx+1, groupGuard,
 	)
 
 	var ev Event
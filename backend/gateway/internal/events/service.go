@@ -22,6 +22,7 @@ import (
 	"context"
 	"encoding/json"
 	"fmt"
+	"net/url"
 	"time"
 
 	"github.com/bukr/gateway/internal/shared"
@@ -39,10 +40,17 @@ type CreditConsumer interface {
 	ConsumeCredit(ctx context.Context, organizerID string) error
 }
 
+// CoreEventCache drops an event from the Rust core's event cache. Core reads
+// titles, dates and the owner from that cache, so edits made here must reach it.
+type CoreEventCache interface {
+	InvalidateEvent(ctx context.Context, eventID, organizerID string)
+}
+
 type Service struct {
 	repo    *Repository
 	rdb     *redis.Client
 	credits CreditConsumer // nil = credits not enforced (dev / free tier)
+	core    CoreEventCache // nil = core not wired (tests)
 }
 
 func NewService(repo *Repository, rdb ...*redis.Client) *Service {
@@ -59,6 +67,11 @@ func (s *Service) WithCredits(c CreditConsumer) {
 	s.credits = c
 }
 
+// WithCoreCache wires in core's event cache invalidation.
+func (s *Service) WithCoreCache(c CoreEventCache) {
+	s.core = c
+}
+
 func (s *Service) GetByID(ctx context.Context, id string) (*EventResponse, error) {
 	cacheKey := "event:id:" + id
 	if resp := s.getCachedEvent(ctx, cacheKey); resp != nil {
@@ -182,6 +195,30 @@ func (s *Service) Create(ctx context.Context, organizerID string, req CreateEven
 		(req.OnlineLink == nil || *req.OnlineLink == "") {
 		return nil, fmt.Errorf("%w: online_link is required for online and hybrid events", shared.ErrValidation)
 	}
+	if err := checkTimezone(req.Timezone); err != nil {
+		return nil, err
+	}
+	if err := checkGeofence(req.VenueLat, req.VenueLon, req.GeofenceRadiusM); err != nil {
+		return nil, err
+	}
+	if err := checkScanWebhook(req.ScanWebhookURL); err != nil {
+		return nil, err
+	}
+	if err := checkBookingFee(req.BookingFeePct, req.BookingFeeFlat); err != nil {
+		return nil, err
+	}
+	if err := checkTax(req.TaxPercentage); err != nil {
+		return nil, err
+	}
+	if err := checkQRTTL(req.QRTTLSeconds); err != nil {
+		return nil, err
+	}
+	if err := checkProviders(req.AllowedProviders); err != nil {
+		return nil, err
+	}
+	if err := checkMinPrice(req.MinPrice, &req.Price); err != nil {
+		return nil, err
+	}
 
 	// Deduct one event credit before writing to DB.
 	// If the organizer has no credits the event is not created.
@@ -202,6 +239,32 @@ func (s *Service) Create(ctx context.Context, organizerID string, req CreateEven
 }
 
 func (s *Service) Update(ctx context.Context, id, organizerID string, req UpdateEventRequest) (*EventResponse, error) {
+	if err := checkTimezone(req.Timezone); err != nil {
+		return nil, err
+	}
+	if err := checkGeofence(req.VenueLat, req.VenueLon, req.GeofenceRadiusM); err != nil {
+		return nil, err
+	}
+	if err := checkScanWebhook(req.ScanWebhookURL); err != nil {
+		return nil, err
+	}
+	if err := checkBookingFee(req.BookingFeePct, req.BookingFeeFlat); err != nil {
+		return nil, err
+	}
+	if err := checkTax(req.TaxPercentage); err != nil {
+		return nil, err
+	}
+	if err := checkQRTTL(req.QRTTLSeconds); err != nil {
+		return nil, err
+	}
+	if err := checkMinPrice(req.MinPrice, req.Price); err != nil {
+		return nil, err
+	}
+	if req.AllowedProviders != nil {
+		if err := checkProviders(*req.AllowedProviders); err != nil {
+			return nil, err
+		}
+	}
 	ev, err := s.repo.Update(ctx, id, organizerID, req)
 	if err != nil {
 		return nil, shared.ErrNotFound
@@ -213,10 +276,118 @@ func (s *Service) Update(ctx context.Context, id, organizerID string, req Update
 			s.bustListCache(context.Background())
 		}()
 	}
+	s.invalidateCore(id, organizerID)
 	resp := ev.ToResponse()
 	return &resp, nil
 }
 
+// checkTimezone: nil means unchanged. Only names Go's tz database can load
+// are stored — "" and "Local" load too, but mean the server's own zone.
+func checkTimezone(tz *string) error {
+	if tz == nil {
+		return nil
+	}
+	if _, err := time.LoadLocation(*tz); err != nil || *tz == "" || *tz == "Local" {
+		return fmt.Errorf("%w: unknown timezone %q — use an IANA name such as Africa/Lagos", shared.ErrValidation, *tz)
+	}
+	return nil
+}
+
+// checkGeofence: the venue point the scanner measures from. Latitude and
+// longitude come together — half a point can't be measured from — and must
+// be in range; the radius must be positive. nil means unchanged.
+func checkGeofence(lat, lon *float64, radiusM *int) error {
+	if (lat == nil) != (lon == nil) {
+		return fmt.Errorf("%w: venue_lat and venue_lon must be set together", shared.ErrValidation)
+	}
+	if lat != nil && (*lat < -90 || *lat > 90 || *lon < -180 || *lon > 180) {
+		return fmt.Errorf("%w: venue_lat must be within ±90 and venue_lon within ±180", shared.ErrValidation)
+	}
+	if radiusM != nil && *radiusM <= 0 {
+		return fmt.Errorf("%w: geofence_radius_m must be greater than 0", shared.ErrValidation)
+	}
+	return nil
+}
+
+// checkScanWebhook: an absolute https URL. Core re-checks the host against
+// its SSRF policy on every send; this only turns away what can never work.
+// nil means unchanged, "" means none.
+func checkScanWebhook(raw *string) error {
+	if raw == nil || *raw == "" {
+		return nil
+	}
+	u, err := url.Parse(*raw)
+	if err != nil || u.Scheme != "https" || u.Host == "" {
+		return fmt.Errorf("%w: scan_webhook_url must be an https URL", shared.ErrValidation)
+	}
+	return nil
+}
+
+// checkBookingFee: the "2.9% + ₦100" style fee charged once per order.
+// The percentage is 0–100 and the flat part is never negative, the same
+// bounds as the columns' CHECKs. nil means unchanged.
+func checkBookingFee(pct, flat *float64) error {
+	if pct != nil && (*pct < 0 || *pct > 100) {
+		return fmt.Errorf("%w: booking_fee_pct must be between 0 and 100", shared.ErrValidation)
+	}
+	if flat != nil && *flat < 0 {
+		return fmt.Errorf("%w: booking_fee_flat must not be negative", shared.ErrValidation)
+	}
+	return nil
+}
+
+// checkTax: 0–100, like the column's CHECK. nil means unchanged.
+func checkTax(pct *float64) error {
+	if pct != nil && (*pct < 0 || *pct > 100) {
+		return fmt.Errorf("%w: tax_percentage must be between 0 and 100", shared.ErrValidation)
+	}
+	return nil
+}
+
+// checkQRTTL: 10–600 seconds, like the column's CHECK. The scanner accepts
+// the current and previous bucket. nil means unchanged.
+func checkQRTTL(ttl *int) error {
+	if ttl != nil && (*ttl < 10 || *ttl > 600) {
+		return fmt.Errorf("%w: qr_ttl_seconds must be between 10 and 600", shared.ErrValidation)
+	}
+	return nil
+}
+
+// knownProviders: what events.allowed_providers' CHECK accepts.
+var knownProviders = map[string]bool{"paystack": true, "stripe": true}
+
+// checkProviders: every entry a provider the platform knows, no repeats.
+// Empty means every provider.
+func checkProviders(providers []string) error {
+	seen := map[string]bool{}
+	for _, p := range providers {
+		if !knownProviders[p] {
+			return fmt.Errorf("%w: unknown payment provider %q — use paystack or stripe", shared.ErrValidation, p)
+		}
+		if seen[p] {
+			return fmt.Errorf("%w: payment provider %q is listed twice", shared.ErrValidation, p)
+		}
+		seen[p] = true
+	}
+	return nil
+}
+
+// checkMinPrice: a pay-what-you-want floor is never negative, and the
+// event's price — the suggested amount buyers get when they name none —
+// must clear it. price is nil when an update leaves it unchanged.
+func checkMinPrice(minPrice, price *float64) error {
+	if minPrice == nil {
+		return nil
+	}
+	if *minPrice < 0 {
+		return fmt.Errorf("%w: min_price must not be negative", shared.ErrValidation)
+	}
+	if price != nil && *price < *minPrice {
+		return fmt.Errorf("%w: price is the suggested amount and must be at least min_price", shared.ErrValidation)
+	}
+	return nil
+}
+
 func (s *Service) Delete(ctx context.Context, id, organizerID string) error {
 	err := s.repo.Delete(ctx, id, organizerID)
 	if err != nil {
@@ -228,9 +399,19 @@ func (s *Service) Delete(ctx context.Context, id, organizerID string) error {
 			s.bustListCache(context.Background())
 		}()
 	}
+	s.invalidateCore(id, organizerID)
 	return nil
 }
 
+// invalidateCore tells core to drop its cached copy of the event. Off the
+// request path — core's entry expires by itself if the call fails.
+func (s *Service) invalidateCore(id, organizerID string) {
+	if s.core == nil {
+		return
+	}
+	go s.core.InvalidateEvent(context.Background(), id, organizerID)
+}
+
 func (s *Service) GetCategories(ctx context.Context) ([]string, error) {
 	const cacheKey = "events:categories"
 	if s.rdb != nil {
@@ -49,7 +49,7 @@ func (r *Repository) List(ctx context.Context, userID string) ([]FavoriteEventRe
 	rows, err := r.db.Query(ctx, `
 		SELECT e.id::text, e.title, e.date::text, e.time::text, e.location,
 			   e.price, e.currency, e.category, e.emoji, e.event_key,
-			   e.thumbnail_url, e.available_tickets, u.name
+			   e.thumbnail_url, GREATEST(e.available_tickets, 0), u.name
 		FROM favorites f
 		JOIN events e ON f.event_id = e.id
 		JOIN users u ON e.organizer_id = u.id
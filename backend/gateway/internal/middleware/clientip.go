@@ -0,0 +1,89 @@
+package middleware
+
+import (
+	"net"
+	"strings"
+
+	"github.com/gofiber/fiber/v2"
+)
+
+// ClientIP collapses X-Forwarded-For to the one address our own proxies
+// vouch for. Fiber's c.IP() takes the left-most entry, which is whatever the
+// client sent — each proxy only appends. Walking from the right and skipping
+// trusted proxy hops leaves the address the outermost trusted proxy actually
+// saw. It must be the first middleware so every later c.IP() call (limiters,
+// session fingerprints, audit rows) sees the rewritten header.
+//
+// Requests whose socket peer isn't a trusted proxy are left alone; Fiber
+// ignores their X-Forwarded-For anyway.
+func ClientIP(trustedProxies []string) fiber.Handler {
+	trusted := parseTrusted(trustedProxies)
+	return func(c *fiber.Ctx) error {
+		xff := c.Get(fiber.HeaderXForwardedFor)
+		if xff == "" || !isTrusted(trusted, c.Context().RemoteIP()) {
+			return c.Next()
+		}
+
+		hops := strings.Split(xff, ",")
+		client := ""
+		for i := len(hops) - 1; i >= 0; i-- {
+			ip := net.ParseIP(strings.TrimSpace(hops[i]))
+			if ip == nil {
+				// Garbage in the chain came from before our proxies; stop here.
+				break
+			}
+			client = ip.String()
+			if !isTrusted(trusted, ip) {
+				break
+			}
+		}
+
+		if client == "" {
+			c.Request().Header.Del(fiber.HeaderXForwardedFor)
+		} else {
+			c.Request().Header.Set(fiber.HeaderXForwardedFor, client)
+		}
+		return c.Next()
+	}
+}
+
+// parseTrusted accepts the same forms as Fiber's TrustedProxies: bare IPs
+// or CIDR ranges.
+func parseTrusted(entries []string) []*net.IPNet {
+	nets := make([]*net.IPNet, 0, len(entries))
+	for _, e := range entries {
+		e = strings.TrimSpace(e)
+		if e == "" {
+			continue
+		}
+		if !strings.Contains(e, "/") {
+			ip := net.ParseIP(e)
+			if ip == nil {
+				continue
+			}
+			bits := 128
+			if ip.To4() != nil {
+				ip = ip.To4()
+				bits = 32
+			}
+			nets = append(nets, &net.IPNet{IP: ip, Mask: net.CIDRMask(bits, bits)})
+			continue
+		}
+		if _, n, err := net.ParseCIDR(e); err == nil {
+			nets = append(nets, n)
+		}
+	}
+	return nets
+}
+
+func isTrusted(nets []*net.IPNet, ip net.IP) bool {
+	if ip == nil {
+		return false
+	}
+	for _, n := range nets {
+		if n.Contains(ip) {
+			return true
+		}
+	}
+	return false
+}
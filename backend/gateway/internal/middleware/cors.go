@@ -54,7 +54,13 @@ func SetupCORS(allowedOrigins string) fiber.Handler {
 		// Authorization: JWT tokens
 		// Content-Type: JSON payloads
 		// X-Request-ID: Request tracing
-		AllowHeaders:     "Authorization,Content-Type,X-Request-ID",
+		// X-Scanner-Code: Gate access code on scanner calls
+		AllowHeaders:     "Authorization,Content-Type,X-Request-ID,X-Scanner-Code",
+		
+		// Which response headers browser JS may read
+		// X-RateLimit-* / Retry-After: let clients self-throttle before a 429
+		// Content-Disposition / X-Next-Page: download names and paged downloads
+		ExposeHeaders:    "X-RateLimit-Limit,X-RateLimit-Remaining,X-RateLimit-Reset,Retry-After,Content-Disposition,X-Next-Page",
 		
 		// Allow credentials (cookies, authorization headers)
 		// Required for JWT authentication
@@ -0,0 +1,71 @@
+package middleware
+
+import (
+	"strconv"
+
+	"github.com/gofiber/fiber/v2"
+)
+
+// Headers Fiber's limiter sets on every response it lets through, plus
+// Retry-After on a 429.
+const (
+	headerRateLimitLimit     = "X-RateLimit-Limit"
+	headerRateLimitRemaining = "X-RateLimit-Remaining"
+	headerRateLimitReset     = "X-RateLimit-Reset"
+)
+
+const tightestRateLimitKey = "ratelimit_tightest"
+
+type rateLimitState struct {
+	limit, remaining, reset string
+}
+
+// TrackRateLimit goes directly in front of a route-level limiter (the ticket
+// group's per-user limit). Limiters are nested — the app-wide per-IP limiter
+// wraps everything — and each one overwrites the X-RateLimit-* headers on the
+// way out, so without this a client would see the loose outer window and run
+// straight into the inner 429. The inner limiter's headers are recorded here
+// and RateLimitHeaders puts back whichever window has less room left.
+func TrackRateLimit() fiber.Handler {
+	return func(c *fiber.Ctx) error {
+		err := c.Next()
+		h := &c.Response().Header
+		st := rateLimitState{
+			limit:     string(h.Peek(headerRateLimitLimit)),
+			remaining: string(h.Peek(headerRateLimitRemaining)),
+			reset:     string(h.Peek(headerRateLimitReset)),
+		}
+		// Rejected requests only carry Retry-After: no room left until then.
+		if c.Response().StatusCode() == fiber.StatusTooManyRequests {
+			st.remaining = "0"
+			st.reset = string(h.Peek(fiber.HeaderRetryAfter))
+		}
+		if st.remaining != "" {
+			c.Locals(tightestRateLimitKey, st)
+		}
+		return err
+	}
+}
+
+// RateLimitHeaders must be registered before the app-wide limiter so it runs
+// last on the way out. When a route-level window recorded by TrackRateLimit
+// has fewer requests left than the app-wide one, its headers win.
+func RateLimitHeaders() fiber.Handler {
+	return func(c *fiber.Ctx) error {
+		err := c.Next()
+		inner, ok := c.Locals(tightestRateLimitKey).(rateLimitState)
+		if !ok {
+			return err
+		}
+		outer, convErr := strconv.Atoi(string(c.Response().Header.Peek(headerRateLimitRemaining)))
+		innerRemaining, _ := strconv.Atoi(inner.remaining)
+		if convErr != nil || innerRemaining < outer {
+			if inner.limit != "" {
+				c.Set(headerRateLimitLimit, inner.limit)
+			}
+			c.Set(headerRateLimitRemaining, inner.remaining)
+			c.Set(headerRateLimitReset, inner.reset)
+		}
+		return err
+	}
+}
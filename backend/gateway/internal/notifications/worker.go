@@ -152,14 +152,14 @@ func (w *Worker) schedule(ctx context.Context) {
 		SELECT
 			e.id::text,
 			e.title,
-			(e.date + e.time) AT TIME ZONE 'UTC' AS starts_at,
+			(e.date + e.time) AT TIME ZONE e.timezone AS starts_at,
 			t.user_id::text,
 			u.email
 		FROM events e
 		JOIN tickets t ON t.event_id = e.id AND t.status = 'valid'
 		JOIN users u   ON u.id = t.user_id
 		WHERE e.status = 'active'
-		  AND (e.date + e.time) BETWEEN NOW() AND NOW() + INTERVAL '25 hours'
+		  AND (e.date + e.time) AT TIME ZONE e.timezone BETWEEN NOW() AND NOW() + INTERVAL '25 hours'
 	`)
 	if err != nil {
 		log.Printf("notifications: scheduler query failed: %v", err)
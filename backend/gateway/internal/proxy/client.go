@@ -23,7 +23,8 @@
  * Forwarded Headers:
  * - X-User-ID: Internal user ID
  * - X-User-Email: User email
- * - X-User-Type: "user" or "organizer"
+ * - X-User-Type: "user", "organizer" or "admin" (admin-token routes)
+ * - X-Scanner-Code: Scanner app's gate access code
  * - X-Paystack-Signature: Webhook verification
  */
 
@@ -31,8 +32,10 @@ package proxy
 
 import (
 	"bytes"
+	"context"
 	"fmt"
 	"io"
+	"log"
 	"net/http"
 	"time"
 
@@ -41,6 +44,11 @@ import (
 	"github.com/gofiber/fiber/v2"
 )
 
+// passthroughHeaders are copied from core's response onto the client's.
+// X-Next-Page is how paged downloads (the QR batch ZIP) say more remain;
+// Content-Encoding and Vary go with a body core compressed.
+var passthroughHeaders = []string{"Content-Type", "Content-Disposition", "X-Next-Page", "Content-Encoding", "Vary"}
+
 // RustProxy forwards requests from the Go gateway to the Rust core service.
 // A single shared http.Client with a tuned Transport is used for all requests.
 // This keeps TCP connections alive between calls instead of dialing fresh each time.
@@ -53,7 +61,9 @@ type RustProxy struct {
 func NewRustProxy(rustServiceURL string, gatewaySecret string) *RustProxy {
 	// Transport is the connection pool. All proxy calls go to one host (Rust),
 	// so MaxIdleConnsPerHost is set high to match expected concurrency.
-	// DisableCompression avoids CPU overhead — JSON payloads are already small.
+	// DisableCompression stops the transport asking for gzip on its own and
+	// transparently decoding it: the client's Accept-Encoding is forwarded
+	// instead, and core's encoded body passes through untouched.
 	transport := &http.Transport{
 		MaxIdleConns:        512,
 		MaxIdleConnsPerHost: 512, // All traffic goes to one host
@@ -121,6 +131,10 @@ func (p *RustProxy) Forward(c *fiber.Ctx, rustPath string) error {
 	if accept := c.Get("Accept"); accept != "" {
 		req.Header.Set("Accept", accept)
 	}
+	// Core compresses large bodies when the client can take them
+	if enc := c.Get("Accept-Encoding"); enc != "" {
+		req.Header.Set("Accept-Encoding", enc)
+	}
 
 	// Forward Authorization header (optional, Rust may use it)
 	if auth := c.Get("Authorization"); auth != "" {
@@ -132,13 +146,23 @@ func (p *RustProxy) Forward(c *fiber.Ctx, rustPath string) error {
 	req.Header.Set("X-Bukr-Internal-Token", p.gatewaySecret)
 
 	// Inject user claims from Go Gateway auth
-	// Rust trusts these headers (no JWT re-validation)
-	if claims := middleware.GetUserClaims(c); claims != nil {
+	// Rust trusts these headers (no JWT re-validation). Admin-token routes
+	// carry admin claims instead, forwarded as X-User-Type: admin.
+	claims := middleware.GetUserClaims(c)
+	if claims == nil {
+		claims = middleware.GetAdminClaims(c)
+	}
+	if claims != nil {
 		req.Header.Set("X-User-ID", claims.UserID)
 		req.Header.Set("X-User-Email", claims.Email)
 		req.Header.Set("X-User-Type", claims.UserType)
 	}
 
+	// Gate the scanner app signed in with, recorded on core's scan log
+	if code := c.Get("X-Scanner-Code"); code != "" {
+		req.Header.Set("X-Scanner-Code", code)
+	}
+
 	// Forward Paystack webhook signature for verification
 	if sig := c.Get("X-Paystack-Signature"); sig != "" {
 		req.Header.Set("X-Paystack-Signature", sig)
@@ -161,18 +185,58 @@ func (p *RustProxy) Forward(c *fiber.Ctx, rustPath string) error {
 	if resp.StatusCode >= 400 {
 		// Check if it's already JSON
 		if resp.Header.Get("Content-Type") == "application/json" {
+			copyPassthroughHeaders(c, resp)
 			return c.Status(resp.StatusCode).Send(respBody)
 		}
 		// Mask raw errors (e.g., panics, 502s) with a clean envelope
 		return shared.Error(c, resp.StatusCode, shared.CodeInternalError, "Internal service error")
 	}
 
-	// Copy response Content-Type header
-	contentType := resp.Header.Get("Content-Type")
-	if contentType != "" {
-		c.Set("Content-Type", contentType)
-	}
+	copyPassthroughHeaders(c, resp)
 
 	// Return proxied response
 	return c.Status(resp.StatusCode).Send(respBody)
 }
+
+// copyPassthroughHeaders: the response headers clients act on — the body's
+// type and encoding, the file name for downloads (CSV exports, ZIPs) and the
+// next page of a paged one.
+func copyPassthroughHeaders(c *fiber.Ctx, resp *http.Response) {
+	for _, name := range passthroughHeaders {
+		if v := resp.Header.Get(name); v != "" {
+			c.Set(name, v)
+		}
+	}
+}
+
+/**
+ * InvalidateEvent: Drop an event from core's event cache
+ *
+ * Called after the gateway updates or deletes an event so core stops
+ * serving the old title, date or owner. Best effort — core's entry
+ * expires on its own after its short TTL if this call is lost.
+ *
+ * @param ctx - Request context
+ * @param eventID - Event that changed
+ * @param organizerID - Organizer who changed it; core checks ownership
+ */
+func (p *RustProxy) InvalidateEvent(ctx context.Context, eventID, organizerID string) {
+	targetURL := fmt.Sprintf("%s/api/v1/admin/events/%s/cache/invalidate", p.baseURL, eventID)
+	req, err := http.NewRequestWithContext(ctx, http.MethodPost, targetURL, nil)
+	if err != nil {
+		return
+	}
+	req.Header.Set("X-Bukr-Internal-Token", p.gatewaySecret)
+	req.Header.Set("X-User-ID", organizerID)
+	req.Header.Set("X-User-Type", "organizer")
+
+	resp, err := p.client.Do(req)
+	if err != nil {
+		log.Printf("event cache invalidate %s: %v", eventID, err)
+		return
+	}
+	resp.Body.Close()
+	if resp.StatusCode >= 400 {
+		log.Printf("event cache invalidate %s: core returned %d", eventID, resp.StatusCode)
+	}
+}
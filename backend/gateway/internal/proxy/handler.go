@@ -65,7 +65,17 @@ func NewHandler(proxy *RustProxy) *Handler {
  * - POST /purchase: Buy tickets
  * - GET /me: Get user's tickets
  * - GET /event/:event_id: Get event tickets
+ * - GET /event/:event_id/qr-batch.zip: Paged ZIP of QR images for will-call
+ * - GET /order/:order_id: Get every ticket in one order
  * - POST /claim-free: Claim free ticket
+ * - GET /:ticket_id/resume-payment: Continue an abandoned checkout
+ * - GET /:ticket_id/qr.png: QR as a PNG image, for wallets and printing
+ * - GET /:ticket_id/barcode.png: Code128 image for barcode-only scanners
+ * - POST /:ticket_id/cancel: Owner cancels an unscanned ticket
+ * - POST /:ticket_id/resend-confirmation: Email the ticket confirmation again
+ * - POST/DELETE /:ticket_id/resale: List a ticket for resale, or take it down
+ * - GET /event/:event_id/resale: Open resale listings for an event
+ * - POST /resale/:listing_id/buy: Buy a listed ticket
  */
 func (h *Handler) RegisterTicketRoutes(router fiber.Router) {
 	// Paid ticket purchase — invite gate runs before forwarding to Rust.
@@ -95,6 +105,14 @@ func (h *Handler) RegisterTicketRoutes(router fiber.Router) {
 		eventID := c.Params("event_id")
 		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/tickets/event/%s", eventID))
 	})
+	router.Get("/event/:event_id/qr-batch.zip", func(c *fiber.Ctx) error {
+		eventID := c.Params("event_id")
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/tickets/event/%s/qr-batch.zip", eventID))
+	})
+	router.Get("/order/:order_id", func(c *fiber.Ctx) error {
+		orderID := c.Params("order_id")
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/tickets/order/%s", orderID))
+	})
 	router.Post("/claim-free", func(c *fiber.Ctx) error {
 		return h.proxy.Forward(c, "/api/v1/tickets/claim-free")
 	})
@@ -102,6 +120,18 @@ func (h *Handler) RegisterTicketRoutes(router fiber.Router) {
 		ticketID := c.Params("ticket_id")
 		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/tickets/%s/qr", ticketID))
 	})
+	router.Get("/:ticket_id/qr.png", func(c *fiber.Ctx) error {
+		ticketID := c.Params("ticket_id")
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/tickets/%s/qr.png", ticketID))
+	})
+	router.Get("/:ticket_id/barcode.png", func(c *fiber.Ctx) error {
+		ticketID := c.Params("ticket_id")
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/tickets/%s/barcode.png", ticketID))
+	})
+	router.Get("/:ticket_id/resume-payment", func(c *fiber.Ctx) error {
+		ticketID := c.Params("ticket_id")
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/tickets/%s/resume-payment", ticketID))
+	})
 	// Explicit suffixes required — Fiber v2 matches the first registered
 	// parametric POST route and stops. Both /transfer and /renew share the
 	// same /:ticket_id/* shape, so they must be distinct named routes.
@@ -113,6 +143,30 @@ func (h *Handler) RegisterTicketRoutes(router fiber.Router) {
 		ticketID := c.Params("ticket_id")
 		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/tickets/%s/renew", ticketID))
 	})
+	router.Post("/:ticket_id/cancel", func(c *fiber.Ctx) error {
+		ticketID := c.Params("ticket_id")
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/tickets/%s/cancel", ticketID))
+	})
+	router.Post("/:ticket_id/resend-confirmation", func(c *fiber.Ctx) error {
+		ticketID := c.Params("ticket_id")
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/tickets/%s/resend-confirmation", ticketID))
+	})
+	router.Post("/:ticket_id/resale", func(c *fiber.Ctx) error {
+		ticketID := c.Params("ticket_id")
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/tickets/%s/resale", ticketID))
+	})
+	router.Delete("/:ticket_id/resale", func(c *fiber.Ctx) error {
+		ticketID := c.Params("ticket_id")
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/tickets/%s/resale", ticketID))
+	})
+	router.Get("/event/:event_id/resale", func(c *fiber.Ctx) error {
+		eventID := c.Params("event_id")
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/tickets/event/%s/resale", eventID))
+	})
+	router.Post("/resale/:listing_id/buy", func(c *fiber.Ctx) error {
+		listingID := c.Params("listing_id")
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/tickets/resale/%s/buy", listingID))
+	})
 }
 
 /**
@@ -121,9 +175,13 @@ func (h *Handler) RegisterTicketRoutes(router fiber.Router) {
  * Routes:
  * - POST /verify-access: Verify scanner access code
  * - POST /validate: Validate ticket QR code
+ * - POST /sync: Apply scans queued offline, retries deduplicated
  * - POST /manual-validate: Manual ticket validation
  * - PATCH /mark-used/:ticket_id: Mark ticket as scanned
+ * - POST /:ticket_id/undo: Take back the caller's last scan of a ticket
+ * - POST /:ticket_id/check-out: Mark an admitted ticket as having left
  * - GET /:event_id/stats: Get scanning statistics
+ * - GET /:event_id/duplicates: Tickets presented at more than one gate
  */
 func (h *Handler) RegisterScannerRoutes(router fiber.Router) {
 	router.Post("/verify-access", func(c *fiber.Ctx) error {
@@ -132,6 +190,9 @@ func (h *Handler) RegisterScannerRoutes(router fiber.Router) {
 	router.Post("/validate", func(c *fiber.Ctx) error {
 		return h.proxy.Forward(c, "/api/v1/scanner/validate")
 	})
+	router.Post("/sync", func(c *fiber.Ctx) error {
+		return h.proxy.Forward(c, "/api/v1/scanner/sync")
+	})
 	router.Post("/manual-validate", func(c *fiber.Ctx) error {
 		return h.proxy.Forward(c, "/api/v1/scanner/manual-validate")
 	})
@@ -139,10 +200,22 @@ func (h *Handler) RegisterScannerRoutes(router fiber.Router) {
 		ticketID := c.Params("ticket_id")
 		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/scanner/mark-used/%s", ticketID))
 	})
+	router.Post("/:ticket_id/undo", func(c *fiber.Ctx) error {
+		ticketID := c.Params("ticket_id")
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/scanner/%s/undo", ticketID))
+	})
+	router.Post("/:ticket_id/check-out", func(c *fiber.Ctx) error {
+		ticketID := c.Params("ticket_id")
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/scanner/%s/check-out", ticketID))
+	})
 	router.Get("/:event_id/stats", func(c *fiber.Ctx) error {
 		eventID := c.Params("event_id")
 		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/scanner/%s/stats", eventID))
 	})
+	router.Get("/:event_id/duplicates", func(c *fiber.Ctx) error {
+		eventID := c.Params("event_id")
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/scanner/%s/duplicates", eventID))
+	})
 }
 
 /**
@@ -182,13 +255,42 @@ func (h *Handler) RegisterPaymentWebhooks(router fiber.Router) {
  * 
  * Routes:
  * - GET /events/:event_id: Event-specific analytics
+ * - POST /events/batch: Analytics for several events in one call
+ * - GET /events/:event_id/scan-timeline: Scans per time bucket for the event
+ * - GET /events/:event_id/attendance.csv: Attendance export as a CSV download
+ * - GET /events/:event_id/funnel: Checkout funnel from started to paid
+ * - GET /organizer/:organizer_id/gate-activity: Scanner leaderboard across events
+ * - GET /organizer/:organizer_id/payout: Estimated net payout after fees and refunds
  * - GET /dashboard: Platform-wide summary
  */
 func (h *Handler) RegisterAnalyticsRoutes(router fiber.Router) {
+	router.Post("/events/batch", func(c *fiber.Ctx) error {
+		return h.proxy.Forward(c, "/api/v1/analytics/events/batch")
+	})
 	router.Get("/events/:event_id", func(c *fiber.Ctx) error {
 		eventID := c.Params("event_id")
 		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/analytics/events/%s", eventID))
 	})
+	router.Get("/events/:event_id/scan-timeline", func(c *fiber.Ctx) error {
+		eventID := c.Params("event_id")
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/analytics/events/%s/scan-timeline", eventID))
+	})
+	router.Get("/events/:event_id/attendance.csv", func(c *fiber.Ctx) error {
+		eventID := c.Params("event_id")
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/analytics/events/%s/attendance.csv", eventID))
+	})
+	router.Get("/events/:event_id/funnel", func(c *fiber.Ctx) error {
+		eventID := c.Params("event_id")
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/analytics/events/%s/funnel", eventID))
+	})
+	router.Get("/organizer/:organizer_id/gate-activity", func(c *fiber.Ctx) error {
+		organizerID := c.Params("organizer_id")
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/analytics/organizer/%s/gate-activity", organizerID))
+	})
+	router.Get("/organizer/:organizer_id/payout", func(c *fiber.Ctx) error {
+		organizerID := c.Params("organizer_id")
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/analytics/organizer/%s/payout", organizerID))
+	})
 	router.Get("/dashboard", func(c *fiber.Ctx) error {
 		return h.proxy.Forward(c, "/api/v1/analytics/dashboard")
 	})
@@ -278,16 +380,36 @@ func (h *Handler) RegisterVendorSelfRoutes(router fiber.Router) {
  * 
  * Routes:
  * - GET /event/:event_id: List promos for event
+ * - GET /event/:event_id/code/:code: One promo by code, any state (editing)
  * - POST /: Create promo code
  * - DELETE /:id: Delete promo code
  * - PATCH /:id/toggle: Toggle promo active status
- * - POST /validate: Validate promo code
+ * - POST /event/:event_id/bulk-toggle: Set many promos active/inactive
+ * - GET/PUT /event/:event_id/budget: Total discount cap across the event's codes
+ * - POST /validate-batch: Check up to 50 codes for one of the organizer's events
+ *
+ * POST /validate (any signed-in buyer) is mounted separately in main.go.
  */
 func (h *Handler) RegisterPromoRoutes(router fiber.Router) {
 	router.Get("/event/:event_id", func(c *fiber.Ctx) error {
 		eventID := c.Params("event_id")
 		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/events/%s/promos", eventID))
 	})
+	router.Get("/event/:event_id/code/:code", func(c *fiber.Ctx) error {
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/events/%s/promos/by-code/%s", c.Params("event_id"), c.Params("code")))
+	})
+	router.Post("/event/:event_id/bulk-toggle", func(c *fiber.Ctx) error {
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/events/%s/promos/bulk-toggle", c.Params("event_id")))
+	})
+	router.Post("/validate-batch", func(c *fiber.Ctx) error {
+		return h.proxy.Forward(c, "/api/v1/promos/validate-batch")
+	})
+	router.Get("/event/:event_id/budget", func(c *fiber.Ctx) error {
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/events/%s/promo-budget", c.Params("event_id")))
+	})
+	router.Put("/event/:event_id/budget", func(c *fiber.Ctx) error {
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/events/%s/promo-budget", c.Params("event_id")))
+	})
 	router.Post("/", func(c *fiber.Ctx) error {
 		var body map[string]interface{}
 		if err := c.BodyParser(&body); err != nil {
@@ -316,3 +438,63 @@ func (h *Handler) RegisterPromoRoutes(router fiber.Router) {
 		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/events/%s/promos/%s/toggle", eventID, promoID))
 	})
 }
+
+/**
+ * RegisterAdminRoutes: Forward admin-only core endpoints to Rust
+ *
+ * Mounted on the admin-token group next to the Go-native admin handlers;
+ * Rust re-checks X-User-Type: admin on every one.
+ *
+ * Routes:
+ * - GET /analytics/revenue-by-provider: Successful payments by provider and currency
+ * - POST /events/:id/scrub-pii: Scrub attendee PII once the retention period is over
+ * - POST /tickets/bulk-cancel: Cancel and refund many tickets at once
+ * - POST /tickets/:id/reassign: Move a ticket to another attendee
+ * - POST, DELETE /tickets/:id/blacklist: Put a ticket on the scan blacklist, or lift it
+ * - GET /payments/reconcile: Our payment rows against the provider's for one day
+ * - GET /payments/transactions: Core's filtered transaction list (GET /payments is the Go dashboard's)
+ * - GET /audit: Core's audit trail of money and ticket actions
+ * - GET /promos/global, POST /promos: Platform-wide promo codes (GET /promos is the Go dashboard's)
+ * - DELETE /promos/:promo_id, PATCH /promos/:promo_id/toggle: Remove or pause a platform-wide code
+ */
+func (h *Handler) RegisterAdminRoutes(router fiber.Router) {
+	router.Get("/analytics/revenue-by-provider", func(c *fiber.Ctx) error {
+		return h.proxy.Forward(c, "/api/v1/analytics/revenue-by-provider")
+	})
+	router.Post("/events/:id/scrub-pii", func(c *fiber.Ctx) error {
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/admin/events/%s/scrub-pii", c.Params("id")))
+	})
+	router.Post("/tickets/bulk-cancel", func(c *fiber.Ctx) error {
+		return h.proxy.Forward(c, "/api/v1/admin/tickets/bulk-cancel")
+	})
+	router.Post("/tickets/:id/reassign", func(c *fiber.Ctx) error {
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/admin/tickets/%s/reassign", c.Params("id")))
+	})
+	router.Post("/tickets/:id/blacklist", func(c *fiber.Ctx) error {
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/admin/tickets/%s/blacklist", c.Params("id")))
+	})
+	router.Delete("/tickets/:id/blacklist", func(c *fiber.Ctx) error {
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/admin/tickets/%s/blacklist", c.Params("id")))
+	})
+	router.Get("/payments/reconcile", func(c *fiber.Ctx) error {
+		return h.proxy.Forward(c, "/api/v1/admin/payments/reconcile")
+	})
+	router.Get("/payments/transactions", func(c *fiber.Ctx) error {
+		return h.proxy.Forward(c, "/api/v1/admin/payments")
+	})
+	router.Get("/audit", func(c *fiber.Ctx) error {
+		return h.proxy.Forward(c, "/api/v1/admin/audit")
+	})
+	router.Get("/promos/global", func(c *fiber.Ctx) error {
+		return h.proxy.Forward(c, "/api/v1/admin/promos")
+	})
+	router.Post("/promos", func(c *fiber.Ctx) error {
+		return h.proxy.Forward(c, "/api/v1/admin/promos")
+	})
+	router.Delete("/promos/:promo_id", func(c *fiber.Ctx) error {
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/admin/promos/%s", c.Params("promo_id")))
+	})
+	router.Patch("/promos/:promo_id/toggle", func(c *fiber.Ctx) error {
+		return h.proxy.Forward(c, fmt.Sprintf("/api/v1/admin/promos/%s/toggle", c.Params("promo_id")))
+	})
+}
@@ -3,6 +3,7 @@ package shared
 import (
 	"log"
 	"os"
+	"strings"
 )
 
 // Config holds all runtime configuration for the Go gateway.
@@ -16,6 +17,11 @@ type Config struct {
 	PaystackSecret  string
 	GatewaySecret   string
 
+	// Load balancer addresses allowed to set X-Forwarded-For. Requests from
+	// anywhere else are keyed on their socket address, so a client can't
+	// spoof its way into a fresh rate-limit bucket.
+	TrustedProxies []string
+
 	// JWT secrets — different keys make user and admin tokens cryptographically separate.
 	AppJWTSecret   string // signs user access tokens (HS256)
 	AdminJWTSecret string // signs admin access tokens (HS256)
@@ -38,6 +44,9 @@ func LoadConfig() *Config {
 		LogLevel:       getEnv("LOG_LEVEL", "info"),
 		PaystackSecret: getEnv("PAYSTACK_SECRET_KEY", ""),
 		GatewaySecret:  getEnv("GATEWAY_SECRET", ""),
+		// None by default: X-Forwarded-For is ignored until the deployment
+		// names its load balancer (IPs or CIDRs, comma-separated).
+		TrustedProxies: splitList(getEnv("TRUSTED_PROXIES", "")),
 
 		AppJWTSecret:   getEnv("APP_JWT_SECRET", ""),
 		AdminJWTSecret: getEnv("ADMIN_JWT_SECRET", ""),
@@ -78,6 +87,17 @@ func LoadConfig() *Config {
 	return cfg
 }
 
+// splitList parses a comma-separated env value, dropping blanks.
+func splitList(s string) []string {
+	var out []string
+	for _, part := range strings.Split(s, ",") {
+		if part = strings.TrimSpace(part); part != "" {
+			out = append(out, part)
+		}
+	}
+	return out
+}
+
 func getEnv(key, fallback string) string {
 	if val := os.Getenv(key); val != "" {
 		return val
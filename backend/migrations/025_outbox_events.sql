@@ -0,0 +1,22 @@
+-- 025_outbox_events.sql
+-- Transactional outbox for side effects that must not be lost (refunds, confirmations).
+--
+-- Rows are written in the SAME transaction as the state change that caused them,
+-- so a committed cancellation always has its refund request recorded, and a
+-- rolled-back one never does. A separate consumer drains unprocessed rows.
+
+CREATE TABLE IF NOT EXISTS outbox_events (
+    id            UUID        PRIMARY KEY DEFAULT gen_random_uuid(),
+    event_type    VARCHAR(100) NOT NULL,   -- e.g. 'ticket.refund_requested'
+    aggregate_id  UUID,                    -- tickets.id, events.id, etc.
+    payload       JSONB       NOT NULL,
+    processed_at  TIMESTAMPTZ,             -- NULL = still pending delivery
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Consumer hot path: oldest unprocessed rows first
+CREATE INDEX IF NOT EXISTS idx_outbox_unprocessed
+    ON outbox_events(created_at)
+    WHERE processed_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_outbox_aggregate ON outbox_events(aggregate_id);
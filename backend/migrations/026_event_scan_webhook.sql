@@ -0,0 +1,9 @@
+-- 026_event_scan_webhook.sql
+-- Optional per-event outbound webhook fired after every successful scan.
+-- Powers venue "welcome screens" that react live to each entry.
+-- NULL = no webhook (the default for every existing event).
+
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS scan_webhook_url TEXT;
+
+COMMENT ON COLUMN events.scan_webhook_url IS 'POSTed asynchronously after each successful scan. Failures never affect the scan result.';
@@ -0,0 +1,22 @@
+-- 027_outbox_delivery.sql
+-- Delivery bookkeeping for the outbox consumer.
+--
+-- Failed deliveries are retried with exponential backoff (next_attempt_at).
+-- After the max attempts a row is dead-lettered: it stays in the table for
+-- inspection and manual replay, but the consumer stops picking it up.
+
+ALTER TABLE outbox_events
+    ADD COLUMN IF NOT EXISTS attempts         INT         NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS next_attempt_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ADD COLUMN IF NOT EXISTS last_error       TEXT,
+    ADD COLUMN IF NOT EXISTS dead_lettered_at TIMESTAMPTZ;  -- NOT NULL = gave up
+
+-- Consumer hot path now orders by next_attempt_at and skips dead letters
+DROP INDEX IF EXISTS idx_outbox_unprocessed;
+CREATE INDEX IF NOT EXISTS idx_outbox_unprocessed
+    ON outbox_events(next_attempt_at)
+    WHERE processed_at IS NULL AND dead_lettered_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_outbox_dead_letter
+    ON outbox_events(dead_lettered_at)
+    WHERE dead_lettered_at IS NOT NULL;
@@ -0,0 +1,18 @@
+-- 028_booking_fees.sql
+-- Per-event booking fee in the processor style "2.9% + ₦100".
+--
+-- fee = round(order_total × booking_fee_pct / 100, 2) + booking_fee_flat
+-- Charged once per order on top of the ticket total. Both default to 0, so
+-- existing events are unaffected.
+--
+-- The resolved amount is stored on the ticket at purchase time — reporting
+-- reads tickets.booking_fee, never recomputes from the (mutable) event config.
+
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS booking_fee_pct  DECIMAL(5,2)  NOT NULL DEFAULT 0.00
+        CHECK (booking_fee_pct >= 0 AND booking_fee_pct <= 100),
+    ADD COLUMN IF NOT EXISTS booking_fee_flat DECIMAL(12,2) NOT NULL DEFAULT 0.00
+        CHECK (booking_fee_flat >= 0);
+
+ALTER TABLE tickets
+    ADD COLUMN IF NOT EXISTS booking_fee DECIMAL(12,2) NOT NULL DEFAULT 0.00;
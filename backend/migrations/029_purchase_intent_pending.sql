@@ -0,0 +1,13 @@
+-- 029_purchase_intent_pending.sql
+-- Purchase intents must also be unique while the ticket is still 'pending'.
+--
+-- 024 only covered 'valid'/'used', so a double-click that landed while the
+-- first ticket was awaiting payment could create a second ticket. Cancelled,
+-- refunded and expired tickets stay excluded so a genuine retry can reuse
+-- the intent.
+
+DROP INDEX IF EXISTS idx_tickets_idempotency;
+
+CREATE UNIQUE INDEX idx_tickets_idempotency
+ON tickets (user_id, event_id, idempotency_key)
+WHERE idempotency_key IS NOT NULL AND status IN ('pending', 'valid', 'used');
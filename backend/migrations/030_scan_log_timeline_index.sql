@@ -0,0 +1,6 @@
+-- 030_scan_log_timeline_index.sql
+-- Supports the per-event scan timeline (date_trunc over scanned_at).
+-- scanned_at already records when each scan_log row was written, so no new
+-- timestamp column is needed.
+
+CREATE INDEX IF NOT EXISTS idx_scan_log_event_time ON scan_log(event_id, scanned_at);
@@ -0,0 +1,17 @@
+-- 031_event_tax.sql
+-- Per-event tax (e.g. VAT) on the post-discount ticket subtotal.
+--
+-- tax_inclusive = false: tax is added on top of the ticket price
+-- tax_inclusive = true:  the ticket price already includes tax; the tax
+--                        portion is backed out for reporting only
+--
+-- The resolved amount is stored on the ticket so tax reports never depend on
+-- the event's current (editable) rate.
+
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS tax_percentage DECIMAL(5,2) NOT NULL DEFAULT 0.00
+        CHECK (tax_percentage >= 0 AND tax_percentage <= 100),
+    ADD COLUMN IF NOT EXISTS tax_inclusive  BOOLEAN      NOT NULL DEFAULT FALSE;
+
+ALTER TABLE tickets
+    ADD COLUMN IF NOT EXISTS tax_amount DECIMAL(12,2) NOT NULL DEFAULT 0.00;
@@ -0,0 +1,13 @@
+-- 032_rotating_qr.sql
+-- Time-bucketed QR codes for high-security events.
+--
+-- rotating_qr = true: the QR payload carries a time bucket of qr_ttl_seconds
+-- and the signature covers it. The scanner accepts the current and previous
+-- bucket only, so a screenshot shared in advance stops scanning within
+-- 2 × qr_ttl_seconds. Raw ticket ids (no signed payload) are rejected at the
+-- gate for these events; staff use manual-validate instead.
+
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS rotating_qr    BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN IF NOT EXISTS qr_ttl_seconds INTEGER NOT NULL DEFAULT 30
+        CHECK (qr_ttl_seconds BETWEEN 10 AND 600);
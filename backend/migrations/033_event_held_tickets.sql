@@ -0,0 +1,12 @@
+-- 033_event_held_tickets.sql
+-- Organizer hold: a block of inventory reserved for sponsors/press.
+--
+-- held_tickets is carved out of available_tickets, not added to it. Public
+-- purchases and free claims may only take available_tickets - held_tickets;
+-- comp tickets draw from the hold, decrementing held_tickets as the insert
+-- trigger decrements available_tickets. held_tickets <= available_tickets is
+-- enforced by the service when the hold is adjusted.
+
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS held_tickets INTEGER NOT NULL DEFAULT 0
+        CHECK (held_tickets >= 0);
@@ -0,0 +1,14 @@
+-- 034_event_allowed_providers.sql
+-- Per-event payment provider allowlist (payout routing).
+--
+-- NULL = every provider the platform supports. Purchase and payment
+-- initialization reject a provider outside the set with the allowed list in
+-- the error message.
+
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS allowed_providers TEXT[]
+        CHECK (
+            allowed_providers IS NULL
+            OR (cardinality(allowed_providers) > 0
+                AND allowed_providers <@ ARRAY['paystack', 'stripe']::TEXT[])
+        );
@@ -0,0 +1,10 @@
+-- 035_scan_log_undo.sql
+-- Allow 'undo' entries in scan_log.
+--
+-- POST /scanner/{ticket_id}/undo reverts an accidental scan (used -> valid)
+-- within a short window. The original 'valid' row stays; the undo is its
+-- own row so the log remains append-only.
+
+ALTER TABLE scan_log DROP CONSTRAINT IF EXISTS scan_log_result_check;
+ALTER TABLE scan_log ADD CONSTRAINT scan_log_result_check
+    CHECK (result IN ('valid', 'invalid', 'already_used', 'undo'));
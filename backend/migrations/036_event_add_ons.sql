@@ -0,0 +1,31 @@
+-- 036_event_add_ons.sql
+-- Ticket add-ons: extras (parking, merch) sold alongside a ticket.
+--
+-- inventory is what is left to sell; NULL means unlimited. The purchase
+-- transaction locks the selected add-on rows, checks inventory and
+-- decrements it, then records each line against the ticket at the price
+-- charged, so later price edits never change a past order.
+
+CREATE TABLE IF NOT EXISTS event_add_ons (
+    id          UUID          PRIMARY KEY DEFAULT gen_random_uuid(),
+    event_id    UUID          NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    name        VARCHAR(100)  NOT NULL,
+    price       NUMERIC(12,2) NOT NULL CHECK (price >= 0),
+    inventory   INTEGER       CHECK (inventory >= 0),
+    is_active   BOOLEAN       NOT NULL DEFAULT true,
+    created_at  TIMESTAMPTZ   NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_add_ons_event ON event_add_ons(event_id);
+
+CREATE TABLE IF NOT EXISTS ticket_add_ons (
+    id          UUID          PRIMARY KEY DEFAULT gen_random_uuid(),
+    ticket_id   UUID          NOT NULL REFERENCES tickets(id) ON DELETE CASCADE,
+    add_on_id   UUID          NOT NULL REFERENCES event_add_ons(id),
+    name        VARCHAR(100)  NOT NULL,
+    quantity    INTEGER       NOT NULL CHECK (quantity > 0),
+    unit_price  NUMERIC(12,2) NOT NULL,
+    created_at  TIMESTAMPTZ   NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_ticket_add_ons_ticket ON ticket_add_ons(ticket_id);
@@ -0,0 +1,6 @@
+-- 037_event_require_rating.sql
+-- Organizer opt-in: make excitement_rating mandatory at purchase.
+-- Off by default, so the rating stays optional for every existing event.
+
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS require_rating BOOLEAN NOT NULL DEFAULT false;
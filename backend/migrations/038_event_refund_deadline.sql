@@ -0,0 +1,6 @@
+-- 038_event_refund_deadline.sql
+-- Per-event cut-off for refunds. NULL keeps the default: refunds allowed
+-- until the event starts. The service rejects deadlines after event start.
+
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS refund_deadline TIMESTAMPTZ;
@@ -0,0 +1,4 @@
+-- 039_payments_created_at_index.sql
+-- Admin payment listing pages newest-first, optionally within a date range.
+
+CREATE INDEX IF NOT EXISTS idx_payments_created_at ON payment_transactions(created_at DESC);
@@ -0,0 +1,7 @@
+-- 040_drop_ticket_decrement_trigger.sql
+-- Inventory is now taken explicitly by TicketRepository::reserve_inventory
+-- before every ticket insert (purchase, free claim, comp). Keeping the
+-- AFTER INSERT trigger as well would decrement twice.
+
+DROP TRIGGER IF EXISTS trg_decrement_tickets ON tickets;
+DROP FUNCTION IF EXISTS decrement_available_tickets();
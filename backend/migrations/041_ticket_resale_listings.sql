@@ -0,0 +1,30 @@
+-- 041_ticket_resale_listings.sql
+-- Face-value resale: a ticket owner lists a valid, unscanned ticket at no more
+-- than its original total_price; a buyer pays through the normal provider
+-- flow and the charge.success webhook moves ownership.
+--
+-- status: listed → pending (buyer checking out, held until reserved_until)
+--         → sold | cancelled (seller withdrew) | void (ticket no longer
+--         sellable when payment landed — buyer refunded via outbox)
+-- At most one open (listed/pending) listing per ticket.
+
+CREATE TABLE IF NOT EXISTS ticket_resale_listings (
+    id              UUID          PRIMARY KEY DEFAULT gen_random_uuid(),
+    ticket_id       UUID          NOT NULL REFERENCES tickets(id) ON DELETE CASCADE,
+    event_id        UUID          NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    seller_id       UUID          NOT NULL REFERENCES users(id),
+    buyer_id        UUID          REFERENCES users(id),
+    price           NUMERIC(12,2) NOT NULL CHECK (price > 0),
+    currency        VARCHAR(3)    NOT NULL,
+    status          VARCHAR(20)   NOT NULL DEFAULT 'listed'
+                    CHECK (status IN ('listed', 'pending', 'sold', 'cancelled', 'void')),
+    payment_ref     VARCHAR(255)  UNIQUE,
+    reserved_until  TIMESTAMPTZ,
+    created_at      TIMESTAMPTZ   NOT NULL DEFAULT NOW(),
+    sold_at         TIMESTAMPTZ
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_resale_open_per_ticket
+    ON ticket_resale_listings(ticket_id) WHERE status IN ('listed', 'pending');
+CREATE INDEX IF NOT EXISTS idx_resale_event_open
+    ON ticket_resale_listings(event_id) WHERE status = 'listed';
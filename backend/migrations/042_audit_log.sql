@@ -0,0 +1,17 @@
+-- 042_audit_log.sql
+-- Organizer and admin mutations recorded by the core service (comp tickets,
+-- holds, promo changes, bulk cancels/refunds, ...). Append-only.
+-- admin_audit_log (021) stays the gateway's record of admin-console actions.
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    id          UUID         PRIMARY KEY DEFAULT gen_random_uuid(),
+    actor_id    UUID         REFERENCES users(id) ON DELETE SET NULL,
+    action      VARCHAR(64)  NOT NULL,   -- e.g. 'promo.create', 'ticket.bulk_cancel'
+    entity      VARCHAR(32)  NOT NULL,   -- 'event', 'promo', 'ticket', 'vendor_hire'
+    entity_id   TEXT,
+    metadata    JSONB        NOT NULL DEFAULT '{}'::jsonb,
+    created_at  TIMESTAMPTZ  NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_created ON audit_log(created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_log_entity  ON audit_log(entity, created_at DESC);
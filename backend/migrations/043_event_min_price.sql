@@ -0,0 +1,8 @@
+-- 043_event_min_price.sql
+-- Pay-what-you-want (donation) events. When min_price is set, buyers name
+-- their own per-ticket price (chosen_price) of at least min_price; the
+-- event's price is the suggested amount used when they don't. NULL keeps
+-- the normal fixed price.
+
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS min_price NUMERIC(12,2) CHECK (min_price >= 0);
@@ -0,0 +1,7 @@
+-- 044_event_self_cancel_deadline.sql
+-- Last moment a buyer may cancel their own ticket. NULL keeps the default:
+-- self-service cancellation is open until the event starts. Past it, buyers
+-- are sent to support. The service rejects deadlines after event start.
+
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS self_cancel_deadline TIMESTAMPTZ;
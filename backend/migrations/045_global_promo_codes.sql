@@ -0,0 +1,12 @@
+-- 045_global_promo_codes.sql
+-- Platform-wide promo codes: event_id NULL applies to every event.
+-- Uniqueness is split so an event's own code and a global code with the same
+-- text can coexist; validation prefers the event's own code.
+
+ALTER TABLE promo_codes ALTER COLUMN event_id DROP NOT NULL;
+
+ALTER TABLE promo_codes DROP CONSTRAINT IF EXISTS promo_codes_event_id_code_key;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_promo_codes_event_code
+    ON promo_codes(event_id, code) WHERE event_id IS NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_promo_codes_global_code
+    ON promo_codes(code) WHERE event_id IS NULL;
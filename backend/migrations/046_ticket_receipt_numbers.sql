@@ -0,0 +1,11 @@
+-- 046_ticket_receipt_numbers.sql
+-- Human-friendly per-event receipt numbers (INV-0001, INV-0002, ...).
+-- events.receipt_seq is bumped inside the purchase transaction while the
+-- event row is already locked FOR UPDATE, so concurrent buyers never share a number.
+
+ALTER TABLE events  ADD COLUMN IF NOT EXISTS receipt_seq    BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE tickets ADD COLUMN IF NOT EXISTS receipt_number TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_tickets_event_receipt
+    ON tickets (event_id, receipt_number)
+    WHERE receipt_number IS NOT NULL;
@@ -0,0 +1,8 @@
+-- 047_event_overbook_allowance.sql
+-- Organizers may sell up to total_tickets + overbook_allowance, expecting no-shows.
+-- Once overbooked, available_tickets goes negative (down to -overbook_allowance);
+-- 0 = no overbooking, the previous behaviour.
+
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS overbook_allowance INTEGER NOT NULL DEFAULT 0
+        CHECK (overbook_allowance >= 0);
@@ -0,0 +1,19 @@
+-- 048_scan_geofence.sql
+-- Scan location logging and venue geofencing for multi-venue tours.
+--
+-- Scanner apps may send the device's lat/lon with a scan; it is kept on the
+-- scan_log row. When an event has venue coordinates, located scans farther
+-- than geofence_radius_m from the venue are rejected as 'wrong_location'.
+
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS venue_lat         DOUBLE PRECISION CHECK (venue_lat BETWEEN -90 AND 90),
+    ADD COLUMN IF NOT EXISTS venue_lon         DOUBLE PRECISION CHECK (venue_lon BETWEEN -180 AND 180),
+    ADD COLUMN IF NOT EXISTS geofence_radius_m INTEGER NOT NULL DEFAULT 500 CHECK (geofence_radius_m > 0);
+
+ALTER TABLE scan_log
+    ADD COLUMN IF NOT EXISTS lat DOUBLE PRECISION,
+    ADD COLUMN IF NOT EXISTS lon DOUBLE PRECISION;
+
+ALTER TABLE scan_log DROP CONSTRAINT IF EXISTS scan_log_result_check;
+ALTER TABLE scan_log ADD CONSTRAINT scan_log_result_check
+    CHECK (result IN ('valid', 'invalid', 'already_used', 'undo', 'wrong_location'));
@@ -0,0 +1,23 @@
+-- 049_bundle_promo_codes.sql
+-- "Buy N get M free" promo codes alongside percentage codes.
+--
+-- Bundle codes store discount_percentage = 0; at purchase the free share of
+-- the order (M free per complete group of N + M) becomes the ticket's
+-- discount_applied.
+
+ALTER TABLE promo_codes
+    ADD COLUMN IF NOT EXISTS promo_type    VARCHAR(20) NOT NULL DEFAULT 'percentage',
+    ADD COLUMN IF NOT EXISTS buy_quantity  INTEGER,
+    ADD COLUMN IF NOT EXISTS free_quantity INTEGER;
+
+ALTER TABLE promo_codes DROP CONSTRAINT IF EXISTS promo_codes_discount_percentage_check;
+ALTER TABLE promo_codes DROP CONSTRAINT IF EXISTS promo_codes_terms_check;
+ALTER TABLE promo_codes ADD CONSTRAINT promo_codes_terms_check CHECK (
+    (promo_type = 'percentage'
+        AND discount_percentage > 0 AND discount_percentage <= 100
+        AND buy_quantity IS NULL AND free_quantity IS NULL)
+    OR
+    (promo_type = 'bundle'
+        AND discount_percentage = 0
+        AND buy_quantity >= 1 AND free_quantity >= 1)
+);
@@ -0,0 +1,11 @@
+-- 050_event_low_stock_alert.sql
+-- Organizer-set low-stock alert. When a purchase leaves the event's public
+-- inventory at or below low_stock_threshold, an "inventory.low" outbox event
+-- is written once; low_stock_alerted records that it fired so later sales
+-- stay quiet. Setting the threshold again re-arms it.
+-- NULL threshold = no alert, the previous behaviour.
+
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS low_stock_threshold INTEGER
+        CHECK (low_stock_threshold >= 0),
+    ADD COLUMN IF NOT EXISTS low_stock_alerted BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,9 @@
+-- 051_event_default_ticket_type.sql
+-- Per-event name for the tier a purchase gets when it names none.
+-- Not every event's main tier is "General Admission"; purchases read this
+-- from the locked event row instead of the hardcoded label.
+-- Sized to match tickets.ticket_type (VARCHAR(50)).
+
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS default_ticket_type VARCHAR(50) NOT NULL DEFAULT 'General Admission'
+        CHECK (btrim(default_ticket_type) <> '');
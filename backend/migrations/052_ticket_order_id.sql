@@ -0,0 +1,10 @@
+-- 052_ticket_order_id.sql
+-- Groups the ticket rows created by one checkout. A purchase writes a single
+-- row today, so every existing ticket becomes its own one-ticket order (the
+-- volatile default is evaluated per row); multi-ticket orders will share one.
+-- Lets a buyer pull up every pass in an order to hand them out to a group.
+
+ALTER TABLE tickets
+    ADD COLUMN IF NOT EXISTS order_id UUID NOT NULL DEFAULT gen_random_uuid();
+
+CREATE INDEX IF NOT EXISTS idx_tickets_order_id ON tickets(order_id);
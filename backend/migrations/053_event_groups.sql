@@ -0,0 +1,21 @@
+-- 053_event_groups.sql
+-- Links sub-events (conference rooms, sessions) under one group so a ticket
+-- for any event in the group scans at the others. NULL = standalone event,
+-- matched on its own id as before.
+--
+-- ticket_session_admissions records which sessions a ticket has entered, so
+-- a single-session pass can't walk into a second room of the same group.
+
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS event_group_id UUID;
+
+CREATE INDEX IF NOT EXISTS idx_events_event_group_id
+    ON events(event_group_id) WHERE event_group_id IS NOT NULL;
+
+CREATE TABLE IF NOT EXISTS ticket_session_admissions (
+    ticket_id   UUID NOT NULL REFERENCES tickets(id) ON DELETE CASCADE,
+    event_id    UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    scanned_by  UUID,
+    admitted_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (ticket_id, event_id)
+);
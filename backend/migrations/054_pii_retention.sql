@@ -0,0 +1,19 @@
+-- 054_pii_retention.sql
+-- Attendee PII is scrubbed from an event's tickets once the retention period
+-- after the event has passed. Ticket rows stay (counts, prices and statuses
+-- feed analytics); the link to the buyer does not.
+--
+-- tickets.user_id is NOT NULL and joined everywhere, so scrubbed tickets are
+-- re-pointed at one placeholder account instead of nulled. holder_hash keeps
+-- a salted hash of the original holder so "same attendee" stays countable.
+
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS pii_scrubbed_at TIMESTAMPTZ;
+
+ALTER TABLE tickets
+    ADD COLUMN IF NOT EXISTS holder_hash TEXT;
+
+-- No password, inactive: the placeholder can never sign in.
+INSERT INTO users (id, email, name, user_type, is_active)
+VALUES ('00000000-0000-0000-0000-000000000000', 'removed-attendee@bukr.invalid', 'Removed attendee', 'user', FALSE)
+ON CONFLICT (id) DO NOTHING;
@@ -0,0 +1,25 @@
+-- 055_promo_allow_partial.sql
+-- Promo uses are counted in tickets, not purchases: ticket_limit = 10 means
+-- ten discounted tickets. A purchase consumes one use per ticket, capped at
+-- what was left — with allow_partial a basket larger than the remaining uses
+-- gets the discount on that many tickets and pays full price for the rest.
+-- Without it such a basket is refused at checkout.
+
+ALTER TABLE promo_codes
+    ADD COLUMN IF NOT EXISTS allow_partial BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE OR REPLACE FUNCTION increment_promo_usage()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.promo_code_id IS NOT NULL THEN
+        UPDATE promo_codes
+        SET used_count = CASE
+                WHEN ticket_limit > 0 THEN LEAST(ticket_limit, used_count + NEW.quantity)
+                ELSE used_count + NEW.quantity
+            END,
+            updated_at = NOW()
+        WHERE id = NEW.promo_code_id;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,22 @@
+-- 056_venue_capacity_guard.sql
+-- Hard venue capacity at the door, for licensed limits on oversold or
+-- standing events. When max_concurrent_inside is set, a scan that would put
+-- more people inside than that is refused as 'at_capacity' even though the
+-- ticket is valid. NULL = no cap.
+--
+-- Inside = admissions not yet checked out, counted in people (a ticket
+-- admits its quantity). checked_out_at marks an admission that has left;
+-- scanning the ticket back in clears it.
+
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS max_concurrent_inside INTEGER CHECK (max_concurrent_inside > 0);
+
+ALTER TABLE ticket_session_admissions
+    ADD COLUMN IF NOT EXISTS checked_out_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_ticket_session_admissions_inside
+    ON ticket_session_admissions(event_id) WHERE checked_out_at IS NULL;
+
+ALTER TABLE scan_log DROP CONSTRAINT IF EXISTS scan_log_result_check;
+ALTER TABLE scan_log ADD CONSTRAINT scan_log_result_check
+    CHECK (result IN ('valid', 'invalid', 'already_used', 'undo', 'wrong_location', 'at_capacity', 'check_out'));
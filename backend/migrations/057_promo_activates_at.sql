@@ -0,0 +1,11 @@
+-- 057_promo_activates_at.sql
+-- Scheduled promo activation for flash sales. A code with activates_at set
+-- can be created ahead of time but validates only from that moment on.
+-- NULL = usable as soon as it is active, as before.
+
+ALTER TABLE promo_codes
+    ADD COLUMN IF NOT EXISTS activates_at TIMESTAMPTZ;
+
+ALTER TABLE promo_codes DROP CONSTRAINT IF EXISTS promo_codes_active_window_check;
+ALTER TABLE promo_codes ADD CONSTRAINT promo_codes_active_window_check
+    CHECK (activates_at IS NULL OR expires_at IS NULL OR activates_at < expires_at);
@@ -0,0 +1,20 @@
+-- 058_group_partial_scan.sql
+-- Group tickets (one row, quantity > 1) can be admitted a few seats at a
+-- time: a family arriving in two cars, a table trickling in. The row stays
+-- 'valid' until scanned_quantity reaches quantity, then turns 'used'.
+--
+-- last_scan_quantity is the size of the latest admission, so an undo puts
+-- back only that scan's seats. Tickets already used were admitted whole.
+
+ALTER TABLE tickets
+    ADD COLUMN IF NOT EXISTS scanned_quantity INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS last_scan_quantity INTEGER NOT NULL DEFAULT 0;
+
+UPDATE tickets
+   SET scanned_quantity = quantity, last_scan_quantity = quantity
+ WHERE status = 'used' AND scanned_quantity = 0;
+
+ALTER TABLE tickets DROP CONSTRAINT IF EXISTS tickets_scanned_quantity_check;
+ALTER TABLE tickets ADD CONSTRAINT tickets_scanned_quantity_check
+    CHECK (scanned_quantity BETWEEN 0 AND quantity
+           AND last_scan_quantity BETWEEN 0 AND scanned_quantity);
@@ -0,0 +1,11 @@
+-- 059_event_promo_budget.sql
+-- Total discount an event's promo codes may give away, across every code
+-- (e.g. 500000.00 = "no more than ₦500,000 off"). NULL = no budget.
+--
+-- The running total is not stored: it is summed from the tickets
+-- themselves (unit_price × quantity × discount_applied / 100) under the
+-- event row lock at checkout. Failed, cancelled and refunded tickets give
+-- their discount back.
+
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS promo_budget NUMERIC(12,2) CHECK (promo_budget >= 0);
@@ -0,0 +1,10 @@
+-- 060_event_timezone.sql
+-- IANA timezone the event takes place in. date + time stay the local start
+-- the organizer entered; this zone turns them into an instant, so cut-offs
+-- and the UTC start in ticket and scanner responses land at the right moment.
+--
+-- Bukr's events are mostly in Nigeria, hence the Africa/Lagos default.
+-- The gateway refuses names it can't load; an unknown one reads as UTC.
+
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS timezone TEXT NOT NULL DEFAULT 'Africa/Lagos';
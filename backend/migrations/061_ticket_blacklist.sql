@@ -0,0 +1,16 @@
+-- 061_ticket_blacklist.sql
+-- Kill-switch for tickets known to be fraudulent (chargeback, leaked QR).
+-- A listed ticket fails every scan as 'blacklisted' whatever its status.
+-- The ticket row itself is untouched, so removing the entry puts it back
+-- exactly as it was.
+
+CREATE TABLE IF NOT EXISTS ticket_blacklist (
+    ticket_id      VARCHAR(50) PRIMARY KEY REFERENCES tickets(ticket_id) ON DELETE CASCADE,
+    reason         TEXT NOT NULL,
+    blacklisted_by UUID,
+    blacklisted_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE scan_log DROP CONSTRAINT IF EXISTS scan_log_result_check;
+ALTER TABLE scan_log ADD CONSTRAINT scan_log_result_check
+    CHECK (result IN ('valid', 'invalid', 'already_used', 'undo', 'wrong_location', 'at_capacity', 'check_out', 'blacklisted'));
@@ -0,0 +1,35 @@
+-- 062_ticket_status_values.sql
+-- tickets.status still had 004's list, so a paid purchase — inserted as
+-- 'pending' until the webhook confirms it — failed the CHECK, and 'failed'
+-- could never be written. The list now matches TicketStatus.
+--
+-- Promo uses are counted when a ticket is paid for, not when it is created:
+-- a ticket inserted as valid (nothing to charge) counts straight away, a
+-- pending one when the webhook moves it to valid. Checkout adds the uses
+-- still held by pending tickets, so unpaid baskets can't overrun the limit.
+
+ALTER TABLE tickets DROP CONSTRAINT IF EXISTS tickets_status_check;
+ALTER TABLE tickets ADD CONSTRAINT tickets_status_check
+    CHECK (status IN ('pending', 'valid', 'used', 'expired', 'cancelled', 'refunded', 'failed'));
+
+CREATE OR REPLACE FUNCTION increment_promo_usage()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.promo_code_id IS NOT NULL AND NEW.status = 'valid'
+       AND (TG_OP = 'INSERT' OR OLD.status = 'pending') THEN
+        UPDATE promo_codes
+        SET used_count = CASE
+                WHEN ticket_limit > 0 THEN LEAST(ticket_limit, used_count + NEW.quantity)
+                ELSE used_count + NEW.quantity
+            END,
+            updated_at = NOW()
+        WHERE id = NEW.promo_code_id;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_increment_promo ON tickets;
+CREATE TRIGGER trg_increment_promo
+AFTER INSERT OR UPDATE OF status ON tickets
+FOR EACH ROW EXECUTE FUNCTION increment_promo_usage();
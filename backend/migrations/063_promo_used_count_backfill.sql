@@ -0,0 +1,20 @@
+-- 063_promo_used_count_backfill.sql
+-- 055 changed used_count from purchases to tickets but left the existing
+-- counts alone, so an old code looked barely used and ticket_limit read in
+-- the new unit let it hand out far more discounted tickets than intended.
+--
+-- Recount every code in tickets, as 062's trigger counts them: tickets
+-- paid for (valid or already used), capped at ticket_limit. Tickets still
+-- pending are held at checkout, not counted here.
+
+UPDATE promo_codes p
+SET used_count = CASE WHEN p.ticket_limit > 0 THEN LEAST(p.ticket_limit, u.tickets) ELSE u.tickets END,
+    updated_at = NOW()
+FROM (
+    SELECT pc.id, COALESCE(SUM(t.quantity) FILTER (WHERE t.status IN ('valid', 'used')), 0)::INT AS tickets
+    FROM promo_codes pc
+    LEFT JOIN tickets t ON t.promo_code_id = pc.id
+    GROUP BY pc.id
+) u
+WHERE u.id = p.id AND p.used_count IS DISTINCT FROM
+      CASE WHEN p.ticket_limit > 0 THEN LEAST(p.ticket_limit, u.tickets) ELSE u.tickets END;
@@ -0,0 +1,13 @@
+-- 065_ticket_promo_units.sql
+-- Which tickets of an order a promo code actually discounted. A bundle's
+-- free tickets, or the ones a partial code still covered, are priced apart
+-- from the rest: fees are per ticket, so one blended discount_applied rate
+-- over the whole order charges the wrong amount.
+--
+-- promo_units tickets were discounted by promo_rate percent; the rest paid
+-- list price. NULL on rows from before this migration: every ticket of the
+-- order at discount_applied, as they were priced.
+
+ALTER TABLE tickets
+    ADD COLUMN IF NOT EXISTS promo_units INT,
+    ADD COLUMN IF NOT EXISTS promo_rate  DECIMAL(5,2);
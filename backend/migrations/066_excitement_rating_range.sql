@@ -0,0 +1,7 @@
+-- 066_excitement_rating_range.sql
+-- excitement_rating is 1–10. 004 created it that way, but all_migrations.sql
+-- shipped a 1–5 check; databases built from it reject 6–10 at insert.
+
+ALTER TABLE tickets DROP CONSTRAINT IF EXISTS tickets_excitement_rating_check;
+ALTER TABLE tickets
+    ADD CONSTRAINT tickets_excitement_rating_check CHECK (excitement_rating BETWEEN 1 AND 10);
@@ -53,7 +53,7 @@ export const purchaseTicketSchema = z.object({
   ticketType: z.string().optional(),
   promoCode: z.string().optional(),
   paymentProvider: z.enum(['paystack']),
-  excitementRating: z.number().int().min(1).max(5).optional(),
+  excitementRating: z.number().int().min(1).max(10).optional(),
 });
 
 /**